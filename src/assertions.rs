@@ -0,0 +1,122 @@
+pub mod assertions {
+    use std::collections::HashSet;
+    use std::io::{Error, ErrorKind};
+
+    use crate::stringer::fnv1a;
+
+    // quality gates checked after a run, run fails when one is violated
+    #[derive(Clone, Copy, PartialEq, Debug, Default)]
+    pub struct Assertions {
+        // duplicates / all strings, 0.01 is 1%
+        pub max_duplicate_rate: Option<f64>,
+        pub min_distinct: Option<u64>,
+        // (mean, tolerance) of length in characters
+        pub mean_length: Option<(f64, f64)>,
+    }
+    impl Assertions {
+        pub fn is_empty(&self) -> bool {
+            return *self == Assertions::default();
+        }
+        // 12:1 is mean 12 with tolerance 1, tolerance defaults to 0.5
+        pub fn set_mean_length(&mut self, s: &str) {
+            let (mean, tolerance) = s.split_once(':').unwrap_or((s, "0.5"));
+            self.mean_length = match (mean.trim().parse(), tolerance.trim().parse()) {
+                (Ok(mean), Ok(tolerance)) => Some((mean, tolerance)),
+                _ => None,
+            };
+        }
+        pub fn check(&self, stats: &RunStats) -> Result<(), Error> {
+            let mut failures: Vec<String> = Vec::new();
+            if let Some(max) = self.max_duplicate_rate {
+                if stats.duplicate_rate() > max {
+                    failures.push(format!(
+                        "duplicate rate {:.4} is above {}",
+                        stats.duplicate_rate(),
+                        max
+                    ));
+                }
+            }
+            if let Some(min) = self.min_distinct {
+                if stats.distinct() < min {
+                    failures.push(format!(
+                        "{} distinct strings, {} required",
+                        stats.distinct(),
+                        min
+                    ));
+                }
+            }
+            if let Some((mean, tolerance)) = self.mean_length {
+                if (stats.mean_length() - mean).abs() > tolerance {
+                    failures.push(format!(
+                        "mean length {:.2} is outside {}±{}",
+                        stats.mean_length(),
+                        mean,
+                        tolerance
+                    ));
+                }
+            }
+            if failures.is_empty() {
+                return Ok(());
+            }
+            let message = format!("run assertions failed: {}", failures.join("; "));
+            return Err(Error::new(ErrorKind::InvalidData, message));
+        }
+    }
+
+    // what a run produced, distinct strings are only tracked when asked for,
+    // and only by hash so secrets are not kept around
+    pub struct RunStats {
+        count: u64,
+        total_length: u64,
+        seen: Option<HashSet<u64>>,
+    }
+    impl RunStats {
+        pub fn new(track_distinct: bool) -> RunStats {
+            let seen = if track_distinct {
+                Some(HashSet::new())
+            } else {
+                None
+            };
+            return RunStats {
+                count: 0,
+                total_length: 0,
+                seen,
+            };
+        }
+        // enough tracking for given assertions
+        pub fn for_assertions(assertions: &Assertions) -> RunStats {
+            let distinct =
+                assertions.max_duplicate_rate.is_some() || assertions.min_distinct.is_some();
+            return RunStats::new(distinct);
+        }
+        pub fn add(&mut self, value: &str) {
+            self.count += 1;
+            self.total_length += value.chars().count() as u64;
+            if let Some(seen) = self.seen.as_mut() {
+                seen.insert(fnv1a(value));
+            }
+        }
+        pub fn count(&self) -> u64 {
+            return self.count;
+        }
+        // without tracking every string counts as distinct
+        pub fn distinct(&self) -> u64 {
+            return match &self.seen {
+                Some(seen) => seen.len() as u64,
+                None => self.count,
+            };
+        }
+        pub fn duplicate_rate(&self) -> f64 {
+            if self.count == 0 {
+                return 0.0;
+            }
+            return (self.count - self.distinct()) as f64 / self.count as f64;
+        }
+        pub fn mean_length(&self) -> f64 {
+            if self.count == 0 {
+                return 0.0;
+            }
+            return self.total_length as f64 / self.count as f64;
+        }
+    }
+}
@@ -0,0 +1,108 @@
+pub mod builder {
+    use crate::stringer::command_parser::{apply_pairs, validate_pairs, KEYS};
+    use crate::stringer::{Config, GeneratorVersion, Languages, Modes, StrgenError};
+
+    // Config::builder().mode(Modes::Password).amount(100).length(20).build()?, settings
+    // apply in order given, first bad one is error of build
+    pub struct ConfigBuilder {
+        conf: Config,
+        // applied in build, when mode is known
+        language: Option<Languages>,
+        error: Option<StrgenError>,
+    }
+    impl Default for ConfigBuilder {
+        fn default() -> Self {
+            return ConfigBuilder::new();
+        }
+    }
+    impl ConfigBuilder {
+        pub fn new() -> ConfigBuilder {
+            return ConfigBuilder {
+                conf: Config::default(),
+                language: None,
+                error: None,
+            };
+        }
+        pub fn mode(mut self, mode: Modes) -> ConfigBuilder {
+            self.conf.set_mode(mode);
+            return self;
+        }
+        pub fn amount(mut self, amount: u32) -> ConfigBuilder {
+            self.conf.set_amount(amount);
+            return self;
+        }
+        pub fn length(mut self, length: u32) -> ConfigBuilder {
+            self.conf.set_length(length);
+            return self;
+        }
+        // every string gets length between min and max, both included
+        pub fn length_range(mut self, min: u32, max: u32) -> ConfigBuilder {
+            if min > max && self.error.is_none() {
+                let range = format!("{}..{}", min, max);
+                self.error = Some(StrgenError::BadNumber(String::from("length range"), range));
+            }
+            self.conf.set_length_range(min, max);
+            return self;
+        }
+        // language of word lists and alphabets, only for modes whose 4th parameter is language
+        // (password modes ignore it), checked in build
+        pub fn language(mut self, language: Languages) -> ConfigBuilder {
+            self.language = Some(language);
+            return self;
+        }
+        // 4th parameter, what it means depends on mode
+        pub fn next(mut self, next: &str) -> ConfigBuilder {
+            self.conf.set_next(String::from(next));
+            self.language = None;
+            return self;
+        }
+        pub fn seed(mut self, seed: u64) -> ConfigBuilder {
+            self.conf.set_seed(Some(seed));
+            return self;
+        }
+        pub fn version(mut self, version: GeneratorVersion) -> ConfigBuilder {
+            self.conf.set_version(version);
+            return self;
+        }
+        // any alt argument, e.g. set("min_dist", "2"), checked like in alt mode
+        pub fn set(mut self, key: &str, value: &str) -> ConfigBuilder {
+            if self.error.is_some() {
+                return self;
+            }
+            if !KEYS.contains(&key) {
+                self.error = Some(StrgenError::UnknownSetting(String::from(key)));
+                return self;
+            }
+            if key == "next" {
+                self.language = None;
+            }
+            let pair = format!("{}={}", key, value);
+            match validate_pairs(&[pair.as_str()]) {
+                Ok(()) => apply_pairs(&mut self.conf, vec![pair.as_str()]),
+                Err(e) => self.error = Some(e),
+            }
+            return self;
+        }
+        pub fn build(mut self) -> Result<Config, StrgenError> {
+            if let Some(e) = self.error {
+                return Err(e);
+            }
+            if self.conf.get_amount() == 0 {
+                return Err(StrgenError::NotPositive(String::from("amount")));
+            }
+            if self.conf.get_length() == 0 {
+                return Err(StrgenError::NotPositive(String::from("length")));
+            }
+            if let Some(language) = self.language {
+                let mode = self.conf.get_mode();
+                let passwords = matches!(mode, Modes::Password | Modes::Password84 | Modes::Hybrid);
+                let fits = mode.takes_language() && language.has_lists();
+                if !passwords && !fits {
+                    return Err(StrgenError::LanguageMismatch(language.abbr(), mode.abbr()));
+                }
+                self.conf.set_next(language.abbr());
+            }
+            return Ok(self.conf);
+        }
+    }
+}
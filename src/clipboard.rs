@@ -0,0 +1,183 @@
+pub mod clipboard {
+    use std::io::{Error, ErrorKind, Read, Write};
+    use std::process::{Command, Stdio};
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    use crate::stringer::{fnv1a, wipe, OutputSink, Record, Wrap};
+
+    // seconds secrets stay on clipboard, same as pass and most password managers
+    pub const DEFAULT_CLIP_CLEAR: u32 = 45;
+    // hidden subcommand run by detached helper
+    pub const CLEAR_COMMAND: &str = "clipboard-clear";
+
+    // copy and paste programs of the desktop, first one found wins
+    fn copy_programs() -> Vec<(&'static str, Vec<&'static str>)> {
+        if cfg!(target_os = "macos") {
+            return vec![("pbcopy", vec![])];
+        }
+        if cfg!(windows) {
+            return vec![("clip.exe", vec![])];
+        }
+        let mut programs = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            programs.push(("wl-copy", vec![]));
+        }
+        programs.push(("xclip", vec!["-selection", "clipboard"]));
+        programs.push(("xsel", vec!["--clipboard", "--input"]));
+        return programs;
+    }
+
+    fn paste_programs() -> Vec<(&'static str, Vec<&'static str>)> {
+        if cfg!(target_os = "macos") {
+            return vec![("pbpaste", vec![])];
+        }
+        if cfg!(windows) {
+            return vec![("powershell", vec!["-NoProfile", "-Command", "Get-Clipboard"])];
+        }
+        let mut programs = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            programs.push(("wl-paste", vec!["--no-newline"]));
+        }
+        programs.push(("xclip", vec!["-selection", "clipboard", "-o"]));
+        programs.push(("xsel", vec!["--clipboard", "--output"]));
+        return programs;
+    }
+
+    // text goes through stdin, never on command line where ps would show it
+    pub fn copy_to_clipboard(text: &str) -> Result<(), Error> {
+        for (program, args) in copy_programs() {
+            let child = Command::new(program)
+                .args(&args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+            let mut child = match child {
+                Ok(child) => child,
+                Err(_e) => continue,
+            };
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(text.as_bytes())?;
+            }
+            if child.wait()?.success() {
+                return Ok(());
+            }
+        }
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            "no clipboard program found (wl-copy, xclip, xsel, pbcopy or clip.exe)",
+        ));
+    }
+
+    pub fn read_clipboard() -> Result<String, Error> {
+        for (program, args) in paste_programs() {
+            let child = Command::new(program)
+                .args(&args)
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn();
+            let mut child = match child {
+                Ok(child) => child,
+                Err(_e) => continue,
+            };
+            let mut text = String::new();
+            if let Some(mut stdout) = child.stdout.take() {
+                stdout.read_to_string(&mut text)?;
+            }
+            if child.wait()?.success() {
+                return Ok(text);
+            }
+        }
+        return Err(Error::new(ErrorKind::NotFound, "no clipboard program found"));
+    }
+
+    // helper outlives this process: own process group, so closing terminal does not take it down;
+    // hash of text goes through stdin too, short secrets could be found back from it
+    pub fn spawn_clear(seconds: u32, text: &str) -> Result<(), Error> {
+        let exe = std::env::current_exe()?;
+        let mut command = Command::new(exe);
+        command
+            .arg(CLEAR_COMMAND)
+            .arg(seconds.to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            command.process_group(0);
+        }
+        let mut child = command.spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            writeln!(stdin, "{:016x}", fnv1a(text))?;
+        }
+        return Ok(());
+    }
+
+    // run by helper: wait, then clear unless user copied something else meanwhile
+    pub fn clear_after(seconds: u32, hash: &str) -> Result<(), Error> {
+        sleep(Duration::from_secs(seconds as u64));
+        let mut current = match read_clipboard() {
+            Ok(text) => text,
+            // can't check, clear anyway
+            Err(_e) => return copy_to_clipboard(""),
+        };
+        let ours = format!("{:016x}", fnv1a(&current)) == hash;
+        wipe(&mut current);
+        if ours {
+            return copy_to_clipboard("");
+        }
+        return Ok(());
+    }
+
+    // strings of the run go to clipboard together at the end, one per line
+    pub struct ClipboardSink {
+        text: String,
+        wrap: Wrap,
+        // seconds until helper clears clipboard, 0 keeps it
+        clear_after: u32,
+    }
+    impl ClipboardSink {
+        pub fn new(wrap: Wrap, clear_after: u32) -> ClipboardSink {
+            return ClipboardSink {
+                text: String::new(),
+                wrap,
+                clear_after,
+            };
+        }
+    }
+    impl Drop for ClipboardSink {
+        fn drop(&mut self) {
+            wipe(&mut self.text);
+        }
+    }
+    impl OutputSink for ClipboardSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            let mut text = record.text(self.wrap);
+            let needed = self.text.len() + text.len() + 1;
+            // growing would leave old buffer behind unwiped, so move over by hand
+            if needed > self.text.capacity() {
+                let mut grown = String::with_capacity(needed.max(self.text.capacity() * 2));
+                grown.push_str(&self.text);
+                wipe(&mut self.text);
+                self.text = grown;
+            }
+            if !self.text.is_empty() {
+                self.text.push('\n');
+            }
+            self.text.push_str(&text);
+            wipe(&mut text);
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            copy_to_clipboard(&self.text)?;
+            if self.clear_after > 0 {
+                spawn_clear(self.clear_after, &self.text)?;
+            }
+            wipe(&mut self.text);
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,73 @@
+pub mod clock {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    pub fn now_millis() -> u64 {
+        return SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+    }
+
+    // days since 1970-01-01 to (year, month, day), Howard Hinnant's civil_from_days
+    pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+        let z = days + 719468;
+        let era = if z >= 0 { z } else { z - 146096 } / 146097;
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+        return (year, month, day);
+    }
+
+    // (year, month, day, hour, minute, second, millisecond) in utc
+    pub fn split_millis(millis: u64) -> (i64, u32, u32, u32, u32, u32, u32) {
+        let secs = millis / 1000;
+        let (year, month, day) = civil_from_days((secs / 86400) as i64);
+        let rem = secs % 86400;
+        return (
+            year,
+            month,
+            day,
+            (rem / 3600) as u32,
+            (rem % 3600 / 60) as u32,
+            (rem % 60) as u32,
+            (millis % 1000) as u32,
+        );
+    }
+
+    // 2024-05-01T12:30:00.123Z
+    pub fn iso8601(millis: u64) -> String {
+        let (y, mo, d, h, mi, s, ms) = split_millis(millis);
+        return format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+            y, mo, d, h, mi, s, ms
+        );
+    }
+
+    // 2024-05-01
+    pub fn iso_date(millis: u64) -> String {
+        let (y, mo, d, _h, _mi, _s, _ms) = split_millis(millis);
+        return format!("{:04}-{:02}-{:02}", y, mo, d);
+    }
+
+    // 01/May/2024:12:30:00 +0000, as in apache access logs
+    pub fn apache(millis: u64) -> String {
+        let (y, mo, d, h, mi, s, _ms) = split_millis(millis);
+        return format!(
+            "{:02}/{}/{:04}:{:02}:{:02}:{:02} +0000",
+            d,
+            MONTHS[(mo - 1) as usize],
+            y,
+            h,
+            mi,
+            s
+        );
+    }
+}
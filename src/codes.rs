@@ -0,0 +1,352 @@
+pub mod codes {
+    use std::collections::HashSet;
+    use std::io::{Error, ErrorKind};
+    use std::path::{Path, PathBuf};
+    use std::sync::OnceLock;
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{
+        iso_date, new_run_id, now_millis, parse_duration, read_lines, search_history, Config,
+        ConstraintEngine, Modes, RngBackend, StrgenError, StringFilter, RNG,
+    };
+
+    // no 0/O, 1/I/L look-alikes, codes get read aloud and typed from print
+    pub const CODE_ALPHABET: &str = "23456789ABCDEFGHJKLMNPQRSTUVWXYZ";
+    // attempts for a code not issued before, then batch is exhausted
+    const MAX_ATTEMPTS: u32 = 1000;
+
+    // luhn mod n check character, catches single typos and swapped neighbours
+    pub fn check_char(payload: &str, alphabet: &str) -> Option<char> {
+        let symbols: Vec<char> = alphabet.chars().collect();
+        let n = symbols.len() as u32;
+        let mut factor = 2;
+        let mut sum = 0;
+        for c in payload.chars().rev() {
+            let index = symbols.iter().position(|s| *s == c)? as u32;
+            let addend = factor * index;
+            sum += addend / n + addend % n;
+            factor = if factor == 2 { 1 } else { 2 };
+        }
+        return Some(symbols[((n - sum % n) % n) as usize]);
+    }
+
+    // code as typed by customer: prefix, dashes, spaces and case don't matter
+    pub fn normalize_code(code: &str, prefix: &str) -> String {
+        let squeeze = |s: &str| -> String {
+            return s
+                .chars()
+                .filter(|c| *c != '-' && !c.is_whitespace())
+                .flat_map(|c| c.to_uppercase())
+                .collect();
+        };
+        let code = squeeze(code);
+        let prefix = squeeze(prefix);
+        return match code.strip_prefix(&prefix) {
+            Some(rest) if !prefix.is_empty() => String::from(rest),
+            _ => code,
+        };
+    }
+
+    pub fn verify_code(code: &str, prefix: &str) -> bool {
+        let code = normalize_code(code, prefix);
+        let mut payload: Vec<char> = code.chars().collect();
+        let check = match payload.pop() {
+            Some(check) => check,
+            None => return false,
+        };
+        let payload: String = payload.into_iter().collect();
+        return check_char(&payload, CODE_ALPHABET) == Some(check);
+    }
+
+    // 4th parameter: [group=4][:check=0][:prefix=SPRING][:batch=id][:ttl=90d|:expires=2025-12-31]
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct CodeBatch {
+        pub group: usize,
+        pub check: bool,
+        pub prefix: String,
+        pub batch: String,
+        // iso date, empty when codes don't expire
+        pub expires: String,
+    }
+    impl Default for CodeBatch {
+        fn default() -> Self {
+            return CodeBatch {
+                group: 4,
+                check: true,
+                prefix: String::new(),
+                batch: new_run_id(),
+                expires: String::new(),
+            };
+        }
+    }
+    impl CodeBatch {
+        pub fn from(s: &str) -> Result<CodeBatch, Error> {
+            let mut batch = CodeBatch::default();
+            for part in s.split(':').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+                let (key, value) = part.split_once('=').unwrap_or((part, ""));
+                match key {
+                    "group" => batch.group = value.parse().unwrap_or(4),
+                    "check" => batch.check = value != "0",
+                    "prefix" => batch.prefix = String::from(value),
+                    "batch" => batch.batch = String::from(value),
+                    "expires" => batch.expires = String::from(value),
+                    "ttl" => {
+                        let ttl = parse_duration(value).ok_or_else(|| {
+                            let message =
+                                format!("code ttl {} is not like 90s, 30m, 8h or 7d", value);
+                            return Error::new(ErrorKind::InvalidInput, message);
+                        })?;
+                        batch.expires = iso_date(now_millis() + ttl);
+                    }
+                    _ => {
+                        let message = format!("unknown code option {}", key);
+                        return Err(Error::new(ErrorKind::InvalidInput, message));
+                    }
+                }
+            }
+            return Ok(batch);
+        }
+    }
+
+    // redemption and one-time codes, unique within batch and against history
+    pub struct RedemptionCodes {
+        batch: CodeBatch,
+        length: usize,
+        rng: Box<dyn RngBackend>,
+        // normalized codes already handed out
+        issued: HashSet<String>,
+        // drawn one ahead, none when no new code could be found
+        pending: Option<String>,
+    }
+    impl Default for RedemptionCodes {
+        fn default() -> Self {
+            return RedemptionCodes::new();
+        }
+    }
+    impl RedemptionCodes {
+        pub fn new() -> RedemptionCodes {
+            let mut rng = RNG::new();
+            rng.seed();
+            return RedemptionCodes {
+                batch: CodeBatch::default(),
+                length: 12,
+                rng: Box::new(rng),
+                issued: HashSet::new(),
+                pending: None,
+            };
+        }
+        pub fn get_batch(&self) -> CodeBatch {
+            return self.batch.clone();
+        }
+        // codes issued by earlier runs, so reprint never repeats a code
+        pub fn load_issued(&mut self, history: &Path) -> Result<(), Error> {
+            if !history.exists() {
+                return Ok(());
+            }
+            for entry in search_history(history, "")? {
+                self.issued
+                    .insert(normalize_code(&entry.value, &self.batch.prefix));
+            }
+            return Ok(());
+        }
+        fn format(&self, code: &str) -> String {
+            let mut grouped = String::new();
+            if !self.batch.prefix.is_empty() {
+                grouped.push_str(&self.batch.prefix);
+                grouped.push('-');
+            }
+            for (i, c) in code.chars().enumerate() {
+                if self.batch.group > 0 && i > 0 && i % self.batch.group == 0 {
+                    grouped.push('-');
+                }
+                grouped.push(c);
+            }
+            return grouped;
+        }
+        fn draw(&mut self) -> String {
+            let symbols: Vec<char> = CODE_ALPHABET.chars().collect();
+            let payload_length = if self.batch.check {
+                self.length.saturating_sub(1).max(1)
+            } else {
+                self.length.max(1)
+            };
+            let mut code: String = (0..payload_length)
+                .map(|_| symbols[self.rng.get() as usize % symbols.len()])
+                .collect();
+            if self.batch.check {
+                if let Some(check) = check_char(&code, CODE_ALPHABET) {
+                    code.push(check);
+                }
+            }
+            return code;
+        }
+        fn prepare(&mut self) {
+            self.pending = None;
+            for _ in 0..MAX_ATTEMPTS {
+                let code = self.draw();
+                if self.issued.insert(code.clone()) {
+                    self.pending = Some(code);
+                    return;
+                }
+            }
+        }
+    }
+    impl StringGenerator for RedemptionCodes {
+        fn get(&mut self) -> String {
+            let code = match self.pending.take() {
+                Some(code) => self.format(&code),
+                None => String::new(),
+            };
+            self.prepare();
+            return code;
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.backend(0)?;
+            self.batch = CodeBatch::from(&conf.get_next())?;
+            self.length = conf.get_length() as usize;
+            if !conf.get_history().is_empty() {
+                self.load_issued(Path::new(&conf.get_history()))?;
+            }
+            self.prepare();
+            return Ok(());
+        }
+        fn exhausted(&self) -> bool {
+            return self.pending.is_none();
+        }
+        fn fields(&self) -> Vec<(String, String)> {
+            return vec![
+                (String::from("batch"), self.batch.batch.clone()),
+                (String::from("expires"), self.batch.expires.clone()),
+            ];
+        }
+    }
+
+    // words no customer should find in their code, lists/profanity has the source
+    pub const PROFANITY: &str = include_str!("../lists/profanity/en.list");
+
+    // digits read as letters, 5H1T is still shit
+    fn unleet(c: char) -> char {
+        return match c {
+            '0' => 'o',
+            '1' => 'i',
+            '3' => 'e',
+            '4' => 'a',
+            '5' => 's',
+            '6' | '9' => 'g',
+            '7' => 't',
+            '8' => 'b',
+            c => c.to_ascii_lowercase(),
+        };
+    }
+
+    // letters only, lowercase and leet read back, dashes and spaces don't hide words
+    pub fn squeeze_leet(s: &str) -> String {
+        return s
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .map(unleet)
+            .collect();
+    }
+
+    // list words as forbidden substrings, normalized same way as candidates
+    fn profanity_engine(list: &str) -> ConstraintEngine {
+        let words: Vec<String> = list
+            .lines()
+            .filter(|line| !line.trim().starts_with('#'))
+            .map(String::from)
+            .collect();
+        return ConstraintEngine::with_normalize(&[], &words, squeeze_leet);
+    }
+
+    static BUILT_IN: OnceLock<ConstraintEngine> = OnceLock::new();
+
+    // listed word hidden anywhere in code, across dashes and in leetspeak
+    pub fn find_profanity(code: &str) -> Option<&'static str> {
+        let engine = BUILT_IN.get_or_init(|| profanity_engine(PROFANITY));
+        return engine.forbidden_in(code);
+    }
+
+    // substrings, not whole words: random letters spell words by chance anywhere in string
+    pub struct ProfanityFilter {
+        words: ConstraintEngine,
+    }
+    impl Default for ProfanityFilter {
+        fn default() -> Self {
+            return ProfanityFilter::new();
+        }
+    }
+    impl ProfanityFilter {
+        pub fn new() -> ProfanityFilter {
+            return ProfanityFilter {
+                words: profanity_engine(PROFANITY),
+            };
+        }
+        // 1 or en is list built into binary, anything else is path of own list
+        pub fn load(spec: &str) -> Result<ProfanityFilter, Error> {
+            if matches!(spec, "1" | "en") {
+                return Ok(ProfanityFilter::new());
+            }
+            let mut list = String::new();
+            for line in read_lines(spec)? {
+                list.push_str(&line?);
+                list.push('\n');
+            }
+            let words = profanity_engine(&list);
+            if words.is_empty() {
+                return Err(StrgenError::EmptyList(PathBuf::from(spec)).into());
+            }
+            return Ok(ProfanityFilter { words });
+        }
+        pub fn find(&self, value: &str) -> Option<&str> {
+            return self.words.forbidden_in(value);
+        }
+    }
+    impl StringFilter for ProfanityFilter {
+        fn accepts(&mut self, value: &str) -> bool {
+            return self.find(value).is_none();
+        }
+    }
+
+    // several constraints under one name
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum OutputProfile {
+        // codes people read, type and see printed on receipts
+        Coupon,
+    }
+    impl OutputProfile {
+        pub fn from(s: &str) -> Result<OutputProfile, Error> {
+            return match s {
+                "coupon" | "voucher" => Ok(OutputProfile::Coupon),
+                _ => {
+                    let message = format!("unknown profile {}, there is coupon", s);
+                    Err(Error::new(ErrorKind::InvalidInput, message))
+                }
+            };
+        }
+        pub fn profanity(&self) -> bool {
+            return matches!(*self, OutputProfile::Coupon);
+        }
+    }
+
+    // coupon is code mode (unambiguous alphabet, groups of 4, check character)
+    // with at least 8 characters, no repeats and profanity screened by build_filters
+    pub fn apply_profile(conf: &Config) -> Result<Config, Error> {
+        let mut conf = conf.clone();
+        if conf.get_profile().is_empty() {
+            return Ok(conf);
+        }
+        match OutputProfile::from(&conf.get_profile())? {
+            OutputProfile::Coupon => {
+                if !matches!(conf.get_mode(), Modes::RedemptionCodes) {
+                    conf.set_mode(Modes::RedemptionCodes);
+                    conf.set_next(String::new());
+                }
+                // options given for code mode stay, checked here before run
+                CodeBatch::from(&conf.get_next())?;
+                conf.set_length(conf.get_length().max(8));
+                conf.set_min_distance(conf.get_min_distance().max(1));
+            }
+        }
+        return Ok(conf);
+    }
+}
@@ -0,0 +1,158 @@
+pub mod columns {
+    use std::io::{Error, ErrorKind};
+    use std::path::Path;
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{
+        in_range, stringer, Config, GeneratorVersion, Languages, ListType, Modes, WordList, RNG,
+    };
+
+    // reserved for documentation (rfc 2606), mail sent to them never reaches anyone
+    pub const EMAIL_DOMAINS: [&str; 3] = ["example.com", "example.org", "example.net"];
+    // names and email numbers, apart from streams of mode columns
+    pub const STREAM: u64 = 11;
+    // seeds of mode columns are this far apart, so no two columns draw same numbers
+    const SEED_STEP: u64 = 0x9E37_79B9_7F4A_7C15;
+
+    #[derive(Clone)]
+    pub enum Column {
+        // first name from names list of language
+        Name,
+        // made of name of same row when it has one
+        Email,
+        Mode(Modes),
+    }
+    impl Column {
+        pub fn from(s: &str) -> Result<Column, Error> {
+            return match s {
+                "name" => Ok(Column::Name),
+                "email" => Ok(Column::Email),
+                _ => Modes::parse(s).map(Column::Mode).ok_or_else(|| {
+                    let message = format!("unknown column {}, expected name, email or mode", s);
+                    return Error::new(ErrorKind::InvalidInput, message);
+                }),
+            };
+        }
+    }
+
+    // name,email,uuid, every column is also its header
+    pub fn parse_columns(s: &str) -> Result<Vec<(String, Column)>, Error> {
+        let mut columns: Vec<(String, Column)> = Vec::new();
+        for spec in s.split(',').map(|c| c.trim()).filter(|c| !c.is_empty()) {
+            columns.push((String::from(spec), Column::from(spec)?));
+        }
+        if columns.is_empty() {
+            let message = "columns need at least one column";
+            return Err(Error::new(ErrorKind::InvalidInput, message));
+        }
+        return Ok(columns);
+    }
+
+    // lower case ascii letters and digits of name, user when none are left
+    pub fn mailbox(name: &str) -> String {
+        let mailbox: String = name
+            .chars()
+            .flat_map(|c| c.to_lowercase())
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect();
+        if mailbox.is_empty() {
+            return String::from("user");
+        }
+        return mailbox;
+    }
+
+    // one row per string: value is first column, all columns are fields of record
+    pub struct ColumnRecords {
+        columns: Vec<(String, Column)>,
+        // generator of every mode column, none for name and email
+        generators: Vec<Option<Box<dyn StringGenerator>>>,
+        names: WordList,
+        row: Vec<(String, String)>,
+        rng: RNG,
+        version: GeneratorVersion,
+    }
+    impl Default for ColumnRecords {
+        fn default() -> Self {
+            return ColumnRecords::new();
+        }
+    }
+    impl ColumnRecords {
+        pub fn new() -> ColumnRecords {
+            return ColumnRecords {
+                columns: Vec::new(),
+                generators: Vec::new(),
+                names: WordList::new(ListType::Names, Languages::English),
+                row: Vec::new(),
+                rng: RNG::new(),
+                version: GeneratorVersion::latest(),
+            };
+        }
+        fn email(&mut self, name: Option<String>) -> String {
+            let name = name.unwrap_or_else(|| self.names.get());
+            let number = in_range(&mut self.rng, 1, 999);
+            let domain = self.version.pick_index(&mut self.rng, EMAIL_DOMAINS.len());
+            return format!("{}{}@{}", mailbox(&name), number, EMAIL_DOMAINS[domain]);
+        }
+    }
+    impl StringGenerator for ColumnRecords {
+        fn get(&mut self) -> String {
+            self.row.clear();
+            let mut name: Option<String> = None;
+            for i in 0..self.columns.len() {
+                let value = match self.columns[i].1 {
+                    Column::Name => {
+                        let value = self.names.get();
+                        name = Some(value.clone());
+                        value
+                    }
+                    Column::Email => self.email(name.clone()),
+                    Column::Mode(_) => match self.generators[i].as_mut() {
+                        Some(generator) => generator.get(),
+                        None => String::new(),
+                    },
+                };
+                self.row.push((self.columns[i].0.clone(), value));
+            }
+            return self
+                .row
+                .first()
+                .map(|(_h, v)| v.clone())
+                .unwrap_or_default();
+        }
+        fn fields(&self) -> Vec<(String, String)> {
+            return self.row.clone();
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.columns = parse_columns(&conf.get_columns())?;
+            self.version = conf.get_version();
+            self.rng = conf.rng(STREAM);
+            self.generators.clear();
+            for (i, (_header, column)) in self.columns.iter().enumerate() {
+                let Column::Mode(mode) = column else {
+                    self.generators.push(None);
+                    continue;
+                };
+                let mut column_conf = conf.clone();
+                column_conf.set_mode(mode.clone());
+                column_conf.set_columns(String::new());
+                let step = (i as u64 + 1).wrapping_mul(SEED_STEP);
+                column_conf.set_seed(conf.get_seed().map(|seed| seed.wrapping_add(step)));
+                let mut generator = stringer(column_conf.clone());
+                generator.setup(&column_conf)?;
+                self.generators.push(Some(generator));
+            }
+            let needs_names = self
+                .columns
+                .iter()
+                .any(|(_h, c)| matches!(c, Column::Name | Column::Email));
+            if needs_names {
+                self.names =
+                    WordList::new(ListType::Names, Languages::from(conf.get_next().as_ref()));
+                self.names.fill(Path::new(""))?;
+                self.names.set_version(conf.get_version());
+                self.names.set_rng(conf.rng(STREAM + 1));
+            }
+            return Ok(());
+        }
+    }
+}
@@ -1,48 +1,439 @@
 pub mod command_parser {
-    use crate::stringer::{safe_u32,Config,Modes};
+    use std::io::{Error, ErrorKind};
 
-    pub fn get_config(vargs: Vec<&str>) -> Config {
-        let a = [String::new(), String::from("02")];
-        //confetti
-        let mut conf = Config::new(&a);
+    use crate::stringer::{
+        parse_exclusions, parse_length_range, parse_number, parse_wraps, safe_u32, Config, Escape,
+        GeneratorVersion, Modes, Noise, Phonetic, KeyboardWalks, ReservationSpec, Screening,
+        Padding, StrgenError, Typos, parse_format, parse_columns, Difficulty, Composition,
+        DEFAULT_CLIP_CLEAR, Wrap, tr,
+    };
 
-        for str in vargs {
-            if str.contains("mode") {
-                let strong = str.to_string();
-                let mo = Modes::from(get_value(strong, "=").as_ref());
-                conf.set_mode(mo);
-            }
-            if str.contains("num") {
-                let strong = str.to_string();
-                let ammount: u32 = get_value(strong, "=").parse().expect("number");
-                conf.set_amount(ammount);
+    // keys of get_config, keep in step with match below
+    pub const KEYS: [&str; 54] = [
+        "mode", "num", "len", "next", "wtf", "dwi", "ver", "mat", "esc", "wrap", "sink", "labels",
+        "label", "group", "cover", "strata", "max_dup", "min_distinct", "mean_len", "min_dist",
+        "min_prefix", "phon", "exclude", "protected", "walks", "policy", "profile", "profanity",
+        "contains", "forbid", "over", "score", "history", "reserve", "confirm_display",
+        "clip_clear", "seed", "rng", "batch", "noise",
+        "typos", "pairs", "pad", "widths", "sentences", "words",
+        "append", "format", "columns", "difficulty", "unique", "exclude_file", "record",
+        "rules",
+    ];
+    // readable flag names for short keys, other keys are flags as they are (--min-dist 2)
+    pub const FLAG_NAMES: [(&str, &str); 8] = [
+        ("amount", "num"),
+        ("length", "len"),
+        ("length-range", "len"),
+        ("lang", "next"),
+        ("write-file", "wtf"),
+        ("no-indices", "dwi"),
+        ("generator", "ver"),
+        ("escape", "esc"),
+    ];
+
+    fn flag_key(flag: &str) -> Option<&'static str> {
+        if let Some((_name, key)) = FLAG_NAMES.iter().find(|(name, _key)| *name == flag) {
+            return Some(key);
+        }
+        let key = flag.replace('-', "_");
+        return KEYS.iter().find(|k| **k == key).copied();
+    }
+
+    // --mode coupled-nouns --amount 50 --length 8 --lang de, in any order;
+    // --flag value or --flag=value, flag without value is 1
+    pub fn get_flag_config(args: &[String]) -> Result<Config, Error> {
+        let mut pairs: Vec<String> = Vec::new();
+        let mut i = 0;
+        while i < args.len() {
+            let arg = &args[i];
+            let flag = match arg.strip_prefix("--") {
+                Some(flag) => flag,
+                None => {
+                    let message = format!("{} is not a flag, flags start with --", arg);
+                    return Err(Error::new(ErrorKind::InvalidInput, message));
+                }
+            };
+            let (flag, value) = match flag.split_once('=') {
+                Some((flag, value)) => (flag, String::from(value)),
+                None => match args.get(i + 1) {
+                    Some(value) if !value.starts_with("--") => {
+                        i += 1;
+                        (flag, value.clone())
+                    }
+                    _ => (flag, String::from("1")),
+                },
+            };
+            i += 1;
+            let key = match flag_key(flag) {
+                Some(key) => key,
+                None => {
+                    let message = format!("unknown flag --{}, see --help", flag);
+                    return Err(Error::new(ErrorKind::InvalidInput, message));
+                }
+            };
+            if key == "mode" {
+                let mode = match Modes::from_name(&value) {
+                    Some(mode) => mode,
+                    None => {
+                        let message = format!("unknown mode {}, see --help", value);
+                        return Err(Error::new(ErrorKind::InvalidInput, message));
+                    }
+                };
+                pairs.push(format!("mode={}", mode.abbr()));
+                continue;
             }
-            if str.contains("len") {
-                let strong = str.to_string();
-                let length: u32 = safe_u32(get_value(strong, "="), 4);
-                conf.set_length(length);
+            if key == "num" && value.parse::<u32>().is_err() {
+                let message = format!("--{} needs a number, got {}", flag, value);
+                return Err(Error::new(ErrorKind::InvalidInput, message));
             }
-            if str.contains("next") {
-                let strong = str.to_string();
-                conf.set_next(get_value(strong, "="));
+            pairs.push(format!("{}={}", key, value));
+        }
+        return Ok(try_get_config(pairs.iter().map(|p| p.as_str()).collect())?);
+    }
+
+    // what --help prints: flags and every mode
+    pub fn flags_help() -> String {
+        let mut help = String::from(
+            "stranameg --mode <mode> [--amount N] [--length N] [--lang xx | --next x] [--flag value..]\n\n",
+        );
+        help.push_str(&format!("{}\n", tr("help.flags")));
+        for (name, key) in FLAG_NAMES.iter() {
+            help.push_str(&format!("  --{:<16} {}=\n", name, key));
+        }
+        help.push_str(&format!("  {}\n", tr("help.any_key")));
+        help.push_str("  --out, --policy, --profile, --seed, --length-range, --clipboard, --confirm-display, --ui-lang\n\n");
+        help.push_str(&format!("{}\n", tr("help.modes")));
+        for mode in Modes::ALL.iter() {
+            help.push_str(&format!("  {:<30} {}\n", mode.name(), mode.abbr()));
+        }
+        help.push_str(&format!("\n{}", tr("help.see")));
+        return help;
+    }
+
+    // get_config, but typo in number or mode is error instead of default
+    pub fn try_get_config(vargs: Vec<&str>) -> Result<Config, StrgenError> {
+        validate_pairs(&vargs)?;
+        return Ok(get_config(vargs));
+    }
+
+    // typo in key, number or choice of key=value pairs
+    pub fn validate_pairs(vargs: &[&str]) -> Result<(), StrgenError> {
+        let mut mode = Config::default().get_mode();
+        let mut zero_length = false;
+        for str in vargs.iter() {
+            // blank lines of params file are skipped, anything else needs key=value
+            if str.trim().is_empty() {
+                continue;
             }
-            if str.contains("wtf") {
-                let strong = str.to_string();
-                conf.set_write_to_file(get_value(strong, "=") == "1");
+            if !str.contains('=') {
+                return Err(StrgenError::UnknownSetting(String::from(str.trim())));
             }
-            if str.contains("dwi") {
-                let strong = str.to_string();
-                conf.set_write_indices(get_value(strong, "=") == "1");
+            let value = get_value(str.to_string(), "=");
+            let key = get_key(str, "=");
+            let bad = || StrgenError::BadValue(key.clone(), value.clone());
+            match key.as_ref() {
+                _ if !KEYS.contains(&key.as_str()) => {
+                    return Err(StrgenError::UnknownSetting(key));
+                }
+                "num" | "batch" | "over" if parse_number(&key, &value)? == 0 => {
+                    return Err(StrgenError::NotPositive(key));
+                }
+                "len" if value.contains("..") => {
+                    if parse_length_range(&value).is_none() {
+                        return Err(StrgenError::BadNumber(String::from("length range"), value));
+                    }
+                    zero_length = false;
+                }
+                "len" => {
+                    zero_length = parse_number("len", &value)? == 0;
+                }
+                "clip_clear" | "min_dist" | "min_prefix" | "min_distinct" => {
+                    parse_number(&key, &value)?;
+                }
+                "max_dup" if value.trim().parse::<f64>().is_err() => {
+                    return Err(StrgenError::BadNumber(key, value));
+                }
+                "esc" if Escape::parse(&value).is_none() => {
+                    return Err(bad());
+                }
+                "wrap" => {
+                    for part in value.split(',') {
+                        let wrap = part.split_once(':').map(|(_target, w)| w).unwrap_or(part);
+                        if Wrap::parse(wrap).is_none() {
+                            return Err(bad());
+                        }
+                    }
+                }
+                "noise" => {
+                    Noise::parse(&value)?;
+                }
+                "ver" if GeneratorVersion::parse(&value).is_none() => {
+                    return Err(bad());
+                }
+                "phon" if !value.is_empty() && Phonetic::from(&value).is_none() => {
+                    return Err(bad());
+                }
+                "seed" if value.trim().parse::<u64>().is_err() => {
+                    return Err(StrgenError::BadNumber(String::from("seed"), value));
+                }
+                "typos" => {
+                    Typos::from(&value)?;
+                }
+                "pad" => {
+                    Padding::from(&value)?;
+                }
+                "format" => {
+                    parse_format(&value)?;
+                }
+                "columns" => {
+                    parse_columns(&value)?;
+                }
+                "sentences" | "words" => {
+                    parse_number(&get_key(str, "="), &value)?;
+                }
+                "difficulty" => {
+                    Difficulty::from(&value)?;
+                }
+                "rules" => {
+                    Composition::from(&value)?;
+                }
+                "widths" if value.split(',').any(|w| w.trim().parse::<usize>().is_err()) => {
+                    return Err(StrgenError::BadNumber(String::from("widths"), value));
+                }
+                "mode" => {
+                    mode = Modes::parse(&value).ok_or(StrgenError::UnknownMode(value))?;
+                }
+                _ => {}
             }
         }
+        if zero_length && mode.needs_length() {
+            return Err(StrgenError::NotPositive(String::from("len")));
+        }
+        return Ok(());
+    }
+
+    pub fn get_config(vargs: Vec<&str>) -> Config {
+        //confetti
+        let mut conf = Config::default();
+        conf.set_amount(2);
+        apply_pairs(&mut conf, vargs);
         return conf;
     }
+
+    // key=value pairs over config, keys are those of KEYS; validate_pairs catches bad ones first
+    pub fn apply_pairs(conf: &mut Config, vargs: Vec<&str>) {
+        for str in vargs {
+            // key=value, keys are matched exactly
+            let key = get_key(str, "=");
+            let strong = str.to_string();
+            match key.as_ref() {
+                "mode" => {
+                    let mo = Modes::from(get_value(strong, "=").as_ref());
+                    conf.set_mode(mo);
+                }
+                "num" => {
+                    let ammount: u32 = safe_u32(get_value(strong, "="), conf.get_amount());
+                    conf.set_amount(ammount);
+                }
+                "len" => {
+                    // len=8..16 picks length of every string in range
+                    let value = get_value(strong, "=");
+                    match parse_length_range(&value) {
+                        Some((min, max)) => conf.set_length_range(min, max),
+                        None => conf.set_length(safe_u32(value, 4)),
+                    }
+                }
+                "next" => {
+                    conf.set_next(get_value(strong, "="));
+                }
+                "wtf" => {
+                    conf.set_write_to_file(get_value(strong, "=") == "1");
+                }
+                "dwi" => {
+                    conf.set_write_indices(get_value(strong, "=") == "1");
+                }
+                "ver" => {
+                    let version = GeneratorVersion::from(get_value(strong, "=").as_ref());
+                    conf.set_version(version);
+                }
+                "mat" => {
+                    conf.set_materialize(get_value(strong, "=") == "1");
+                }
+                "esc" => {
+                    conf.set_escape(Escape::from(get_value(strong, "=").as_ref()));
+                }
+                "noise" => {
+                    conf.set_noise(Noise::from(get_value(strong, "=").as_ref()));
+                }
+                "typos" => {
+                    conf.set_typos(Typos::from(get_value(strong, "=").as_ref()).ok());
+                }
+                "pairs" => {
+                    conf.set_pairs(get_value(strong, "=") == "1");
+                }
+                "pad" => {
+                    conf.set_padding(Padding::from(get_value(strong, "=").as_ref()).ok());
+                }
+                "sentences" => {
+                    conf.set_sentences(safe_u32(get_value(strong, "="), 5));
+                }
+                "words" => {
+                    conf.set_words(safe_u32(get_value(strong, "="), 8));
+                }
+                "difficulty" => {
+                    conf.set_difficulty(get_value(strong, "="));
+                }
+                "rules" => {
+                    conf.set_rules(get_value(strong, "="));
+                }
+                "wrap" => {
+                    conf.set_wraps(parse_wraps(get_value(strong, "=").as_ref()));
+                }
+                "sink" => {
+                    conf.add_sink(get_value(strong, "="));
+                }
+                "append" => {
+                    conf.set_append(get_value(strong, "=") == "1");
+                }
+                "columns" => {
+                    conf.set_columns(get_value(strong, "="));
+                }
+                "format" => {
+                    if let Ok((format, fields)) = parse_format(get_value(strong, "=").as_ref()) {
+                        conf.set_format(format);
+                        conf.set_format_fields(fields);
+                    }
+                }
+                "labels" => {
+                    conf.set_labels(get_value(strong, "=") == "1");
+                }
+                "label" => {
+                    conf.set_label(get_value(strong, "="));
+                }
+                "group" => {
+                    conf.set_group(get_value(strong, "=") == "1");
+                }
+                "cover" => {
+                    conf.set_coverage(get_value(strong, "=") == "1");
+                }
+                "strata" => {
+                    conf.set_strata(get_value(strong, "="));
+                }
+                "max_dup" => {
+                    let mut assertions = conf.get_assertions();
+                    assertions.max_duplicate_rate = get_value(strong, "=").trim().parse().ok();
+                    conf.set_assertions(assertions);
+                }
+                "min_distinct" => {
+                    let mut assertions = conf.get_assertions();
+                    assertions.min_distinct = get_value(strong, "=").trim().parse().ok();
+                    conf.set_assertions(assertions);
+                }
+                "mean_len" => {
+                    let mut assertions = conf.get_assertions();
+                    assertions.set_mean_length(get_value(strong, "=").as_ref());
+                    conf.set_assertions(assertions);
+                }
+                "min_dist" => {
+                    conf.set_min_distance(safe_u32(get_value(strong, "="), 0) as usize);
+                }
+                "min_prefix" => {
+                    conf.set_min_prefix(safe_u32(get_value(strong, "="), 0) as usize);
+                }
+                "unique" => {
+                    conf.set_unique(get_value(strong, "=") == "1");
+                }
+                "exclude_file" => {
+                    conf.set_exclude_file(get_value(strong, "="));
+                }
+                "record" => {
+                    conf.set_record(get_value(strong, "="));
+                }
+                "phon" => {
+                    conf.set_phonetic(Phonetic::from(get_value(strong, "=").as_ref()));
+                }
+                "exclude" => {
+                    conf.set_exclusions(parse_exclusions(get_value(strong, "=").as_ref()));
+                }
+                "protected" => {
+                    conf.set_screening(Some(Screening::from(get_value(strong, "=").as_ref())));
+                }
+                "walks" => {
+                    let walks = KeyboardWalks::from(get_value(strong, "=").as_ref());
+                    conf.set_keyboard_walks(Some(walks));
+                }
+                "policy" => {
+                    conf.set_policy(get_value(strong, "="));
+                }
+                "profile" => {
+                    conf.set_profile(get_value(strong, "="));
+                }
+                "profanity" => {
+                    conf.set_profanity(get_value(strong, "="));
+                }
+                "rng" => {
+                    conf.set_rng_backend(get_value(strong, "="));
+                }
+                "seed" => {
+                    conf.set_seed(get_value(strong, "=").trim().parse().ok());
+                }
+                "contains" => {
+                    conf.set_required(split_list(&get_value(strong, "=")));
+                }
+                "forbid" => {
+                    conf.set_forbidden(split_list(&get_value(strong, "=")));
+                }
+                "over" => {
+                    conf.set_oversample(safe_u32(get_value(strong, "="), 10));
+                }
+                "score" => {
+                    conf.set_scoring(get_value(strong, "="));
+                }
+                "history" => {
+                    conf.set_history(get_value(strong, "="));
+                }
+                "reserve" => {
+                    let spec = ReservationSpec::from(get_value(strong, "=").as_ref());
+                    conf.set_reservation(Some(spec));
+                }
+                "confirm_display" => {
+                    conf.set_confirm_display(get_value(strong, "=") == "1");
+                }
+                "clip_clear" => {
+                    conf.set_clip_clear(safe_u32(get_value(strong, "="), DEFAULT_CLIP_CLEAR));
+                }
+                "batch" => {
+                    conf.set_batch_size(safe_u32(get_value(strong, "="), 100));
+                }
+                "widths" => {
+                    let value = get_value(strong, "=");
+                    let widths = value.split(',').map(|w| safe_u32(String::from(w.trim()), 0));
+                    conf.set_widths(widths.map(|w| w as usize).collect());
+                }
+                _ => {}
+            }
+        }
+    }
+    fn get_key(strong: &str, delimiter: &str) -> String {
+        let key = strong.split(delimiter).next().unwrap_or("");
+        return String::from(key.trim());
+    }
     fn get_value(strong: String, delimiter: &str) -> String {
         // return ;
         //going fishing
-        let v= strong.split(delimiter).collect::<Vec<&str>>();
+        let v = strong.splitn(2, delimiter).collect::<Vec<&str>>();
         // return String::from(v[1]);
-        let value = String::from(v[1]);
+        // key without value is empty value
+        let value = String::from(v.get(1).copied().unwrap_or(""));
         return value;
     }
+    // cat,dog -> [cat, dog], empty entries dropped
+    fn split_list(s: &str) -> Vec<String> {
+        return s
+            .split(',')
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .map(String::from)
+            .collect();
+    }
 }
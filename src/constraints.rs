@@ -0,0 +1,215 @@
+pub mod constraints {
+    use std::collections::{HashMap, VecDeque};
+    use std::fmt;
+
+    use crate::stringer::StringFilter;
+
+    // aho-corasick automaton, all words searched in one pass over text
+    // however long the list is, so screening big batches stays fast
+    pub struct AhoCorasick {
+        patterns: Vec<String>,
+        // transitions of trie, state 0 is root
+        next: Vec<HashMap<char, usize>>,
+        // longest proper suffix of state that is also state
+        fail: Vec<usize>,
+        // patterns ending in state, own and through fail links
+        found: Vec<Vec<usize>>,
+    }
+    impl AhoCorasick {
+        pub fn new(patterns: &[String]) -> AhoCorasick {
+            let mut next: Vec<HashMap<char, usize>> = vec![HashMap::new()];
+            let mut found: Vec<Vec<usize>> = vec![Vec::new()];
+            for (i, pattern) in patterns.iter().enumerate() {
+                if pattern.is_empty() {
+                    continue;
+                }
+                let mut state = 0;
+                for c in pattern.chars() {
+                    state = match next[state].get(&c) {
+                        Some(child) => *child,
+                        None => {
+                            next.push(HashMap::new());
+                            found.push(Vec::new());
+                            let child = next.len() - 1;
+                            next[state].insert(c, child);
+                            child
+                        }
+                    };
+                }
+                found[state].push(i);
+            }
+            // breadth first, so fail links of shorter states are ready
+            let mut fail: Vec<usize> = vec![0; next.len()];
+            let mut queue: VecDeque<usize> = next[0].values().copied().collect();
+            while let Some(state) = queue.pop_front() {
+                let edges: Vec<(char, usize)> = next[state].iter().map(|(c, s)| (*c, *s)).collect();
+                for (c, child) in edges {
+                    let mut link = fail[state];
+                    while link != 0 && !next[link].contains_key(&c) {
+                        link = fail[link];
+                    }
+                    fail[child] = next[link].get(&c).copied().unwrap_or(0);
+                    let inherited = found[fail[child]].clone();
+                    found[child].extend(inherited);
+                    queue.push_back(child);
+                }
+            }
+            return AhoCorasick {
+                patterns: patterns.to_vec(),
+                next,
+                fail,
+                found,
+            };
+        }
+        pub fn len(&self) -> usize {
+            return self.patterns.len();
+        }
+        pub fn is_empty(&self) -> bool {
+            return self.patterns.is_empty();
+        }
+        fn step(&self, mut state: usize, c: char) -> usize {
+            loop {
+                if let Some(child) = self.next[state].get(&c) {
+                    return *child;
+                }
+                if state == 0 {
+                    return 0;
+                }
+                state = self.fail[state];
+            }
+        }
+        // pattern that ends first in text
+        pub fn find(&self, text: &str) -> Option<&str> {
+            let mut state = 0;
+            for c in text.chars() {
+                state = self.step(state, c);
+                if let Some(i) = self.found[state].first() {
+                    return Some(&self.patterns[*i]);
+                }
+            }
+            return None;
+        }
+        // for every pattern, whether it occurs in text
+        pub fn occurring(&self, text: &str) -> Vec<bool> {
+            let mut seen = vec![false; self.patterns.len()];
+            let mut state = 0;
+            for c in text.chars() {
+                state = self.step(state, c);
+                for i in self.found[state].iter() {
+                    seen[*i] = true;
+                }
+            }
+            return seen;
+        }
+        // every occurrence as (character index where it starts, pattern)
+        pub fn find_all(&self, text: &str) -> Vec<(usize, &str)> {
+            let mut result: Vec<(usize, &str)> = Vec::new();
+            let mut state = 0;
+            for (end, c) in text.chars().enumerate() {
+                state = self.step(state, c);
+                for i in self.found[state].iter() {
+                    let pattern = &self.patterns[*i];
+                    result.push((end + 1 - pattern.chars().count(), pattern));
+                }
+            }
+            return result;
+        }
+    }
+
+    // substring rule candidate breaks
+    #[derive(Clone, PartialEq, Debug)]
+    pub enum Violation {
+        Missing(String),
+        Forbidden(String),
+    }
+    impl fmt::Display for Violation {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            return match self {
+                Violation::Missing(s) => write!(f, "no {}", s),
+                Violation::Forbidden(s) => write!(f, "contains {}", s),
+            };
+        }
+    }
+
+    fn unchanged(s: &str) -> String {
+        return String::from(s);
+    }
+
+    // required and forbidden substrings, compiled once, checked in one pass each per candidate
+    // used by blocklists, password policies and vanity search (contains=, forbid=)
+    pub struct ConstraintEngine {
+        // every one must occur
+        required: AhoCorasick,
+        // none may occur
+        forbidden: AhoCorasick,
+        // applied to patterns and candidates alike, e.g. lowercase
+        normalize: fn(&str) -> String,
+    }
+    impl ConstraintEngine {
+        pub fn new(required: &[String], forbidden: &[String]) -> ConstraintEngine {
+            return ConstraintEngine::with_normalize(required, forbidden, unchanged);
+        }
+        pub fn with_normalize(
+            required: &[String],
+            forbidden: &[String],
+            normalize: fn(&str) -> String,
+        ) -> ConstraintEngine {
+            let compile = |patterns: &[String]| -> AhoCorasick {
+                let normalized: Vec<String> = patterns
+                    .iter()
+                    .map(|p| normalize(p))
+                    .filter(|p| !p.is_empty())
+                    .collect();
+                return AhoCorasick::new(&normalized);
+            };
+            return ConstraintEngine {
+                required: compile(required),
+                forbidden: compile(forbidden),
+                normalize,
+            };
+        }
+        pub fn is_empty(&self) -> bool {
+            return self.required.is_empty() && self.forbidden.is_empty();
+        }
+        // forbidden pattern found in value, as normalized
+        pub fn forbidden_in(&self, value: &str) -> Option<&str> {
+            if self.forbidden.is_empty() {
+                return None;
+            }
+            return self.forbidden.find(&(self.normalize)(value));
+        }
+        // required pattern not found in value, as normalized
+        pub fn missing_in(&self, value: &str) -> Option<&str> {
+            if self.required.is_empty() {
+                return None;
+            }
+            let seen = self.required.occurring(&(self.normalize)(value));
+            let first = seen.iter().position(|found| !found)?;
+            return Some(&self.required.patterns[first]);
+        }
+        pub fn violation(&self, value: &str) -> Option<Violation> {
+            if let Some(pattern) = self.forbidden_in(value) {
+                return Some(Violation::Forbidden(String::from(pattern)));
+            }
+            if let Some(pattern) = self.missing_in(value) {
+                return Some(Violation::Missing(String::from(pattern)));
+            }
+            return None;
+        }
+    }
+
+    // contains= and forbid= of config
+    pub struct ConstraintFilter {
+        engine: ConstraintEngine,
+    }
+    impl ConstraintFilter {
+        pub fn new(engine: ConstraintEngine) -> ConstraintFilter {
+            return ConstraintFilter { engine };
+        }
+    }
+    impl StringFilter for ConstraintFilter {
+        fn accepts(&mut self, value: &str) -> bool {
+            return self.engine.violation(value).is_none();
+        }
+    }
+}
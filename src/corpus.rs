@@ -0,0 +1,343 @@
+pub mod corpus {
+    use std::ffi::OsString;
+    use std::fs::File;
+    use std::io::{BufRead, BufReader, BufWriter, Error, ErrorKind, Read, Seek, SeekFrom, Write};
+    use std::path::{Path, PathBuf};
+
+    use crate::paths::paths::split_os;
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{
+        quotas, read_input, scramble, sidecar, wipe, Config, GeneratorVersion, RngBackend, RNG,
+    };
+
+    // uniform in (0, 1), never exactly 0 so it can be raised to any power
+    pub fn unit_float(rng: &mut dyn RngBackend) -> f64 {
+        return (rng.get() as f64 + 1.0) / (u32::MAX as f64 + 2.0);
+    }
+
+    // start of every line, so file itself is never held in memory
+    pub fn line_offsets(reader: &mut BufReader<File>) -> Result<Vec<u64>, Error> {
+        let mut offsets: Vec<u64> = Vec::new();
+        let mut position: u64 = 0;
+        let mut line: Vec<u8> = Vec::new();
+        loop {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if read == 0 {
+                break;
+            }
+            offsets.push(position);
+            position += read as u64;
+        }
+        return Ok(offsets);
+    }
+
+    // lines of existing text file sampled with or without replacement,
+    // optionally weighted by numeric column
+    pub struct CorpusSample {
+        path: PathBuf,
+        reader: Option<BufReader<File>>,
+        offsets: Vec<u64>,
+        replacement: bool,
+        // 1 based column holding weight, 0 means unweighted
+        weight_column: usize,
+        separator: char,
+        // running sum of weights for weighted draws with replacement
+        cumulative: Vec<f64>,
+        // line indices not drawn yet, or drawing order for weighted draws without replacement
+        pool: Vec<usize>,
+        version: GeneratorVersion,
+        rng: RNG,
+    }
+    impl Default for CorpusSample {
+        fn default() -> Self {
+            return CorpusSample::new();
+        }
+    }
+    impl CorpusSample {
+        pub fn new() -> CorpusSample {
+            let mut rng = RNG::new();
+            rng.seed();
+            return CorpusSample {
+                path: PathBuf::new(),
+                reader: None,
+                offsets: Vec::new(),
+                replacement: true,
+                weight_column: 0,
+                separator: '\t',
+                cumulative: Vec::new(),
+                pool: Vec::new(),
+                version: GeneratorVersion::latest(),
+                rng,
+            };
+        }
+        pub fn set_path(&mut self, path: PathBuf) {
+            self.path = path;
+        }
+        pub fn set_replacement(&mut self, replacement: bool) {
+            self.replacement = replacement;
+        }
+        pub fn set_weight_column(&mut self, column: usize, separator: char) {
+            self.weight_column = column;
+            self.separator = separator;
+        }
+        // file[:norep][:col=N][:sep=,], options are taken from the end so paths may hold ':'
+        pub fn set_options(&mut self, next: &OsString) {
+            let mut parts = split_os(next, ':');
+            while parts.len() > 1 {
+                let last = parts[parts.len() - 1].to_string_lossy().into_owned();
+                if last == "norep" {
+                    self.replacement = false;
+                } else if let Some(column) = last.strip_prefix("col=") {
+                    self.weight_column = column.parse().unwrap_or(0);
+                } else if let Some(separator) = last.strip_prefix("sep=") {
+                    self.separator = match separator {
+                        "tab" | "" => '\t',
+                        _ => separator.chars().next().unwrap_or('\t'),
+                    };
+                } else {
+                    break;
+                }
+                parts.pop();
+            }
+            let mut path = OsString::new();
+            for (i, part) in parts.iter().enumerate() {
+                if i > 0 {
+                    path.push(":");
+                }
+                path.push(part);
+            }
+            self.path = PathBuf::from(path);
+        }
+        pub fn len(&self) -> usize {
+            return self.offsets.len();
+        }
+        pub fn is_empty(&self) -> bool {
+            return self.offsets.is_empty();
+        }
+        fn read_line(&mut self, index: usize) -> Result<String, Error> {
+            let reader = match self.reader.as_mut() {
+                Some(reader) => reader,
+                None => return Err(Error::other("corpus is not opened")),
+            };
+            reader.seek(SeekFrom::Start(self.offsets[index]))?;
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            return Ok(String::from(line.trim_end_matches(['\n', '\r'])));
+        }
+        fn weight(&self, line: &str) -> f64 {
+            let field = line.split(self.separator).nth(self.weight_column - 1);
+            return match field.and_then(|f| f.trim().parse::<f64>().ok()) {
+                Some(weight) if weight > 0.0 && weight.is_finite() => weight,
+                _ => 0.0,
+            };
+        }
+        fn read_weights(&mut self) -> Result<Vec<f64>, Error> {
+            let mut weights: Vec<f64> = Vec::new();
+            for index in 0..self.offsets.len() {
+                let line = self.read_line(index)?;
+                weights.push(self.weight(&line));
+            }
+            return Ok(weights);
+        }
+        // index of next line, None when sampling without replacement ran out
+        fn next_index(&mut self) -> Option<usize> {
+            if self.replacement && self.weight_column > 0 {
+                let total = *self.cumulative.last()?;
+                let point = unit_float(&mut self.rng) * total;
+                let index = self.cumulative.partition_point(|sum| *sum <= point);
+                return Some(index.min(self.offsets.len() - 1));
+            }
+            if self.replacement {
+                return Some(self.version.pick_index(&mut self.rng, self.offsets.len()));
+            }
+            if self.pool.is_empty() {
+                return None;
+            }
+            if self.weight_column > 0 {
+                // order was drawn in setup
+                return self.pool.pop();
+            }
+            let pick = self.version.pick_index(&mut self.rng, self.pool.len());
+            return Some(self.pool.swap_remove(pick));
+        }
+    }
+    impl StringGenerator for CorpusSample {
+        fn get(&mut self) -> String {
+            return match self.next_index() {
+                Some(index) => self.read_line(index).unwrap_or_default(),
+                None => String::new(),
+            };
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            self.set_options(&conf.get_next_path().into_os_string());
+            self.version = conf.get_version();
+            let mut reader = BufReader::new(File::open(&self.path)?);
+            self.offsets = line_offsets(&mut reader)?;
+            self.reader = Some(reader);
+            if self.offsets.is_empty() {
+                let message = format!("corpus {} has no lines", self.path.display());
+                return Err(Error::new(ErrorKind::InvalidData, message));
+            }
+            let weights = if self.weight_column > 0 {
+                self.read_weights()?
+            } else {
+                Vec::new()
+            };
+            let available = if self.weight_column > 0 {
+                weights.iter().filter(|w| **w > 0.0).count()
+            } else {
+                self.offsets.len()
+            };
+            if available == 0 {
+                let message = format!("no line of {} has positive weight", self.path.display());
+                return Err(Error::new(ErrorKind::InvalidData, message));
+            }
+            if !self.replacement && (conf.get_amount() as usize) > available {
+                let message = format!(
+                    "can't draw {} lines without replacement from {} lines",
+                    conf.get_amount(),
+                    available
+                );
+                return Err(Error::new(ErrorKind::InvalidInput, message));
+            }
+            if self.replacement && self.weight_column > 0 {
+                let mut sum = 0.0;
+                self.cumulative = weights
+                    .iter()
+                    .map(|w| {
+                        sum += w;
+                        sum
+                    })
+                    .collect();
+            } else if self.weight_column > 0 {
+                // Efraimidis-Spirakis: key u^(1/w), largest keys first
+                let mut keyed: Vec<(f64, usize)> = Vec::new();
+                for (index, weight) in weights.iter().enumerate() {
+                    if *weight > 0.0 {
+                        let key = unit_float(&mut self.rng).powf(1.0 / weight);
+                        keyed.push((key, index));
+                    }
+                }
+                keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+                // pool is popped from the end
+                self.pool = keyed.iter().map(|(_key, index)| *index).collect();
+            } else if !self.replacement {
+                self.pool = (0..self.offsets.len()).collect();
+            }
+            return Ok(());
+        }
+    }
+
+    // names of partition files: train/val/test for up to three splits, part1.. otherwise
+    pub fn split_names(count: usize) -> Vec<String> {
+        return match count {
+            1 => vec![String::from("shuffled")],
+            2 => vec![String::from("train"), String::from("test")],
+            3 => vec![
+                String::from("train"),
+                String::from("val"),
+                String::from("test"),
+            ],
+            _ => (1..=count).map(|i| format!("part{}", i)).collect(),
+        };
+    }
+
+    // shuffles lines of file with seeded rng and writes them split by percentages
+    // (80,10,10) into file.train, file.val and file.test, returns written paths
+    pub fn shuffle_file(path: &Path, splits: &[u32], seed: u64) -> Result<Vec<PathBuf>, Error> {
+        if splits.is_empty() || splits.iter().all(|s| *s == 0) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "splits must not be all zero",
+            ));
+        }
+        let mut reader = BufReader::new(File::open(path)?);
+        let offsets = line_offsets(&mut reader)?;
+        let mut order: Vec<usize> = (0..offsets.len()).collect();
+        let mut rng = RNG::from_seed(seed);
+        let version = GeneratorVersion::latest();
+        // fisher-yates
+        for i in (1..order.len()).rev() {
+            let j = version.pick_index(&mut rng, i + 1);
+            order.swap(i, j);
+        }
+        let counts = quotas(splits, offsets.len() as u32);
+        let mut written: Vec<PathBuf> = Vec::new();
+        let mut taken = 0;
+        for (count, name) in counts.iter().zip(split_names(splits.len())) {
+            let out_path = sidecar(path, &name);
+            let mut writer = BufWriter::new(File::create(&out_path)?);
+            for index in order[taken..taken + *count as usize].iter() {
+                reader.seek(SeekFrom::Start(offsets[*index]))?;
+                let end = match offsets.get(*index + 1) {
+                    Some(end) => *end,
+                    None => u64::MAX,
+                };
+                let mut line: Vec<u8> = Vec::new();
+                (&mut reader)
+                    .take(end - offsets[*index])
+                    .read_to_end(&mut line)?;
+                // last line may lack newline
+                if !line.ends_with(b"\n") {
+                    line.push(b'\n');
+                }
+                writer.write_all(&line)?;
+            }
+            writer.flush()?;
+            taken += *count as usize;
+            written.push(out_path);
+        }
+        return Ok(written);
+    }
+
+    // lines of input scrambled one by one, for scrubbing logs and screenshots of real data
+    pub struct Scrubbed {
+        lines: Vec<String>,
+        position: usize,
+        rng: RNG,
+    }
+    impl Default for Scrubbed {
+        fn default() -> Self {
+            return Scrubbed::new();
+        }
+    }
+    impl Scrubbed {
+        pub fn new() -> Scrubbed {
+            let mut rng = RNG::new();
+            rng.seed();
+            return Scrubbed {
+                lines: Vec::new(),
+                position: 0,
+                rng,
+            };
+        }
+    }
+    impl Drop for Scrubbed {
+        fn drop(&mut self) {
+            self.lines.iter_mut().for_each(wipe);
+        }
+    }
+    impl StringGenerator for Scrubbed {
+        fn get(&mut self) -> String {
+            let Some(line) = self.lines.get_mut(self.position) else {
+                return String::new();
+            };
+            self.position += 1;
+            let scrambled = scramble(line, &mut self.rng);
+            // real line is not needed anymore
+            wipe(line);
+            return scrambled;
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            self.lines = read_input(&conf.get_next())?;
+            self.position = 0;
+            return Ok(());
+        }
+        fn exhausted(&self) -> bool {
+            return self.position >= self.lines.len();
+        }
+    }
+}
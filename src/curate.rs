@@ -0,0 +1,171 @@
+pub mod curate {
+    use std::fs::{File, OpenOptions};
+    use std::io::{stdin, stdout, BufWriter, Error, Read, Write};
+    use std::path::Path;
+
+    use crate::stringer::{build_filters, next_accepted, sidecar, stringer, tr, tr_with, Config};
+
+    // what user decided about candidate
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum Verdict {
+        Accept,
+        Reject,
+        Star,
+        Quit,
+    }
+    impl Verdict {
+        pub fn from(key: char) -> Option<Verdict> {
+            return match key.to_ascii_lowercase() {
+                'a' | 'y' | '+' => Some(Verdict::Accept),
+                'r' | 'n' | '-' => Some(Verdict::Reject),
+                's' | '*' => Some(Verdict::Star),
+                'q' => Some(Verdict::Quit),
+                _ => None,
+            };
+        }
+    }
+
+    // counts of one session
+    #[derive(Clone, Copy, PartialEq, Debug, Default)]
+    pub struct Curation {
+        pub shown: u32,
+        pub accepted: u32,
+        pub starred: u32,
+        pub rejected: u32,
+    }
+
+    // next key meaning something, other keys (newlines too) are skipped, None at end of input
+    fn next_verdict(input: &mut dyn Read) -> Result<Option<Verdict>, Error> {
+        let mut byte = [0u8; 1];
+        loop {
+            if input.read(&mut byte)? == 0 {
+                return Ok(None);
+            }
+            if let Some(verdict) = Verdict::from(byte[0] as char) {
+                return Ok(Some(verdict));
+            }
+        }
+    }
+
+    // shows up to amount candidates, page at a time, and asks verdict for each in order;
+    // accepted and starred go to accepted, starred also to starred
+    pub fn curate(
+        conf: &Config,
+        page: usize,
+        input: &mut dyn Read,
+        screen: &mut dyn Write,
+        accepted: &mut dyn Write,
+        starred: &mut dyn Write,
+    ) -> Result<Curation, Error> {
+        let mut sg = stringer(conf.clone());
+        sg.setup(conf)?;
+        let mut filters = build_filters(conf)?;
+        let mut curation = Curation::default();
+        let page = page.max(1);
+        while curation.shown < conf.get_amount() {
+            let mut candidates: Vec<String> = Vec::new();
+            while candidates.len() < page && curation.shown < conf.get_amount() {
+                match next_accepted(sg.as_mut(), &mut filters)? {
+                    Some(value) => candidates.push(conf.get_escape().apply(&value)),
+                    None => break,
+                }
+                curation.shown += 1;
+            }
+            if candidates.is_empty() {
+                break;
+            }
+            for (i, candidate) in candidates.iter().enumerate() {
+                writeln!(screen, "{:>4}  {}", i + 1, candidate)?;
+            }
+            writeln!(screen, "{}", tr("curate.prompt"))?;
+            screen.flush()?;
+            for candidate in candidates.iter() {
+                match next_verdict(input)? {
+                    Some(Verdict::Accept) => {
+                        writeln!(accepted, "{}", candidate)?;
+                        curation.accepted += 1;
+                    }
+                    Some(Verdict::Star) => {
+                        writeln!(accepted, "{}", candidate)?;
+                        writeln!(starred, "{}", candidate)?;
+                        curation.accepted += 1;
+                        curation.starred += 1;
+                    }
+                    Some(Verdict::Reject) => {
+                        curation.rejected += 1;
+                    }
+                    Some(Verdict::Quit) | None => {
+                        accepted.flush()?;
+                        starred.flush()?;
+                        return Ok(curation);
+                    }
+                }
+                // decisions survive ctrl-c
+                accepted.flush()?;
+                starred.flush()?;
+            }
+        }
+        return Ok(curation);
+    }
+
+    // terminal hands over keys without waiting for enter while this lives
+    struct SingleKeys {
+        active: bool,
+    }
+    impl SingleKeys {
+        fn enable() -> SingleKeys {
+            use std::io::IsTerminal;
+            if !cfg!(unix) || !stdin().is_terminal() {
+                return SingleKeys { active: false };
+            }
+            let status = std::process::Command::new("stty")
+                .args(["-icanon", "min", "1"])
+                .status();
+            return SingleKeys {
+                active: matches!(status, Ok(s) if s.success()),
+            };
+        }
+    }
+    impl Drop for SingleKeys {
+        fn drop(&mut self) {
+            if self.active {
+                let _ = std::process::Command::new("stty").arg("icanon").status();
+            }
+        }
+    }
+
+    fn append(path: &Path) -> Result<BufWriter<File>, Error> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        return Ok(BufWriter::new(file));
+    }
+
+    // interactive session on terminal, names are appended so sessions add up
+    pub fn run_curate(conf: &Config, path: &Path, page: usize) -> Result<Curation, Error> {
+        let mut accepted = append(path)?;
+        let mut starred = append(&sidecar(path, "starred"))?;
+        let keys = SingleKeys::enable();
+        let result = curate(
+            conf,
+            page,
+            &mut stdin(),
+            &mut stdout(),
+            &mut accepted,
+            &mut starred,
+        );
+        drop(keys);
+        let curation = result?;
+        println!(
+            "{}",
+            tr_with(
+                "curate.summary",
+                &[
+                    &curation.accepted.to_string(),
+                    &curation.starred.to_string(),
+                    &curation.rejected.to_string(),
+                    &path.display().to_string(),
+                ]
+            )
+        );
+        return Ok(curation);
+    }
+}
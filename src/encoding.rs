@@ -0,0 +1,272 @@
+pub mod encoding {
+    const BASE62: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+    pub fn base62(value: u64) -> String {
+        return base62_u128(value as u128);
+    }
+
+    pub fn base62_u128(mut value: u128) -> String {
+        if value == 0 {
+            return String::from("0");
+        }
+        let mut digits: Vec<u8> = Vec::new();
+        while value > 0 {
+            digits.push(BASE62[(value % 62) as usize]);
+            value /= 62;
+        }
+        digits.reverse();
+        return String::from_utf8(digits).unwrap();
+    }
+
+    // none for other characters or number over 128 bits, case matters
+    pub fn base62_decode(s: &str) -> Option<u128> {
+        let s = s.trim();
+        if s.is_empty() {
+            return None;
+        }
+        let mut value: u128 = 0;
+        for c in s.bytes() {
+            let digit = BASE62.iter().position(|d| *d == c)? as u128;
+            value = value.checked_mul(62)?.checked_add(digit)?;
+        }
+        return Some(value);
+    }
+
+    // crc-32 of zip and png (ieee polynomial, reflected)
+    pub fn crc32(bytes: &[u8]) -> u32 {
+        let mut crc: u32 = 0xFFFF_FFFF;
+        for byte in bytes {
+            crc ^= *byte as u32;
+            for _ in 0..8 {
+                let mask = (crc & 1).wrapping_neg();
+                crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+            }
+        }
+        return !crc;
+    }
+
+    // escapes for use inside a json string literal, quotes are not added
+    pub fn json_escape(s: &str) -> String {
+        let mut escaped = String::with_capacity(s.len());
+        for c in s.chars() {
+            match c {
+                '"' => escaped.push_str("\\\""),
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\t' => escaped.push_str("\\t"),
+                c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+                c => escaped.push(c),
+            }
+        }
+        return escaped;
+    }
+
+    const BASE64URL: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    // url safe base64 without padding, as used in jwt
+    pub fn base64url(bytes: &[u8]) -> String {
+        let mut encoded = String::with_capacity(bytes.len() * 4 / 3 + 3);
+        for chunk in bytes.chunks(3) {
+            let b0 = chunk[0] as u32;
+            let b1 = *chunk.get(1).unwrap_or(&0) as u32;
+            let b2 = *chunk.get(2).unwrap_or(&0) as u32;
+            let triple = (b0 << 16) | (b1 << 8) | b2;
+            let symbols = chunk.len() + 1;
+            for i in 0..symbols {
+                let index = (triple >> (18 - 6 * i)) & 0x3f;
+                encoded.push(BASE64URL[index as usize] as char);
+            }
+        }
+        return encoded;
+    }
+
+    // crockford's base32: no i, l, o, u, so nothing reads as other character or word
+    pub const CROCKFORD32: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+    // lowest 5 * digits bits of value, most significant first, zero padded
+    pub fn crockford32(value: u128, digits: usize) -> String {
+        let mut encoded = String::with_capacity(digits);
+        for i in (0..digits).rev() {
+            let index = (value >> (5 * i)) & 0x1f;
+            encoded.push(CROCKFORD32[index as usize] as char);
+        }
+        return encoded;
+    }
+
+    // check symbols 32..36 of crockford base32, check symbol is value mod 37
+    pub const CROCKFORD_CHECK: &[u8] = b"*~$=U";
+
+    // value in fewest digits, or at least digits zero padded, and check symbol when asked
+    pub fn crockford_number(value: u128, digits: usize, check: bool) -> String {
+        let needed = ((128 - value.leading_zeros() as usize).div_ceil(5)).max(1);
+        let mut encoded = crockford32(value, needed.max(digits.min(26)));
+        if check {
+            let symbol = (value % 37) as usize;
+            encoded.push(if symbol < 32 {
+                CROCKFORD32[symbol] as char
+            } else {
+                CROCKFORD_CHECK[symbol - 32] as char
+            });
+        }
+        return encoded;
+    }
+
+    // any case, hyphens ignored, i l read as 1 and o as 0; none when check symbol is wrong
+    pub fn crockford_decode(s: &str, check: bool) -> Option<u128> {
+        let mut symbols: Vec<u8> = s
+            .trim()
+            .bytes()
+            .filter(|c| *c != b'-')
+            .map(|c| match c.to_ascii_uppercase() {
+                b'I' | b'L' => b'1',
+                b'O' => b'0',
+                c => c,
+            })
+            .collect();
+        let expected = if check { symbols.pop() } else { None };
+        if symbols.is_empty() {
+            return None;
+        }
+        let mut value: u128 = 0;
+        for c in symbols {
+            let digit = CROCKFORD32.iter().position(|d| *d == c)? as u128;
+            value = value.checked_mul(32)?.checked_add(digit)?;
+        }
+        if let Some(expected) = expected {
+            let symbol = (value % 37) as usize;
+            let actual = if symbol < 32 {
+                CROCKFORD32[symbol]
+            } else {
+                CROCKFORD_CHECK[symbol - 32]
+            };
+            if actual != expected {
+                return None;
+            }
+        }
+        return Some(value);
+    }
+
+    const PROQUINT_CONSONANTS: &[u8] = b"bdfghjklmnprstvz";
+    const PROQUINT_VOWELS: &[u8] = b"aiou";
+
+    // proquints: every 16 bits are consonant vowel consonant vowel consonant, words joined by
+    // dashes (127.0.0.1 is lusab-babad); odd last byte is padded with zero byte
+    pub fn proquint(bytes: &[u8]) -> String {
+        let mut words: Vec<String> = Vec::with_capacity(bytes.len().div_ceil(2));
+        for pair in bytes.chunks(2) {
+            let value = ((pair[0] as usize) << 8) | *pair.get(1).unwrap_or(&0) as usize;
+            let word = [
+                PROQUINT_CONSONANTS[(value >> 12) & 0xf],
+                PROQUINT_VOWELS[(value >> 10) & 0x3],
+                PROQUINT_CONSONANTS[(value >> 6) & 0xf],
+                PROQUINT_VOWELS[(value >> 4) & 0x3],
+                PROQUINT_CONSONANTS[value & 0xf],
+            ];
+            words.push(String::from_utf8_lossy(&word).into_owned());
+        }
+        return words.join("-");
+    }
+
+    // none when any word is not proquint
+    pub fn proquint_decode(s: &str) -> Option<Vec<u8>> {
+        let mut bytes: Vec<u8> = Vec::new();
+        for word in s.trim().split('-') {
+            let letters = word.as_bytes();
+            if letters.len() != 5 {
+                return None;
+            }
+            let consonant = |i: usize| PROQUINT_CONSONANTS.iter().position(|c| *c == letters[i]);
+            let vowel = |i: usize| PROQUINT_VOWELS.iter().position(|v| *v == letters[i]);
+            let value = (consonant(0)? << 12)
+                | (vowel(1)? << 10)
+                | (consonant(2)? << 6)
+                | (vowel(3)? << 4)
+                | consonant(4)?;
+            bytes.push((value >> 8) as u8);
+            bytes.push((value & 0xff) as u8);
+        }
+        return Some(bytes);
+    }
+
+    const BUBBLE_VOWELS: &[u8] = b"aeiouy";
+    const BUBBLE_CONSONANTS: &[u8] = b"bcdfghklmnprstvzx";
+
+    // bubble babble of openssh fingerprints: x, tuples of five letters for every two bytes with
+    // running checksum woven into vowels, x; empty input is xexax
+    pub fn bubblebabble(bytes: &[u8]) -> String {
+        let vowel = |i: usize| BUBBLE_VOWELS[i] as char;
+        let consonant = |i: usize| BUBBLE_CONSONANTS[i] as char;
+        let mut encoded = String::from("x");
+        let mut seed: usize = 1;
+        let rounds = bytes.len() / 2 + 1;
+        for i in 0..rounds {
+            if i + 1 < rounds || !bytes.len().is_multiple_of(2) {
+                let b1 = bytes[2 * i] as usize;
+                encoded.push(vowel((((b1 >> 6) & 3) + seed) % 6));
+                encoded.push(consonant((b1 >> 2) & 15));
+                encoded.push(vowel(((b1 & 3) + seed / 6) % 6));
+                if i + 1 < rounds {
+                    let b2 = bytes[2 * i + 1] as usize;
+                    encoded.push(consonant((b2 >> 4) & 15));
+                    encoded.push('-');
+                    encoded.push(consonant(b2 & 15));
+                    seed = (seed * 5 + b1 * 7 + b2) % 36;
+                }
+            } else {
+                encoded.push(vowel(seed % 6));
+                encoded.push(consonant(16));
+                encoded.push(vowel(seed / 6));
+            }
+        }
+        encoded.push('x');
+        return encoded;
+    }
+
+    // none when string is not bubble babble or its checksum is wrong
+    pub fn bubblebabble_decode(s: &str) -> Option<Vec<u8>> {
+        let letters = s.trim().as_bytes();
+        if letters.len() < 5 || letters[0] != b'x' || letters[letters.len() - 1] != b'x' {
+            return None;
+        }
+        let body = &letters[1..letters.len() - 1];
+        let vowel = |c: u8| BUBBLE_VOWELS.iter().position(|v| *v == c);
+        let consonant = |c: u8| BUBBLE_CONSONANTS.iter().position(|v| *v == c);
+        // byte of vowel, consonant, vowel, checked against seed
+        let byte = |t: &[u8], seed: usize| {
+            let high = (vowel(t[0])? + 6 - seed % 6) % 6;
+            let middle = consonant(t[1]).filter(|c| *c < 16)?;
+            let low = (vowel(t[2])? + 6 - (seed / 6) % 6) % 6;
+            if high > 3 || low > 3 {
+                return None;
+            }
+            return Some(((high << 6) | (middle << 2) | low) as u8);
+        };
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut seed: usize = 1;
+        let mut rest = body;
+        while rest.len() > 3 {
+            if rest.len() < 6 || rest[4] != b'-' {
+                return None;
+            }
+            let b1 = byte(&rest[..3], seed)?;
+            let b2 = (consonant(rest[3]).filter(|c| *c < 16)? << 4)
+                | consonant(rest[5]).filter(|c| *c < 16)?;
+            bytes.push(b1);
+            bytes.push(b2 as u8);
+            seed = (seed * 5 + b1 as usize * 7 + b2) % 36;
+            rest = &rest[6..];
+        }
+        if rest.len() != 3 {
+            return None;
+        }
+        if rest[1] == b'x' {
+            if vowel(rest[0])? != seed % 6 || vowel(rest[2])? != seed / 6 {
+                return None;
+            }
+        } else {
+            bytes.push(byte(rest, seed)?);
+        }
+        return Some(bytes);
+    }
+}
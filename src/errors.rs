@@ -0,0 +1,103 @@
+pub mod errors {
+    use std::fmt;
+    use std::io::{Error, ErrorKind};
+    use std::path::PathBuf;
+
+    use crate::stringer::tr_with;
+
+    // what can go wrong with input given by user, instead of panic
+    #[derive(Debug)]
+    pub enum StrgenError {
+        // what was expected to be number, and what was given
+        BadNumber(String, String),
+        UnknownMode(String),
+        // key that is none of alt arguments
+        UnknownSetting(String),
+        // amount or length of zero
+        NotPositive(String),
+        // setting and value that is none of its choices
+        BadValue(String, String),
+        // language and mode it can't be used with
+        LanguageMismatch(String, String),
+        MissingListFile(PathBuf),
+        EmptyList(PathBuf),
+        Io(Error),
+    }
+    impl StrgenError {
+        pub fn kind(&self) -> ErrorKind {
+            return match self {
+                StrgenError::BadNumber(_, _)
+                | StrgenError::UnknownMode(_)
+                | StrgenError::UnknownSetting(_)
+                | StrgenError::NotPositive(_)
+                | StrgenError::BadValue(_, _)
+                | StrgenError::LanguageMismatch(_, _) => ErrorKind::InvalidInput,
+                StrgenError::MissingListFile(_) => ErrorKind::NotFound,
+                StrgenError::EmptyList(_) => ErrorKind::InvalidData,
+                StrgenError::Io(e) => e.kind(),
+            };
+        }
+    }
+    // text is in ui language, see locales/
+    impl fmt::Display for StrgenError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let text = match self {
+                StrgenError::BadNumber(what, value) => tr_with("error.bad_number", &[what, value]),
+                StrgenError::UnknownMode(mode) => tr_with("error.unknown_mode", &[mode]),
+                StrgenError::UnknownSetting(key) => tr_with("error.unknown_setting", &[key]),
+                StrgenError::NotPositive(what) => tr_with("error.not_positive", &[what]),
+                StrgenError::BadValue(what, value) => tr_with("error.bad_value", &[what, value]),
+                StrgenError::LanguageMismatch(language, mode) => {
+                    tr_with("error.language_mismatch", &[language, mode])
+                }
+                StrgenError::MissingListFile(path) => {
+                    tr_with("error.missing_list", &[&path.display().to_string()])
+                }
+                StrgenError::EmptyList(path) => {
+                    tr_with("error.empty_list", &[&path.display().to_string()])
+                }
+                StrgenError::Io(e) => e.to_string(),
+            };
+            return write!(f, "{}", text);
+        }
+    }
+    impl std::error::Error for StrgenError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            return match self {
+                StrgenError::Io(e) => Some(e),
+                _ => None,
+            };
+        }
+    }
+    impl From<Error> for StrgenError {
+        fn from(e: Error) -> Self {
+            // comes back out of io error it was wrapped in
+            if e.get_ref().is_some_and(|inner| inner.is::<StrgenError>()) {
+                let inner = e.into_inner().unwrap();
+                return *inner.downcast::<StrgenError>().unwrap();
+            }
+            return StrgenError::Io(e);
+        }
+    }
+    // generators and sinks return io errors, this one travels inside them
+    impl From<StrgenError> for Error {
+        fn from(e: StrgenError) -> Self {
+            return match e {
+                StrgenError::Io(e) => e,
+                e => Error::new(e.kind(), e),
+            };
+        }
+    }
+
+    // strgen error carried by io error, if there is one
+    pub fn strgen_error(e: &Error) -> Option<&StrgenError> {
+        return e.get_ref()?.downcast_ref::<StrgenError>();
+    }
+
+    pub fn parse_number(what: &str, value: &str) -> Result<u32, StrgenError> {
+        return value
+            .trim()
+            .parse()
+            .map_err(|_e| StrgenError::BadNumber(String::from(what), String::from(value)));
+    }
+}
@@ -1,29 +1,34 @@
 pub mod fast_switch {
     use crate::stringer::Config;
     use crate::stringer::Modes;
-    pub fn get_fsconf(onkstr: String) -> Config {
+    use crate::stringer::tr;
+    use crate::stringer::{parse_number, StrgenError};
+    pub fn get_fsconf(onkstr: String) -> Result<Config, StrgenError> {
         let mut conf = Config::default();
         let split: Vec<&str> = onkstr.split("-f").collect();
         // println!("{}",split[0]);
         let s: String = String::from(split[1]);
-        let zero = s.chars().nth(0).unwrap();
+        let zero = s.chars().next().unwrap_or(' ');
         if 'n' == zero {
             // number
             // -fn16
             let split: Vec<&str> = s.split("n").collect();
-            let num: u32 = split[1].parse().expect("number isnot?!");
+            let num: u32 = parse_number("amount", split[1])?;
             conf.set_amount(num);
         } else if 's' == zero {
             // length/size
             // -fs32
             let split: Vec<&str> = s.split("s").collect();
-            let num: u32 = split[1].parse().expect("number isnot?!");
+            let num: u32 = parse_number("length", split[1])?;
             conf.set_length(num);
         } else if 'm' == zero {
             // mode
             // -fmrla
             let split: Vec<&str> = s.split("m").collect();
-            let mode = Modes::from(split[1]);
+            let mode = match Modes::parse(split[1]) {
+                Some(mode) => mode,
+                None => return Err(StrgenError::UnknownMode(String::from(split[1]))),
+            };
             conf.set_mode(mode);
         } else if 'l' == zero {
             // language
@@ -34,7 +39,7 @@ pub mod fast_switch {
         } else {
             // do nothing
         }
-        return conf;
+        return Ok(conf);
     }
 
     pub fn alias_config(strung: String, next: String) -> Config {
@@ -46,7 +51,7 @@ pub mod fast_switch {
         };
 
         if next.len() < required_length {
-            println!("one paramter missing, returning defualt config");
+            println!("{}", tr("alias.missing"));
             return conf;
         }
         match option {
@@ -122,10 +127,10 @@ pub mod fast_switch {
     }
 
     pub fn is_alias(strong: &str) -> bool {
-        return match strong {
+        return matches!(
+            strong,
             "R1" | "R2" | "R3" | "C1" | "C2" | "C3" | "N1" | "S1" | "S2" | "S3"
-            | "S4" | "S5" | "S6" | "S7" | "S8" | "S9" | "SX" | "PWD" | "PWD84" => true,
-            _ => false,
-        };
+                | "S4" | "S5" | "S6" | "S7" | "S8" | "S9" | "SX" | "PWD" | "PWD84"
+        );
     }
 }
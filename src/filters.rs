@@ -0,0 +1,1033 @@
+pub mod filters {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::{HashMap, HashSet};
+    use std::fs::{File, OpenOptions};
+    use std::hash::{Hash, Hasher};
+    use std::io::{BufWriter, Error, ErrorKind, Write};
+    use std::path::{Path, PathBuf};
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{
+        fnv1a, read_lines, Config, ConstraintEngine, ConstraintFilter, OutputProfile,
+        PasswordPolicy, PolicyFilter, ProfanityFilter, ReservationFilter, Reservations,
+    };
+    use crate::stringer::tr_with;
+
+    // generated strings that fail a filter are thrown away and generated again
+    pub trait StringFilter {
+        fn accepts(&mut self, value: &str) -> bool;
+        // called for every string that made it to output
+        fn remember(&mut self, _value: &str) {}
+        // error that made filter reject, e.g. lost database, stops the run
+        fn failure(&mut self) -> Option<Error> {
+            return None;
+        }
+    }
+
+    // consecutive rejections before run gives up
+    pub const MAX_REJECTS: u32 = 10000;
+
+    // edit distance over characters, insert, delete and substitute cost 1
+    pub fn levenshtein(a: &str, b: &str) -> usize {
+        let b: Vec<char> = b.chars().collect();
+        let mut row: Vec<usize> = (0..=b.len()).collect();
+        for (i, ca) in a.chars().enumerate() {
+            let mut diagonal = row[0];
+            row[0] = i + 1;
+            for (j, cb) in b.iter().enumerate() {
+                let above = row[j + 1];
+                let cost = if ca == *cb { 0 } else { 1 };
+                row[j + 1] = (above + 1).min(row[j] + 1).min(diagonal + cost);
+                diagonal = above;
+            }
+        }
+        return row[b.len()];
+    }
+
+    // burkhard-keller tree, finds words within edit distance without comparing to all
+    pub struct BkTree {
+        // word and children by their distance to it
+        nodes: Vec<(String, HashMap<usize, usize>)>,
+    }
+    impl Default for BkTree {
+        fn default() -> Self {
+            return BkTree::new();
+        }
+    }
+    impl BkTree {
+        pub fn new() -> BkTree {
+            return BkTree { nodes: Vec::new() };
+        }
+        pub fn len(&self) -> usize {
+            return self.nodes.len();
+        }
+        pub fn is_empty(&self) -> bool {
+            return self.nodes.is_empty();
+        }
+        pub fn insert(&mut self, word: &str) {
+            if self.nodes.is_empty() {
+                self.nodes.push((String::from(word), HashMap::new()));
+                return;
+            }
+            let mut current = 0;
+            loop {
+                let distance = levenshtein(word, &self.nodes[current].0);
+                if distance == 0 {
+                    return;
+                }
+                match self.nodes[current].1.get(&distance) {
+                    Some(child) => current = *child,
+                    None => {
+                        let index = self.nodes.len();
+                        self.nodes.push((String::from(word), HashMap::new()));
+                        self.nodes[current].1.insert(distance, index);
+                        return;
+                    }
+                }
+            }
+        }
+        // true when some word is within max_distance of word
+        pub fn has_within(&self, word: &str, max_distance: usize) -> bool {
+            return self.find_within(word, max_distance).is_some();
+        }
+        // some word within max_distance of word and its distance
+        pub fn find_within(&self, word: &str, max_distance: usize) -> Option<(String, usize)> {
+            if self.nodes.is_empty() {
+                return None;
+            }
+            let mut stack: Vec<usize> = vec![0];
+            while let Some(current) = stack.pop() {
+                let (node_word, children) = &self.nodes[current];
+                let distance = levenshtein(word, node_word);
+                if distance <= max_distance {
+                    return Some((node_word.clone(), distance));
+                }
+                // triangle inequality, only these subtrees can hold close words
+                let low = distance.saturating_sub(max_distance);
+                let high = distance + max_distance;
+                for (child_distance, child) in children.iter() {
+                    if *child_distance >= low && *child_distance <= high {
+                        stack.push(*child);
+                    }
+                }
+            }
+            return None;
+        }
+    }
+
+    // every two outputs differ in at least min_distance edits
+    pub struct EditDistanceFilter {
+        min_distance: usize,
+        tree: BkTree,
+    }
+    impl EditDistanceFilter {
+        pub fn new(min_distance: usize) -> EditDistanceFilter {
+            return EditDistanceFilter {
+                min_distance,
+                tree: BkTree::new(),
+            };
+        }
+    }
+    impl StringFilter for EditDistanceFilter {
+        fn accepts(&mut self, value: &str) -> bool {
+            return !self.tree.has_within(value, self.min_distance - 1);
+        }
+        fn remember(&mut self, value: &str) {
+            self.tree.insert(value);
+        }
+    }
+
+    // no two outputs start with same prefix_length characters
+    pub struct PrefixFilter {
+        prefix_length: usize,
+        prefixes: HashSet<String>,
+    }
+    impl PrefixFilter {
+        pub fn new(prefix_length: usize) -> PrefixFilter {
+            return PrefixFilter {
+                prefix_length,
+                prefixes: HashSet::new(),
+            };
+        }
+        fn prefix(&self, value: &str) -> String {
+            return value.chars().take(self.prefix_length).collect();
+        }
+    }
+    impl StringFilter for PrefixFilter {
+        fn accepts(&mut self, value: &str) -> bool {
+            return !self.prefixes.contains(&self.prefix(value));
+        }
+        fn remember(&mut self, value: &str) {
+            let prefix = self.prefix(value);
+            self.prefixes.insert(prefix);
+        }
+    }
+
+    // runs of more strings than this remember them in bloom filter, not by hash
+    pub const UNIQUE_BLOOM_ABOVE: u32 = 1_000_000;
+    // duplicates in a row before unique run gives up
+    pub const UNIQUE_RETRIES: u32 = 1000;
+
+    // bits of k hashes per string, may take new string for seen one but never the other way
+    pub struct BloomFilter {
+        bits: Vec<u64>,
+        hashes: u32,
+    }
+    impl BloomFilter {
+        // sized for expected strings with given chance of false positive
+        pub fn new(expected: usize, false_positive: f64) -> BloomFilter {
+            let ln2 = std::f64::consts::LN_2;
+            let bits = (-(expected.max(1) as f64) * false_positive.ln() / (ln2 * ln2)).ceil();
+            let hashes = ((bits / expected.max(1) as f64) * ln2).round().max(1.0) as u32;
+            return BloomFilter {
+                bits: vec![0; (bits as usize).div_ceil(64).max(1)],
+                hashes,
+            };
+        }
+        // double hashing, fnv1a and sip give every bit index
+        fn indices(&self, value: &str) -> Vec<usize> {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            let (a, b) = (fnv1a(value), hasher.finish() | 1);
+            let len = (self.bits.len() * 64) as u64;
+            return (0..self.hashes as u64)
+                .map(|i| (a.wrapping_add(i.wrapping_mul(b)) % len) as usize)
+                .collect();
+        }
+        pub fn contains(&self, value: &str) -> bool {
+            return self
+                .indices(value)
+                .iter()
+                .all(|i| self.bits[i / 64] & (1 << (i % 64)) != 0);
+        }
+        pub fn insert(&mut self, value: &str) {
+            for i in self.indices(value) {
+                self.bits[i / 64] |= 1 << (i % 64);
+            }
+        }
+    }
+
+    // strings given so far, by hash so secrets are not kept around
+    enum Seen {
+        Hashes(HashSet<u64>),
+        Bloom(BloomFilter),
+    }
+
+    // no string twice in one run, run stops when mode has no new ones left
+    pub struct UniqueFilter {
+        seen: Seen,
+        count: u64,
+        duplicates: u32,
+    }
+    impl UniqueFilter {
+        pub fn new(amount: u32) -> UniqueFilter {
+            let seen = if amount > UNIQUE_BLOOM_ABOVE {
+                Seen::Bloom(BloomFilter::new(amount as usize, 1e-6))
+            } else {
+                Seen::Hashes(HashSet::new())
+            };
+            return UniqueFilter {
+                seen,
+                count: 0,
+                duplicates: 0,
+            };
+        }
+    }
+    impl StringFilter for UniqueFilter {
+        fn accepts(&mut self, value: &str) -> bool {
+            let seen = match &self.seen {
+                Seen::Hashes(hashes) => hashes.contains(&fnv1a(value)),
+                Seen::Bloom(bloom) => bloom.contains(value),
+            };
+            if seen {
+                self.duplicates += 1;
+            }
+            return !seen;
+        }
+        fn remember(&mut self, value: &str) {
+            match &mut self.seen {
+                Seen::Hashes(hashes) => {
+                    hashes.insert(fnv1a(value));
+                }
+                Seen::Bloom(bloom) => bloom.insert(value),
+            }
+            self.count += 1;
+            self.duplicates = 0;
+        }
+        fn failure(&mut self) -> Option<Error> {
+            if self.duplicates < UNIQUE_RETRIES {
+                return None;
+            }
+            let message = format!(
+                "mode ran out of unique strings after {}, {} duplicates in a row; \
+                 lower amount, raise length or use longer lists",
+                self.count, self.duplicates
+            );
+            return Some(Error::other(message));
+        }
+    }
+
+    // strings of earlier runs, one per line, are never given again; new ones can be
+    // appended to record file, same file for both keeps names unique across runs
+    pub struct ExcludeFileFilter {
+        // by hash, like unique strings of run
+        seen: HashSet<u64>,
+        record: Option<File>,
+        // failed append, reported at next string so no run goes on unrecorded
+        error: Option<Error>,
+    }
+    impl ExcludeFileFilter {
+        pub fn new() -> ExcludeFileFilter {
+            return ExcludeFileFilter {
+                seen: HashSet::new(),
+                record: None,
+                error: None,
+            };
+        }
+        pub fn open(exclude: &str, record: &str) -> Result<ExcludeFileFilter, Error> {
+            let mut filter = ExcludeFileFilter::new();
+            // record file of first run doesn't exist yet
+            let first_run = exclude == record && !Path::new(exclude).exists();
+            if !exclude.is_empty() && !first_run {
+                filter.exclude(Path::new(exclude))?;
+            }
+            if !record.is_empty() {
+                let file = OpenOptions::new().create(true).append(true).open(record)?;
+                filter.record = Some(file);
+            }
+            return Ok(filter);
+        }
+        pub fn exclude(&mut self, path: &Path) -> Result<(), Error> {
+            for line in read_lines(path)? {
+                let line = line?;
+                let value = line.trim();
+                if !value.is_empty() {
+                    self.seen.insert(fnv1a(value));
+                }
+            }
+            return Ok(());
+        }
+        pub fn excluded(&self) -> usize {
+            return self.seen.len();
+        }
+    }
+    impl Default for ExcludeFileFilter {
+        fn default() -> Self {
+            return ExcludeFileFilter::new();
+        }
+    }
+    impl StringFilter for ExcludeFileFilter {
+        fn accepts(&mut self, value: &str) -> bool {
+            return self.error.is_none() && !self.seen.contains(&fnv1a(value.trim()));
+        }
+        fn remember(&mut self, value: &str) {
+            self.seen.insert(fnv1a(value.trim()));
+            if let Some(record) = self.record.as_mut() {
+                let line = format!("{}\n", value);
+                if let Err(e) = record.write_all(line.as_bytes()) {
+                    self.error = Some(e);
+                }
+            }
+        }
+        fn failure(&mut self) -> Option<Error> {
+            return self.error.take();
+        }
+    }
+
+    // american soundex of one word: first letter and three digits, R163
+    pub fn soundex(word: &str) -> String {
+        let letters: Vec<char> = word
+            .chars()
+            .filter(|c| c.is_ascii_alphabetic())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        if letters.is_empty() {
+            return String::new();
+        }
+        let digit = |c: char| -> char {
+            return match c {
+                'B' | 'F' | 'P' | 'V' => '1',
+                'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => '2',
+                'D' | 'T' => '3',
+                'L' => '4',
+                'M' | 'N' => '5',
+                'R' => '6',
+                'H' | 'W' => '-',
+                _ => '0',
+            };
+        };
+        let mut code = String::from(letters[0]);
+        let mut last = digit(letters[0]);
+        for c in letters[1..].iter() {
+            let d = digit(*c);
+            // h and w don't separate equal codes, vowels do
+            if d == '-' {
+                continue;
+            }
+            if d != '0' && d != last {
+                code.push(d);
+            }
+            last = d;
+            if code.len() == 4 {
+                break;
+            }
+        }
+        while code.len() < 4 {
+            code.push('0');
+        }
+        return code;
+    }
+
+    // original metaphone of one word, 0 stands for th
+    pub fn metaphone(word: &str) -> String {
+        let mut w: Vec<char> = word
+            .chars()
+            .filter(|c| c.is_ascii_alphabetic())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        // doubled letters count once, except c
+        w.dedup_by(|a, b| a == b && *a != 'C');
+        if w.len() >= 2 {
+            match (w[0], w[1]) {
+                ('A', 'E') | ('G', 'N') | ('K', 'N') | ('P', 'N') | ('W', 'R') => {
+                    w.remove(0);
+                }
+                ('W', 'H') => {
+                    w.remove(1);
+                }
+                _ => {}
+            }
+        }
+        if w.first() == Some(&'X') {
+            w[0] = 'S';
+        }
+        let is_vowel = |c: Option<&char>| matches!(c, Some('A' | 'E' | 'I' | 'O' | 'U'));
+        let is_front = |c: Option<&char>| matches!(c, Some('E' | 'I' | 'Y'));
+        let mut code = String::new();
+        for i in 0..w.len() {
+            let c = w[i];
+            let prev = if i > 0 { w.get(i - 1) } else { None };
+            let next = w.get(i + 1);
+            let after = w.get(i + 2);
+            match c {
+                'A' | 'E' | 'I' | 'O' | 'U' => {
+                    if i == 0 {
+                        code.push(c);
+                    }
+                }
+                'B' => {
+                    // dumb
+                    if !(prev == Some(&'M') && next.is_none()) {
+                        code.push('B');
+                    }
+                }
+                'C' => {
+                    if prev == Some(&'S') && is_front(next) {
+                        // scene, science
+                    } else if next == Some(&'I') && after == Some(&'A') {
+                        code.push('X');
+                    } else if next == Some(&'H') {
+                        code.push(if prev == Some(&'S') { 'K' } else { 'X' });
+                    } else if is_front(next) {
+                        code.push('S');
+                    } else {
+                        code.push('K');
+                    }
+                }
+                'D' => {
+                    if next == Some(&'G') && is_front(after) {
+                        code.push('J');
+                    } else {
+                        code.push('T');
+                    }
+                }
+                'G' => {
+                    if next == Some(&'H') && !(after.is_none() || is_vowel(after)) {
+                        // night
+                    } else if next == Some(&'N') && (after.is_none() || w[i + 2..] == ['E', 'D']) {
+                        // sign, signed
+                    } else if is_front(next) && prev != Some(&'G') {
+                        code.push('J');
+                    } else {
+                        code.push('K');
+                    }
+                }
+                'H' => {
+                    let after_vowel = is_vowel(prev) && !is_vowel(next);
+                    let after_group = matches!(prev, Some('C' | 'S' | 'P' | 'T' | 'G'));
+                    if !after_vowel && !after_group {
+                        code.push('H');
+                    }
+                }
+                'K' => {
+                    if prev != Some(&'C') {
+                        code.push('K');
+                    }
+                }
+                'P' => code.push(if next == Some(&'H') { 'F' } else { 'P' }),
+                'Q' => code.push('K'),
+                'S' => {
+                    let sio = next == Some(&'I') && matches!(after, Some('O' | 'A'));
+                    if next == Some(&'H') || sio {
+                        code.push('X');
+                    } else {
+                        code.push('S');
+                    }
+                }
+                'T' => {
+                    if next == Some(&'I') && matches!(after, Some('O' | 'A')) {
+                        code.push('X');
+                    } else if next == Some(&'H') {
+                        code.push('0');
+                    } else if !(next == Some(&'C') && after == Some(&'H')) {
+                        code.push('T');
+                    }
+                }
+                'V' => code.push('F'),
+                'W' | 'Y' => {
+                    if is_vowel(next) {
+                        code.push(c);
+                    }
+                }
+                'X' => code.push_str("KS"),
+                'Z' => code.push('S'),
+                _ => code.push(c),
+            }
+        }
+        return code;
+    }
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum Phonetic {
+        Soundex,
+        Metaphone,
+    }
+    impl Phonetic {
+        pub fn from(s: &str) -> Option<Phonetic> {
+            return match s {
+                "soundex" | "sdx" => Some(Phonetic::Soundex),
+                "metaphone" | "mph" => Some(Phonetic::Metaphone),
+                _ => None,
+            };
+        }
+        // code of every word, words without latin letters are kept as they are
+        pub fn encode(&self, value: &str) -> String {
+            let words = value.split(|c: char| !c.is_alphanumeric());
+            let codes: Vec<String> = words
+                .filter(|w| !w.is_empty())
+                .map(|word| {
+                    let code = match *self {
+                        Phonetic::Soundex => soundex(word),
+                        Phonetic::Metaphone => metaphone(word),
+                    };
+                    if code.is_empty() {
+                        word.to_lowercase()
+                    } else {
+                        code
+                    }
+                })
+                .collect();
+            return codes.join(" ");
+        }
+    }
+
+    // no two outputs sound alike
+    pub struct PhoneticFilter {
+        phonetic: Phonetic,
+        codes: HashSet<String>,
+    }
+    impl PhoneticFilter {
+        pub fn new(phonetic: Phonetic) -> PhoneticFilter {
+            return PhoneticFilter {
+                phonetic,
+                codes: HashSet::new(),
+            };
+        }
+    }
+    impl StringFilter for PhoneticFilter {
+        fn accepts(&mut self, value: &str) -> bool {
+            return !self.codes.contains(&self.phonetic.encode(value));
+        }
+        fn remember(&mut self, value: &str) {
+            self.codes.insert(self.phonetic.encode(value));
+        }
+    }
+
+    // reserved words shipped with binary, lists/reserved has the sources
+    pub const RESERVED_SETS: [(&str, &str); 8] = [
+        ("sql", include_str!("../lists/reserved/sql.list")),
+        ("rust", include_str!("../lists/reserved/rust.list")),
+        ("python", include_str!("../lists/reserved/python.list")),
+        (
+            "javascript",
+            include_str!("../lists/reserved/javascript.list"),
+        ),
+        ("java", include_str!("../lists/reserved/java.list")),
+        ("c", include_str!("../lists/reserved/c.list")),
+        ("go", include_str!("../lists/reserved/go.list")),
+        ("windows", include_str!("../lists/reserved/windows.list")),
+    ];
+
+    // [mode:]set entries, e.g. sql,fpath:windows
+    pub fn parse_exclusions(s: &str) -> Vec<(String, String)> {
+        let mut result: Vec<(String, String)> = Vec::new();
+        for part in s.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            let (mode, set) = part.split_once(':').unwrap_or(("*", part));
+            result.push((String::from(mode), String::from(set)));
+        }
+        return result;
+    }
+
+    // generated identifiers and file names never equal reserved tokens, case is ignored
+    pub struct ReservedWordFilter {
+        words: HashSet<String>,
+        // windows names are reserved with any extension and in any path component
+        file_names: bool,
+    }
+    impl ReservedWordFilter {
+        pub fn new(sets: &[String]) -> Result<ReservedWordFilter, Error> {
+            let mut words: HashSet<String> = HashSet::new();
+            let mut file_names = false;
+            for set in sets.iter() {
+                let content = match RESERVED_SETS.iter().find(|(name, _c)| name == set) {
+                    Some((_name, content)) => content,
+                    None => {
+                        let names: Vec<&str> = RESERVED_SETS.iter().map(|(n, _c)| *n).collect();
+                        let message = format!(
+                            "unknown reserved word set {}, known: {}",
+                            set,
+                            names.join(", ")
+                        );
+                        return Err(Error::new(ErrorKind::InvalidInput, message));
+                    }
+                };
+                file_names = file_names || set == "windows";
+                for word in content.split([',', '\n']).map(|w| w.trim()) {
+                    if !word.is_empty() {
+                        words.insert(word.to_lowercase());
+                    }
+                }
+            }
+            return Ok(ReservedWordFilter { words, file_names });
+        }
+        pub fn is_reserved(&self, value: &str) -> bool {
+            if self.words.contains(&value.to_lowercase()) {
+                return true;
+            }
+            if self.file_names {
+                for component in value.split(['/', '\\']) {
+                    let stem = component.split('.').next().unwrap_or("").trim_end();
+                    if self.words.contains(&stem.to_lowercase()) {
+                        return true;
+                    }
+                }
+            }
+            return false;
+        }
+    }
+    impl StringFilter for ReservedWordFilter {
+        fn accepts(&mut self, value: &str) -> bool {
+            return !self.is_reserved(value);
+        }
+    }
+
+    // protected names file with options: names.txt[:dist=N][:phon=soundex][:log=rejected.txt]
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct Screening {
+        pub path: PathBuf,
+        // rejected when edit distance is at most this
+        pub max_distance: usize,
+        pub phonetic: Option<Phonetic>,
+        pub log: Option<PathBuf>,
+    }
+    impl Screening {
+        pub fn from(s: &str) -> Screening {
+            let mut screening = Screening {
+                path: PathBuf::new(),
+                max_distance: 2,
+                phonetic: None,
+                log: None,
+            };
+            let mut parts: Vec<&str> = s.split(':').collect();
+            // options come last, path may hold ':' itself
+            while parts.len() > 1 {
+                let last = parts[parts.len() - 1];
+                if let Some(distance) = last.strip_prefix("dist=") {
+                    screening.max_distance = distance.parse().unwrap_or(2);
+                } else if let Some(phonetic) = last.strip_prefix("phon=") {
+                    screening.phonetic = Phonetic::from(phonetic);
+                } else if let Some(log) = last.strip_prefix("log=") {
+                    screening.log = Some(PathBuf::from(log));
+                } else {
+                    break;
+                }
+                parts.pop();
+            }
+            screening.path = PathBuf::from(parts.join(":"));
+            return screening;
+        }
+    }
+
+    // brand names are compared without case, spaces and punctuation: Coca-Cola is cocacola
+    pub fn brand_key(s: &str) -> String {
+        return s
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(|c| c.to_lowercase())
+            .collect();
+    }
+
+    // rejects candidates too close to protected names, in spelling or in sound
+    pub struct TrademarkFilter {
+        tree: BkTree,
+        max_distance: usize,
+        // brand key -> protected name as listed
+        names: HashMap<String, String>,
+        // phonetic code -> protected name
+        sounds: HashMap<String, String>,
+        phonetic: Option<Phonetic>,
+        log: Option<BufWriter<File>>,
+    }
+    impl TrademarkFilter {
+        pub fn new(
+            names: &[String],
+            max_distance: usize,
+            phonetic: Option<Phonetic>,
+        ) -> TrademarkFilter {
+            let mut tree = BkTree::new();
+            let mut keys: HashMap<String, String> = HashMap::new();
+            let mut sounds: HashMap<String, String> = HashMap::new();
+            for name in names.iter() {
+                tree.insert(&brand_key(name));
+                keys.insert(brand_key(name), name.clone());
+                if let Some(phonetic) = phonetic {
+                    sounds.insert(phonetic.encode(name), name.clone());
+                }
+            }
+            return TrademarkFilter {
+                tree,
+                max_distance,
+                names: keys,
+                sounds,
+                phonetic,
+                log: None,
+            };
+        }
+        pub fn load(screening: &Screening) -> Result<TrademarkFilter, Error> {
+            let mut names: Vec<String> = Vec::new();
+            for line in read_lines(&screening.path)? {
+                let line = line?;
+                if !line.trim().is_empty() && !line.starts_with('#') {
+                    names.push(String::from(line.trim()));
+                }
+            }
+            let mut filter =
+                TrademarkFilter::new(&names, screening.max_distance, screening.phonetic);
+            if let Some(log) = &screening.log {
+                filter.set_log(BufWriter::new(File::create(log)?));
+            }
+            return Ok(filter);
+        }
+        // rejected candidates are written there as candidate<tab>protected name<tab>reason
+        pub fn set_log(&mut self, log: BufWriter<File>) {
+            self.log = Some(log);
+        }
+        // protected name candidate collides with and why
+        pub fn conflict(&self, value: &str) -> Option<(String, String)> {
+            if let Some((key, distance)) =
+                self.tree.find_within(&brand_key(value), self.max_distance)
+            {
+                let name = self.names.get(&key).cloned().unwrap_or(key);
+                return Some((name, format!("edit {}", distance)));
+            }
+            if let Some(phonetic) = self.phonetic {
+                if let Some(name) = self.sounds.get(&phonetic.encode(value)) {
+                    return Some((name.clone(), format!("{:?}", phonetic).to_lowercase()));
+                }
+            }
+            return None;
+        }
+    }
+    impl StringFilter for TrademarkFilter {
+        fn accepts(&mut self, value: &str) -> bool {
+            let (name, reason) = match self.conflict(value) {
+                Some(conflict) => conflict,
+                None => return true,
+            };
+            if let Some(log) = self.log.as_mut() {
+                // review log is best effort, it never stops the run
+                let _ = writeln!(log, "{}\t{}\t{}", value, name, reason);
+                let _ = log.flush();
+            }
+            return false;
+        }
+    }
+
+    // next string passing all filters, None when generator ran out
+    pub fn next_accepted(
+        sg: &mut dyn StringGenerator,
+        filters: &mut [Box<dyn StringFilter>],
+    ) -> Result<Option<String>, Error> {
+        let mut rejects = 0;
+        loop {
+            if sg.exhausted() {
+                return Ok(None);
+            }
+            let value = sg.get();
+            if let Some(e) = sg.failure() {
+                return Err(e);
+            }
+            if filters.iter_mut().all(|f| f.accepts(&value)) {
+                for filter in filters.iter_mut() {
+                    filter.remember(&value);
+                }
+                return Ok(Some(value));
+            }
+            for filter in filters.iter_mut() {
+                if let Some(e) = filter.failure() {
+                    return Err(e);
+                }
+            }
+            rejects += 1;
+            if rejects >= MAX_REJECTS {
+                let message = tr_with("filter.rejected", &[&rejects.to_string()]);
+                return Err(Error::other(message));
+            }
+        }
+    }
+
+    // filters asked for in config
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum KeyboardLayout {
+        Qwerty,
+        Qwertz,
+        Azerty,
+        Dvorak,
+    }
+    impl KeyboardLayout {
+        pub fn from(s: &str) -> Option<KeyboardLayout> {
+            return match s {
+                "qwerty" | "us" | "en" => Some(KeyboardLayout::Qwerty),
+                "qwertz" | "de" => Some(KeyboardLayout::Qwertz),
+                "azerty" | "fr" => Some(KeyboardLayout::Azerty),
+                "dvorak" => Some(KeyboardLayout::Dvorak),
+                _ => None,
+            };
+        }
+        // unshifted rows, number row first
+        fn rows(&self) -> [&'static str; 4] {
+            return match *self {
+                KeyboardLayout::Qwerty => {
+                    ["1234567890-=", "qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./"]
+                }
+                KeyboardLayout::Qwertz => {
+                    ["1234567890ß", "qwertzuiopü+", "asdfghjklöä#", "<yxcvbnm,.-"]
+                }
+                KeyboardLayout::Azerty => [
+                    "&é\"'(-è_çà)=",
+                    "azertyuiop^$",
+                    "qsdfghjklmù*",
+                    "<wxcvbn,;:!",
+                ],
+                KeyboardLayout::Dvorak => {
+                    ["1234567890[]", "',.pyfgcrl/=", "aoeuidhtns-", ";qjkxbmwvz"]
+                }
+            };
+        }
+        // same keys with shift, key for key
+        fn shifted_rows(&self) -> [&'static str; 4] {
+            return match *self {
+                KeyboardLayout::Qwerty => {
+                    ["!@#$%^&*()_+", "QWERTYUIOP{}", "ASDFGHJKL:\"", "ZXCVBNM<>?"]
+                }
+                KeyboardLayout::Qwertz => [
+                    "!\"§$%&/()=?",
+                    "QWERTZUIOPÜ*",
+                    "ASDFGHJKLÖÄ'",
+                    ">YXCVBNM;:_",
+                ],
+                KeyboardLayout::Azerty => [
+                    "1234567890°+",
+                    "AZERTYUIOP¨£",
+                    "QSDFGHJKLM%µ",
+                    ">WXCVBN?./§",
+                ],
+                KeyboardLayout::Dvorak => {
+                    ["!@#$%^&*(){}", "\"<>PYFGCRL?+", "AOEUIDHTNS_", ":QJKXBMWVZ"]
+                }
+            };
+        }
+        // rows are staggered: tab row starts half key right of number row, home row quarter more
+        const OFFSETS: [f32; 4] = [0.0, 0.5, 0.75, 1.25];
+
+        // row and horizontal position of the key typing c
+        pub fn position(&self, c: char) -> Option<(usize, f32)> {
+            let rows = self.rows().into_iter().enumerate();
+            let shifted = self.shifted_rows().into_iter().enumerate();
+            for (row, keys) in rows.chain(shifted) {
+                if let Some(column) = keys.chars().position(|k| k == c) {
+                    return Some((row, column as f32 + KeyboardLayout::OFFSETS[row]));
+                }
+            }
+            return None;
+        }
+        // keys touching each other, including diagonal ones like 1 and q
+        pub fn adjacent(&self, a: char, b: char) -> bool {
+            let (Some((row_a, x_a)), Some((row_b, x_b))) = (self.position(a), self.position(b))
+            else {
+                return false;
+            };
+            let dx = (x_a - x_b).abs();
+            if row_a == row_b {
+                return dx > 0.0 && dx <= 1.0;
+            }
+            return row_a.abs_diff(row_b) == 1 && dx <= 1.0;
+        }
+        // keys around the one typing c, with shift held same as for c
+        pub fn neighbours(&self, c: char) -> Vec<char> {
+            let rows = if self.rows().iter().any(|keys| keys.contains(c)) {
+                self.rows()
+            } else {
+                self.shifted_rows()
+            };
+            return rows
+                .iter()
+                .flat_map(|keys| keys.chars())
+                .filter(|k| *k != c && self.adjacent(c, *k))
+                .collect();
+        }
+        // first run of at least min_run keys each next to the one before, like qwert or 1qaz
+        pub fn find_walk(&self, s: &str, min_run: usize) -> Option<String> {
+            let chars: Vec<char> = s.chars().collect();
+            let mut start = 0;
+            for i in 1..=chars.len() {
+                if i < chars.len() && self.adjacent(chars[i - 1], chars[i]) {
+                    continue;
+                }
+                if i - start >= min_run.max(2) {
+                    return Some(chars[start..i].iter().collect());
+                }
+                start = i;
+            }
+            return None;
+        }
+    }
+
+    // walks=layout[,layout..][:N], e.g. walks=qwerty or walks=qwertz,azerty:3
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct KeyboardWalks {
+        pub layouts: Vec<String>,
+        // shortest run of neighbouring keys that counts as walk
+        pub min_run: usize,
+    }
+    impl KeyboardWalks {
+        pub fn from(s: &str) -> KeyboardWalks {
+            let (names, min_run) = match s.rsplit_once(':') {
+                Some((names, n)) => (names, n.trim().parse().unwrap_or(4)),
+                None => (s, 4),
+            };
+            let mut layouts: Vec<String> = names
+                .split(',')
+                .map(|n| String::from(n.trim()))
+                .filter(|n| !n.is_empty())
+                .collect();
+            if layouts.is_empty() {
+                layouts.push(String::from("qwerty"));
+            }
+            return KeyboardWalks { layouts, min_run };
+        }
+    }
+
+    // rejects strings with keyboard walks, a usual password policy rule
+    pub struct KeyboardWalkFilter {
+        layouts: Vec<KeyboardLayout>,
+        min_run: usize,
+    }
+    impl KeyboardWalkFilter {
+        pub fn new(walks: &KeyboardWalks) -> Result<KeyboardWalkFilter, Error> {
+            let mut layouts = Vec::new();
+            for name in walks.layouts.iter() {
+                match KeyboardLayout::from(name) {
+                    Some(layout) => layouts.push(layout),
+                    None => {
+                        let message = format!("unknown keyboard layout {}", name);
+                        return Err(Error::new(ErrorKind::InvalidInput, message));
+                    }
+                }
+            }
+            return Ok(KeyboardWalkFilter {
+                layouts,
+                min_run: walks.min_run,
+            });
+        }
+        pub fn find_walk(&self, s: &str) -> Option<String> {
+            return self
+                .layouts
+                .iter()
+                .find_map(|layout| layout.find_walk(s, self.min_run));
+        }
+    }
+    impl StringFilter for KeyboardWalkFilter {
+        fn accepts(&mut self, value: &str) -> bool {
+            return self.find_walk(value).is_none();
+        }
+    }
+
+    pub fn build_filters(conf: &Config) -> Result<Vec<Box<dyn StringFilter>>, Error> {
+        let mut filters: Vec<Box<dyn StringFilter>> = Vec::new();
+        if conf.get_min_distance() > 0 {
+            filters.push(Box::new(EditDistanceFilter::new(conf.get_min_distance())));
+        }
+        if conf.get_min_prefix() > 0 {
+            filters.push(Box::new(PrefixFilter::new(conf.get_min_prefix())));
+        }
+        if conf.get_unique() {
+            filters.push(Box::new(UniqueFilter::new(conf.get_amount())));
+        }
+        if !conf.get_exclude_file().is_empty() || !conf.get_record().is_empty() {
+            let filter = ExcludeFileFilter::open(&conf.get_exclude_file(), &conf.get_record())?;
+            filters.push(Box::new(filter));
+        }
+        // sets without mode apply to every run, others only to runs of that mode
+        let mode = conf.get_mode().abbr();
+        let sets: Vec<String> = conf
+            .get_exclusions()
+            .iter()
+            .filter(|(m, _set)| m == "*" || *m == mode)
+            .map(|(_m, set)| set.clone())
+            .collect();
+        if !sets.is_empty() {
+            filters.push(Box::new(ReservedWordFilter::new(&sets)?));
+        }
+        if let Some(screening) = conf.get_screening() {
+            filters.push(Box::new(TrademarkFilter::load(&screening)?));
+        }
+        if let Some(phonetic) = conf.get_phonetic() {
+            filters.push(Box::new(PhoneticFilter::new(phonetic)));
+        }
+        if !conf.get_policy().is_empty() {
+            let policy = PasswordPolicy::load(&conf.get_policy())?;
+            filters.push(Box::new(PolicyFilter::new(policy)?));
+        }
+        if !conf.get_profanity().is_empty() {
+            filters.push(Box::new(ProfanityFilter::load(&conf.get_profanity())?));
+        } else if !conf.get_profile().is_empty()
+            && OutputProfile::from(&conf.get_profile())?.profanity()
+        {
+            filters.push(Box::new(ProfanityFilter::new()));
+        }
+        if let Some(walks) = conf.get_keyboard_walks() {
+            filters.push(Box::new(KeyboardWalkFilter::new(&walks)?));
+        }
+        let engine = ConstraintEngine::new(&conf.get_required(), &conf.get_forbidden());
+        if !engine.is_empty() {
+            filters.push(Box::new(ConstraintFilter::new(engine)));
+        }
+        // last, so only strings passing everything else get claimed
+        if let Some(spec) = conf.get_reservation() {
+            let reservations = Reservations::open_spec(&spec)?;
+            filters.push(Box::new(ReservationFilter::new(reservations)));
+        }
+        return Ok(filters);
+    }
+}
@@ -0,0 +1,584 @@
+pub mod fixtures {
+    use std::fs::{create_dir_all, OpenOptions};
+    use std::io::Error;
+    use std::path::{Path, PathBuf};
+
+    use crate::strgen::string_generator_module::*;
+    use crate::stringer::{
+        apache, base64url, iso8601, json_escape, now_millis, Config, Languages, ListType, RNG,
+    };
+
+    #[derive(Clone)]
+    pub enum LogFormat {
+        Logfmt,
+        Json,
+        Apache,
+    }
+    impl LogFormat {
+        pub fn from(s: &str) -> LogFormat {
+            return match s {
+                "json" => LogFormat::Json,
+                "apache" | "combined" => LogFormat::Apache,
+                _ => LogFormat::Logfmt,
+            };
+        }
+    }
+
+    // fabricated log lines, timestamps move forward from now
+    pub struct LogLines {
+        format: LogFormat,
+        modules: WordList,
+        messages: SimpleSentences,
+        request_ids: LettterSequence,
+        rng: RNG,
+        millis: u64,
+    }
+    impl LogLines {
+        const LEVELS: [&'static str; 10] = [
+            "INFO", "INFO", "INFO", "INFO", "INFO", "DEBUG", "DEBUG", "WARN", "WARN", "ERROR",
+        ];
+        const METHODS: [&'static str; 6] = ["GET", "GET", "GET", "POST", "PUT", "DELETE"];
+        const STATUSES: [u32; 8] = [200, 200, 200, 201, 204, 301, 404, 500];
+        const AGENTS: [&'static str; 3] = [
+            "Mozilla/5.0 (X11; Linux x86_64)",
+            "curl/8.5.0",
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64)",
+        ];
+
+        pub fn new(format: LogFormat) -> LogLines {
+            let mut rng = RNG::new();
+            rng.seed();
+            return LogLines {
+                format,
+                modules: WordList::new(ListType::Nouns, Languages::English),
+                messages: SimpleSentences::new(Languages::English),
+                request_ids: LettterSequence::new("0123456789abcdef", 16),
+                rng,
+                millis: now_millis(),
+            };
+        }
+        fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+            let index = self.rng.get() as usize % items.len();
+            return items[index];
+        }
+        fn module_path(&mut self) -> String {
+            let first = self.modules.get().to_lowercase().replace(' ', "_");
+            let second = self.modules.get().to_lowercase().replace(' ', "_");
+            return format!("app::{}::{}", first, second);
+        }
+    }
+    impl StringGenerator for LogLines {
+        fn get(&mut self) -> String {
+            self.millis += (self.rng.get() % 2000) as u64;
+            match self.format {
+                LogFormat::Logfmt => {
+                    let level = self.pick(&LogLines::LEVELS);
+                    let module = self.module_path();
+                    return format!(
+                        "ts={} level={} module={} request_id={} msg=\"{}\"",
+                        iso8601(self.millis),
+                        level.to_lowercase(),
+                        module,
+                        self.request_ids.get(),
+                        self.messages.get().replace('"', "\\\"")
+                    );
+                }
+                LogFormat::Json => {
+                    let level = self.pick(&LogLines::LEVELS);
+                    let module = self.module_path();
+                    return format!(
+                        "{{\"ts\":\"{}\",\"level\":\"{}\",\"module\":\"{}\",\"request_id\":\"{}\",\"msg\":\"{}\"}}",
+                        iso8601(self.millis),
+                        level,
+                        json_escape(&module),
+                        self.request_ids.get(),
+                        json_escape(&self.messages.get())
+                    );
+                }
+                LogFormat::Apache => {
+                    let ip = format!(
+                        "10.{}.{}.{}",
+                        self.rng.get() % 256,
+                        self.rng.get() % 256,
+                        self.rng.get() % 256
+                    );
+                    let method = self.pick(&LogLines::METHODS);
+                    let path = self.module_path().replace("::", "/");
+                    let status =
+                        LogLines::STATUSES[self.rng.get() as usize % LogLines::STATUSES.len()];
+                    let agent = self.pick(&LogLines::AGENTS);
+                    return format!(
+                        "{} - - [{}] \"{} /{} HTTP/1.1\" {} {} \"-\" \"{}\"",
+                        ip,
+                        apache(self.millis),
+                        method,
+                        path,
+                        status,
+                        self.rng.get() % 50000,
+                        agent
+                    );
+                }
+            }
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            self.format = LogFormat::from(conf.get_next().as_ref());
+            self.modules.set_version(conf.get_version());
+            self.modules.fill(Path::new(""))?;
+            self.messages.setup(conf)?;
+            return Ok(());
+        }
+    }
+
+    // characters that are invalid in file names on at least one major os
+    const UNSAFE_CHARS: &str = "<>:\"/\\|?*";
+    const RESERVED_NAMES: [&str; 22] = [
+        "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+        "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+    ];
+
+    // file name component safe on windows, macos and linux
+    pub fn safe_file_name(s: &str) -> String {
+        let mut name: String = s
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_control() || c == ' ' || UNSAFE_CHARS.contains(c) {
+                    '_'
+                } else {
+                    c
+                }
+            })
+            .collect();
+        // windows drops trailing dots and spaces
+        while name.ends_with('.') {
+            name.pop();
+        }
+        if name.is_empty() {
+            name = String::from("_");
+        }
+        let stem = name.split('.').next().unwrap_or("").to_lowercase();
+        if RESERVED_NAMES.contains(&stem.as_ref()) {
+            name = format!("_{}", name);
+        }
+        return name;
+    }
+
+    // relative file paths with directories from word lists
+    pub struct FilePaths {
+        extensions: Vec<String>,
+        max_depth: u32,
+        materialize: bool,
+        nouns: WordList,
+        adjectives: WordList,
+        rng: RNG,
+    }
+    impl FilePaths {
+        pub fn new() -> FilePaths {
+            let mut rng = RNG::new();
+            rng.seed();
+            return FilePaths {
+                extensions: vec![String::from("txt")],
+                max_depth: 3,
+                materialize: false,
+                nouns: WordList::new(ListType::Nouns, Languages::English),
+                adjectives: WordList::new(ListType::Adjectives, Languages::English),
+                rng,
+            };
+        }
+        pub fn set_extensions(&mut self, extensions: &str) {
+            self.extensions = extensions
+                .split(',')
+                .map(|e| e.trim().trim_start_matches('.'))
+                .filter(|e| !e.is_empty())
+                .map(String::from)
+                .collect();
+            if self.extensions.is_empty() {
+                self.extensions.push(String::from("txt"));
+            }
+        }
+        pub fn set_max_depth(&mut self, depth: u32) {
+            self.max_depth = depth;
+        }
+        pub fn set_materialize(&mut self, materialize: bool) {
+            self.materialize = materialize;
+        }
+        pub fn next_path(&mut self) -> PathBuf {
+            let mut path = PathBuf::new();
+            let depth = self.rng.get() % (self.max_depth + 1);
+            for _i in 0..depth {
+                path.push(safe_file_name(&self.nouns.get().to_lowercase()));
+            }
+            let index = self.rng.get() as usize % self.extensions.len();
+            let stem = format!("{}_{}", self.adjectives.get(), self.nouns.get()).to_lowercase();
+            let file_name = format!("{}.{}", safe_file_name(&stem), self.extensions[index]);
+            path.push(file_name);
+            return path;
+        }
+        fn create_empty(&self, path: &Path) -> Result<(), Error> {
+            if let Some(parent) = path.parent() {
+                create_dir_all(parent)?;
+            }
+            OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(path)?;
+            return Ok(());
+        }
+    }
+    impl Default for FilePaths {
+        fn default() -> Self {
+            return FilePaths::new();
+        }
+    }
+    impl StringGenerator for FilePaths {
+        fn get(&mut self) -> String {
+            let path = self.next_path();
+            if self.materialize {
+                if let Err(e) = self.create_empty(&path) {
+                    eprintln!("{}: {}", path.display(), e);
+                }
+            }
+            return path.to_string_lossy().into_owned();
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            // next: extensions separated by comma[:max depth]
+            let next = conf.get_next();
+            let parts: Vec<&str> = next.split(':').collect();
+            self.set_extensions(parts[0]);
+            if parts.len() > 1 {
+                self.set_max_depth(parts[1].parse().unwrap_or(3));
+            }
+            self.set_materialize(conf.get_materialize());
+            self.nouns.set_version(conf.get_version());
+            self.adjectives.set_version(conf.get_version());
+            self.nouns.fill(Path::new(""))?;
+            self.adjectives.fill(Path::new(""))?;
+            return Ok(());
+        }
+    }
+
+    // lowercase ascii slug, other characters become hyphens
+    pub fn slugify(s: &str) -> String {
+        let mut slug = String::new();
+        for c in s.trim().chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c.to_ascii_lowercase());
+            } else if !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        return String::from(slug.trim_matches('-'));
+    }
+
+    // json descriptions of http requests for api gateway tests
+    pub struct HttpFixtures {
+        adjectives: WordList,
+        nouns: WordList,
+        tokens: LettterSequence,
+        request_ids: LettterSequence,
+        rng: RNG,
+    }
+    impl Default for HttpFixtures {
+        fn default() -> Self {
+            return HttpFixtures::new();
+        }
+    }
+    impl HttpFixtures {
+        const METHODS: [&'static str; 7] = ["GET", "GET", "GET", "POST", "PUT", "PATCH", "DELETE"];
+        const ACCEPT: [&'static str; 3] = ["application/json", "*/*", "text/html"];
+        const LANGUAGES: [&'static str; 4] = ["en-US", "de-DE", "ka-GE", "fr-FR"];
+        const AGENTS: [&'static str; 3] = [
+            "Mozilla/5.0 (X11; Linux x86_64)",
+            "curl/8.5.0",
+            "okhttp/4.12.0",
+        ];
+
+        pub fn new() -> HttpFixtures {
+            let mut rng = RNG::new();
+            rng.seed();
+            let token_alphabet = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+            return HttpFixtures {
+                adjectives: WordList::new(ListType::Adjectives, Languages::English),
+                nouns: WordList::new(ListType::Nouns, Languages::English),
+                tokens: LettterSequence::new(token_alphabet, 40),
+                request_ids: LettterSequence::new("0123456789abcdef", 16),
+                rng,
+            };
+        }
+        fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+            let index = self.rng.get() as usize % items.len();
+            return items[index];
+        }
+        fn path(&mut self) -> String {
+            let mut path = format!("/api/v{}", self.rng.get() % 3 + 1);
+            let depth = self.rng.get() % 3 + 1;
+            for _i in 0..depth {
+                path.push('/');
+                path.push_str(&slugify(&self.nouns.get()));
+            }
+            if self.rng.get().is_multiple_of(3) {
+                path.push_str(&format!("?page={}", self.rng.get() % 20 + 1));
+            }
+            return path;
+        }
+    }
+    impl StringGenerator for HttpFixtures {
+        fn get(&mut self) -> String {
+            let method = self.pick(&HttpFixtures::METHODS);
+            let path = self.path();
+            let mut headers: Vec<(String, String)> = vec![
+                (
+                    String::from("Authorization"),
+                    format!("Bearer {}", self.tokens.get()),
+                ),
+                (
+                    String::from("Accept"),
+                    String::from(self.pick(&HttpFixtures::ACCEPT)),
+                ),
+                (
+                    String::from("User-Agent"),
+                    String::from(self.pick(&HttpFixtures::AGENTS)),
+                ),
+            ];
+            if self.rng.get().is_multiple_of(2) {
+                headers.push((String::from("X-Request-Id"), self.request_ids.get()));
+            }
+            if self.rng.get().is_multiple_of(2) {
+                let language = self.pick(&HttpFixtures::LANGUAGES);
+                headers.push((String::from("Accept-Language"), String::from(language)));
+            }
+            let has_body = matches!(method, "POST" | "PUT" | "PATCH");
+            if has_body {
+                headers.push((
+                    String::from("Content-Type"),
+                    String::from("application/json"),
+                ));
+            }
+            let header_json: Vec<String> = headers
+                .iter()
+                .map(|(k, v)| format!("\"{}\":\"{}\"", k, json_escape(v)))
+                .collect();
+            let mut strong = format!(
+                "{{\"method\":\"{}\",\"path\":\"{}\",\"headers\":{{{}}}",
+                method,
+                json_escape(&path),
+                header_json.join(",")
+            );
+            if has_body {
+                let name = format!("{} {}", self.adjectives.get(), self.nouns.get());
+                strong.push_str(&format!(
+                    ",\"body\":{{\"name\":\"{}\",\"quantity\":{}}}",
+                    json_escape(&name),
+                    self.rng.get() % 100
+                ));
+            }
+            strong.push('}');
+            return strong;
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            self.adjectives.set_version(conf.get_version());
+            self.nouns.set_version(conf.get_version());
+            self.adjectives.fill(Path::new(""))?;
+            self.nouns.fill(Path::new(""))?;
+            return Ok(());
+        }
+    }
+
+    // strings shaped like jwt (header.payload.signature) that never verify,
+    // for parsers and log scrubbers that must recognize token shapes
+    pub struct JwtShaped {
+        payload_size: usize,
+        signature_size: Option<usize>,
+        rng: RNG,
+    }
+    impl Default for JwtShaped {
+        fn default() -> Self {
+            return JwtShaped::new();
+        }
+    }
+    impl JwtShaped {
+        // algorithm with its usual signature size in bytes
+        const ALGORITHMS: [(&'static str, usize); 4] =
+            [("HS256", 32), ("HS512", 64), ("RS256", 256), ("ES256", 64)];
+
+        pub fn new() -> JwtShaped {
+            let mut rng = RNG::new();
+            rng.seed();
+            return JwtShaped {
+                payload_size: 96,
+                signature_size: None,
+                rng,
+            };
+        }
+        pub fn set_sizes(&mut self, payload: usize, signature: Option<usize>) {
+            self.payload_size = payload;
+            self.signature_size = signature;
+        }
+        fn random_bytes(&mut self, n: usize) -> Vec<u8> {
+            return (0..n).map(|_i| self.rng.get() as u8).collect();
+        }
+    }
+    impl StringGenerator for JwtShaped {
+        fn get(&mut self) -> String {
+            let index = self.rng.get() as usize % JwtShaped::ALGORITHMS.len();
+            let (alg, default_size) = JwtShaped::ALGORITHMS[index];
+            let header = format!(
+                "{{\"alg\":\"{}\",\"typ\":\"JWT\",\"kid\":\"{:08x}\"}}",
+                alg,
+                self.rng.get()
+            );
+            let payload = self.random_bytes(self.payload_size);
+            let signature = self.random_bytes(self.signature_size.unwrap_or(default_size));
+            return format!(
+                "{}.{}.{}",
+                base64url(header.as_bytes()),
+                base64url(&payload),
+                base64url(&signature)
+            );
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            // next: payload bytes[:signature bytes]
+            let next = conf.get_next();
+            let parts: Vec<&str> = next.split(':').collect();
+            let payload = parts[0].parse().unwrap_or(96);
+            let signature = if parts.len() > 1 {
+                parts[1].parse().ok()
+            } else {
+                None
+            };
+            self.set_sizes(payload, signature);
+            return Ok(());
+        }
+    }
+
+    // labeled secret-like strings for testing secret scanners, jsonl records with
+    // the surrounding text and the span of the secret in it (byte offsets)
+    pub struct SecretsCorpus {
+        labels: Vec<String>,
+        rng: RNG,
+    }
+    impl Default for SecretsCorpus {
+        fn default() -> Self {
+            return SecretsCorpus::new();
+        }
+    }
+    impl SecretsCorpus {
+        pub const LABELS: [&'static str; 8] = [
+            "aws_access_key_id",
+            "aws_secret_access_key",
+            "github_token",
+            "github_fine_grained_token",
+            "slack_token",
+            "stripe_test_key",
+            "hex_secret",
+            "none",
+        ];
+        const UPPER_DIGITS: &'static str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+        const ALNUM: &'static str =
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+        const BASE64: &'static str =
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        const HEX: &'static str = "0123456789abcdef";
+        const DECOYS: [&'static str; 4] = [
+            "password = changeme",
+            "token = ${TOKEN}",
+            "api_key = <your key here>",
+            "secret = null",
+        ];
+
+        pub fn new() -> SecretsCorpus {
+            let mut rng = RNG::new();
+            rng.seed();
+            return SecretsCorpus {
+                labels: SecretsCorpus::LABELS
+                    .iter()
+                    .map(|l| String::from(*l))
+                    .collect(),
+                rng,
+            };
+        }
+        // comma separated subset of labels, unknown ones are ignored
+        pub fn set_labels(&mut self, s: &str) {
+            let labels: Vec<String> = s
+                .split(',')
+                .map(|l| l.trim())
+                .filter(|l| SecretsCorpus::LABELS.contains(l))
+                .map(String::from)
+                .collect();
+            if !labels.is_empty() {
+                self.labels = labels;
+            }
+        }
+        fn random(&mut self, alphabet: &str, n: usize) -> String {
+            let chars: Vec<char> = alphabet.chars().collect();
+            return (0..n)
+                .map(|_i| chars[self.rng.get() as usize % chars.len()])
+                .collect();
+        }
+        // secret and a line of config or code it is embedded in
+        pub fn sample(&mut self, label: &str) -> (String, String) {
+            let secret = match label {
+                "aws_access_key_id" => {
+                    format!("AKIA{}", self.random(SecretsCorpus::UPPER_DIGITS, 16))
+                }
+                "aws_secret_access_key" => self.random(SecretsCorpus::BASE64, 40),
+                "github_token" => format!("ghp_{}", self.random(SecretsCorpus::ALNUM, 36)),
+                "github_fine_grained_token" => format!(
+                    "github_pat_{}_{}",
+                    self.random(SecretsCorpus::ALNUM, 22),
+                    self.random(SecretsCorpus::ALNUM, 59)
+                ),
+                "slack_token" => format!(
+                    "xoxb-{}-{}-{}",
+                    self.random("0123456789", 12),
+                    self.random("0123456789", 13),
+                    self.random(SecretsCorpus::ALNUM, 24)
+                ),
+                "stripe_test_key" => format!("sk_test_{}", self.random(SecretsCorpus::ALNUM, 24)),
+                "hex_secret" => self.random(SecretsCorpus::HEX, 64),
+                _ => {
+                    let index = self.rng.get() as usize % SecretsCorpus::DECOYS.len();
+                    let decoy = String::from(SecretsCorpus::DECOYS[index]);
+                    return (String::new(), decoy);
+                }
+            };
+            let text = match self.rng.get() % 3 {
+                0 => format!("export {}={}", label.to_uppercase(), secret),
+                1 => format!("{}: \"{}\"", label, secret),
+                _ => format!("const {} = '{}';", label.to_uppercase(), secret),
+            };
+            return (secret, text);
+        }
+    }
+    impl StringGenerator for SecretsCorpus {
+        fn get(&mut self) -> String {
+            let index = self.rng.get() as usize % self.labels.len();
+            let label = self.labels[index].clone();
+            let (secret, text) = self.sample(&label);
+            let (start, end) = match text.find(&secret) {
+                Some(start) if !secret.is_empty() => (start, start + secret.len()),
+                _ => (0, 0),
+            };
+            return format!(
+                "{{\"text\":\"{}\",\"secret\":\"{}\",\"label\":\"{}\",\"start\":{},\"end\":{},\"synthetic\":true}}",
+                json_escape(&text),
+                json_escape(&secret),
+                label,
+                start,
+                end
+            );
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            if !conf.get_next().is_empty() {
+                self.set_labels(conf.get_next().as_ref());
+            }
+            return Ok(());
+        }
+    }
+}
@@ -1,5 +1,12 @@
 pub mod grammar {
-    use crate::stringer::read_lines;
+    use std::collections::HashMap;
+    use std::path::Path;
+    use std::sync::{Arc, Mutex, OnceLock};
+
+    use crate::stringer::{lists_dir, read_lines, Languages};
+
+    // gendered noun lists by language, read once however many generators are set up
+    static NOUN_LISTS: OnceLock<Mutex<HashMap<String, Arc<GermanNounList>>>> = OnceLock::new();
 
     #[derive(Clone)]
     pub enum GermanGenders {
@@ -21,6 +28,12 @@ pub mod grammar {
             return gg;
         }
     }
+    // cases nouns of simple sentences take, subject and direct object
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum GermanCase {
+        Nominative,
+        Accusative,
+    }
     #[derive(Clone)]
     pub struct GermanNoun {
         noun: String,
@@ -46,10 +59,48 @@ pub mod grammar {
             };
             return String::from(s);
         }
+        pub fn get_noun(&self) -> String {
+            return self.noun.clone();
+        }
+        // definite article, lower case
+        pub fn article(&self, case: GermanCase) -> String {
+            let article = match (&self.gender, case) {
+                (GermanGenders::Masculine, GermanCase::Nominative) => "der",
+                (GermanGenders::Masculine, GermanCase::Accusative) => "den",
+                (GermanGenders::Feminine, _) => "die",
+                (GermanGenders::Neuter, _) => "das",
+            };
+            return String::from(article);
+        }
+        // weak declension after definite article: -en for masculine accusative, -e else
+        pub fn adjective_ending(&self, case: GermanCase) -> String {
+            let ending = match (&self.gender, case) {
+                (GermanGenders::Masculine, GermanCase::Accusative) => "en",
+                _ => "e",
+            };
+            return String::from(ending);
+        }
+        // der alte Baum, den alten Baum
+        pub fn with_adjective(&self, adjective: &str, case: GermanCase) -> String {
+            // leise + e is leise, not leisee
+            let stem = adjective.strip_suffix('e').unwrap_or(adjective);
+            return format!(
+                "{} {}{} {}",
+                self.article(case),
+                stem,
+                self.adjective_ending(case),
+                self.noun
+            );
+        }
     }
     pub struct GermanNounList {
         list: Vec<GermanNoun>,
     }
+    impl Default for GermanNounList {
+        fn default() -> Self {
+            return GermanNounList::new();
+        }
+    }
     impl GermanNounList {
         pub fn new() -> GermanNounList {
             let list: Vec<GermanNoun> = Vec::new();
@@ -58,8 +109,17 @@ pub mod grammar {
         pub fn add(&mut self, noun: GermanNoun) {
             self.list.push(noun);
         }
-        pub fn get_adapted(&mut self, noun: String, adjective: String) -> String {
-            if 0 == self.list.len() {
+        pub fn len(&self) -> usize {
+            return self.list.len();
+        }
+        pub fn is_empty(&self) -> bool {
+            return self.list.is_empty();
+        }
+        pub fn get_at(&self, index: usize) -> Option<&GermanNoun> {
+            return self.list.get(index);
+        }
+        pub fn get_adapted(&self, noun: String, adjective: String) -> String {
+            if self.list.is_empty() {
                 return String::from("empty");
             }
             //first find word in list
@@ -73,22 +133,33 @@ pub mod grammar {
             }
             return String::new();
         }
+        // nouns.xx.dic of language from cache, read on first use; missing file is empty list
+        pub fn shared(language: &Languages) -> Arc<GermanNounList> {
+            let lists = NOUN_LISTS.get_or_init(|| Mutex::new(HashMap::new()));
+            let mut lists = lists.lock().unwrap_or_else(|e| e.into_inner());
+            let list = lists.entry(language.abbr()).or_insert_with(|| {
+                let mut list = GermanNounList::new();
+                list.fill_from(&lists_dir().join(format!("nouns.{}.dic", language.abbr())));
+                return Arc::new(list);
+            });
+            return Arc::clone(list);
+        }
         pub fn fill(&mut self) {
-            let filename = "./lists/nouns.de.dic";
+            self.fill_from(&lists_dir().join("nouns.de.dic"));
+        }
+        pub fn fill_from(&mut self, filename: &Path) {
             if let Ok(lines) = read_lines(filename) {
-                for line in lines {
-                    if let Ok(ip) = line {
-                        let chazar = ip.split(",");
-                        for chaz in chazar {
-                            if chaz == "" {
-                                continue;
-                            }
-                            let spl: Vec<&str> = chaz.trim().split(" ").collect();
-                            let gender = GermanGenders::from(spl[0]);
-                            let noun = String::from(spl[1]);
-                            let gnoun = GermanNoun::new(noun, gender);
-                            self.add(gnoun);
+                for ip in lines.map_while(Result::ok) {
+                    let chazar = ip.split(",");
+                    for chaz in chazar {
+                        if chaz.is_empty() {
+                            continue;
                         }
+                        let spl: Vec<&str> = chaz.trim().split(" ").collect();
+                        let gender = GermanGenders::from(spl[0]);
+                        let noun = String::from(spl[1]);
+                        let gnoun = GermanNoun::new(noun, gender);
+                        self.add(gnoun);
                     }
                 }
             }
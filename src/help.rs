@@ -1,10 +1,26 @@
 pub mod help {
+    use crate::stringer::{tr, Languages};
+    use std::io::{Error, Write};
+
     pub fn print_help() {
         print!("help");
     }
-    pub fn print_help2() {
+    pub fn print_help2() -> Result<(), Error> {
+        use crate::messages::messages::ui_language;
         use std::fs::read_to_string;
-        let help_string = read_to_string("HELP.md").expect("Something went wrong reading the file");
-        println!("{}", help_string);
+        // translated help is HELP.[language].md, english HELP.md is the fallback
+        let lang = ui_language();
+        let translated = format!("HELP.{}.md", lang.abbr());
+        let help_string = match read_to_string(translated) {
+            Ok(content) => content,
+            Err(_e) => {
+                if !matches!(lang, Languages::English) {
+                    println!("{}", tr("help.untranslated"));
+                }
+                read_to_string("HELP.md")?
+            }
+        };
+        writeln!(std::io::stdout(), "{}", help_string)?;
+        return Ok(());
     }
 }
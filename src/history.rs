@@ -0,0 +1,230 @@
+pub mod history {
+    use std::fs::{File, OpenOptions};
+    use std::io::{BufWriter, Error, ErrorKind, Write};
+    use std::path::Path;
+
+    use crate::stringer::{iso8601, now_millis, read_lines, OutputSink, Record, RNG};
+
+    pub const DEFAULT_HISTORY: &str = "strings.history";
+    pub const REDACTED: &str = "[secret]";
+
+    // one issued string as kept in history
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct HistoryEntry {
+        pub timestamp: String,
+        pub run_id: String,
+        pub mode: String,
+        pub value: String,
+    }
+    impl HistoryEntry {
+        // timestamp<tab>run<tab>mode<tab>value, value last and with \n, \r and \\ escaped
+        pub fn line(&self) -> String {
+            let value = self
+                .value
+                .replace('\\', "\\\\")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r");
+            return format!(
+                "{}\t{}\t{}\t{}",
+                self.timestamp, self.run_id, self.mode, value
+            );
+        }
+        pub fn parse(line: &str) -> Option<HistoryEntry> {
+            let mut parts = line.splitn(4, '\t');
+            let timestamp = parts.next()?;
+            let run_id = parts.next()?;
+            let mode = parts.next()?;
+            let escaped = parts.next()?;
+            let mut value = String::new();
+            let mut chars = escaped.chars();
+            while let Some(c) = chars.next() {
+                if c != '\\' {
+                    value.push(c);
+                    continue;
+                }
+                match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some(other) => value.push(other),
+                    None => value.push('\\'),
+                }
+            }
+            return Some(HistoryEntry {
+                timestamp: String::from(timestamp),
+                run_id: String::from(run_id),
+                mode: String::from(mode),
+                value,
+            });
+        }
+    }
+
+    // start time in millis and random part, so ids sort by time and rarely clash
+    pub fn new_run_id() -> String {
+        let mut rng = RNG::new();
+        rng.seed();
+        return format!("{}-{:08x}", now_millis(), rng.get());
+    }
+
+    // .sqlite and .db files are databases, anything else is text
+    pub fn is_database(path: &Path) -> bool {
+        return matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("sqlite") | Some("db")
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn to_io(e: rusqlite::Error) -> Error {
+        return Error::other(e);
+    }
+    #[cfg(feature = "sqlite")]
+    pub fn open_database(path: &Path) -> Result<rusqlite::Connection, Error> {
+        let connection = rusqlite::Connection::open(path).map_err(to_io)?;
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS history (ts TEXT, run_id TEXT, mode TEXT, value TEXT);
+                 CREATE INDEX IF NOT EXISTS history_value ON history (value);",
+            )
+            .map_err(to_io)?;
+        return Ok(connection);
+    }
+    #[cfg(not(feature = "sqlite"))]
+    fn no_database() -> Error {
+        return Error::new(
+            ErrorKind::Unsupported,
+            "sqlite history needs the sqlite feature (cargo build --features sqlite)",
+        );
+    }
+
+    enum Store {
+        Text(BufWriter<File>),
+        #[cfg(feature = "sqlite")]
+        Database(rusqlite::Connection),
+    }
+
+    // appends every emitted string to history, sits next to real outputs
+    pub struct HistorySink {
+        store: Store,
+        run_id: String,
+        // secrets are recorded as issued but never as themselves
+        redact: bool,
+    }
+    impl HistorySink {
+        pub fn open(path: &Path) -> Result<HistorySink, Error> {
+            let store = if is_database(path) {
+                #[cfg(feature = "sqlite")]
+                {
+                    let connection = open_database(path)?;
+                    connection.execute_batch("BEGIN;").map_err(to_io)?;
+                    Store::Database(connection)
+                }
+                #[cfg(not(feature = "sqlite"))]
+                return Err(no_database());
+            } else {
+                let file = OpenOptions::new().create(true).append(true).open(path)?;
+                Store::Text(BufWriter::new(file))
+            };
+            return Ok(HistorySink {
+                store,
+                run_id: new_run_id(),
+                redact: false,
+            });
+        }
+        pub fn set_redact(&mut self, redact: bool) {
+            self.redact = redact;
+        }
+        pub fn get_run_id(&self) -> String {
+            return self.run_id.clone();
+        }
+    }
+    impl OutputSink for HistorySink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            let entry = HistoryEntry {
+                timestamp: iso8601(now_millis()),
+                run_id: self.run_id.clone(),
+                mode: record.mode.clone(),
+                value: if self.redact {
+                    String::from(REDACTED)
+                } else {
+                    record.value.clone()
+                },
+            };
+            match &mut self.store {
+                Store::Text(writer) => {
+                    writeln!(writer, "{}", entry.line())?;
+                }
+                #[cfg(feature = "sqlite")]
+                Store::Database(connection) => {
+                    connection
+                        .execute(
+                            "INSERT INTO history (ts, run_id, mode, value) VALUES (?1, ?2, ?3, ?4)",
+                            rusqlite::params![
+                                entry.timestamp,
+                                entry.run_id,
+                                entry.mode,
+                                entry.value
+                            ],
+                        )
+                        .map_err(to_io)?;
+                }
+            }
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            match &mut self.store {
+                Store::Text(writer) => writer.flush()?,
+                #[cfg(feature = "sqlite")]
+                Store::Database(connection) => {
+                    connection.execute_batch("COMMIT;").map_err(to_io)?;
+                }
+            }
+            return Ok(());
+        }
+    }
+
+    // entries whose value contains needle, case ignored, oldest first
+    pub fn search_history(path: &Path, needle: &str) -> Result<Vec<HistoryEntry>, Error> {
+        if !path.exists() {
+            let message = format!("history {} does not exist", path.display());
+            return Err(Error::new(ErrorKind::NotFound, message));
+        }
+        let needle = needle.to_lowercase();
+        let mut found: Vec<HistoryEntry> = Vec::new();
+        if is_database(path) {
+            #[cfg(feature = "sqlite")]
+            {
+                let connection = open_database(path)?;
+                let mut statement = connection
+                    .prepare(
+                        "SELECT ts, run_id, mode, value FROM history
+                         WHERE instr(lower(value), ?1) > 0 ORDER BY rowid",
+                    )
+                    .map_err(to_io)?;
+                let rows = statement
+                    .query_map([&needle], |row| {
+                        return Ok(HistoryEntry {
+                            timestamp: row.get(0)?,
+                            run_id: row.get(1)?,
+                            mode: row.get(2)?,
+                            value: row.get(3)?,
+                        });
+                    })
+                    .map_err(to_io)?;
+                for row in rows {
+                    found.push(row.map_err(to_io)?);
+                }
+                return Ok(found);
+            }
+            #[cfg(not(feature = "sqlite"))]
+            return Err(no_database());
+        }
+        for line in read_lines(path)? {
+            if let Some(entry) = HistoryEntry::parse(&line?) {
+                if entry.value.to_lowercase().contains(&needle) {
+                    found.push(entry);
+                }
+            }
+        }
+        return Ok(found);
+    }
+}
@@ -0,0 +1,498 @@
+pub mod ids {
+    use std::fs::OpenOptions;
+    use std::io::{Error, ErrorKind, Read, Seek, SeekFrom, Write};
+    use std::path::PathBuf;
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{
+        base62, base62_u128, bubblebabble, crockford32, crockford_number, in_range, now_millis,
+        proquint, Config, RngBackend, RNG,
+    };
+
+    // monotonically increasing ids like INV-000123
+    // counter is kept in a file and whole run is reserved at once under a file lock,
+    // so parallel invocations get disjoint ranges; ids rejected by filters make run
+    // reserve another block when the first one is used up
+    pub struct SequentialId {
+        prefix: String,
+        width: usize,
+        counter_path: PathBuf,
+        current: u64,
+        // last id of reserved block and size of blocks
+        last: u64,
+        block: u64,
+        // counter file could not be extended, nothing more is issued
+        stalled: bool,
+        error: Option<Error>,
+    }
+    impl Default for SequentialId {
+        fn default() -> Self {
+            return SequentialId::new();
+        }
+    }
+    impl SequentialId {
+        pub fn new() -> SequentialId {
+            return SequentialId {
+                prefix: String::new(),
+                width: 6,
+                counter_path: PathBuf::from("strgen.counter"),
+                current: 0,
+                last: 0,
+                block: 1,
+                stalled: false,
+                error: None,
+            };
+        }
+        pub fn set_prefix(&mut self, prefix: &str) {
+            self.prefix = String::from(prefix);
+        }
+        pub fn set_width(&mut self, width: usize) {
+            self.width = width;
+        }
+        pub fn set_counter_path(&mut self, path: PathBuf) {
+            self.counter_path = path;
+        }
+        // returns first number of reserved block, counter file holds last issued number
+        pub fn reserve(&mut self, amount: u64) -> Result<u64, Error> {
+            let mut file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&self.counter_path)?;
+            file.lock()?;
+            let mut content = String::new();
+            file.read_to_string(&mut content)?;
+            let last: u64 = if content.trim().is_empty() {
+                0
+            } else {
+                match content.trim().parse() {
+                    Ok(value) => value,
+                    Err(_e) => {
+                        let message = format!("counter file holds no number: {}", content.trim());
+                        return Err(Error::new(ErrorKind::InvalidData, message));
+                    }
+                }
+            };
+            let first = last + 1;
+            file.set_len(0)?;
+            file.seek(SeekFrom::Start(0))?;
+            writeln!(file, "{}", last + amount)?;
+            file.sync_all()?;
+            file.unlock()?;
+            self.current = first;
+            self.last = last + amount;
+            self.block = amount.max(1);
+            return Ok(first);
+        }
+    }
+    impl StringGenerator for SequentialId {
+        fn get(&mut self) -> String {
+            // block used up, only now so runs don't leave unused blocks behind
+            if self.current > self.last {
+                if let Err(e) = self.reserve(self.block) {
+                    let message = format!("{}: {}", self.counter_path.display(), e);
+                    self.error = Some(Error::new(e.kind(), message));
+                    self.stalled = true;
+                    return String::new();
+                }
+            }
+            let strong = format!(
+                "{}{:0width$}",
+                self.prefix,
+                self.current,
+                width = self.width
+            );
+            self.current += 1;
+            return strong;
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            // next: prefix[:width[:counter file]]
+            let next = conf.get_next();
+            let parts: Vec<&str> = next.splitn(3, ':').collect();
+            self.set_prefix(parts[0]);
+            if parts.len() > 1 && !parts[1].is_empty() {
+                let width = parts[1].trim().parse().map_err(|_e| {
+                    let message = format!("sequential id width must be a number, got {}", parts[1]);
+                    return Error::new(ErrorKind::InvalidInput, message);
+                })?;
+                self.set_width(width);
+            }
+            if parts.len() > 2 && !parts[2].is_empty() {
+                self.set_counter_path(PathBuf::from(parts[2]));
+            }
+            self.reserve(conf.get_amount() as u64)?;
+            return Ok(());
+        }
+        fn exhausted(&self) -> bool {
+            return self.stalled;
+        }
+        fn failure(&mut self) -> Option<Error> {
+            return self.error.take();
+        }
+    }
+
+    // 64 bit time ordered ids: 41 bits milliseconds since epoch, 10 bits worker, 12 bits sequence
+    pub struct Snowflake {
+        epoch: u64,
+        worker: u64,
+        sequence: u64,
+        last_millis: u64,
+        base62: bool,
+    }
+    impl Default for Snowflake {
+        fn default() -> Self {
+            return Snowflake::new();
+        }
+    }
+    impl Snowflake {
+        // twitter epoch, 2010-11-04
+        pub const DEFAULT_EPOCH: u64 = 1288834974657;
+        const WORKER_BITS: u64 = 10;
+        const SEQUENCE_BITS: u64 = 12;
+
+        pub fn new() -> Snowflake {
+            return Snowflake {
+                epoch: Snowflake::DEFAULT_EPOCH,
+                worker: 0,
+                sequence: 0,
+                last_millis: 0,
+                base62: false,
+            };
+        }
+        pub fn set_epoch(&mut self, epoch: u64) {
+            self.epoch = epoch;
+        }
+        pub fn set_worker(&mut self, worker: u64) {
+            self.worker = worker & ((1 << Snowflake::WORKER_BITS) - 1);
+        }
+        pub fn set_base62(&mut self, base62: bool) {
+            self.base62 = base62;
+        }
+        pub fn next_id(&mut self) -> u64 {
+            let mut millis = now_millis();
+            if millis == self.last_millis {
+                self.sequence = (self.sequence + 1) & ((1 << Snowflake::SEQUENCE_BITS) - 1);
+                if self.sequence == 0 {
+                    // sequence exhausted, wait for next millisecond
+                    while millis <= self.last_millis {
+                        millis = now_millis();
+                    }
+                }
+            } else {
+                self.sequence = 0;
+            }
+            self.last_millis = millis;
+            let timestamp = millis.saturating_sub(self.epoch);
+            return (timestamp << (Snowflake::WORKER_BITS + Snowflake::SEQUENCE_BITS))
+                | (self.worker << Snowflake::SEQUENCE_BITS)
+                | self.sequence;
+        }
+    }
+    impl StringGenerator for Snowflake {
+        fn get(&mut self) -> String {
+            let id = self.next_id();
+            if self.base62 {
+                return base62(id);
+            }
+            return id.to_string();
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            // next: worker[:epoch[:dec|b62]]
+            let next = conf.get_next();
+            let parts: Vec<&str> = next.split(':').collect();
+            if !parts[0].is_empty() {
+                self.set_worker(parts[0].parse().unwrap_or(0));
+            }
+            if parts.len() > 1 && !parts[1].is_empty() {
+                self.set_epoch(parts[1].parse().unwrap_or(Snowflake::DEFAULT_EPOCH));
+            }
+            if parts.len() > 2 {
+                self.set_base62(parts[2] == "b62");
+            }
+            return Ok(());
+        }
+    }
+
+    // rfc 4122 version 4: 122 random bits, version nibble 4, variant bits 10
+    pub fn uuid_v4(mut bytes: [u8; 16]) -> String {
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+        let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        return format!(
+            "{}-{}-{}-{}-{}",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32]
+        );
+    }
+
+    // random uuids, like uuidgen -r
+    pub struct Uuid {
+        rng: Box<dyn RngBackend>,
+        upper: bool,
+    }
+    impl Default for Uuid {
+        fn default() -> Self {
+            return Uuid::new();
+        }
+    }
+    impl Uuid {
+        pub fn new() -> Uuid {
+            let mut rng = RNG::new();
+            rng.seed();
+            return Uuid {
+                rng: Box::new(rng),
+                upper: false,
+            };
+        }
+    }
+    impl StringGenerator for Uuid {
+        fn get(&mut self) -> String {
+            let mut bytes = [0u8; 16];
+            for chunk in bytes.chunks_mut(4) {
+                chunk.copy_from_slice(&self.rng.get().to_le_bytes());
+            }
+            let uuid = uuid_v4(bytes);
+            if self.upper {
+                return uuid.to_uppercase();
+            }
+            return uuid;
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.backend(0)?;
+            self.upper = conf.get_next() == "upper";
+            return Ok(());
+        }
+    }
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum ByteEncoding {
+        Proquint,
+        BubbleBabble,
+    }
+    impl ByteEncoding {
+        // bytes when length is not given: ipv4 address, openssh fingerprint sized otherwise
+        pub fn default_bytes(&self) -> usize {
+            return match *self {
+                ByteEncoding::Proquint => 4,
+                ByteEncoding::BubbleBabble => 8,
+            };
+        }
+        pub fn encode(&self, bytes: &[u8]) -> String {
+            return match *self {
+                ByteEncoding::Proquint => proquint(bytes),
+                ByteEncoding::BubbleBabble => bubblebabble(bytes),
+            };
+        }
+    }
+
+    // random bytes spelled to be read aloud, length is number of bytes
+    pub struct PronounceableBytes {
+        encoding: ByteEncoding,
+        bytes: usize,
+        rng: Box<dyn RngBackend>,
+    }
+    impl PronounceableBytes {
+        pub fn new(encoding: ByteEncoding) -> PronounceableBytes {
+            let mut rng = RNG::new();
+            rng.seed();
+            return PronounceableBytes {
+                encoding,
+                bytes: encoding.default_bytes(),
+                rng: Box::new(rng),
+            };
+        }
+    }
+    impl StringGenerator for PronounceableBytes {
+        fn get(&mut self) -> String {
+            let mut bytes: Vec<u8> = Vec::with_capacity(self.bytes + 3);
+            while bytes.len() < self.bytes {
+                bytes.extend_from_slice(&self.rng.get().to_le_bytes());
+            }
+            bytes.truncate(self.bytes);
+            return self.encoding.encode(&bytes);
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.backend(0)?;
+            self.bytes = match conf.get_length() as usize {
+                0 => self.encoding.default_bytes(),
+                n => n,
+            };
+            // proquint words are two bytes each
+            if self.encoding == ByteEncoding::Proquint {
+                self.bytes = self.bytes.next_multiple_of(2);
+            }
+            return Ok(());
+        }
+    }
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum NumberEncoding {
+        Crockford,
+        Base62,
+    }
+    impl NumberEncoding {
+        // characters of random number when length is not given, and most that fit 128 bits
+        pub fn digits(&self) -> (u32, u32) {
+            return match *self {
+                NumberEncoding::Crockford => (13, 25),
+                NumberEncoding::Base62 => (11, 21),
+            };
+        }
+    }
+
+    // 4th parameter: number[:check], random number of len characters when left out;
+    // random ones are zero padded to len, so they sort like numbers
+    pub struct EncodedNumbers {
+        encoding: NumberEncoding,
+        number: Option<u128>,
+        digits: u32,
+        check: bool,
+        last: u128,
+        rng: Box<dyn RngBackend>,
+    }
+    impl EncodedNumbers {
+        pub fn new(encoding: NumberEncoding) -> EncodedNumbers {
+            let mut rng = RNG::new();
+            rng.seed();
+            return EncodedNumbers {
+                encoding,
+                number: None,
+                digits: encoding.digits().0,
+                check: false,
+                last: 0,
+                rng: Box::new(rng),
+            };
+        }
+    }
+    impl StringGenerator for EncodedNumbers {
+        fn get(&mut self) -> String {
+            self.last = match self.number {
+                Some(number) => number,
+                None => {
+                    let base: u64 = match self.encoding {
+                        NumberEncoding::Crockford => 32,
+                        NumberEncoding::Base62 => 62,
+                    };
+                    let rng = self.rng.as_mut();
+                    (0..self.digits).fold(0u128, |value, _| {
+                        return value * base as u128 + in_range(rng, 0, base - 1) as u128;
+                    })
+                }
+            };
+            let width = match self.number {
+                Some(_) => 0,
+                None => self.digits as usize,
+            };
+            return match self.encoding {
+                NumberEncoding::Crockford => crockford_number(self.last, width, self.check),
+                NumberEncoding::Base62 => {
+                    format!("{:0>width$}", base62_u128(self.last), width = width)
+                }
+            };
+        }
+        fn fields(&self) -> Vec<(String, String)> {
+            return vec![(String::from("number"), self.last.to_string())];
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.backend(0)?;
+            let (default, most) = self.encoding.digits();
+            self.digits = match conf.get_length() {
+                0 => default,
+                n => n.min(most),
+            };
+            let next = conf.get_next();
+            let (number, option) = next.split_once(':').unwrap_or((next.as_str(), ""));
+            self.check = option == "check";
+            // only crockford base32 has check symbol
+            let known = self.check && self.encoding == NumberEncoding::Crockford;
+            if !option.is_empty() && !known {
+                let message = format!("unknown option {}, only crockford base32 has check", option);
+                return Err(Error::new(ErrorKind::InvalidInput, message));
+            }
+            self.number = match number.trim() {
+                "" => None,
+                number => Some(number.parse::<u128>().map_err(|_e| {
+                    let message = format!("not a number below 2^128: {}", number);
+                    return Error::new(ErrorKind::InvalidInput, message);
+                })?),
+            };
+            return Ok(());
+        }
+    }
+
+    // 48 bits of milliseconds and 80 random bits, 26 characters of crockford base32
+    pub fn ulid(millis: u64, random: u128) -> String {
+        let random = random & ((1 << Ulid::RANDOM_BITS) - 1);
+        let value = ((millis as u128 & ((1 << 48) - 1)) << Ulid::RANDOM_BITS) | random;
+        return crockford32(value, 26);
+    }
+
+    // sortable by time they were made, with mono also within one millisecond
+    pub struct Ulid {
+        rng: Box<dyn RngBackend>,
+        monotonic: bool,
+        last_millis: u64,
+        last_random: u128,
+    }
+    impl Default for Ulid {
+        fn default() -> Self {
+            return Ulid::new();
+        }
+    }
+    impl Ulid {
+        const RANDOM_BITS: u32 = 80;
+
+        pub fn new() -> Ulid {
+            let mut rng = RNG::new();
+            rng.seed();
+            return Ulid {
+                rng: Box::new(rng),
+                monotonic: false,
+                last_millis: 0,
+                last_random: 0,
+            };
+        }
+        pub fn set_monotonic(&mut self, monotonic: bool) {
+            self.monotonic = monotonic;
+        }
+        fn random(&mut self) -> u128 {
+            let mut random: u128 = 0;
+            for _ in 0..3 {
+                random = (random << 32) | self.rng.get() as u128;
+            }
+            return random & ((1 << Ulid::RANDOM_BITS) - 1);
+        }
+        // next ulid for given time; monotonic ones in same millisecond count up from last
+        pub fn next_at(&mut self, millis: u64) -> Option<String> {
+            if self.monotonic && millis <= self.last_millis {
+                if self.last_random == (1 << Ulid::RANDOM_BITS) - 1 {
+                    // random part ran over, caller waits for next millisecond
+                    return None;
+                }
+                self.last_random += 1;
+                return Some(ulid(self.last_millis, self.last_random));
+            }
+            self.last_millis = millis;
+            self.last_random = self.random();
+            return Some(ulid(millis, self.last_random));
+        }
+    }
+    impl StringGenerator for Ulid {
+        fn get(&mut self) -> String {
+            loop {
+                if let Some(id) = self.next_at(now_millis()) {
+                    return id;
+                }
+            }
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.backend(0)?;
+            self.set_monotonic(conf.get_next() == "mono");
+            return Ok(());
+        }
+    }
+}
@@ -4,6 +4,8 @@ pub mod languages {
         English,
         Georgian,
         German,
+        French,
+        Spanish,
     }
 
     impl Languages {
@@ -12,12 +14,19 @@ pub mod languages {
                 Languages::English => "en",
                 Languages::Georgian => "ka",
                 Languages::German => "de",
+                Languages::French => "fr",
+                Languages::Spanish => "es",
             };
             return String::from(result);
         }
+        // fr and es are ui languages only, there are no lists or alphabets for them
+        pub fn has_lists(&self) -> bool {
+            return !matches!(*self, Languages::French | Languages::Spanish);
+        }
         pub fn is_german(&self) -> bool {
             return matches!(*self, Languages::German);
         }
+        // language of word lists and alphabets, only ones with bundled lists
         pub fn from(s: &str) -> Languages {
             return match s {
                 "en" | "En" | "eN" | "EN" => Languages::English,
@@ -26,6 +35,14 @@ pub mod languages {
                 _ => Languages::English,
             };
         }
+        // language of messages and of texts lists are built from, fr and es have no lists
+        pub fn from_code(s: &str) -> Languages {
+            return match s.to_lowercase().as_ref() {
+                "fr" => Languages::French,
+                "es" => Languages::Spanish,
+                _ => Languages::from(s),
+            };
+        }
         pub fn get_alphabet(&self) -> String {
             let result = match *self {
                 Languages::Georgian => "აბგდევზთიკლმნოპჟრსტუფქღყშჩცძწჭხჯჰააააეეეიიიოოოუუ",
@@ -34,5 +51,110 @@ pub mod languages {
             };
             return String::from(result);
         }
+        // consonants and clusters starting syllable, as spelled
+        pub fn onsets(&self) -> &'static [&'static str] {
+            return match *self {
+                Languages::English => &[
+                    "b", "c", "d", "f", "g", "h", "j", "k", "l", "m", "n", "p", "r", "s", "t", "v",
+                    "w", "y", "z", "bl", "br", "ch", "cl", "cr", "dr", "fl", "fr", "gr", "pl", "pr",
+                    "sh", "sl", "sp", "st", "th", "tr",
+                ],
+                Languages::German => &[
+                    "b", "d", "f", "g", "h", "k", "l", "m", "n", "p", "r", "s", "t", "w", "z",
+                    "br", "fr", "gr", "kl", "kr", "pf", "sch", "sp", "st", "tr",
+                ],
+                Languages::French => &[
+                    "b", "c", "d", "f", "g", "j", "l", "m", "n", "p", "r", "s", "t", "v", "br",
+                    "ch", "cr", "dr", "fr", "gr", "pl", "tr",
+                ],
+                Languages::Spanish => &[
+                    "b", "c", "d", "f", "g", "j", "l", "m", "n", "p", "r", "s", "t", "v", "br",
+                    "ch", "cr", "dr", "fl", "gr", "ll", "pl", "tr",
+                ],
+                Languages::Georgian => &[
+                    "ბ", "გ", "დ", "ვ", "ზ", "თ", "კ", "ლ", "მ", "ნ", "პ", "რ", "ს", "ტ", "ქ",
+                    "ღ", "შ", "ჩ", "ც", "ძ", "წ", "ხ", "ჯ", "ბრ", "გრ", "თვ", "მწ",
+                ],
+            };
+        }
+        // vowels and diphthongs at heart of syllable
+        pub fn nuclei(&self) -> &'static [&'static str] {
+            return match *self {
+                Languages::English => &["a", "e", "i", "o", "u", "ai", "ea", "ee", "oo", "ou"],
+                Languages::German => &["a", "e", "i", "o", "u", "au", "ei", "ie", "ä", "ö", "ü"],
+                Languages::French => &["a", "e", "i", "o", "u", "ai", "au", "eu", "ou", "é"],
+                Languages::Spanish => &["a", "e", "i", "o", "u", "ia", "ie", "ue"],
+                Languages::Georgian => &["ა", "ე", "ი", "ო", "უ"],
+            };
+        }
+        // consonants closing syllable, empty ones make most syllables open
+        pub fn codas(&self) -> &'static [&'static str] {
+            return match *self {
+                Languages::English => &["", "", "", "", "l", "m", "n", "r", "s", "t", "nd", "st"],
+                Languages::German => &["", "", "", "ch", "l", "n", "r", "s", "t", "ng", "nd"],
+                Languages::French => &["", "", "", "", "l", "n", "r", "s"],
+                Languages::Spanish => &["", "", "", "", "l", "n", "r", "s"],
+                Languages::Georgian => &["", "", "", "ლ", "მ", "ნ", "რ", "ს"],
+            };
+        }
+        // endings of inflected forms and what takes their place in stem, longest first
+        pub fn inflections(&self) -> &'static [(&'static str, &'static str)] {
+            return match *self {
+                Languages::English => &[
+                    ("ies", "y"), ("sses", "ss"), ("ches", "ch"), ("shes", "sh"), ("xes", "x"),
+                    ("s", ""),
+                ],
+                Languages::German => &[
+                    ("ern", ""), ("em", ""), ("en", ""), ("er", ""), ("es", ""), ("e", ""),
+                ],
+                Languages::French => &[("aux", "al"), ("es", ""), ("x", ""), ("s", ""), ("e", "")],
+                Languages::Spanish => &[
+                    ("ces", "z"), ("es", ""), ("as", ""), ("os", ""), ("s", ""), ("a", ""),
+                    ("o", ""),
+                ],
+                Languages::Georgian => &[],
+            };
+        }
+        // stem inflected forms of word share, lower case with umlauts folded: Bäume, Baum -> baum
+        pub fn stem(&self, word: &str) -> String {
+            let mut word = word.to_lowercase();
+            if self.is_german() {
+                word = word.replace('ä', "a").replace('ö', "o").replace('ü', "u");
+            }
+            for (suffix, replacement) in self.inflections() {
+                let Some(stem) = word.strip_suffix(suffix) else {
+                    continue;
+                };
+                // glass, bus and basis are no plurals
+                let english_s = matches!(*self, Languages::English) && *suffix == "s";
+                if stem.chars().count() < 3 || (english_s && stem.ends_with(['s', 'u', 'i'])) {
+                    continue;
+                }
+                return format!("{}{}", stem, replacement);
+            }
+            return word;
+        }
+        pub fn decimal_separator(&self) -> char {
+            return match *self {
+                Languages::English => '.',
+                _ => ',',
+            };
+        }
+        pub fn group_separator(&self) -> char {
+            return match *self {
+                Languages::English => ',',
+                Languages::German | Languages::Spanish => '.',
+                Languages::French | Languages::Georgian => ' ',
+            };
+        }
+        // currency symbol and whether it goes before the amount
+        pub fn currency(&self) -> (String, bool) {
+            let result = match *self {
+                Languages::English => ("$", true),
+                Languages::Georgian => ("₾", false),
+                _ => ("€", false),
+            };
+            return (String::from(result.0), result.1);
+        }
     }
 }
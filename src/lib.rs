@@ -7,29 +7,201 @@ pub mod languages;
 pub mod modes;
 
 pub mod command_parser;
+pub mod errors;
 
 pub mod strgen;
+pub mod versions;
+
+pub mod assertions;
+pub mod builder;
+pub mod clipboard;
+pub mod clock;
+pub mod columns;
+pub mod codes;
+pub mod constraints;
+pub mod corpus;
+pub mod curate;
+pub mod encoding;
+pub mod filters;
+pub mod history;
+pub mod fixtures;
+pub mod structured;
+#[cfg(feature = "tagging")]
+pub mod tagging;
+pub mod sinks;
+pub mod transforms;
+pub mod vault;
+pub mod ids;
+pub mod lists;
+pub mod lorem;
+pub mod markov;
+pub mod mnemonic;
+pub mod mix;
+pub mod passwords;
+pub mod pseudonyms;
+pub mod ranking;
+pub mod regexes;
+pub mod reservations;
+pub mod secrets;
+pub mod templates;
+pub mod tokens;
+pub mod variants;
+pub mod wordcodes;
 
 pub mod fast_switch;
+pub mod messages;
+pub mod paths;
 pub mod repl;
 
+// enough to embed generator in own program, everything else is under stringer
+pub use stringer::{
+    run_generator, run_generator_filtered, run_generator_with, stringer, strings, Config,
+    ConfigBuilder, Languages, Modes, OutputSink, Record, StringFilter, StringGenerator,
+    StrgenError,
+};
+
 pub mod stringer {
-    use std::fs::File;
-    use std::io::{Error, Write};
+    use std::collections::VecDeque;
+    use std::io::Error;
+    use std::path::PathBuf;
 
     pub use super::command_parser::command_parser;
-    pub use super::grammar::grammar::GermanNounList;
+    pub use super::builder::builder::ConfigBuilder;
+    pub use super::errors::errors::{parse_number, strgen_error, StrgenError};
+    pub use super::grammar::grammar::{GermanCase, GermanNoun, GermanNounList};
     pub use super::help::help::print_help2 as print_help;
     pub use super::languages::languages::Languages;
     pub use super::modes::modes::Modes;
-    pub use super::reader::reader::read_lines;
-    pub use super::rng::rng::RNG;
+    pub use super::reader::reader::{read_input, read_lines};
+    pub use super::rng::rng::{OsEntropy, RngBackend, RNG};
+    pub use super::versions::versions::GeneratorVersion;
 
-    use super::strgen::string_generator_module::*;
+    pub use super::strgen::string_generator_module::{
+        CartesianPairs, CoupledWords, Difficulty, LettterSequence, SimpleSentences, Strata,
+        StringGenerator, SvoSentences, WordList,
+    };
+    pub use super::assertions::assertions::{Assertions, RunStats};
+    pub use super::clock::clock::{apache, iso8601, iso_date, now_millis};
+    pub use super::columns::columns::{
+        mailbox, parse_columns, Column, ColumnRecords, EMAIL_DOMAINS,
+    };
+    pub use super::encoding::encoding::{
+        base62, base62_decode, base62_u128, base64url, bubblebabble, bubblebabble_decode, crc32,
+        crockford32, crockford_decode, crockford_number, json_escape, proquint, proquint_decode,
+        CROCKFORD32, CROCKFORD_CHECK,
+    };
+    pub use super::filters::filters::{
+        brand_key, build_filters, levenshtein, metaphone, next_accepted, parse_exclusions,
+        soundex, BkTree, EditDistanceFilter, KeyboardLayout, KeyboardWalkFilter, KeyboardWalks,
+        Phonetic, PhoneticFilter, PrefixFilter, ReservedWordFilter, Screening, StringFilter,
+        TrademarkFilter, UniqueFilter, ExcludeFileFilter, BloomFilter, MAX_REJECTS, RESERVED_SETS,
+        UNIQUE_BLOOM_ABOVE, UNIQUE_RETRIES,
+    };
+    pub use super::fixtures::fixtures::{
+        safe_file_name, slugify, FilePaths, HttpFixtures, JwtShaped, LogFormat, LogLines,
+        SecretsCorpus,
+    };
+    pub use super::sinks::sinks::{
+        csv_field, fixed_cell, open_output, open_sink, open_sinks, out_spec, parse_format,
+        tsv_field, FixedWidthSink, FormattedSink, GroupingSink, HttpPostSink, OutputFormat,
+        OutputSink, Record, SlowReader, TeeSink, FORMAT_FIELDS, STREAM_BUFFER,
+    };
+    #[cfg(unix)]
+    pub use super::sinks::sinks::StreamSink;
+    pub use super::transforms::transforms::{
+        backslash_escape, flip_case, idn_encode, parse_wraps, percent_encode, punycode, scramble,
+        Escape,
+        Noise, Padding, Typos, Wrap,
+    };
+    pub use super::structured::structured::{
+        fnv1a, geohash, group_digits, hsl_to_hex, iban_check_digits, in_range, is_valid_iban,
+        ColorPalette, Coordinates, CronExpression, CurrencyAmount, GeoFormat, Iban, PaletteFormat,
+        SemVer,
+    };
+    #[cfg(feature = "kafka")]
+    pub use super::sinks::sinks::KafkaSink;
+    pub use super::ids::ids::{
+        ulid, uuid_v4, ByteEncoding, EncodedNumbers, NumberEncoding, PronounceableBytes,
+        SequentialId, Snowflake, Ulid, Uuid,
+    };
+    pub use super::corpus::corpus::{
+        line_offsets, shuffle_file, split_names, unit_float, CorpusSample, Scrubbed,
+    };
+    pub use super::lists::lists::{
+        alphabet_of, dice_index, diceware_lines, diceware_table, extract_list, extract_words,
+        lemmas, list_hash, list_hashes, list_key, list_lines, profanity_words, read_entries,
+        read_list, frequency_tiers, tier_lines, ExtractOptions, ListDiff, ListStats, TierOptions,
+        DICE, DICEWARE_ENTRIES, TIERS,
+    };
+    pub use super::lorem::lorem::{LoremParagraphs, LOREM};
+    #[cfg(feature = "tagging")]
+    pub use super::tagging::tagging::{
+        determiners, function_words, read_lexicon, suffixes, Tag, Tagger,
+    };
+    pub use super::markov::markov::{MarkovChain, MarkovWord};
+    pub use super::mnemonic::mnemonic::{major_digits, MajorWords};
+    pub use super::wordcodes::wordcodes::{
+        convert_base, number_digits, parse_grid_point, WordGrid, WordTriples, Wordified,
+        TRIPLE_SEPARATOR, WORDIFY_SEPARATOR,
+    };
+    pub use super::mix::mix::{parse_mix, quotas, MixEntry, MixedModes};
+    pub use super::curate::curate::{curate, run_curate, Curation, Verdict};
+    pub use super::reservations::reservations::{
+        default_owner, parse_duration, Reservation, ReservationFilter, ReservationSpec,
+        Reservations, DEFAULT_RESERVATIONS,
+    };
+    pub use super::secrets::secrets::{
+        ci_detected, confirm_display, recording_hint, wipe, wipe_all, wipe_bytes,
+    };
+    pub use super::constraints::constraints::{
+        AhoCorasick, ConstraintEngine, ConstraintFilter, Violation,
+    };
+    pub use super::codes::codes::{
+        apply_profile, check_char, find_profanity, normalize_code, squeeze_leet, verify_code,
+        CodeBatch, OutputProfile, ProfanityFilter, RedemptionCodes, CODE_ALPHABET, PROFANITY,
+    };
+    pub use super::clipboard::clipboard::{
+        clear_after, copy_to_clipboard, read_clipboard, spawn_clear, ClipboardSink, CLEAR_COMMAND,
+        DEFAULT_CLIP_CLEAR,
+    };
+    pub use super::vault::vault::{
+        decrypt, encrypt, encrypt_with, encryption_available, read_passphrase, EncryptedFileSink,
+        PASSPHRASE_VAR,
+    };
+    pub use super::history::history::{
+        is_database, new_run_id, search_history, HistoryEntry, HistorySink, DEFAULT_HISTORY,
+        REDACTED,
+    };
+    pub use super::passwords::passwords::{
+        apply_policy, hybrid_entropy, hybrid_shape, is_weak_pin, Blocklist, CharClass,
+        ComposedPassword, Composition, HybridPassword, PasswordPolicy, PinCode, PolicyFilter, COMMON_PASSWORDS, COMMON_PINS,
+        CONSONANTS, INJECTED, PIN_LENGTHS, VOWELS, syllables_for, SyllablePassword,
+    };
+    pub use super::pseudonyms::pseudonyms::{
+        hmac_sha256, PseudonymSpec, Pseudonymizer, Pseudonyms, KEY_VAR, PSEUDONYM_ROUNDS,
+    };
+    pub use super::regexes::regexes::{Node, RegexParser, RegexStrings, MAX_REPEAT};
+    pub use super::templates::templates::{Part, Template, TemplateStrings, ALNUM, HEX};
+    pub use super::tokens::tokens::{
+        TestTokens, TokenRegistry, TokenScheme, DEFAULT_TOKEN_REGISTRY,
+    };
+    pub use super::variants::variants::{
+        EditOp, Neighborhood, SimilarStrings, SoundAlikeSpec, SoundAlikes, SPELLINGS,
+        TRANSLITERATIONS,
+    };
+    pub use super::ranking::ranking::{
+        alliteration, feature, parse_expression, pronounceability, rarity, Expr, RankedCandidates,
+        Scoring, FEATURES,
+    };
 
     pub use super::fast_switch::fast_switch;
+    pub use super::messages::messages::{set_ui_language, tr, tr_with};
+    pub use super::paths::paths::{lists_dir, sidecar, split_list_files, utf8_console};
     pub use super::repl::repl::run_repl;
 
+    // strings generated at once when nothing needs to look at them one by one
+    pub const GENERATE_BATCH: usize = 1024;
+
     #[derive(Clone)]
     pub enum ListType {
         Nouns,
@@ -43,11 +215,23 @@ pub mod stringer {
         }
     }
 
+    // generator of mode, set up and ready to iterate: strings(&conf)?.take(100).collect()
+    pub fn strings(conf: &Config) -> Result<Box<dyn StringGenerator>, Error> {
+        let mut sg = stringer(conf.clone());
+        sg.setup(conf)?;
+        return Ok(sg);
+    }
+
     pub fn stringer(conf: Config) -> Box<dyn StringGenerator> {
+        let length = conf.get_length() as usize;
         let result_box: Box<dyn StringGenerator> = match conf.mode {
-            Modes::Password => Box::new(LettterSequence::pass_generator(16)),
-            Modes::Password84 => Box::new(LettterSequence::pass_generator84(16)),
-            Modes::RandomLetters => Box::new(LettterSequence::new("abc", 16)),
+            // several generators per row instead of mode
+            _ if !conf.columns.is_empty() => Box::new(ColumnRecords::new()),
+            // rules= say what password is made of
+            Modes::Password if !conf.rules.is_empty() => Box::new(ComposedPassword::new()),
+            Modes::Password => Box::new(LettterSequence::pass_generator(length)),
+            Modes::Password84 => Box::new(LettterSequence::pass_generator84(length)),
+            Modes::RandomLetters => Box::new(LettterSequence::new("abc", length)),
 
             Modes::CoupledWordsNouns => Box::new(CoupledWords::new(
                 ListType::Nouns,
@@ -63,30 +247,189 @@ pub mod stringer {
             )),
             //for now english only
             Modes::SimpleSentences => Box::new(SimpleSentences::new(Languages::English)),
-            _ => Box::new(LettterSequence::new("abc", 16)),
+            Modes::SequentialId => Box::new(SequentialId::new()),
+            Modes::Snowflake => Box::new(Snowflake::new()),
+            Modes::LogLines => Box::new(LogLines::new(LogFormat::Logfmt)),
+            Modes::FilePaths => Box::new(FilePaths::new()),
+            Modes::SemVer => Box::new(SemVer::new()),
+            Modes::CurrencyAmount => Box::new(CurrencyAmount::new(Languages::English)),
+            Modes::Iban => Box::new(Iban::new()),
+            Modes::Coordinates => Box::new(Coordinates::new()),
+            Modes::Color => Box::new(ColorPalette::new(1, PaletteFormat::Hex)),
+            Modes::Palette => Box::new(ColorPalette::new(5, PaletteFormat::Json)),
+            Modes::Cron => Box::new(CronExpression::new()),
+            Modes::HttpFixtures => Box::new(HttpFixtures::new()),
+            Modes::JwtShaped => Box::new(JwtShaped::new()),
+            Modes::SecretsCorpus => Box::new(SecretsCorpus::new()),
+            Modes::Mixed => Box::new(MixedModes::new()),
+            Modes::CorpusSample => Box::new(CorpusSample::new()),
+            Modes::CartesianPairs => Box::new(CartesianPairs::new(Languages::English)),
+            Modes::Ranked => Box::new(RankedCandidates::new()),
+            Modes::Hybrid => Box::new(HybridPassword::new(16)),
+            Modes::Pin => Box::new(PinCode::new(6)),
+            Modes::RedemptionCodes => Box::new(RedemptionCodes::new()),
+            Modes::Similar => Box::new(SimilarStrings::new()),
+            Modes::SoundAlike => Box::new(SoundAlikes::new()),
+            Modes::Uuid => Box::new(Uuid::new()),
+            Modes::Pseudonymize => Box::new(Pseudonyms::new()),
+            Modes::Ulid => Box::new(Ulid::new()),
+            Modes::Scrub => Box::new(Scrubbed::new()),
+            Modes::Template => Box::new(TemplateStrings::new()),
+            Modes::Regex => Box::new(RegexStrings::new()),
+            Modes::TestToken => Box::new(TestTokens::new()),
+            Modes::Markov => Box::new(MarkovWord::new()),
+            Modes::Syllables => Box::new(SyllablePassword::default()),
+            Modes::Sentence => Box::new(SvoSentences::new(Languages::from(conf.next.as_ref()))),
+            Modes::Lorem => Box::new(LoremParagraphs::new()),
+            Modes::Major => Box::new(MajorWords::new()),
+            Modes::Proquint => Box::new(PronounceableBytes::new(ByteEncoding::Proquint)),
+            Modes::BubbleBabble => Box::new(PronounceableBytes::new(ByteEncoding::BubbleBabble)),
+            Modes::Triple => Box::new(WordTriples::new()),
+            Modes::Wordify => Box::new(Wordified::new()),
+            Modes::Crockford => Box::new(EncodedNumbers::new(NumberEncoding::Crockford)),
+            Modes::Base62 => Box::new(EncodedNumbers::new(NumberEncoding::Base62)),
+            _ => Box::new(LettterSequence::new("abc", length)),
         };
         return result_box;
     }
     pub fn run_generator(conf: &Config) -> Result<(), Error> {
-        const OUTPUT_NAME: &str = "strings.textout";
+        let conf = &apply_profile(&apply_policy(conf)?)?;
+        let to_stdout = conf.get_sinks().iter().any(|s| s == "stdout" || s == "-");
+        if conf.get_confirm_display() && conf.mode.is_secret() && to_stdout {
+            confirm_display()?;
+        }
+        let mut sink = open_sinks(&conf.get_sinks(), conf)?;
+        return run_generator_with(conf, sink.as_mut());
+    }
+    // same as run_generator but strings go to caller supplied sink
+    pub fn run_generator_with(conf: &Config, sink: &mut dyn OutputSink) -> Result<(), Error> {
+        return run_generator_filtered(conf, sink, Vec::new());
+    }
+    // caller supplied filters run after the ones from config, e.g. own screening rules
+    pub fn run_generator_filtered(
+        conf: &Config,
+        sink: &mut dyn OutputSink,
+        extra: Vec<Box<dyn StringFilter>>,
+    ) -> Result<(), Error> {
+        // policy and profile decide mode and length before generator is set up
+        let conf = &apply_profile(&apply_policy(conf)?)?;
         let mut sg = stringer(conf.clone());
-        sg.setup(&conf)?;
-        let mut output = File::create(OUTPUT_NAME)?;
-        for _i in 0..conf.amount {
-            let strang = sg.get();
-            if conf.write_to_file {
-                writeln!(output, "{}", strang)?;
+        sg.setup(conf)?;
+        let mode = conf.mode.abbr();
+        let mut stats = RunStats::for_assertions(&conf.assertions);
+        let mut filters = build_filters(conf)?;
+        filters.extend(extra);
+        // secrets don't outlive their write
+        let secret = conf.mode.is_secret();
+        let mut noise_rng = conf.rng(Noise::STREAM);
+        let mut typo_rng = conf.rng(Typos::STREAM);
+        let mut pad_rng = conf.rng(Padding::STREAM);
+        // pairs without any noise asked for get usual typos
+        let typos = match conf.typos {
+            None if conf.pairs && conf.noise.is_empty() => Some(Typos::default()),
+            typos => typos,
+        };
+        // plain generators without filters give strings in batches, decided after first string:
+        // labels and fields are of single strings, secrets shouldn't wait in memory
+        let mut batched = false;
+        let mut pending: VecDeque<String> = VecDeque::new();
+        for i in 0..conf.amount {
+            if batched && pending.is_empty() {
+                let left = (conf.amount - i) as usize;
+                pending.extend(sg.generate_batch(left.min(GENERATE_BATCH)));
+                if let Some(e) = sg.failure() {
+                    return Err(e);
+                }
+            }
+            let next = if batched {
+                pending.pop_front()
             } else {
-                let mut strong = format!("{}:{}\n", strang, _i);
-                if conf.dont_write_indices {
-                    strong = format!("{}\n", strang);
+                next_accepted(sg.as_mut(), &mut filters)?
+            };
+            let (strang, pair) = match next {
+                Some(mut value) => {
+                    if let Some(padding) = conf.padding.as_ref() {
+                        let padded = padding.apply(&value, &mut pad_rng);
+                        wipe(&mut value);
+                        value = padded;
+                    }
+                    let mut messy = conf.noise.apply(&value, &mut noise_rng);
+                    if let Some(typos) = typos {
+                        let typed = typos.apply(&messy, &mut typo_rng);
+                        wipe(&mut messy);
+                        messy = typed;
+                    }
+                    // messy copy goes next to clean one, or takes its place
+                    let pair = if conf.pairs {
+                        Some(conf.escape.apply(&messy))
+                    } else {
+                        None
+                    };
+                    if !conf.pairs {
+                        std::mem::swap(&mut value, &mut messy);
+                    }
+                    wipe(&mut messy);
+                    let escaped = conf.escape.apply(&value);
+                    if secret {
+                        wipe(&mut value);
+                    }
+                    (escaped, pair)
+                }
+                None => break,
+            };
+            let mut record = Record::new(i, strang, mode.clone()).with_fields(sg.fields());
+            if i == 0 {
+                batched = filters.is_empty()
+                    && !secret
+                    && record.fields.is_empty()
+                    && sg.label().is_none()
+                    && !matches!(conf.mode, Modes::Ranked);
+            }
+            if let Some(pair) = pair {
+                record = record.with_pair(pair);
+            }
+            // ranked strings always come with their score
+            if conf.labels || matches!(conf.mode, Modes::Ranked) {
+                // generator knows better, otherwise run label or mode
+                let label = match sg.label() {
+                    Some(label) => label,
+                    None if !conf.label.is_empty() => conf.label.clone(),
+                    None => mode.clone(),
+                };
+                record = record.with_label(label);
+            }
+            if !conf.assertions.is_empty() {
+                stats.add(&record.value);
+            }
+            sink.write(&record)?;
+            if secret {
+                wipe(&mut record.value);
+                if let Some(pair) = record.pair.as_mut() {
+                    wipe(pair);
+                }
+                for (_name, value) in record.fields.iter_mut() {
+                    wipe(value);
                 }
-                print!("{}\n", strong);
             }
         }
+        sink.finish()?;
+        // output is kept, so failed run can be inspected
+        conf.assertions.check(&stats)?;
         return Ok(());
     }
 
+    // 8..16 or 8..=16, both ends included; none for plain number or backwards range
+    pub fn parse_length_range(s: &str) -> Option<(u32, u32)> {
+        let (min, max) = s.trim().split_once("..")?;
+        let max = max.strip_prefix('=').unwrap_or(max);
+        let min: u32 = min.trim().parse().ok()?;
+        let max: u32 = max.trim().parse().ok()?;
+        if min > max {
+            return None;
+        }
+        return Some((min, max));
+    }
+
     //returns default value as safe, when it can't parse string
     pub fn safe_u32(strong: String, default: u32) -> u32 {
         return match strong.parse() {
@@ -97,17 +440,103 @@ pub mod stringer {
     #[derive(Clone)]
     pub struct Config {
         mode: Modes,
-        length: u32,
+        // none until given, then default of mode applies
+        length: Option<u32>,
+        // random length per string within range, length is its upper end
+        length_range: Option<(u32, u32)>,
         amount: u32,
         write_to_file: bool,
         dont_write_indices: bool,
         next: String,
+        // raw next argument when it is a path, may not be valid utf-8
+        next_path: Option<PathBuf>,
+        version: GeneratorVersion,
+        materialize: bool,
+        escape: Escape,
+        // case flips, doubled letters and swaps put into every string
+        noise: Noise,
+        // neighbouring keys, swapped and missed keys of given layout
+        typos: Option<Typos>,
+        // clean string and its noisy copy side by side
+        pairs: bool,
+        // every string cut or filled to exact width
+        padding: Option<Padding>,
+        // paragraph of lorem mode, sentences of words each
+        sentences: u32,
+        words: u32,
+        // easy, medium or hard words for word modes, empty takes all
+        difficulty: String,
+        // what pass mode passwords are made of, digit=2:symbol=1, empty is plain pass
+        rules: String,
+        // (target, wrap), target * applies to all
+        wraps: Vec<(String, Wrap)>,
+        // outputs as kind[:path], empty means stdout or file depending on write_to_file
+        sinks: Vec<String>,
+        // file outputs keep what earlier runs wrote
+        append: bool,
+        // how stdout and text files write strings, and fields of structured formats
+        format: OutputFormat,
+        format_fields: Vec<String>,
+        // name,email,uuid: every string is row of these columns
+        columns: String,
+        // strings per request for batched outputs
+        batch_size: u32,
+        // column widths of fixed output, 0 fits longest cell
+        widths: Vec<usize>,
+        // emit label column, label empty means mode (or what generator says)
+        labels: bool,
+        label: String,
+        // output grouped by label
+        group: bool,
+        // word lists are used up before any word repeats
+        coverage: bool,
+        // quotas over tagged words or word lengths, e.g. tag:animal=30,object=70
+        strata: String,
+        // checked after run
+        assertions: Assertions,
+        // smallest edit distance between any two outputs, 0 is off
+        min_distance: usize,
+        // outputs must differ within first min_prefix characters, 0 is off
+        min_prefix: usize,
+        // no string twice in run
+        unique: bool,
+        // strings of earlier runs not to give again, one per line
+        exclude_file: String,
+        // file strings of run are appended to
+        record: String,
+        // no two outputs with same phonetic code
+        phonetic: Option<Phonetic>,
+        // (mode, reserved word set), mode * applies to all
+        exclusions: Vec<(String, String)>,
+        // protected names candidates must stay away from
+        screening: Option<Screening>,
+        keyboard_walks: Option<KeyboardWalks>,
+        // preset name or policy file
+        policy: String,
+        // constraint preset, e.g. coupon
+        profile: String,
+        // blocklist of words not to be found inside strings, 1 for built in
+        profanity: String,
+        // substrings every string must have, and must not have
+        required: Vec<String>,
+        forbidden: Vec<String>,
+        // same seed and config give same output, none seeds from clock
+        seed: Option<u64>,
+        // fast or secure, empty picks by mode
+        rng_backend: String,
+        // candidates generated per ranked string
+        oversample: u32,
+        scoring: String,
+        // file or sqlite db every emitted string is recorded in, empty for none
+        history: String,
+        // shared db strings are claimed in
+        reservation: Option<ReservationSpec>,
+        // ask before secrets are shown on recorded terminal, refuse in ci
+        confirm_display: bool,
+        clip_clear: u32,
     }
-    impl Config {
-        pub fn new(args: &[String]) -> Config {
-            return Config::from(args);
-        }
-        pub fn default() -> Config {
+    impl Default for Config {
+        fn default() -> Self {
             let amount = 16;
             let mode = Modes::RandomLetters;
             let write_to_file = false;
@@ -115,57 +544,114 @@ pub mod stringer {
 
             let next = String::new();
 
-            let length: u32 = 12;
+            let length = None;
+            let version = GeneratorVersion::latest();
             return Config {
                 mode,
                 length,
+                length_range: None,
                 amount,
                 write_to_file,
                 next,
+                next_path: None,
                 dont_write_indices,
+                version,
+                materialize: false,
+                escape: Escape::None,
+                noise: Noise::default(),
+                typos: None,
+                pairs: false,
+                padding: None,
+                sentences: 5,
+                words: 8,
+                difficulty: String::new(),
+                rules: String::new(),
+                wraps: Vec::new(),
+                sinks: Vec::new(),
+                append: false,
+                format: OutputFormat::Plain,
+                format_fields: ["index", "value", "mode", "lang"].map(String::from).to_vec(),
+                columns: String::new(),
+                batch_size: 100,
+                widths: Vec::new(),
+                labels: false,
+                label: String::new(),
+                group: false,
+                coverage: false,
+                strata: String::new(),
+                assertions: Assertions::default(),
+                min_distance: 0,
+                min_prefix: 0,
+                unique: false,
+                exclude_file: String::new(),
+                record: String::new(),
+                phonetic: None,
+                exclusions: Vec::new(),
+                screening: None,
+                keyboard_walks: None,
+                policy: String::new(),
+                profile: String::new(),
+                profanity: String::new(),
+                required: Vec::new(),
+                forbidden: Vec::new(),
+                seed: None,
+                rng_backend: String::new(),
+                oversample: 10,
+                scoring: String::new(),
+                history: String::new(),
+                reservation: None,
+                confirm_display: false,
+                clip_clear: DEFAULT_CLIP_CLEAR,
             };
         }
-        pub fn from(args: &[String]) -> Config {
-            let mut amount = 16;
-            let mut mode = Modes::RandomLetters;
-            let mut write_to_file = false;
-            let mut dont_write_indices = false;
-
-            let mut next = String::new();
-
-            let mut length: u32 = 12;
+    }
+    impl Config {
+        // typed settings for library callers, instead of arguments of command line
+        pub fn builder() -> ConfigBuilder {
+            return ConfigBuilder::new();
+        }
+        pub fn new(args: &[String]) -> Result<Config, StrgenError> {
+            return Config::from(args);
+        }
+        pub fn from(args: &[String]) -> Result<Config, StrgenError> {
+            let mut conf = Config::default();
 
             if args.len() > 1 {
                 //println!("{}",&args[1]);
-                amount = args[1].parse().expect("Number must be");
+                conf.amount = parse_number("amount", &args[1])?;
+                if conf.amount == 0 {
+                    return Err(StrgenError::NotPositive(String::from("amount")));
+                }
             }
 
             if args.len() > 2 {
                 println!("{}", &args[2]);
-                length = safe_u32(args[2].clone(), 4);
+                match parse_length_range(&args[2]) {
+                    Some((min, max)) => conf.set_length_range(min, max),
+                    None => conf.length = Some(parse_number("length", &args[2])?),
+                }
             }
             if args.len() > 3 {
-                mode = Modes::from(args[3].as_ref());
+                conf.mode = match Modes::parse(&args[3]) {
+                    Some(mode) => mode,
+                    None => return Err(StrgenError::UnknownMode(args[3].clone())),
+                };
             }
             if args.len() > 4 {
-                next = args[4].clone();
+                conf.next = args[4].clone();
             }
 
             if args.len() > 5 {
-                write_to_file = args[5] == "1";
+                conf.write_to_file = args[5] == "1";
             }
 
             if args.len() > 6 {
-                dont_write_indices = args[6] == "1";
+                conf.dont_write_indices = args[6] == "1";
             }
-            return Config {
-                mode,
-                length,
-                amount,
-                write_to_file,
-                next,
-                dont_write_indices,
-            };
+            if conf.length == Some(0) && conf.mode.needs_length() {
+                return Err(StrgenError::NotPositive(String::from("length")));
+            }
+            return Ok(conf);
         }
         pub fn set_mode(&mut self, mode: Modes) {
             self.mode = mode;
@@ -175,10 +661,20 @@ pub mod stringer {
         }
 
         pub fn set_length(&mut self, length: u32) {
-            self.length = length;
+            self.length = Some(length);
+            self.length_range = None;
         }
         pub fn get_length(&self) -> u32 {
-            return self.length;
+            return self.length.unwrap_or(self.mode.default_length());
+        }
+        pub fn set_length_range(&mut self, min: u32, max: u32) {
+            self.length = Some(max);
+            self.length_range = Some((min, max));
+        }
+        // (min, max), both length when no range was given
+        pub fn get_length_range(&self) -> (u32, u32) {
+            let length = self.get_length();
+            return self.length_range.unwrap_or((length, length));
         }
 
         pub fn set_amount(&mut self, amount: u32) {
@@ -204,9 +700,414 @@ pub mod stringer {
 
         pub fn set_next(&mut self, next: String) {
             self.next = next;
+            self.next_path = None;
         }
         pub fn get_next(&self) -> String {
             return self.next.clone();
         }
+
+        pub fn set_next_path(&mut self, path: PathBuf) {
+            self.next = path.to_string_lossy().into_owned();
+            self.next_path = Some(path);
+        }
+        // next as path, lossless even for non utf-8 arguments
+        pub fn get_next_path(&self) -> PathBuf {
+            return match &self.next_path {
+                Some(path) => path.clone(),
+                None => PathBuf::from(&self.next),
+            };
+        }
+
+        pub fn set_version(&mut self, version: GeneratorVersion) {
+            self.version = version;
+        }
+        pub fn get_version(&self) -> GeneratorVersion {
+            return self.version;
+        }
+
+        pub fn set_materialize(&mut self, materialize: bool) {
+            self.materialize = materialize;
+        }
+        pub fn get_materialize(&self) -> bool {
+            return self.materialize;
+        }
+
+        pub fn set_escape(&mut self, escape: Escape) {
+            self.escape = escape;
+        }
+        pub fn get_escape(&self) -> Escape {
+            return self.escape;
+        }
+
+        pub fn set_noise(&mut self, noise: Noise) {
+            self.noise = noise;
+        }
+        pub fn get_noise(&self) -> Noise {
+            return self.noise;
+        }
+        pub fn set_typos(&mut self, typos: Option<Typos>) {
+            self.typos = typos;
+        }
+        pub fn get_typos(&self) -> Option<Typos> {
+            return self.typos;
+        }
+        pub fn set_pairs(&mut self, pairs: bool) {
+            self.pairs = pairs;
+        }
+        pub fn get_pairs(&self) -> bool {
+            return self.pairs;
+        }
+        pub fn set_padding(&mut self, padding: Option<Padding>) {
+            self.padding = padding;
+        }
+        pub fn get_padding(&self) -> Option<Padding> {
+            return self.padding.clone();
+        }
+        pub fn set_sentences(&mut self, sentences: u32) {
+            self.sentences = sentences;
+        }
+        pub fn get_sentences(&self) -> u32 {
+            return self.sentences;
+        }
+        pub fn set_words(&mut self, words: u32) {
+            self.words = words;
+        }
+        pub fn get_words(&self) -> u32 {
+            return self.words;
+        }
+        pub fn set_difficulty(&mut self, difficulty: String) {
+            self.difficulty = difficulty;
+        }
+        pub fn get_difficulty(&self) -> String {
+            return self.difficulty.clone();
+        }
+        pub fn set_rules(&mut self, rules: String) {
+            self.rules = rules;
+        }
+        pub fn get_rules(&self) -> String {
+            return self.rules.clone();
+        }
+
+        pub fn set_wraps(&mut self, wraps: Vec<(String, Wrap)>) {
+            self.wraps = wraps;
+        }
+        // wrap for output target (file, stdout), specific entry wins over *
+        pub fn get_wrap(&self, target: &str) -> Wrap {
+            let mut result = Wrap::None;
+            for (t, wrap) in self.wraps.iter() {
+                if t == target {
+                    return *wrap;
+                }
+                if t == "*" {
+                    result = *wrap;
+                }
+            }
+            return result;
+        }
+
+        pub fn set_sink(&mut self, sink: String) {
+            self.sinks = vec![sink];
+        }
+        // one more output, every string goes to all of them
+        pub fn add_sink(&mut self, sink: String) {
+            self.sinks.push(sink);
+        }
+        pub fn set_append(&mut self, append: bool) {
+            self.append = append;
+        }
+        pub fn get_append(&self) -> bool {
+            return self.append;
+        }
+        pub fn set_format(&mut self, format: OutputFormat) {
+            self.format = format;
+        }
+        pub fn get_format(&self) -> OutputFormat {
+            return self.format;
+        }
+        pub fn set_format_fields(&mut self, format_fields: Vec<String>) {
+            self.format_fields = format_fields;
+        }
+        pub fn get_format_fields(&self) -> Vec<String> {
+            return self.format_fields.clone();
+        }
+        pub fn set_columns(&mut self, columns: String) {
+            self.columns = columns;
+        }
+        pub fn get_columns(&self) -> String {
+            return self.columns.clone();
+        }
+        pub fn get_sink(&self) -> String {
+            return self.get_sinks()[0].clone();
+        }
+        pub fn get_sinks(&self) -> Vec<String> {
+            if !self.sinks.is_empty() {
+                return self.sinks.clone();
+            }
+            if self.write_to_file {
+                return vec![String::from("file")];
+            }
+            return vec![String::from("stdout")];
+        }
+
+        pub fn set_batch_size(&mut self, batch_size: u32) {
+            self.batch_size = batch_size;
+        }
+        pub fn get_batch_size(&self) -> u32 {
+            return self.batch_size;
+        }
+        pub fn set_widths(&mut self, widths: Vec<usize>) {
+            self.widths = widths;
+        }
+        pub fn get_widths(&self) -> Vec<usize> {
+            return self.widths.clone();
+        }
+
+        pub fn set_labels(&mut self, labels: bool) {
+            self.labels = labels;
+        }
+        pub fn get_labels(&self) -> bool {
+            return self.labels;
+        }
+        // fixed label for the run, turns labels on
+        pub fn set_label(&mut self, label: String) {
+            self.labels = true;
+            self.label = label;
+        }
+        pub fn get_label(&self) -> String {
+            return self.label.clone();
+        }
+
+        pub fn set_group(&mut self, group: bool) {
+            self.group = group;
+        }
+        pub fn get_group(&self) -> bool {
+            return self.group;
+        }
+
+        pub fn set_coverage(&mut self, coverage: bool) {
+            self.coverage = coverage;
+        }
+        pub fn get_coverage(&self) -> bool {
+            return self.coverage;
+        }
+
+        pub fn set_strata(&mut self, strata: String) {
+            self.strata = strata;
+        }
+        pub fn get_strata(&self) -> String {
+            return self.strata.clone();
+        }
+
+        pub fn set_assertions(&mut self, assertions: Assertions) {
+            self.assertions = assertions;
+        }
+        pub fn get_assertions(&self) -> Assertions {
+            return self.assertions;
+        }
+
+        pub fn set_min_distance(&mut self, min_distance: usize) {
+            self.min_distance = min_distance;
+        }
+        pub fn get_min_distance(&self) -> usize {
+            return self.min_distance;
+        }
+
+        pub fn set_min_prefix(&mut self, min_prefix: usize) {
+            self.min_prefix = min_prefix;
+        }
+        pub fn get_min_prefix(&self) -> usize {
+            return self.min_prefix;
+        }
+
+        pub fn set_unique(&mut self, unique: bool) {
+            self.unique = unique;
+        }
+        pub fn get_unique(&self) -> bool {
+            return self.unique;
+        }
+
+        pub fn set_exclude_file(&mut self, exclude_file: String) {
+            self.exclude_file = exclude_file;
+        }
+        pub fn get_exclude_file(&self) -> String {
+            return self.exclude_file.clone();
+        }
+
+        pub fn set_record(&mut self, record: String) {
+            self.record = record;
+        }
+        pub fn get_record(&self) -> String {
+            return self.record.clone();
+        }
+
+        pub fn set_phonetic(&mut self, phonetic: Option<Phonetic>) {
+            self.phonetic = phonetic;
+        }
+        pub fn get_phonetic(&self) -> Option<Phonetic> {
+            return self.phonetic;
+        }
+
+        pub fn set_exclusions(&mut self, exclusions: Vec<(String, String)>) {
+            self.exclusions = exclusions;
+        }
+        pub fn get_exclusions(&self) -> Vec<(String, String)> {
+            return self.exclusions.clone();
+        }
+
+        pub fn set_screening(&mut self, screening: Option<Screening>) {
+            self.screening = screening;
+        }
+        pub fn get_screening(&self) -> Option<Screening> {
+            return self.screening.clone();
+        }
+
+        pub fn set_keyboard_walks(&mut self, keyboard_walks: Option<KeyboardWalks>) {
+            self.keyboard_walks = keyboard_walks;
+        }
+        pub fn get_keyboard_walks(&self) -> Option<KeyboardWalks> {
+            return self.keyboard_walks.clone();
+        }
+
+        pub fn set_policy(&mut self, policy: String) {
+            self.policy = policy;
+        }
+        pub fn get_policy(&self) -> String {
+            return self.policy.clone();
+        }
+
+        pub fn set_profile(&mut self, profile: String) {
+            self.profile = profile;
+        }
+        pub fn get_profile(&self) -> String {
+            return self.profile.clone();
+        }
+        pub fn set_profanity(&mut self, profanity: String) {
+            self.profanity = profanity;
+        }
+        pub fn get_profanity(&self) -> String {
+            return self.profanity.clone();
+        }
+        pub fn set_required(&mut self, required: Vec<String>) {
+            self.required = required;
+        }
+        pub fn get_required(&self) -> Vec<String> {
+            return self.required.clone();
+        }
+        pub fn set_forbidden(&mut self, forbidden: Vec<String>) {
+            self.forbidden = forbidden;
+        }
+        pub fn get_forbidden(&self) -> Vec<String> {
+            return self.forbidden.clone();
+        }
+        pub fn set_seed(&mut self, seed: Option<u64>) {
+            self.seed = seed;
+        }
+        pub fn get_seed(&self) -> Option<u64> {
+            return self.seed;
+        }
+        // rng for generator, stream tells apart several rngs of one generator
+        pub fn rng(&self, stream: u64) -> RNG {
+            return RNG::seeded(self.seed, stream);
+        }
+        pub fn set_rng_backend(&mut self, rng_backend: String) {
+            self.rng_backend = rng_backend;
+        }
+        pub fn get_rng_backend(&self) -> String {
+            return self.rng_backend.clone();
+        }
+        // secure for passwords and tokens unless seed asks for repeatable run, fast otherwise
+        pub fn uses_secure_rng(&self) -> Result<bool, Error> {
+            return match self.rng_backend.as_str() {
+                "" => Ok(self.mode.needs_secure_rng() && self.seed.is_none()),
+                "fast" => Ok(false),
+                "secure" | "os" => Ok(true),
+                other => {
+                    let message = format!("unknown rng {}, there are fast and secure", other);
+                    Err(Error::new(std::io::ErrorKind::InvalidInput, message))
+                }
+            };
+        }
+        pub fn backend(&self, stream: u64) -> Result<Box<dyn RngBackend>, Error> {
+            if self.uses_secure_rng()? {
+                return Ok(Box::new(OsEntropy::new()?));
+            }
+            return Ok(Box::new(self.rng(stream)));
+        }
+
+        pub fn set_oversample(&mut self, oversample: u32) {
+            self.oversample = oversample.max(1);
+        }
+        pub fn get_oversample(&self) -> u32 {
+            return self.oversample;
+        }
+        pub fn set_scoring(&mut self, scoring: String) {
+            self.scoring = scoring;
+        }
+        pub fn get_scoring(&self) -> String {
+            return self.scoring.clone();
+        }
+
+        pub fn set_history(&mut self, history: String) {
+            self.history = history;
+        }
+        pub fn get_history(&self) -> String {
+            return self.history.clone();
+        }
+
+        pub fn set_reservation(&mut self, reservation: Option<ReservationSpec>) {
+            self.reservation = reservation;
+        }
+        pub fn get_reservation(&self) -> Option<ReservationSpec> {
+            return self.reservation.clone();
+        }
+
+        pub fn set_confirm_display(&mut self, confirm_display: bool) {
+            self.confirm_display = confirm_display;
+        }
+        pub fn get_confirm_display(&self) -> bool {
+            return self.confirm_display;
+        }
+
+        pub fn set_clip_clear(&mut self, clip_clear: u32) {
+            self.clip_clear = clip_clear;
+        }
+        pub fn get_clip_clear(&self) -> u32 {
+            return self.clip_clear;
+        }
+
+        // one line description of the run, enough to reproduce it
+        pub fn metadata(&self) -> String {
+            let mut meta = format!(
+                "stranameg={} generator=v{} mode={} length={} amount={} next={}",
+                env!("CARGO_PKG_VERSION"),
+                self.version.number(),
+                self.mode.abbr(),
+                match self.length_range {
+                    Some((min, max)) => format!("{}..{}", min, max),
+                    None => self.get_length().to_string(),
+                },
+                self.amount,
+                self.next
+            );
+            // list edits change output of same seed, hashes tell which list changed
+            if self.mode.uses_word_lists() {
+                let hashes = list_hashes(&Languages::from(self.next.as_ref()));
+                meta = format!("{} lists={}", meta, hashes.join(","));
+            }
+            if let Some(seed) = self.seed {
+                meta = format!("{} seed={}", meta, seed);
+            }
+            if !self.rules.is_empty() {
+                meta = format!("{} rules={}", meta, self.rules);
+            }
+            // passwords carry their policy and when they should be changed
+            if self.policy.is_empty() {
+                return meta;
+            }
+            return match PasswordPolicy::load(&self.policy) {
+                Ok(policy) => format!("{} policy={} expires={}", meta, policy.name, policy.expiry_hint()),
+                Err(_e) => meta,
+            };
+        }
     }
 }
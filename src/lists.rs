@@ -0,0 +1,590 @@
+pub mod lists {
+    use std::collections::{BTreeMap, HashMap, HashSet};
+    use std::io::{Error, ErrorKind};
+    use std::path::Path;
+
+    use sha2::{Digest, Sha256};
+
+    use crate::paths::paths::lists_dir;
+    #[cfg(feature = "tagging")]
+    use crate::stringer::{read_lexicon, Tag, Tagger};
+    use crate::stringer::{tr, tr_with, Languages, ListType, StrgenError, WordList, PROFANITY};
+
+    // five dice, 6^5 entries
+    pub const DICE: u32 = 5;
+    pub const DICEWARE_ENTRIES: usize = 7776;
+
+    // words of list file as word lists read them (comma or line separated, #tags dropped),
+    // repeated ones included
+    pub fn read_entries(path: &Path) -> Result<Vec<String>, Error> {
+        let mut list = WordList::new(ListType::Nouns, Languages::English);
+        list.fill(path)?;
+        let words: Vec<String> = (0..list.get_list_len())
+            .map(|i| list.get_at(i))
+            .filter(|word| !word.is_empty())
+            .collect();
+        if words.is_empty() {
+            return Err(StrgenError::EmptyList(path.to_path_buf()).into());
+        }
+        return Ok(words);
+    }
+
+    // first occurrence of every word kept
+    pub fn read_list(path: &Path) -> Result<Vec<String>, Error> {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut words = read_entries(path)?;
+        words.retain(|word| seen.insert(word.clone()));
+        return Ok(words);
+    }
+
+    // what curators of language packs check before list ships
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct ListStats {
+        pub entries: usize,
+        pub unique: usize,
+        // length in characters -> entries
+        pub lengths: BTreeMap<usize, usize>,
+        pub non_ascii: usize,
+        // first letter, lower case -> entries
+        pub starts: BTreeMap<char, usize>,
+    }
+    impl ListStats {
+        pub fn of(words: &[String]) -> ListStats {
+            let unique: HashSet<&String> = words.iter().collect();
+            let mut stats = ListStats {
+                entries: words.len(),
+                unique: unique.len(),
+                lengths: BTreeMap::new(),
+                non_ascii: 0,
+                starts: BTreeMap::new(),
+            };
+            for word in words {
+                *stats.lengths.entry(word.chars().count()).or_default() += 1;
+                if !word.is_ascii() {
+                    stats.non_ascii += 1;
+                }
+                if let Some(first) = word.chars().next().and_then(|c| c.to_lowercase().next()) {
+                    *stats.starts.entry(first).or_default() += 1;
+                }
+            }
+            return stats;
+        }
+        pub fn duplicates(&self) -> usize {
+            return self.entries - self.unique;
+        }
+        pub fn mean_length(&self) -> f64 {
+            if self.entries == 0 {
+                return 0.0;
+            }
+            let total: usize = self.lengths.iter().map(|(length, n)| length * n).sum();
+            return total as f64 / self.entries as f64;
+        }
+        pub fn non_ascii_fraction(&self) -> f64 {
+            if self.entries == 0 {
+                return 0.0;
+            }
+            return self.non_ascii as f64 / self.entries as f64;
+        }
+        // bits one word drawn uniformly from list adds to passphrase
+        pub fn entropy(&self) -> f64 {
+            if self.unique == 0 {
+                return 0.0;
+            }
+            return (self.unique as f64).log2();
+        }
+        pub fn report(&self) -> String {
+            let mut text = String::new();
+            let entries = tr("report.entries");
+            text.push_str(&format!("{}\t{}\n", entries, self.entries));
+            text.push_str(&format!("{}\t{}\n", tr("report.unique"), self.unique));
+            text.push_str(&format!("{}\t{}\n", tr("report.duplicates"), self.duplicates()));
+            let mean = format!("{:.2}", self.mean_length());
+            text.push_str(&format!("{}\t{}\n", tr("report.mean_length"), mean));
+            let non_ascii = format!("{:.1}", self.non_ascii_fraction() * 100.0);
+            text.push_str(&format!("{}\t{}%\n", tr("report.non_ascii"), non_ascii));
+            let entropy = tr_with("report.bits_per_word", &[&format!("{:.2}", self.entropy())]);
+            text.push_str(&format!("{}\t{}\n", tr("report.entropy"), entropy));
+            text.push_str(&format!("\n{}\t{}\n", tr("report.length"), entries));
+            for (length, n) in self.lengths.iter() {
+                text.push_str(&format!("{}\t{}\n", length, n));
+            }
+            text.push_str(&format!("\n{}\t{}\n", tr("report.starts_with"), entries));
+            for (first, n) in self.starts.iter() {
+                text.push_str(&format!("{}\t{}\n", first, n));
+            }
+            return text;
+        }
+    }
+
+    // entries compared without case and with whitespace squeezed, so Apfel and apfel
+    // are one entry that changed spelling
+    pub fn list_key(word: &str) -> String {
+        let words: Vec<String> = word.split_whitespace().map(|w| w.to_lowercase()).collect();
+        return words.join(" ");
+    }
+
+    // what edit of list did, entries in order of list they come from
+    #[derive(Clone, PartialEq, Debug, Default)]
+    pub struct ListDiff {
+        pub added: Vec<String>,
+        pub removed: Vec<String>,
+        // old spelling and new one
+        pub changed: Vec<(String, String)>,
+    }
+    impl ListDiff {
+        pub fn of(old: &[String], new: &[String]) -> ListDiff {
+            let keyed = |words: &[String]| -> HashMap<String, String> {
+                let mut map: HashMap<String, String> = HashMap::new();
+                for word in words {
+                    map.entry(list_key(word)).or_insert_with(|| word.clone());
+                }
+                return map;
+            };
+            let (old_keys, new_keys) = (keyed(old), keyed(new));
+            let mut diff = ListDiff::default();
+            let mut seen: HashSet<String> = HashSet::new();
+            for word in old {
+                let key = list_key(word);
+                if !seen.insert(key.clone()) {
+                    continue;
+                }
+                match new_keys.get(&key) {
+                    None => diff.removed.push(word.clone()),
+                    Some(spelled) if spelled != word => {
+                        diff.changed.push((word.clone(), spelled.clone()))
+                    }
+                    Some(_same) => {}
+                }
+            }
+            seen.clear();
+            for word in new {
+                let key = list_key(word);
+                if seen.insert(key.clone()) && !old_keys.contains_key(&key) {
+                    diff.added.push(word.clone());
+                }
+            }
+            return diff;
+        }
+        pub fn is_empty(&self) -> bool {
+            return self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty();
+        }
+        // + added, - removed, ~ old -> new, then counts
+        pub fn report(&self) -> String {
+            let mut text = String::new();
+            for word in self.added.iter() {
+                text.push_str(&format!("+ {}\n", word));
+            }
+            for word in self.removed.iter() {
+                text.push_str(&format!("- {}\n", word));
+            }
+            for (old, new) in self.changed.iter() {
+                text.push_str(&format!("~ {} -> {}\n", old, new));
+            }
+            let counts = [self.added.len(), self.removed.len(), self.changed.len()];
+            let counts: Vec<String> = counts.iter().map(|n| n.to_string()).collect();
+            let summary = tr_with("report.diff", &[&counts[0], &counts[1], &counts[2]]);
+            text.push_str(&format!("{}\n", summary));
+            return text;
+        }
+    }
+
+    // first 12 hex digits of sha256 of file content
+    pub fn list_hash(path: &Path) -> Result<String, Error> {
+        let digest = Sha256::digest(std::fs::read(path)?);
+        let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+        return Ok(String::from(&hex[..12]));
+    }
+
+    // name:hash of every bundled list of language, sorted by name, for run metadata
+    pub fn list_hashes(language: &Languages) -> Vec<String> {
+        let suffixes = [
+            format!(".{}.list", language.abbr()),
+            format!(".{}.dic", language.abbr()),
+        ];
+        let Ok(entries) = std::fs::read_dir(lists_dir()) else {
+            return Vec::new();
+        };
+        let mut hashes: Vec<String> = entries
+            .map_while(Result::ok)
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if !suffixes.iter().any(|s| name.ends_with(s.as_str())) {
+                    return None;
+                }
+                let hash = list_hash(&entry.path()).ok()?;
+                return Some(format!("{}:{}", name, hash));
+            })
+            .collect();
+        hashes.sort();
+        return hashes;
+    }
+
+    // lower case words of profanity list, # comments skipped
+    pub fn profanity_words(list: &str) -> HashSet<String> {
+        return list
+            .lines()
+            .map(|line| line.trim().to_lowercase())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+    }
+
+    // latin, german, georgian or cyrillic letters, anything else is letters given
+    pub fn alphabet_of(name: &str) -> Vec<char> {
+        return match name {
+            "latin" => ('a'..='z').collect(),
+            "german" => ('a'..='z').chain(['ä', 'ö', 'ü', 'ß']).collect(),
+            "georgian" => ('ა'..='ჰ').collect(),
+            "cyrillic" => ('а'..='я').chain(['ё']).collect(),
+            _ => name.chars().flat_map(|c| c.to_lowercase()).collect(),
+        };
+    }
+
+    // what words of corpus make it into list
+    pub struct ExtractOptions {
+        // times word must occur in text
+        pub min_count: usize,
+        pub min_length: usize,
+        pub max_length: usize,
+        // allowed letters in lower case, empty allows every letter
+        pub alphabet: Vec<char>,
+        // most frequent words kept, 0 keeps all
+        pub top: usize,
+        // words stay as written instead of lower case, for german nouns
+        pub keep_case: bool,
+        // inflected forms counted as one word of this language
+        pub stem: Option<Languages>,
+        pub profanity: HashSet<String>,
+        // only words tagger of pos_language takes for this part of speech
+        #[cfg(feature = "tagging")]
+        pub pos: Option<Tag>,
+        #[cfg(feature = "tagging")]
+        pub pos_language: Languages,
+        // own words of tagger, for language without bundled lists
+        #[cfg(feature = "tagging")]
+        pub lexicon: Vec<(String, Tag)>,
+    }
+    impl Default for ExtractOptions {
+        fn default() -> Self {
+            return ExtractOptions {
+                min_count: 2,
+                min_length: 3,
+                max_length: 12,
+                alphabet: Vec::new(),
+                top: 0,
+                keep_case: false,
+                stem: None,
+                profanity: profanity_words(PROFANITY),
+                #[cfg(feature = "tagging")]
+                pos: None,
+                #[cfg(feature = "tagging")]
+                pos_language: Languages::English,
+                #[cfg(feature = "tagging")]
+                lexicon: Vec::new(),
+            };
+        }
+    }
+    impl ExtractOptions {
+        // --min-count 3, --alphabet latin.., false when flag is not option of extract
+        pub fn set(&mut self, flag: &str, value: &str) -> Result<bool, Error> {
+            let number = || {
+                return value.trim().parse::<usize>().map_err(|_e| {
+                    let message = format!("{} needs a number, got {}", flag, value);
+                    return Error::new(ErrorKind::InvalidInput, message);
+                });
+            };
+            match flag {
+                "--min-count" => self.min_count = number()?,
+                "--min-length" => self.min_length = number()?,
+                "--max-length" => self.max_length = number()?,
+                "--top" => self.top = number()?,
+                "--alphabet" => self.alphabet = alphabet_of(value),
+                "--stem" => self.stem = Some(Languages::from_code(value)),
+                "--profanity" => {
+                    self.profanity = profanity_words(&std::fs::read_to_string(value)?);
+                }
+                #[cfg(feature = "tagging")]
+                "--pos" => self.pos = Some(Tag::from(value)?),
+                #[cfg(feature = "tagging")]
+                "--lang" => self.pos_language = Languages::from_code(value),
+                #[cfg(feature = "tagging")]
+                "--lexicon" => self.lexicon = read_lexicon(Path::new(value))?,
+                #[cfg(not(feature = "tagging"))]
+                "--pos" | "--lang" | "--lexicon" => {
+                    let message = format!(
+                        "{} needs the tagging feature (cargo build --features tagging)",
+                        flag
+                    );
+                    return Err(Error::other(message));
+                }
+                _ => return Ok(false),
+            }
+            return Ok(true);
+        }
+        fn accepts(&self, word: &str, count: usize) -> bool {
+            let length = word.chars().count();
+            return count >= self.min_count
+                && length >= self.min_length
+                && length <= self.max_length
+                && (self.alphabet.is_empty()
+                    || word
+                        .chars()
+                        .flat_map(|c| c.to_lowercase())
+                        .all(|c| self.alphabet.contains(&c)))
+                && !self.profanity.contains(&word.to_lowercase());
+        }
+    }
+
+    // forms sharing stem become one word with their counts added up: form that is stem itself
+    // (Baum for Baum, Bäume, Bäumen), else most frequent, then shortest one
+    pub fn lemmas(counts: HashMap<String, usize>, language: &Languages) -> HashMap<String, usize> {
+        let mut groups: HashMap<String, Vec<(String, usize)>> = HashMap::new();
+        for (word, count) in counts {
+            groups
+                .entry(language.stem(&word))
+                .or_default()
+                .push((word, count));
+        }
+        let mut lemmas: HashMap<String, usize> = HashMap::new();
+        for (stem, mut forms) in groups {
+            let total = forms.iter().map(|(_w, count)| count).sum();
+            forms.sort_by(|a, b| {
+                // nothing cut off, endings are always replaced by something shorter
+                let is_stem = |word: &str| stem.chars().count() == word.chars().count();
+                return is_stem(&b.0)
+                    .cmp(&is_stem(&a.0))
+                    .then(b.1.cmp(&a.1))
+                    .then(a.0.chars().count().cmp(&b.0.chars().count()))
+                    .then(a.0.cmp(&b.0));
+            });
+            lemmas.insert(forms.swap_remove(0).0, total);
+        }
+        return lemmas;
+    }
+
+    // words of text split at everything but letters, most frequent first, then alphabetical
+    pub fn extract_words(text: &str, options: &ExtractOptions) -> Vec<String> {
+        #[cfg(feature = "tagging")]
+        let tags = options.pos.map(|_pos| {
+            let mut tagger = Tagger::new(&options.pos_language);
+            for (word, tag) in options.lexicon.iter() {
+                tagger.add(word, *tag);
+            }
+            return tagger.tags(text);
+        });
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for token in text.split(|c: char| !c.is_alphabetic()) {
+            if token.is_empty() {
+                continue;
+            }
+            #[cfg(feature = "tagging")]
+            if let Some(tags) = tags.as_ref() {
+                if tags.get(&token.to_lowercase()) != options.pos.as_ref() {
+                    continue;
+                }
+            }
+            let word = if options.keep_case {
+                String::from(token)
+            } else {
+                token.to_lowercase()
+            };
+            *counts.entry(word).or_default() += 1;
+        }
+        if let Some(language) = options.stem.as_ref() {
+            counts = lemmas(counts, language);
+        }
+        let mut words: Vec<(String, usize)> = counts
+            .into_iter()
+            .filter(|(word, count)| options.accepts(word, *count))
+            .collect();
+        words.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        if options.top > 0 {
+            words.truncate(options.top);
+        }
+        return words.into_iter().map(|(word, _count)| word).collect();
+    }
+
+    pub fn extract_list(path: &Path, options: &ExtractOptions) -> Result<Vec<String>, Error> {
+        let words = extract_words(&std::fs::read_to_string(path)?, options);
+        if words.is_empty() {
+            let message = format!("no words of {} passed filters", path.display());
+            return Err(Error::new(ErrorKind::InvalidData, message));
+        }
+        return Ok(words);
+    }
+
+    // list file as bundled ones are written, ten words per line separated by commas
+    pub fn list_lines(words: &[String]) -> String {
+        let mut text = String::new();
+        for line in words.chunks(10) {
+            text.push_str(&line.join(","));
+            text.push('\n');
+        }
+        return text;
+    }
+
+    // tags of frequency tiers, most frequent first
+    pub const TIERS: [&str; 3] = ["common", "uncommon", "rare"];
+
+    // how list is split into tiers and which tiers are dropped
+    pub struct TierOptions {
+        // percent of words that are common, and common or uncommon
+        pub cuts: (usize, usize),
+        // text frequencies are counted in, else list is taken as most frequent first
+        pub corpus: Option<String>,
+        pub drop: Vec<String>,
+    }
+    impl Default for TierOptions {
+        fn default() -> Self {
+            return TierOptions {
+                cuts: (20, 50),
+                corpus: None,
+                drop: Vec::new(),
+            };
+        }
+    }
+    impl TierOptions {
+        // --cuts 20,50, --corpus text, --drop rare.., false when flag is not option of tiers
+        pub fn set(&mut self, flag: &str, value: &str) -> Result<bool, Error> {
+            match flag {
+                "--cuts" => {
+                    let cuts: Vec<usize> = value
+                        .split(',')
+                        .filter_map(|c| c.trim().parse().ok())
+                        .collect();
+                    self.cuts = match cuts.as_slice() {
+                        [common, uncommon] if common <= uncommon && *uncommon <= 100 => {
+                            (*common, *uncommon)
+                        }
+                        _ => {
+                            let message =
+                                format!("--cuts needs two percents like 20,50, got {}", value);
+                            return Err(Error::new(ErrorKind::InvalidInput, message));
+                        }
+                    };
+                }
+                "--corpus" => self.corpus = Some(std::fs::read_to_string(value)?),
+                "--drop" => {
+                    for tier in value.split(',').map(|t| t.trim()) {
+                        if !TIERS.contains(&tier) {
+                            let message =
+                                format!("unknown tier {}, expected common, uncommon or rare", tier);
+                            return Err(Error::new(ErrorKind::InvalidInput, message));
+                        }
+                        self.drop.push(String::from(tier));
+                    }
+                }
+                _ => return Ok(false),
+            }
+            return Ok(true);
+        }
+    }
+
+    // tier of every word in list order; rank is place in corpus counts (words missing from
+    // corpus are rare) or in list itself, as extract writes most frequent first
+    pub fn frequency_tiers(words: &[String], options: &TierOptions) -> Vec<(String, &'static str)> {
+        let mut ranked: Vec<usize> = (0..words.len()).collect();
+        let mut missing: HashSet<usize> = HashSet::new();
+        if let Some(corpus) = options.corpus.as_ref() {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for token in corpus.split(|c: char| !c.is_alphabetic()) {
+                if !token.is_empty() {
+                    *counts.entry(token.to_lowercase()).or_default() += 1;
+                }
+            }
+            let count = |i: &usize| counts.get(&words[*i].to_lowercase()).copied().unwrap_or(0);
+            // stable, words of same count keep list order
+            ranked.sort_by_key(|i| std::cmp::Reverse(count(i)));
+            missing = (0..words.len()).filter(|i| count(i) == 0).collect();
+        }
+        let mut tiers: Vec<&'static str> = vec![TIERS[2]; words.len()];
+        for (rank, i) in ranked.into_iter().enumerate() {
+            let percent = rank * 100 / words.len();
+            tiers[i] = if missing.contains(&i) {
+                TIERS[2]
+            } else if percent < options.cuts.0 {
+                TIERS[0]
+            } else if percent < options.cuts.1 {
+                TIERS[1]
+            } else {
+                TIERS[2]
+            };
+        }
+        return words
+            .iter()
+            .cloned()
+            .zip(tiers)
+            .filter(|(_w, tier)| !options.drop.iter().any(|d| d == tier))
+            .collect();
+    }
+
+    // word#tier, ready for strata=tag:common=..
+    pub fn tier_lines(tiers: &[(String, &'static str)]) -> String {
+        let tagged: Vec<String> = tiers
+            .iter()
+            .map(|(word, tier)| format!("{}#{}", word, tier))
+            .collect();
+        return list_lines(&tagged);
+    }
+
+    // 0 is 11111, 7775 is 66666
+    pub fn dice_index(index: usize, dice: u32) -> String {
+        let mut rolls: Vec<char> = Vec::with_capacity(dice as usize);
+        let mut rest = index;
+        for _ in 0..dice {
+            rolls.push((b'1' + (rest % 6) as u8) as char);
+            rest /= 6;
+        }
+        rolls.reverse();
+        return rolls.into_iter().collect();
+    }
+
+    // exactly 7776 words in alphabetical order; short list is error unless pad is given, then
+    // list words with number after them fill it up, long one is error unless trim keeps shortest
+    pub fn diceware_table(words: &[String], pad: bool, trim: bool) -> Result<Vec<String>, Error> {
+        let mut table: Vec<String> = words.to_vec();
+        if table.len() > DICEWARE_ENTRIES {
+            if !trim {
+                let message = format!(
+                    "list has {} words, diceware table takes {}, use --trim to keep shortest",
+                    table.len(),
+                    DICEWARE_ENTRIES
+                );
+                return Err(Error::new(ErrorKind::InvalidData, message));
+            }
+            table.sort_by(|a, b| a.chars().count().cmp(&b.chars().count()).then(a.cmp(b)));
+            table.truncate(DICEWARE_ENTRIES);
+        }
+        if table.len() < DICEWARE_ENTRIES {
+            if !pad {
+                let message = format!(
+                    "list has {} words, diceware table takes {}, use --pad to fill it up",
+                    table.len(),
+                    DICEWARE_ENTRIES
+                );
+                return Err(Error::new(ErrorKind::InvalidData, message));
+            }
+            let mut taken: HashSet<String> = table.iter().cloned().collect();
+            let mut number = 2;
+            while table.len() < DICEWARE_ENTRIES {
+                for word in words.iter() {
+                    let padded = format!("{}{}", word, number);
+                    if table.len() < DICEWARE_ENTRIES && taken.insert(padded.clone()) {
+                        table.push(padded);
+                    }
+                }
+                number += 1;
+            }
+        }
+        table.sort();
+        return Ok(table);
+    }
+
+    // lines of 5 dice, tab and word
+    pub fn diceware_lines(table: &[String]) -> String {
+        let mut text = String::new();
+        for (i, word) in table.iter().enumerate() {
+            text.push_str(&format!("{}\t{}\n", dice_index(i, DICE), word));
+        }
+        return text;
+    }
+}
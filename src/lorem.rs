@@ -0,0 +1,174 @@
+pub mod lorem {
+    use std::io::{Error, ErrorKind};
+    use std::path::Path;
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{Config, GeneratorVersion, Languages, ListType, WordList, RNG};
+
+    // words of classic placeholder text, used when no language is given
+    pub const LOREM: [&str; 64] = [
+        "lorem",
+        "ipsum",
+        "dolor",
+        "sit",
+        "amet",
+        "consectetur",
+        "adipiscing",
+        "elit",
+        "sed",
+        "do",
+        "eiusmod",
+        "tempor",
+        "incididunt",
+        "ut",
+        "labore",
+        "et",
+        "dolore",
+        "magna",
+        "aliqua",
+        "enim",
+        "ad",
+        "minim",
+        "veniam",
+        "quis",
+        "nostrud",
+        "exercitation",
+        "ullamco",
+        "laboris",
+        "nisi",
+        "aliquip",
+        "ex",
+        "ea",
+        "commodo",
+        "consequat",
+        "duis",
+        "aute",
+        "irure",
+        "in",
+        "reprehenderit",
+        "voluptate",
+        "velit",
+        "esse",
+        "cillum",
+        "eu",
+        "fugiat",
+        "nulla",
+        "pariatur",
+        "excepteur",
+        "sint",
+        "occaecat",
+        "cupidatat",
+        "non",
+        "proident",
+        "sunt",
+        "culpa",
+        "qui",
+        "officia",
+        "deserunt",
+        "mollit",
+        "anim",
+        "id",
+        "est",
+        "laborum",
+        "vel",
+    ];
+    // one word in this many is followed by comma, never last one of sentence
+    const COMMA_ONE_IN: usize = 6;
+
+    fn capitalized(word: &str) -> String {
+        let mut chars = word.chars();
+        return match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        };
+    }
+
+    // 4th parameter: language of word lists, built in latin words when empty;
+    // every string is paragraph of sentences= sentences with words= words each
+    pub struct LoremParagraphs {
+        // adjectives, nouns and verbs of language, empty for built in words
+        lists: Vec<WordList>,
+        sentences: u32,
+        words: u32,
+        rng: RNG,
+        version: GeneratorVersion,
+    }
+    impl Default for LoremParagraphs {
+        fn default() -> Self {
+            return LoremParagraphs::new();
+        }
+    }
+    impl LoremParagraphs {
+        pub fn new() -> LoremParagraphs {
+            let mut rng = RNG::new();
+            rng.seed();
+            return LoremParagraphs {
+                lists: Vec::new(),
+                sentences: 5,
+                words: 8,
+                rng,
+                version: GeneratorVersion::latest(),
+            };
+        }
+        fn word(&mut self) -> String {
+            if self.lists.is_empty() {
+                let index = self.version.pick_index(&mut self.rng, LOREM.len());
+                return String::from(LOREM[index]);
+            }
+            let index = self.version.pick_index(&mut self.rng, self.lists.len());
+            return self.lists[index].get();
+        }
+        pub fn sentence(&mut self) -> String {
+            let mut sentence = String::new();
+            for i in 0..self.words {
+                let word = self.word();
+                if i == 0 {
+                    sentence.push_str(&capitalized(&word));
+                    continue;
+                }
+                let comma =
+                    i + 1 < self.words && self.version.pick_index(&mut self.rng, COMMA_ONE_IN) == 0;
+                sentence.push(' ');
+                sentence.push_str(&word);
+                if comma {
+                    sentence.push(',');
+                }
+            }
+            sentence.push('.');
+            return sentence;
+        }
+    }
+    impl StringGenerator for LoremParagraphs {
+        fn get(&mut self) -> String {
+            let sentences: Vec<String> = (0..self.sentences).map(|_| self.sentence()).collect();
+            return sentences.join(" ");
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.sentences = conf.get_sentences().max(1);
+            self.words = conf.get_words().max(1);
+            self.version = conf.get_version();
+            self.rng = conf.rng(0);
+            self.lists.clear();
+            let next = conf.get_next();
+            if next.is_empty() {
+                return Ok(());
+            }
+            let kinds = [ListType::Adjectives, ListType::Nouns, ListType::Verbs];
+            for (i, kind) in kinds.into_iter().enumerate() {
+                let mut list = WordList::new(kind, Languages::from(next.as_ref()));
+                // languages without verbs list still have adjectives and nouns
+                if list.fill(Path::new("")).is_err() || list.get_list_len() == 0 {
+                    continue;
+                }
+                list.set_version(conf.get_version());
+                list.set_rng(conf.rng(i as u64 + 1));
+                self.lists.push(list);
+            }
+            if self.lists.is_empty() {
+                let message = format!("no word lists for language {}", next);
+                return Err(Error::new(ErrorKind::NotFound, message));
+            }
+            return Ok(());
+        }
+    }
+}
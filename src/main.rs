@@ -1,29 +1,670 @@
 use std::env;
+use std::ffi::OsString;
+use std::io::{Error, ErrorKind, Write};
+use std::path::Path;
 
 use stranameg::stringer::{
-    command_parser, fast_switch, print_help, run_generator, run_repl, Config,
+    command_parser, fast_switch, print_help, run_generator, run_repl, set_ui_language, tr,
+    out_spec, safe_u32, shuffle_file, tr_with, utf8_console, Config, Languages, RNG,
+    run_curate, search_history, DEFAULT_HISTORY, Reservations, DEFAULT_RESERVATIONS,
+    default_owner, parse_duration, decrypt, read_passphrase, clear_after, CLEAR_COMMAND,
+    DEFAULT_CLIP_CLEAR, StrgenError, parse_length_range, TokenRegistry, read_list,
+    diceware_table, diceware_lines, read_entries, ListDiff, ListStats, ExtractOptions,
+    extract_list, list_lines, TierOptions, frequency_tiers, tier_lines, proquint_decode,
+    bubblebabble_decode, WordGrid, TRIPLE_SEPARATOR, crockford_decode, base62_decode,
 };
+use stranameg::paths::paths::strip_prefix_os;
+
+// removes --ui-lang xx (or --ui-lang=xx) from arguments and applies it
+fn take_ui_lang(args: &mut Vec<OsString>) {
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].to_string_lossy().into_owned();
+        if let Some(lang) = arg.strip_prefix("--ui-lang=") {
+            set_ui_language(Languages::from_code(lang));
+            args.remove(i);
+            continue;
+        }
+        if arg == "--ui-lang" && i + 1 < args.len() {
+            set_ui_language(Languages::from_code(args[i + 1].to_string_lossy().as_ref()));
+            args.drain(i..i + 2);
+            continue;
+        }
+        i += 1;
+    }
+}
+
+// removes every --out x (or --out=x), in order given
+fn take_outs(args: &mut Vec<OsString>) -> Vec<String> {
+    let mut outs = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].to_string_lossy().into_owned();
+        if let Some(out) = arg.strip_prefix("--out=") {
+            outs.push(out_spec(out));
+            args.remove(i);
+            continue;
+        }
+        if arg == "--out" && i + 1 < args.len() {
+            outs.push(out_spec(args[i + 1].to_string_lossy().as_ref()));
+            args.drain(i..i + 2);
+            continue;
+        }
+        i += 1;
+    }
+    return outs;
+}
+
+// shuffle <file> [--splits 80,10,10] [--seed N]
+fn shuffle_command(args: &[OsString]) {
+    let mut path: Option<&OsString> = None;
+    let mut splits: Vec<u32> = vec![100];
+    let mut seed: Option<u64> = None;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].to_string_lossy();
+        let value = args.get(i + 1).map(|v| v.to_string_lossy().into_owned());
+        if arg == "--splits" && value.is_some() {
+            let value = value.unwrap_or_default();
+            splits = value.split(',').map(|s| safe_u32(String::from(s.trim()), 0)).collect();
+            i += 2;
+        } else if arg == "--seed" && value.is_some() {
+            seed = value.and_then(|v| v.parse().ok());
+            i += 2;
+        } else {
+            path = Some(&args[i]);
+            i += 1;
+        }
+    }
+    let path = match path {
+        Some(path) => Path::new(path),
+        None => {
+            println!("{}", tr("shuffle.usage"));
+            return;
+        }
+    };
+    let seed = match seed {
+        Some(seed) => seed,
+        None => {
+            let mut rng = RNG::new();
+            rng.seed();
+            ((rng.get() as u64) << 32) | rng.get() as u64
+        }
+    };
+    // printed so that run can be repeated
+    println!("{}", tr_with("shuffle.seed", &[&seed.to_string()]));
+    match shuffle_file(path, &splits, seed) {
+        Ok(written) => {
+            for out in written {
+                println!("{}", out.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", tr_with("cli.error", &[&e.to_string()]));
+        }
+    }
+}
+
+// curate [--to file] [--page N] <alt arguments>
+fn curate_command(args: &[String]) {
+    let mut path = String::from("curated.txt");
+    let mut page: usize = 1;
+    let mut rest: Vec<&str> = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1);
+        if args[i] == "--to" && value.is_some() {
+            path = value.cloned().unwrap_or_default();
+            i += 2;
+        } else if args[i] == "--page" && value.is_some() {
+            page = safe_u32(value.cloned().unwrap_or_default(), 1) as usize;
+            i += 2;
+        } else {
+            rest.push(args[i].as_ref());
+            i += 1;
+        }
+    }
+    if rest.is_empty() {
+        println!("{}", tr("curate.usage"));
+        return;
+    }
+    let config = or_exit(command_parser::try_get_config(rest));
+    if let Err(e) = run_curate(&config, Path::new(&path), page) {
+        eprintln!("{}", tr_with("cli.error", &[&e.to_string()]));
+        std::process::exit(1);
+    }
+}
+
+// history search <text> [--file path]
+fn history_command(args: &[OsString]) {
+    let mut path = std::path::PathBuf::from(DEFAULT_HISTORY);
+    let mut words: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--file" && i + 1 < args.len() {
+            path = args[i + 1].clone().into();
+            i += 2;
+        } else {
+            words.push(args[i].to_string_lossy().into_owned());
+            i += 1;
+        }
+    }
+    if words.len() != 2 || words[0] != "search" {
+        println!("{}", tr("history.usage"));
+        return;
+    }
+    match search_history(&path, &words[1]) {
+        Ok(found) if found.is_empty() => {
+            println!("{}", tr_with("history.none", &[&words[1]]));
+        }
+        Ok(found) => {
+            for entry in found {
+                println!("{}  {}  {}  {}", entry.timestamp, entry.run_id, entry.mode, entry.value);
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", tr_with("cli.error", &[&e.to_string()]));
+            std::process::exit(1);
+        }
+    }
+}
+
+// reservations list|who <name>|release <name>|renew <name>|purge
+//   [--db file] [--ns namespace] [--owner name] [--ttl 8h]
+fn reservations_command(args: &[OsString]) {
+    let mut path = std::path::PathBuf::from(DEFAULT_RESERVATIONS);
+    let mut namespace = String::from("default");
+    let mut owner = default_owner();
+    let mut ttl: Option<u64> = None;
+    let mut words: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--db" && i + 1 < args.len() {
+            path = args[i + 1].clone().into();
+            i += 2;
+        } else if args[i] == "--ns" && i + 1 < args.len() {
+            namespace = args[i + 1].to_string_lossy().into_owned();
+            i += 2;
+        } else if args[i] == "--owner" && i + 1 < args.len() {
+            owner = args[i + 1].to_string_lossy().into_owned();
+            i += 2;
+        } else if args[i] == "--ttl" && i + 1 < args.len() {
+            ttl = parse_duration(args[i + 1].to_string_lossy().as_ref());
+            i += 2;
+        } else {
+            words.push(args[i].to_string_lossy().into_owned());
+            i += 1;
+        }
+    }
+    let words: Vec<&str> = words.iter().map(|w| w.as_ref()).collect();
+    let result = Reservations::open(&path, &namespace, &owner).and_then(|mut r| {
+        r.set_ttl(ttl);
+        match words[..] {
+            ["list"] => {
+                for reservation in r.list()? {
+                    println!("{}", reservation.line());
+                }
+            }
+            ["who", name] => match r.find(name)? {
+                Some(reservation) => println!("{}", reservation.line()),
+                None => println!("{}", tr_with("reservations.free", &[name, &namespace])),
+            },
+            ["release", name] => {
+                let key = if r.release(name)? { "reservations.released" } else { "reservations.not_yours" };
+                println!("{}", tr_with(key, &[name]));
+            }
+            ["renew", name] => {
+                let key = if r.renew(name)? { "reservations.renewed" } else { "reservations.not_yours" };
+                println!("{}", tr_with(key, &[name]));
+            }
+            ["purge"] => {
+                println!("{}", tr_with("reservations.purged", &[&r.purge_expired()?.to_string()]));
+            }
+            _ => println!("{}", tr("reservations.usage")),
+        }
+        return Ok(());
+    });
+    if let Err(e) = result {
+        eprintln!("{}", tr_with("cli.error", &[&e.to_string()]));
+        std::process::exit(1);
+    }
+}
+
+// decrypt <file> [--to path], plaintext goes to stdout without --to
+fn decrypt_command(args: &[OsString]) {
+    let mut path: Option<&OsString> = None;
+    let mut to: Option<&OsString> = None;
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--to" && i + 1 < args.len() {
+            to = Some(&args[i + 1]);
+            i += 2;
+        } else {
+            path = Some(&args[i]);
+            i += 1;
+        }
+    }
+    let path = match path {
+        Some(path) => Path::new(path),
+        None => {
+            println!("{}", tr("decrypt.usage"));
+            return;
+        }
+    };
+    let result = std::fs::read(path).and_then(|data| {
+        let plain = decrypt(&data, &read_passphrase(false)?)?;
+        return match to {
+            Some(to) => std::fs::write(to, plain),
+            None => std::io::Write::write_all(&mut std::io::stdout(), &plain),
+        };
+    });
+    if let Err(e) = result {
+        eprintln!("{}", tr_with("cli.error", &[&e.to_string()]));
+        std::process::exit(1);
+    }
+}
+
+// verify-token <token> [--registry file], exit code 1 for invalid token
+fn verify_token_command(args: &[String]) {
+    let mut registry = String::new();
+    let mut token: Option<&String> = None;
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--registry" && i + 1 < args.len() {
+            registry = args[i + 1].clone();
+            i += 2;
+        } else {
+            token = Some(&args[i]);
+            i += 1;
+        }
+    }
+    let token = match token {
+        Some(token) => token,
+        None => {
+            println!("{}", tr("verify.usage"));
+            return;
+        }
+    };
+    let registry = or_exit(TokenRegistry::open(&registry));
+    match registry.verify(token) {
+        Some(scheme) => println!("{}", tr_with("verify.valid", &[&scheme.name])),
+        None => {
+            println!("{}", tr("verify.invalid"));
+            std::process::exit(1);
+        }
+    }
+}
+
+// decode <proquint|bubble babble>, prints bytes as hex, exit code 1 for invalid string
+// | decode <word.word.word> [--lang xx] [--cell], prints number or lat,long of cell
+// | decode --words <word-word..> [--lang xx] [--hex], prints wordified number
+// | decode --crockford <id> [--check] | decode --base62 <id>, prints number
+fn decode_command(args: &[String]) {
+    let mut lang = String::from("en");
+    let mut cell = false;
+    let mut words = false;
+    let mut hex = false;
+    let mut crockford = false;
+    let mut check = false;
+    let mut base62 = false;
+    let mut encoded: Option<String> = None;
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--lang" && i + 1 < args.len() {
+            lang = args[i + 1].clone();
+            i += 2;
+            continue;
+        }
+        if args[i] == "--cell" {
+            cell = true;
+        } else if args[i] == "--words" {
+            words = true;
+        } else if args[i] == "--hex" {
+            hex = true;
+        } else if args[i] == "--crockford" {
+            crockford = true;
+        } else if args[i] == "--check" {
+            check = true;
+        } else if args[i] == "--base62" {
+            base62 = true;
+        } else {
+            encoded = Some(String::from(args[i].trim()));
+        }
+        i += 1;
+    }
+    let encoded = match encoded {
+        Some(encoded) => encoded,
+        None => {
+            println!("{}", tr("decode.usage"));
+            return;
+        }
+    };
+    // numbers of base32 and base62 are any letters and digits, so they are asked for too
+    if crockford || base62 {
+        let number = if crockford {
+            crockford_decode(&encoded, check)
+        } else {
+            base62_decode(&encoded)
+        };
+        match number {
+            Some(number) => println!("{}", number),
+            None => {
+                println!("{}", tr("decode.invalid"));
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    // base62 is case sensitive, everything else is not
+    let encoded = encoded.to_lowercase();
+    // wordified numbers look like any hyphenated words, so they are asked for
+    if words {
+        let grid = or_exit(WordGrid::for_language(&Languages::from(lang.as_str())));
+        match grid.unwordify(&encoded, hex) {
+            Some(number) => println!("{}", number),
+            None => {
+                println!("{}", tr("decode.invalid"));
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    // only word triples have dots
+    if encoded.contains(TRIPLE_SEPARATOR) {
+        let grid = or_exit(WordGrid::for_language(&Languages::from(lang.as_str())));
+        let number = grid.decode(&encoded);
+        match (number, cell) {
+            (Some(number), false) => println!("{}", number),
+            (Some(number), true) if grid.cell_center(number).is_some() => {
+                let (lat, long) = grid.cell_center(number).unwrap_or_default();
+                println!("{:.5},{:.5}", lat, long);
+            }
+            _ => {
+                println!("{}", tr("decode.invalid"));
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    // proquint consonants have no x, bubble babble always starts with it
+    let bytes = if encoded.starts_with('x') {
+        bubblebabble_decode(&encoded)
+    } else {
+        proquint_decode(&encoded)
+    };
+    match bytes {
+        Some(bytes) => {
+            let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+            println!("{}", hex);
+        }
+        None => {
+            println!("{}", tr("decode.invalid"));
+            std::process::exit(1);
+        }
+    }
+}
+
+// lists diceware <file> [--to path] [--pad] [--trim] | lists stats <file>
+// | lists diff <old> <new> | lists extract <text> [--min-count N] [--alphabet latin]..
+// | lists tiers <file> [--corpus text] [--cuts 20,50] [--drop rare]
+fn lists_command(args: &[OsString]) {
+    let mut to: Option<&OsString> = None;
+    let mut pad = false;
+    let mut trim = false;
+    let mut options = ExtractOptions::default();
+    let mut tiers = TierOptions::default();
+    let mut words: Vec<&OsString> = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let flag = args[i].to_string_lossy();
+        let value = args.get(i + 1).map(|v| v.to_string_lossy());
+        if args[i] == "--to" && i + 1 < args.len() {
+            to = Some(&args[i + 1]);
+            i += 2;
+        } else if value.is_some_and(|v| {
+            return or_exit(options.set(&flag, &v)) || or_exit(tiers.set(&flag, &v));
+        }) {
+            i += 2;
+        } else {
+            match args[i].to_string_lossy().as_ref() {
+                "--pad" => pad = true,
+                "--trim" => trim = true,
+                "--keep-case" => options.keep_case = true,
+                _ => words.push(&args[i]),
+            }
+            i += 1;
+        }
+    }
+    let result = match words.as_slice() {
+        [command, path] if *command == "diceware" => read_list(Path::new(path))
+            .and_then(|list| diceware_table(&list, pad, trim))
+            .map(|table| diceware_lines(&table)),
+        [command, path] if *command == "stats" => {
+            read_entries(Path::new(path)).map(|words| ListStats::of(&words).report())
+        }
+        [command, old, new] if *command == "diff" => read_entries(Path::new(old))
+            .and_then(|old| Ok(ListDiff::of(&old, &read_entries(Path::new(new))?).report())),
+        [command, path] if *command == "extract" => {
+            extract_list(Path::new(path), &options).map(|words| list_lines(&words))
+        }
+        [command, path] if *command == "tiers" => {
+            read_list(Path::new(path)).map(|words| tier_lines(&frequency_tiers(&words, &tiers)))
+        }
+        _ => {
+            println!("{}", tr("lists.usage"));
+            return;
+        }
+    };
+    let result = result.and_then(|text| {
+        return match to {
+            Some(to) => std::fs::write(to, text),
+            None => closed_pipe_is_end(std::io::stdout().write_all(text.as_bytes())),
+        };
+    });
+    if let Err(e) = result {
+        eprintln!("{}", tr_with("cli.error", &[&e.to_string()]));
+        std::process::exit(1);
+    }
+}
+
+// --clipboard or --clipboard=seconds, value is empty without seconds
+fn take_clipboard(args: &mut Vec<OsString>) -> Option<String> {
+    let position = args.iter().position(|arg| {
+        let arg = arg.to_string_lossy();
+        return arg == "--clipboard" || arg.starts_with("--clipboard=");
+    })?;
+    let arg = args.remove(position).to_string_lossy().into_owned();
+    let seconds = arg.strip_prefix("--clipboard=").unwrap_or("");
+    return Some(String::from(seconds));
+}
+
+// detached helper spawned by clipboard output: clipboard-clear <seconds>, hash comes on stdin
+fn clipboard_clear_command(args: &[String]) {
+    let mut hash = String::new();
+    if args.is_empty() || std::io::stdin().read_line(&mut hash).is_err() {
+        return;
+    }
+    let seconds = args[0].parse::<u32>().unwrap_or(DEFAULT_CLIP_CLEAR);
+    let _ = clear_after(seconds, hash.trim());
+}
+
+// --flag value or --flag=value, last one wins
+fn take_value(args: &mut Vec<OsString>, flag: &str) -> Option<String> {
+    let mut value = None;
+    let prefix = format!("{}=", flag);
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].to_string_lossy().into_owned();
+        if let Some(v) = arg.strip_prefix(&prefix) {
+            value = Some(String::from(v));
+            args.remove(i);
+            continue;
+        }
+        if arg == flag && i + 1 < args.len() {
+            value = Some(args[i + 1].to_string_lossy().into_owned());
+            args.drain(i..i + 2);
+            continue;
+        }
+        i += 1;
+    }
+    return value;
+}
+
+// removes flag without value from arguments, true when it was present
+fn take_flag(args: &mut Vec<OsString>, flag: &str) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != flag);
+    return args.len() != before;
+}
+
+// reader of piped output went away (| head), rest of output isn't wanted: not an error
+fn closed_pipe_is_end(result: Result<(), Error>) -> Result<(), Error> {
+    return match result {
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    };
+}
+
+// bad input ends run with message, not with panic backtrace
+fn or_exit<T, E: std::fmt::Display>(result: Result<T, E>) -> T {
+    return match result {
+        Ok(value) => value,
+        Err(e) => {
+            eprintln!("{}", tr_with("cli.error", &[&e.to_string()]));
+            std::process::exit(1);
+        }
+    };
+}
+
+// text to stdout, piped into reader that quits early is fine
+fn print_out(text: &str) {
+    or_exit(closed_pipe_is_end(writeln!(std::io::stdout(), "{}", text)));
+}
+
+// config only sees lossy strings, hand it the original argument for next
+// so list and alphabet files with non utf-8 names can still be opened
+fn restore_next_path(raw_args: &[OsString], config: &mut Config) {
+    let next = config.get_next();
+    if next.is_empty() {
+        return;
+    }
+    for raw in raw_args.iter().skip(2) {
+        if raw.to_string_lossy() == next {
+            config.set_next_path(raw.into());
+            return;
+        }
+        if let Some(value) = strip_prefix_os(raw, "next=") {
+            if value.to_string_lossy() == next {
+                config.set_next_path(value.into());
+                return;
+            }
+        }
+    }
+}
+
+// subcommands take their own flags, --seed of shuffle is not the run seed
+fn run_subcommand(raw_args: &[OsString]) -> bool {
+    if raw_args.len() < 2 {
+        return false;
+    }
+    let args: Vec<String> = raw_args
+        .iter()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect();
+    let flag = args[1].trim();
+    if "shuffle" == flag {
+        shuffle_command(&raw_args[2..]);
+        return true;
+    }
+
+    if "history" == flag {
+        history_command(&raw_args[2..]);
+        return true;
+    }
+
+    if "reservations" == flag {
+        reservations_command(&raw_args[2..]);
+        return true;
+    }
+
+    if "decrypt" == flag {
+        decrypt_command(&raw_args[2..]);
+        return true;
+    }
+
+    if "lists" == flag {
+        lists_command(&raw_args[2..]);
+        return true;
+    }
+
+    if "verify-token" == flag {
+        verify_token_command(&args[2..]);
+        return true;
+    }
+
+    if "decode" == flag {
+        decode_command(&args[2..]);
+        return true;
+    }
+
+    if CLEAR_COMMAND == flag {
+        clipboard_clear_command(&args[2..]);
+        return true;
+    }
+
+    if "curate" == flag {
+        curate_command(&args[2..]);
+        return true;
+    }
+
+    return false;
+}
 
 fn main() {
     use std::fs::read_to_string;
     const VERSION:&str = "0.12.4";
 
-    let args: Vec<String> = env::args().collect();
+    utf8_console();
+    let mut raw_args: Vec<OsString> = env::args_os().collect();
+    take_ui_lang(&mut raw_args);
+    if run_subcommand(&raw_args) {
+        return;
+    }
+    let materialize = take_flag(&mut raw_args, "--materialize");
+    let confirm_display = take_flag(&mut raw_args, "--confirm-display");
+    let unique = take_flag(&mut raw_args, "--unique");
+    let append = take_flag(&mut raw_args, "--append");
+    let exclude_file = take_value(&mut raw_args, "--exclude-file");
+    let record = take_value(&mut raw_args, "--record");
+    let clipboard = take_clipboard(&mut raw_args);
+    let policy = take_value(&mut raw_args, "--policy");
+    let profile = take_value(&mut raw_args, "--profile");
+    let seed = take_value(&mut raw_args, "--seed");
+    let length_range = take_value(&mut raw_args, "--length-range");
+    let outs = take_outs(&mut raw_args);
+    let args: Vec<String> = raw_args
+        .iter()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect();
 
     if args.len() < 2 {
-        println!("pass enough parameters to calculate");
-        print_help();
+        println!("{}", tr("cli.not_enough"));
+        or_exit(closed_pipe_is_end(print_help()));
         return;
     }
     // trimmed
     let flag = args[1].trim();
     // -h -H H Helpt help
     if "-h" == flag || "printh" == flag || "help" == flag {
-        print_help();
+        or_exit(closed_pipe_is_end(print_help()));
+        return;
+    }
+    if "--help" == flag {
+        print_out(&command_parser::flags_help());
         return;
     }
     if "--version" == flag || "-V" == flag {
-        println!("{}", VERSION);
+        print_out(VERSION);
         return;
     }
 
@@ -32,45 +673,94 @@ fn main() {
         return;
     }
 
-    let config = if "-a" == flag || "alt" == flag {
+    let mut config = if "-a" == flag || "alt" == flag {
         let mut v = Vec::new();
-        for x in 2..args.len() {
-            v.push(args[x].as_ref());
+        for arg in args.iter().skip(2) {
+            v.push(arg.as_ref());
         }
-        command_parser::get_config(v)
+        or_exit(command_parser::try_get_config(v))
     } else if "pf" == flag || "paramsfile" == flag {
         let mut v = Vec::new();
-        let mut fileargs = String::from(".params");
-        if args.len() > 2 {
-            fileargs = match read_to_string(&args[2]) {
-                Ok(content) => content,
-                Err(e) => panic!("{}", e),
-            };
-        }
+        let path = if args.len() > 2 {
+            raw_args[2].clone()
+        } else {
+            OsString::from(".params")
+        };
+        let fileargs = or_exit(read_to_string(&path));
         for line in fileargs.lines() {
             v.push(line.trim());
         }
-        command_parser::get_config(v)
+        or_exit(command_parser::try_get_config(v))
+    } else if flag.starts_with("--") {
+        or_exit(command_parser::get_flag_config(&args[1..]))
     } else if args[1].contains("-f") {
         // fastswitch
         let stronk = args[1].clone();
-        fast_switch::get_fsconf(stronk)
+        or_exit(fast_switch::get_fsconf(stronk))
     } else if fast_switch::is_alias(flag) {
         let next = String::new();
-        let fast = if args.len() > 2 {
+        if args.len() > 2 {
             fast_switch::alias_config(args[1].clone(), args[2].clone())
         } else {
             fast_switch::alias_config(args[1].clone(), next)
-        };
-        fast
+        }
     } else {
-        Config::new(&args)
+        or_exit(Config::new(&args))
     };
+    restore_next_path(&raw_args, &mut config);
+    if materialize {
+        config.set_materialize(true);
+    }
+    if confirm_display {
+        config.set_confirm_display(true);
+    }
+    if unique {
+        config.set_unique(true);
+    }
+    if append {
+        config.set_append(true);
+    }
+    if let Some(exclude_file) = exclude_file {
+        config.set_exclude_file(exclude_file);
+    }
+    if let Some(record) = record {
+        config.set_record(record);
+    }
+    for out in outs {
+        config.add_sink(out);
+    }
+    if let Some(policy) = policy {
+        config.set_policy(policy);
+    }
+    if let Some(profile) = profile {
+        config.set_profile(profile);
+    }
+    if let Some(seed) = seed {
+        let parsed = seed.parse::<u64>();
+        let bad = || StrgenError::BadNumber(String::from("seed"), seed.clone());
+        config.set_seed(Some(or_exit(parsed.map_err(|_e| bad()))));
+    }
+    if let Some(range) = length_range {
+        let parsed = parse_length_range(&range);
+        let bad = || StrgenError::BadNumber(String::from("length range"), range.clone());
+        let (min, max) = or_exit(parsed.ok_or_else(bad));
+        config.set_length_range(min, max);
+    }
+    if let Some(seconds) = clipboard {
+        if !seconds.is_empty() {
+            let parsed = seconds.parse::<u32>();
+            let bad = || StrgenError::BadNumber(String::from("clipboard"), seconds.clone());
+            config.set_clip_clear(or_exit(parsed.map_err(|_e| bad())));
+        }
+        config.add_sink(String::from("clip"));
+    }
 
-    match run_generator(&config) {
+    match closed_pipe_is_end(run_generator(&config)) {
         Ok(_result) => {}
         Err(e) => {
-            println!("Error:{}", e);
+            eprintln!("{}", tr_with("cli.error", &[&e.to_string()]));
+            // scripts and ci see failed run
+            std::process::exit(1);
         }
     }
     return;
@@ -0,0 +1,185 @@
+pub mod markov {
+    use std::collections::{HashMap, HashSet};
+    use std::io::{Error, ErrorKind};
+    use std::path::{Path, PathBuf};
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{
+        Config, GeneratorVersion, Languages, ListType, StrgenError, WordList, RNG,
+    };
+
+    // markers around every training word, never part of list words
+    const START: char = '\u{2}';
+    const END: char = '\u{3}';
+    // tries for new word of right length, then last one is taken anyway
+    const MAX_ATTEMPTS: u32 = 100;
+
+    // next character counts after every `order` characters seen in training words
+    pub struct MarkovChain {
+        order: usize,
+        // context -> (character, count), characters in order first seen
+        transitions: HashMap<String, Vec<(char, u32)>>,
+    }
+    impl MarkovChain {
+        pub fn new(order: usize) -> MarkovChain {
+            return MarkovChain {
+                order: order.max(1),
+                transitions: HashMap::new(),
+            };
+        }
+        pub fn train(&mut self, word: &str) {
+            let mut chars: Vec<char> = vec![START; self.order];
+            chars.extend(word.chars());
+            chars.push(END);
+            for window in chars.windows(self.order + 1) {
+                let context: String = window[..self.order].iter().collect();
+                let next = window[self.order];
+                let counts = self.transitions.entry(context).or_default();
+                match counts.iter_mut().find(|(c, _n)| *c == next) {
+                    Some((_c, n)) => *n += 1,
+                    None => counts.push((next, 1)),
+                }
+            }
+        }
+        pub fn is_empty(&self) -> bool {
+            return self.transitions.is_empty();
+        }
+        // walk from start to end, none when word grows past max_length
+        pub fn walk(
+            &self,
+            rng: &mut RNG,
+            version: &GeneratorVersion,
+            max_length: usize,
+        ) -> Option<String> {
+            let mut context: Vec<char> = vec![START; self.order];
+            let mut word = String::new();
+            loop {
+                let key: String = context.iter().collect();
+                let counts = self.transitions.get(&key)?;
+                let weights: Vec<u32> = counts.iter().map(|(_c, n)| *n).collect();
+                let next = counts[version.pick_weighted(rng, &weights)].0;
+                if next == END {
+                    return Some(word);
+                }
+                word.push(next);
+                if word.chars().count() > max_length {
+                    return None;
+                }
+                context.remove(0);
+                context.push(next);
+            }
+        }
+    }
+
+    // 4th parameter: list file[:order=3], nouns of english when file is left out
+    pub struct MarkovWord {
+        chain: MarkovChain,
+        // training words, never given out
+        known: HashSet<String>,
+        min_length: usize,
+        max_length: usize,
+        rng: RNG,
+        version: GeneratorVersion,
+        // no new word was found, list or length leave too little room
+        stalled: bool,
+        error: Option<Error>,
+    }
+    impl Default for MarkovWord {
+        fn default() -> Self {
+            return MarkovWord::new();
+        }
+    }
+    impl MarkovWord {
+        pub fn new() -> MarkovWord {
+            return MarkovWord {
+                chain: MarkovChain::new(2),
+                known: HashSet::new(),
+                min_length: 3,
+                max_length: 12,
+                rng: RNG::new(),
+                version: GeneratorVersion::latest(),
+                stalled: false,
+                error: None,
+            };
+        }
+        pub fn train(&mut self, words: &[String]) {
+            for word in words {
+                self.chain.train(word);
+                self.known.insert(word.clone());
+            }
+        }
+    }
+    impl StringGenerator for MarkovWord {
+        fn get(&mut self) -> String {
+            for _ in 0..MAX_ATTEMPTS {
+                let walked = self
+                    .chain
+                    .walk(&mut self.rng, &self.version, self.max_length);
+                let Some(word) = walked else {
+                    continue;
+                };
+                if word.chars().count() >= self.min_length && !self.known.contains(&word) {
+                    return word;
+                }
+            }
+            // word of list would be given out otherwise
+            let message = format!(
+                "no new word in {} walks, take longer list, lower order or wider length",
+                MAX_ATTEMPTS
+            );
+            self.error = Some(Error::other(message));
+            self.stalled = true;
+            return String::new();
+        }
+        fn exhausted(&self) -> bool {
+            return self.stalled;
+        }
+        fn failure(&mut self) -> Option<Error> {
+            return self.error.take();
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            let next = conf.get_next();
+            let mut parts = next.split(':');
+            let path = parts.next().unwrap_or("");
+            let mut order: usize = 2;
+            for part in parts {
+                if let Some(("order", n)) = part.split_once('=') {
+                    order = match n.trim().parse() {
+                        Ok(n @ 1..=5) => n,
+                        _ => {
+                            let message = format!("markov order is 1 to 5, got {}", n);
+                            return Err(Error::new(ErrorKind::InvalidInput, message));
+                        }
+                    };
+                }
+            }
+            let mut list = WordList::new(ListType::Nouns, Languages::English);
+            list.fill(Path::new(path))?;
+            if list.get_list_len() == 0 {
+                let file = if path.is_empty() {
+                    list.get_file_name()
+                } else {
+                    PathBuf::from(path)
+                };
+                return Err(StrgenError::EmptyList(file).into());
+            }
+            let words: Vec<String> = (0..list.get_list_len()).map(|i| list.get_at(i)).collect();
+            self.chain = MarkovChain::new(order);
+            self.known.clear();
+            self.train(&words);
+            // length is upper bound (0 for none), range sets lower one too
+            let (min, max) = conf.get_length_range();
+            self.max_length = if max == 0 { usize::MAX } else { max as usize };
+            self.min_length = if min < max {
+                min as usize
+            } else {
+                self.max_length.min(3)
+            };
+            self.rng = conf.rng(0);
+            self.version = conf.get_version();
+            self.stalled = false;
+            self.error = None;
+            return Ok(());
+        }
+    }
+}
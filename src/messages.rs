@@ -0,0 +1,82 @@
+pub mod messages {
+    use std::cell::RefCell;
+    use std::env;
+
+    use crate::stringer::Languages;
+
+    // catalogs live in locales/ and are compiled in, so binary works from any directory
+    const CATALOG_EN: &str = include_str!("../locales/messages.en.cat");
+    const CATALOG_DE: &str = include_str!("../locales/messages.de.cat");
+    const CATALOG_FR: &str = include_str!("../locales/messages.fr.cat");
+    const CATALOG_ES: &str = include_str!("../locales/messages.es.cat");
+    const CATALOG_KA: &str = include_str!("../locales/messages.ka.cat");
+
+    thread_local! {
+        // per thread, so callers serving users of several languages don't mix them up
+        static UI_LANGUAGE: RefCell<Option<Languages>> = const { RefCell::new(None) };
+    }
+
+    pub fn set_ui_language(lang: Languages) {
+        UI_LANGUAGE.with(|ui| *ui.borrow_mut() = Some(lang));
+    }
+    // explicitly set language or the one from system locale
+    pub fn ui_language() -> Languages {
+        return match UI_LANGUAGE.with(|ui| ui.borrow().clone()) {
+            Some(lang) => lang,
+            None => system_language(),
+        };
+    }
+    // LC_ALL, LC_MESSAGES and LANG in that order, e.g. de_DE.UTF-8
+    pub fn system_language() -> Languages {
+        for var in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+            if let Ok(value) = env::var(var) {
+                if value.is_empty() {
+                    continue;
+                }
+                let abbr: String = value.chars().take(2).collect();
+                return Languages::from_code(&abbr);
+            }
+        }
+        return Languages::English;
+    }
+
+    fn catalog(lang: &Languages) -> &'static str {
+        return match *lang {
+            Languages::German => CATALOG_DE,
+            Languages::French => CATALOG_FR,
+            Languages::Spanish => CATALOG_ES,
+            Languages::Georgian => CATALOG_KA,
+            _ => CATALOG_EN,
+        };
+    }
+    fn lookup(catalog: &str, key: &str) -> Option<String> {
+        for line in catalog.lines() {
+            if line.starts_with('#') {
+                continue;
+            }
+            if let Some((k, v)) = line.split_once('=') {
+                if k.trim() == key {
+                    return Some(String::from(v));
+                }
+            }
+        }
+        return None;
+    }
+
+    // message in ui language, falls back to english and then to the key itself
+    pub fn tr(key: &str) -> String {
+        let lang = ui_language();
+        if let Some(message) = lookup(catalog(&lang), key) {
+            return message;
+        }
+        return lookup(CATALOG_EN, key).unwrap_or_else(|| String::from(key));
+    }
+    // replaces each {} with next argument
+    pub fn tr_with(key: &str, args: &[&str]) -> String {
+        let mut message = tr(key);
+        for arg in args {
+            message = message.replacen("{}", arg, 1);
+        }
+        return message;
+    }
+}
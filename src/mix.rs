@@ -0,0 +1,155 @@
+pub mod mix {
+    use std::io::{Error, ErrorKind};
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{stringer, Config, GeneratorVersion, Modes, StrgenError, RNG};
+
+    // one part of mixed run: mode with its own 4th parameter and share of output
+    pub struct MixEntry {
+        pub mode: Modes,
+        pub next: String,
+        pub weight: u32,
+        // text of entry without weight, used as label
+        pub label: String,
+    }
+
+    // cow@de=70,jwt=20,secrets=10[,blocks]
+    pub fn parse_mix(s: &str) -> Result<(Vec<MixEntry>, bool), Error> {
+        let mut entries: Vec<MixEntry> = Vec::new();
+        let mut blocks = false;
+        for part in s.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            if part == "blocks" {
+                blocks = true;
+                continue;
+            }
+            let (spec, weight) = match part.rsplit_once('=') {
+                Some((spec, weight)) => match weight.parse() {
+                    Ok(weight) => (spec, weight),
+                    Err(_e) => {
+                        let message = format!("mix weight is not a number: {}", part);
+                        return Err(Error::new(ErrorKind::InvalidInput, message));
+                    }
+                },
+                None => (part, 1),
+            };
+            let (mode, next) = spec.split_once('@').unwrap_or((spec, ""));
+            if mode == "mix" {
+                return Err(Error::new(ErrorKind::InvalidInput, "mix can't contain mix"));
+            }
+            // typo in mode is error, not random letters
+            let unknown = || StrgenError::UnknownMode(String::from(mode));
+            entries.push(MixEntry {
+                mode: Modes::parse(mode).ok_or_else(unknown)?,
+                next: String::from(next),
+                weight,
+                label: String::from(spec),
+            });
+        }
+        if entries.iter().all(|e| e.weight == 0) {
+            let message = format!("mix needs at least one mode with weight: {}", s);
+            return Err(Error::new(ErrorKind::InvalidInput, message));
+        }
+        return Ok((entries, blocks));
+    }
+
+    // splits amount by weights, leftovers go to largest remainders so counts sum to amount
+    pub fn quotas(weights: &[u32], amount: u32) -> Vec<u32> {
+        let total: u64 = weights.iter().map(|w| *w as u64).sum();
+        let mut counts: Vec<u32> = Vec::new();
+        let mut remainders: Vec<(u64, usize)> = Vec::new();
+        for (i, weight) in weights.iter().enumerate() {
+            let share = *weight as u64 * amount as u64;
+            counts.push((share / total) as u32);
+            remainders.push((share % total, i));
+        }
+        let given: u32 = counts.iter().sum();
+        // biggest remainder first, earlier entry wins ties
+        remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        for (_rem, i) in remainders.iter().take((amount - given) as usize) {
+            counts[*i] += 1;
+        }
+        return counts;
+    }
+
+    // several modes in one run with exact ratios, randomly interleaved or in blocks
+    pub struct MixedModes {
+        generators: Vec<Box<dyn StringGenerator>>,
+        labels: Vec<String>,
+        weights: Vec<u32>,
+        // strings left for each generator in this run
+        remaining: Vec<u32>,
+        blocks: bool,
+        last: usize,
+        version: GeneratorVersion,
+        rng: RNG,
+    }
+    impl Default for MixedModes {
+        fn default() -> Self {
+            return MixedModes::new();
+        }
+    }
+    impl MixedModes {
+        pub fn new() -> MixedModes {
+            let mut rng = RNG::new();
+            rng.seed();
+            return MixedModes {
+                generators: Vec::new(),
+                labels: Vec::new(),
+                weights: Vec::new(),
+                remaining: Vec::new(),
+                blocks: false,
+                last: 0,
+                version: GeneratorVersion::latest(),
+                rng,
+            };
+        }
+        pub fn set_blocks(&mut self, blocks: bool) {
+            self.blocks = blocks;
+        }
+        fn next_index(&mut self) -> usize {
+            let left: u32 = self.remaining.iter().sum();
+            if left == 0 {
+                // past planned amount, e.g. repl, keep ratios on average
+                return self.version.pick_weighted(&mut self.rng, &self.weights);
+            }
+            if self.blocks {
+                return self.remaining.iter().position(|r| *r > 0).unwrap_or(0);
+            }
+            // drawing from what is left is shuffling the quotas
+            return self.version.pick_weighted(&mut self.rng, &self.remaining);
+        }
+    }
+    impl StringGenerator for MixedModes {
+        fn get(&mut self) -> String {
+            let index = self.next_index();
+            if self.remaining[index] > 0 {
+                self.remaining[index] -= 1;
+            }
+            self.last = index;
+            return self.generators[index].get();
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            let (entries, blocks) = parse_mix(conf.get_next().as_ref())?;
+            self.blocks = blocks;
+            self.version = conf.get_version();
+            for (i, entry) in entries.iter().enumerate() {
+                let mut sub = conf.clone();
+                sub.set_mode(entry.mode.clone());
+                sub.set_next(entry.next.clone());
+                // two entries of same mode must not give same strings
+                sub.set_seed(conf.get_seed().map(|seed| seed ^ ((i as u64 + 1) << 48)));
+                let mut generator = stringer(sub.clone());
+                generator.setup(&sub)?;
+                self.generators.push(generator);
+                self.labels.push(entry.label.clone());
+                self.weights.push(entry.weight);
+            }
+            self.remaining = quotas(&self.weights, conf.get_amount());
+            return Ok(());
+        }
+        fn label(&self) -> Option<String> {
+            return Some(self.labels[self.last].clone());
+        }
+    }
+}
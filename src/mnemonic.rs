@@ -0,0 +1,199 @@
+pub mod mnemonic {
+    use std::collections::HashMap;
+    use std::io::{Error, ErrorKind};
+    use std::path::Path;
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{Config, GeneratorVersion, Languages, ListType, WordList, RNG};
+
+    // digits no list word stands for are written as they are, but only as last resort
+    const DIGIT_COST: usize = 100;
+
+    // digits word stands for in major system, by its english spelling: consonant sounds
+    // s z 0, t d th 1, n 2, m 3, r 4, l 5, j sh ch soft g 6, k hard c hard g q 7, f v ph 8,
+    // p b 9; vowels, w h y and silent gh are free, doubled letters count once (butter 914)
+    pub fn major_digits(word: &str) -> String {
+        let chars: Vec<char> = word.to_lowercase().chars().collect();
+        let mut digits = String::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let next = chars.get(i + 1).copied();
+            let soft = matches!(next, Some('e' | 'i' | 'y'));
+            // two letters, one sound
+            let pair = match (c, next) {
+                ('s', Some('h')) | ('c', Some('h')) => Some("6"),
+                ('t', Some('h')) => Some("1"),
+                ('p', Some('h')) => Some("8"),
+                ('g', Some('h')) => Some(""),
+                ('c', Some('k')) => Some("7"),
+                _ => None,
+            };
+            if let Some(pair) = pair {
+                digits.push_str(pair);
+                i += 2;
+                continue;
+            }
+            // butter, sitting: doubled letter is one sound
+            if i > 0 && chars[i - 1] == c {
+                i += 1;
+                continue;
+            }
+            let digit = match c {
+                's' | 'z' => "0",
+                't' | 'd' => "1",
+                'n' => "2",
+                'm' => "3",
+                'r' => "4",
+                'l' => "5",
+                'j' => "6",
+                'c' if soft => "0",
+                'g' if soft => "6",
+                'k' | 'c' | 'g' | 'q' => "7",
+                'x' => "70",
+                'f' | 'v' => "8",
+                'p' | 'b' => "9",
+                _ => "",
+            };
+            digits.push_str(digit);
+            i += 1;
+        }
+        return digits;
+    }
+
+    // 4th parameter: number[:language], random number of len digits when number is left out;
+    // every string is other words for same number
+    pub struct MajorWords {
+        // digits -> words standing for them
+        index: HashMap<String, Vec<String>>,
+        // longest digits one word stands for
+        longest: usize,
+        number: String,
+        digits: usize,
+        // number of last string, when it is random
+        last: String,
+        rng: RNG,
+        version: GeneratorVersion,
+    }
+    impl Default for MajorWords {
+        fn default() -> Self {
+            return MajorWords::new();
+        }
+    }
+    impl MajorWords {
+        pub fn new() -> MajorWords {
+            let mut rng = RNG::new();
+            rng.seed();
+            return MajorWords {
+                index: HashMap::new(),
+                longest: 0,
+                number: String::new(),
+                digits: 4,
+                last: String::new(),
+                rng,
+                version: GeneratorVersion::latest(),
+            };
+        }
+        pub fn add_word(&mut self, word: &str) {
+            let digits = major_digits(word);
+            if digits.is_empty() {
+                return;
+            }
+            self.longest = self.longest.max(digits.len());
+            let words = self.index.entry(digits).or_default();
+            if !words.iter().any(|w| w == word) {
+                words.push(String::from(word));
+            }
+        }
+        // fewest words for digits, any of equally short splits and any word of each part
+        pub fn encode(&mut self, number: &str) -> String {
+            let digits: Vec<char> = number.chars().collect();
+            let n = digits.len();
+            // cost[i] is cost of digits from i on
+            let mut cost: Vec<usize> = vec![0; n + 1];
+            for i in (0..n).rev() {
+                cost[i] = DIGIT_COST + cost[i + 1];
+                for j in i + 1..=n.min(i + self.longest) {
+                    let part: String = digits[i..j].iter().collect();
+                    if self.index.contains_key(&part) {
+                        cost[i] = cost[i].min(1 + cost[j]);
+                    }
+                }
+            }
+            let mut words: Vec<String> = Vec::new();
+            let mut i = 0;
+            while i < n {
+                let ends: Vec<usize> = (i + 1..=n.min(i + self.longest))
+                    .filter(|j| {
+                        let part: String = digits[i..*j].iter().collect();
+                        return self.index.contains_key(&part) && 1 + cost[*j] == cost[i];
+                    })
+                    .collect();
+                if ends.is_empty() {
+                    words.push(digits[i].to_string());
+                    i += 1;
+                    continue;
+                }
+                let j = ends[self.version.pick_index(&mut self.rng, ends.len())];
+                let part: String = digits[i..j].iter().collect();
+                let candidates = &self.index[&part];
+                let index = self.version.pick_index(&mut self.rng, candidates.len());
+                words.push(candidates[index].clone());
+                i = j;
+            }
+            return words.join(" ");
+        }
+    }
+    impl StringGenerator for MajorWords {
+        fn get(&mut self) -> String {
+            if !self.number.is_empty() {
+                let number = self.number.clone();
+                return self.encode(&number);
+            }
+            self.last = (0..self.digits)
+                .map(|_| char::from(b'0' + self.version.pick_index(&mut self.rng, 10) as u8))
+                .collect();
+            let number = self.last.clone();
+            return self.encode(&number);
+        }
+        fn fields(&self) -> Vec<(String, String)> {
+            let number = if self.number.is_empty() {
+                &self.last
+            } else {
+                &self.number
+            };
+            return vec![(String::from("number"), number.clone())];
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            let next = conf.get_next();
+            let (number, language) = next.split_once(':').unwrap_or((next.as_str(), "en"));
+            let number: String = number.chars().filter(|c| !c.is_whitespace()).collect();
+            if !number.chars().all(|c| c.is_ascii_digit()) {
+                let message = format!("major system encodes digits, got {}", number);
+                return Err(Error::new(ErrorKind::InvalidInput, message));
+            }
+            self.number = number;
+            self.digits = conf.get_length().max(1) as usize;
+            self.version = conf.get_version();
+            self.rng = conf.rng(0);
+            self.index.clear();
+            self.longest = 0;
+            let kinds = [ListType::Nouns, ListType::Adjectives, ListType::Verbs];
+            for kind in kinds {
+                let mut list = WordList::new(kind, Languages::from(language));
+                // languages without verbs list still have nouns and adjectives
+                if list.fill(Path::new("")).is_err() {
+                    continue;
+                }
+                for i in 0..list.get_list_len() {
+                    self.add_word(&list.get_at(i));
+                }
+            }
+            if self.index.is_empty() {
+                let message = format!("no word lists for language {}", language);
+                return Err(Error::new(ErrorKind::NotFound, message));
+            }
+            return Ok(());
+        }
+    }
+}
@@ -10,8 +10,103 @@ pub mod modes {
         CoupledWordsNames,
         CoupledWordsListFiles,
         SimpleSentences,
+        SequentialId,
+        Snowflake,
+        LogLines,
+        FilePaths,
+        SemVer,
+        CurrencyAmount,
+        Iban,
+        Coordinates,
+        Color,
+        Palette,
+        Cron,
+        HttpFixtures,
+        JwtShaped,
+        SecretsCorpus,
+        Mixed,
+        CorpusSample,
+        CartesianPairs,
+        Ranked,
+        Hybrid,
+        Pin,
+        RedemptionCodes,
+        Similar,
+        SoundAlike,
+        Uuid,
+        Pseudonymize,
+        Ulid,
+        Scrub,
+        Template,
+        Regex,
+        TestToken,
+        Markov,
+        Syllables,
+        Sentence,
+        Lorem,
+        Major,
+        Proquint,
+        BubbleBabble,
+        Triple,
+        Wordify,
+        Crockford,
+        Base62,
     }
     impl Modes {
+        // every mode, in order of help
+        pub const ALL: [Modes; 50] = [
+            Modes::Password,
+            Modes::Password84,
+            Modes::RandomLetters,
+            Modes::RandomLettersFromCustomAlphabet,
+            Modes::RandomLettersFromAlphabetFile,
+            Modes::CoupledWordsNouns,
+            Modes::CoupledWordsNames,
+            Modes::CoupledWordsListFiles,
+            Modes::SimpleSentences,
+            Modes::SequentialId,
+            Modes::Snowflake,
+            Modes::LogLines,
+            Modes::FilePaths,
+            Modes::SemVer,
+            Modes::CurrencyAmount,
+            Modes::Iban,
+            Modes::Coordinates,
+            Modes::Color,
+            Modes::Palette,
+            Modes::Cron,
+            Modes::HttpFixtures,
+            Modes::JwtShaped,
+            Modes::SecretsCorpus,
+            Modes::Mixed,
+            Modes::CorpusSample,
+            Modes::CartesianPairs,
+            Modes::Ranked,
+            Modes::Hybrid,
+            Modes::Pin,
+            Modes::RedemptionCodes,
+            Modes::Similar,
+            Modes::SoundAlike,
+            Modes::Uuid,
+            Modes::Pseudonymize,
+            Modes::Ulid,
+            Modes::Scrub,
+            Modes::Template,
+            Modes::Regex,
+            Modes::TestToken,
+            Modes::Markov,
+            Modes::Syllables,
+            Modes::Sentence,
+            Modes::Lorem,
+            Modes::Major,
+            Modes::Proquint,
+            Modes::BubbleBabble,
+            Modes::Triple,
+            Modes::Wordify,
+            Modes::Crockford,
+            Modes::Base62,
+        ];
+
         pub fn from(s: &str) -> Modes {
             return match s {
                 "pass"=>Modes::Password,
@@ -23,8 +118,241 @@ pub mod modes {
                 "cowe" | "cwe" => Modes::CoupledWordsNames,
                 "cowf" | "cwf" => Modes::CoupledWordsListFiles,
                 "sen" => Modes::SimpleSentences,
+                "seq" => Modes::SequentialId,
+                "snow" => Modes::Snowflake,
+                "log" => Modes::LogLines,
+                "fpath" => Modes::FilePaths,
+                "semver" => Modes::SemVer,
+                "cur" => Modes::CurrencyAmount,
+                "iban" => Modes::Iban,
+                "geo" => Modes::Coordinates,
+                "color" => Modes::Color,
+                "palette" => Modes::Palette,
+                "cron" => Modes::Cron,
+                "http" => Modes::HttpFixtures,
+                "jwt" => Modes::JwtShaped,
+                "secrets" => Modes::SecretsCorpus,
+                "mix" => Modes::Mixed,
+                "sample" => Modes::CorpusSample,
+                "pairs" => Modes::CartesianPairs,
+                "rank" => Modes::Ranked,
+                "hyb" => Modes::Hybrid,
+                "pin" => Modes::Pin,
+                "code" => Modes::RedemptionCodes,
+                "near" => Modes::Similar,
+                "sound" => Modes::SoundAlike,
+                "uuid" => Modes::Uuid,
+                "pseud" => Modes::Pseudonymize,
+                "ulid" => Modes::Ulid,
+                "scrub" => Modes::Scrub,
+                "tpl" => Modes::Template,
+                "regex" => Modes::Regex,
+                "token" => Modes::TestToken,
+                "markov" => Modes::Markov,
+                "syl" => Modes::Syllables,
+                "svo" => Modes::Sentence,
+                "lorem" => Modes::Lorem,
+                "major" => Modes::Major,
+                "proquint" => Modes::Proquint,
+                "bubble" => Modes::BubbleBabble,
+                "triple" => Modes::Triple,
+                "wordify" => Modes::Wordify,
+                "b32" => Modes::Crockford,
+                "b62" => Modes::Base62,
                 _ => Modes::RandomLetters,
             };
         }
+        // modes producing real credentials, kept out of memory, history and screen recordings
+        pub fn is_secret(&self) -> bool {
+            return matches!(
+                *self,
+                Modes::Password | Modes::Password84 | Modes::Hybrid | Modes::Pin | Modes::Syllables
+            );
+        }
+        // secrets and codes worth money, predictable rng would give them away
+        pub fn needs_secure_rng(&self) -> bool {
+            return self.is_secret()
+                || matches!(
+                    *self,
+                    Modes::RedemptionCodes | Modes::Uuid | Modes::Ulid | Modes::TestToken
+                );
+        }
+        // modes drawing from bundled word lists of language in next
+        pub fn uses_word_lists(&self) -> bool {
+            return matches!(
+                *self,
+                Modes::CoupledWordsNouns
+                    | Modes::CoupledWordsNames
+                    | Modes::CoupledWordsListFiles
+                    | Modes::SimpleSentences
+                    | Modes::CartesianPairs
+                    | Modes::Sentence
+                    | Modes::Lorem
+            );
+        }
+        // 4th parameter is language (en, de, ka), or starts with it
+        pub fn takes_language(&self) -> bool {
+            return matches!(
+                *self,
+                Modes::RandomLetters
+                    | Modes::CoupledWordsNouns
+                    | Modes::CoupledWordsNames
+                    | Modes::Sentence
+                    | Modes::Lorem
+                    | Modes::Syllables
+                    | Modes::CurrencyAmount
+            );
+        }
+        // length is length of every string, 0 would give empty ones
+        pub fn needs_length(&self) -> bool {
+            return matches!(
+                *self,
+                Modes::Password
+                    | Modes::Password84
+                    | Modes::RandomLetters
+                    | Modes::RandomLettersFromCustomAlphabet
+                    | Modes::RandomLettersFromAlphabetFile
+                    | Modes::Hybrid
+            );
+        }
+        // long name for --mode, e.g. coupled-nouns
+        pub fn name(&self) -> String {
+            let result = match *self {
+                Modes::Password => "password",
+                Modes::Password84 => "password84",
+                Modes::RandomLetters => "random-letters",
+                Modes::RandomLettersFromCustomAlphabet => "random-letters-alphabet",
+                Modes::RandomLettersFromAlphabetFile => "random-letters-alphabet-file",
+                Modes::CoupledWordsNouns => "coupled-nouns",
+                Modes::CoupledWordsNames => "coupled-names",
+                Modes::CoupledWordsListFiles => "coupled-list-files",
+                Modes::SimpleSentences => "sentences",
+                Modes::SequentialId => "sequential-id",
+                Modes::Snowflake => "snowflake",
+                Modes::LogLines => "log-lines",
+                Modes::FilePaths => "file-paths",
+                Modes::SemVer => "semver",
+                Modes::CurrencyAmount => "currency",
+                Modes::Iban => "iban",
+                Modes::Coordinates => "coordinates",
+                Modes::Color => "color",
+                Modes::Palette => "palette",
+                Modes::Cron => "cron",
+                Modes::HttpFixtures => "http-fixtures",
+                Modes::JwtShaped => "jwt",
+                Modes::SecretsCorpus => "secrets-corpus",
+                Modes::Mixed => "mixed",
+                Modes::CorpusSample => "corpus-sample",
+                Modes::CartesianPairs => "cartesian-pairs",
+                Modes::Ranked => "ranked",
+                Modes::Hybrid => "hybrid",
+                Modes::Pin => "pin",
+                Modes::RedemptionCodes => "redemption-codes",
+                Modes::Similar => "similar-to",
+                Modes::SoundAlike => "sounds-like",
+                Modes::Uuid => "uuid",
+                Modes::Pseudonymize => "pseudonymize",
+                Modes::Ulid => "ulid",
+                Modes::Scrub => "scrub",
+                Modes::Template => "template",
+                Modes::Regex => "regex",
+                Modes::TestToken => "test-token",
+                Modes::Markov => "markov",
+                Modes::Syllables => "syllables",
+                Modes::Sentence => "svo-sentences",
+                Modes::Lorem => "lorem-ipsum",
+                Modes::Major => "major-system",
+                Modes::Proquint => "proquint",
+                Modes::BubbleBabble => "bubble-babble",
+                Modes::Triple => "word-triple",
+                Modes::Wordify => "wordify",
+                Modes::Crockford => "crockford32",
+                Modes::Base62 => "base62",
+            };
+            return String::from(result);
+        }
+        // long name or abbreviation, none for unknown ones
+        pub fn from_name(s: &str) -> Option<Modes> {
+            return Modes::ALL
+                .iter()
+                .find(|mode| mode.name() == s || mode.abbr() == s)
+                .cloned();
+        }
+        // length when none is given, passwords are longer
+        pub fn default_length(&self) -> u32 {
+            return match *self {
+                Modes::Password | Modes::Password84 | Modes::Hybrid => 16,
+                Modes::Pin => 6,
+                // number of bytes
+                Modes::Proquint => 4,
+                Modes::BubbleBabble => 8,
+                // 64 and 65 bits
+                Modes::Crockford => 13,
+                Modes::Base62 => 11,
+                _ => 12,
+            };
+        }
+        // like from, but unknown mode is none instead of random letters
+        pub fn parse(s: &str) -> Option<Modes> {
+            return match s {
+                "cwo" | "cwe" | "cwf" => Some(Modes::from(s)),
+                _ => Modes::from_name(s),
+            };
+        }
+        pub fn abbr(&self) -> String {
+            let result = match *self {
+                Modes::Password => "pass",
+                Modes::Password84 => "pass2",
+                Modes::RandomLetters => "rls",
+                Modes::RandomLettersFromCustomAlphabet => "rla",
+                Modes::RandomLettersFromAlphabetFile => "rlaf",
+                Modes::CoupledWordsNouns => "cow",
+                Modes::CoupledWordsNames => "cowe",
+                Modes::CoupledWordsListFiles => "cowf",
+                Modes::SimpleSentences => "sen",
+                Modes::SequentialId => "seq",
+                Modes::Snowflake => "snow",
+                Modes::LogLines => "log",
+                Modes::FilePaths => "fpath",
+                Modes::SemVer => "semver",
+                Modes::CurrencyAmount => "cur",
+                Modes::Iban => "iban",
+                Modes::Coordinates => "geo",
+                Modes::Color => "color",
+                Modes::Palette => "palette",
+                Modes::Cron => "cron",
+                Modes::HttpFixtures => "http",
+                Modes::JwtShaped => "jwt",
+                Modes::SecretsCorpus => "secrets",
+                Modes::Mixed => "mix",
+                Modes::CorpusSample => "sample",
+                Modes::CartesianPairs => "pairs",
+                Modes::Ranked => "rank",
+                Modes::Hybrid => "hyb",
+                Modes::Pin => "pin",
+                Modes::RedemptionCodes => "code",
+                Modes::Similar => "near",
+                Modes::SoundAlike => "sound",
+                Modes::Uuid => "uuid",
+                Modes::Pseudonymize => "pseud",
+                Modes::Ulid => "ulid",
+                Modes::Scrub => "scrub",
+                Modes::Template => "tpl",
+                Modes::Regex => "regex",
+                Modes::TestToken => "token",
+                Modes::Markov => "markov",
+                Modes::Syllables => "syl",
+                Modes::Sentence => "svo",
+                Modes::Lorem => "lorem",
+                Modes::Major => "major",
+                Modes::Proquint => "proquint",
+                Modes::BubbleBabble => "bubble",
+                Modes::Triple => "triple",
+                Modes::Wordify => "wordify",
+                Modes::Crockford => "b32",
+                Modes::Base62 => "b62",
+            };
+            return String::from(result);
+        }
     }
 }
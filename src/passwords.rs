@@ -0,0 +1,777 @@
+pub mod passwords {
+    use std::collections::HashSet;
+    use std::fs::read_to_string;
+    use std::io::{Error, ErrorKind};
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{
+        in_range, iso_date, now_millis, wipe, Config, ConstraintEngine, GeneratorVersion,
+        Languages, RngBackend, StringFilter, RNG,
+    };
+
+    // c and q sound like k, w x y read differently across languages
+    pub const CONSONANTS: &str = "bdfghjklmnprstvz";
+    pub const VOWELS: &str = "aeiou";
+    // digits and symbols that survive most password forms
+    pub const INJECTED: &str = "0123456789!@#$%&*?-+";
+
+    fn pick(rng: &mut dyn RngBackend, set: &str) -> char {
+        let chars: Vec<char> = set.chars().collect();
+        return chars[rng.get() as usize % chars.len()];
+    }
+
+    fn log2_binomial(n: usize, k: usize) -> f64 {
+        let mut bits = 0.0;
+        for i in 0..k {
+            bits += ((n - i) as f64).log2() - ((i + 1) as f64).log2();
+        }
+        return bits;
+    }
+
+    // syllables and injected characters making up password of given length
+    pub fn hybrid_shape(length: usize) -> (usize, usize) {
+        let length = length.max(4);
+        let mut injected = (length / 5).max(1);
+        if (length - injected) % 2 == 1 {
+            injected += 1;
+        }
+        return ((length - injected) / 2, injected);
+    }
+
+    // bits of generation process itself: every syllable is consonant (either case) and vowel,
+    // injected characters go to distinct syllable boundaries. Letters and injected characters
+    // never mix, so each choice is visible in the output and none of the bits count twice
+    pub fn hybrid_entropy(length: usize) -> f64 {
+        let (syllables, injected) = hybrid_shape(length);
+        let consonants = CONSONANTS.len() as f64;
+        let vowels = VOWELS.len() as f64;
+        let per_syllable = (2.0 * consonants * vowels).log2();
+        let boundaries = log2_binomial(syllables + 1, injected);
+        let per_injected = (INJECTED.len() as f64).log2();
+        return syllables as f64 * per_syllable + boundaries + injected as f64 * per_injected;
+    }
+
+    // pronounceable syllable chunks with digits and symbols between them, e.g. Tamo7Bizu%ke
+    pub struct HybridPassword {
+        // length of last password, drawn from lengths
+        length: usize,
+        lengths: (usize, usize),
+        rng: Box<dyn RngBackend>,
+        held_string: String,
+    }
+    impl Default for HybridPassword {
+        fn default() -> Self {
+            return HybridPassword::new(16);
+        }
+    }
+    impl HybridPassword {
+        pub fn new(length: usize) -> HybridPassword {
+            let mut rng = RNG::new();
+            rng.seed();
+            return HybridPassword {
+                length,
+                lengths: (length, length),
+                rng: Box::new(rng),
+                held_string: String::new(),
+            };
+        }
+        pub fn entropy(&self) -> f64 {
+            return hybrid_entropy(self.length);
+        }
+        // distinct boundaries, uniformly: partial shuffle of all of them
+        fn boundaries(&mut self, syllables: usize, injected: usize) -> Vec<usize> {
+            let mut all: Vec<usize> = (0..=syllables).collect();
+            for i in 0..injected {
+                let j = i + self.rng.get() as usize % (all.len() - i);
+                all.swap(i, j);
+            }
+            all.truncate(injected);
+            all.sort();
+            return all;
+        }
+    }
+    impl Drop for HybridPassword {
+        fn drop(&mut self) {
+            wipe(&mut self.held_string);
+        }
+    }
+    impl StringGenerator for HybridPassword {
+        fn get(&mut self) -> String {
+            let (min, max) = self.lengths;
+            self.length = in_range(self.rng.as_mut(), min as u64, max as u64) as usize;
+            let (syllables, injected) = hybrid_shape(self.length);
+            let boundaries = self.boundaries(syllables, injected);
+            wipe(&mut self.held_string);
+            for position in 0..=syllables {
+                if boundaries.contains(&position) {
+                    let symbol = pick(self.rng.as_mut(), INJECTED);
+                    self.held_string.push(symbol);
+                }
+                if position == syllables {
+                    break;
+                }
+                let consonant = pick(self.rng.as_mut(), CONSONANTS);
+                if self.rng.get() & 1 == 0 {
+                    self.held_string.push(consonant.to_ascii_uppercase());
+                } else {
+                    self.held_string.push(consonant);
+                }
+                let vowel = pick(self.rng.as_mut(), VOWELS);
+                self.held_string.push(vowel);
+            }
+            return self.held_string.clone();
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.backend(0)?;
+            let (min, max) = conf.get_length_range();
+            self.length = max as usize;
+            self.lengths = (min as usize, max as usize);
+            return Ok(());
+        }
+        fn label(&self) -> Option<String> {
+            return Some(format!("{:.1} bits", self.entropy()));
+        }
+    }
+
+    // pins people pick most, from published pin frequency studies
+    pub const COMMON_PINS: &str = include_str!("../lists/passwords/pins.list");
+    pub const PIN_LENGTHS: (usize, usize) = (4, 12);
+
+    // whole code is one unit repeated: 1111, 1212, 123123
+    fn repeats(digits: &[u8]) -> bool {
+        let n = digits.len();
+        return (1..n)
+            .filter(|unit| n.is_multiple_of(*unit))
+            .any(|unit| (unit..n).all(|i| digits[i] == digits[i - unit]));
+    }
+    // every digit one up or one down from previous, 7890 included
+    fn sequence(digits: &[u8]) -> bool {
+        let steps: Vec<u8> = digits.windows(2).map(|w| (w[1] + 10 - w[0]) % 10).collect();
+        return steps.iter().all(|s| *s == 1) || steps.iter().all(|s| *s == 9);
+    }
+
+    // codes guessed first: repeats, sequences, years 1900-2099 and common pins
+    pub fn is_weak_pin(pin: &str) -> bool {
+        if !pin.chars().all(|c| c.is_ascii_digit()) {
+            return true;
+        }
+        let digits: Vec<u8> = pin.bytes().map(|b| b - b'0').collect();
+        if repeats(&digits) || sequence(&digits) {
+            return true;
+        }
+        if digits.len() == 4 && (pin.starts_with("19") || pin.starts_with("20")) {
+            return true;
+        }
+        return COMMON_PINS.lines().any(|common| common.trim() == pin);
+    }
+
+    // numeric codes for device unlock and 2fa fixtures, weak ones are drawn again
+    pub struct PinCode {
+        length: usize,
+        lengths: (usize, usize),
+        rng: Box<dyn RngBackend>,
+        held_string: String,
+    }
+    impl Default for PinCode {
+        fn default() -> Self {
+            return PinCode::new(6);
+        }
+    }
+    impl PinCode {
+        pub fn new(length: usize) -> PinCode {
+            let mut rng = RNG::new();
+            rng.seed();
+            let length = length.clamp(PIN_LENGTHS.0, PIN_LENGTHS.1);
+            return PinCode {
+                length,
+                lengths: (length, length),
+                rng: Box::new(rng),
+                held_string: String::new(),
+            };
+        }
+        pub fn get_length(&self) -> usize {
+            return self.length;
+        }
+    }
+    impl Drop for PinCode {
+        fn drop(&mut self) {
+            wipe(&mut self.held_string);
+        }
+    }
+    impl StringGenerator for PinCode {
+        fn get(&mut self) -> String {
+            let (min, max) = self.lengths;
+            self.length = in_range(self.rng.as_mut(), min as u64, max as u64) as usize;
+            loop {
+                wipe(&mut self.held_string);
+                for _ in 0..self.length {
+                    let digit = (self.rng.get() % 10) as u8;
+                    self.held_string.push((b'0' + digit) as char);
+                }
+                if !is_weak_pin(&self.held_string) {
+                    return self.held_string.clone();
+                }
+            }
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.backend(0)?;
+            let (min, max) = conf.get_length_range();
+            let min = (min as usize).clamp(PIN_LENGTHS.0, PIN_LENGTHS.1);
+            self.length = (max as usize).clamp(PIN_LENGTHS.0, PIN_LENGTHS.1);
+            self.lengths = (min, self.length);
+            return Ok(());
+        }
+    }
+
+    // about three letters per syllable, at least one
+    pub fn syllables_for(length: usize) -> usize {
+        return ((length + 1) / 3).max(1);
+    }
+
+    // onset, nucleus and optional coda of language, strings that can be read aloud
+    pub struct SyllablePassword {
+        language: Languages,
+        // syllable counts follow from length range
+        syllables: (usize, usize),
+        separator: String,
+        rng: Box<dyn RngBackend>,
+        held_string: String,
+    }
+    impl Default for SyllablePassword {
+        fn default() -> Self {
+            return SyllablePassword::new(Languages::English, 4);
+        }
+    }
+    impl SyllablePassword {
+        pub fn new(language: Languages, syllables: usize) -> SyllablePassword {
+            let mut rng = RNG::new();
+            rng.seed();
+            return SyllablePassword {
+                language,
+                syllables: (syllables, syllables),
+                separator: String::new(),
+                rng: Box::new(rng),
+                held_string: String::new(),
+            };
+        }
+        fn part(&mut self, parts: &[&'static str]) -> &'static str {
+            return parts[self.rng.get() as usize % parts.len()];
+        }
+    }
+    impl Drop for SyllablePassword {
+        fn drop(&mut self) {
+            wipe(&mut self.held_string);
+        }
+    }
+    impl StringGenerator for SyllablePassword {
+        fn get(&mut self) -> String {
+            let (min, max) = self.syllables;
+            let count = in_range(self.rng.as_mut(), min as u64, max as u64) as usize;
+            wipe(&mut self.held_string);
+            for i in 0..count {
+                if i > 0 {
+                    self.held_string.push_str(&self.separator);
+                }
+                let onset = self.part(self.language.onsets());
+                let nucleus = self.part(self.language.nuclei());
+                let coda = self.part(self.language.codas());
+                self.held_string.push_str(onset);
+                self.held_string.push_str(nucleus);
+                self.held_string.push_str(coda);
+            }
+            return self.held_string.clone();
+        }
+        // 4th parameter: language[:separator], e.g. de or en:-
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.backend(0)?;
+            let next = conf.get_next();
+            let (language, separator) = next.split_once(':').unwrap_or((&next, ""));
+            self.language = Languages::from(language);
+            self.separator = String::from(separator);
+            let (min, max) = conf.get_length_range();
+            self.syllables = (syllables_for(min as usize), syllables_for(max as usize));
+            return Ok(());
+        }
+    }
+
+    // most used passwords from public breach corpora, lists/passwords has the source
+    pub const COMMON_PASSWORDS: &str = include_str!("../lists/passwords/common.list");
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum CharClass {
+        Lower,
+        Upper,
+        Letter,
+        Digit,
+        Symbol,
+    }
+    impl CharClass {
+        pub fn from(s: &str) -> Option<CharClass> {
+            return match s {
+                "lower" => Some(CharClass::Lower),
+                "upper" => Some(CharClass::Upper),
+                "letter" | "alpha" => Some(CharClass::Letter),
+                "digit" | "number" => Some(CharClass::Digit),
+                "symbol" | "special" => Some(CharClass::Symbol),
+                _ => None,
+            };
+        }
+        pub fn matches(&self, c: char) -> bool {
+            return match *self {
+                CharClass::Lower => c.is_lowercase(),
+                CharClass::Upper => c.is_uppercase(),
+                CharClass::Letter => c.is_alphabetic(),
+                CharClass::Digit => c.is_ascii_digit(),
+                CharClass::Symbol => !c.is_alphanumeric() && !c.is_whitespace(),
+            };
+        }
+        pub fn name(&self) -> &'static str {
+            return match *self {
+                CharClass::Lower => "lower",
+                CharClass::Upper => "upper",
+                CharClass::Letter => "letter",
+                CharClass::Digit => "digit",
+                CharClass::Symbol => "symbol",
+            };
+        }
+        // characters passwords of this class are built from
+        pub fn alphabet(&self) -> &'static str {
+            return match *self {
+                CharClass::Lower => "abcdefghijklmnopqrstuvwxyz",
+                CharClass::Upper => "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+                CharClass::Letter => "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ",
+                CharClass::Digit => "0123456789",
+                CharClass::Symbol => "!@#$%^&*()[]{};:,.<>?|-_=+",
+            };
+        }
+    }
+
+    // where blocked passwords come from
+    #[derive(Clone, PartialEq, Debug)]
+    pub enum Blocklist {
+        None,
+        Common,
+        File(String),
+    }
+
+    // rules passwords have to follow, named preset or policy file
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct PasswordPolicy {
+        pub name: String,
+        pub min_length: u32,
+        // 0 means no upper limit
+        pub max_length: u32,
+        pub required: Vec<CharClass>,
+        // at least this many of lower, upper, digit and symbol, the "3 of 4" rule
+        pub min_classes: u32,
+        pub blocklist: Blocklist,
+        // substrings password must not contain, e.g. company or product name, case ignored
+        pub forbidden: Vec<String>,
+        // days until password should be changed, 0 means it doesn't expire
+        pub expiry_days: u32,
+    }
+    impl PasswordPolicy {
+        pub const PRESETS: [&'static str; 2] = ["nist-63b", "pci-dss"];
+
+        pub fn preset(name: &str) -> Option<PasswordPolicy> {
+            return match name {
+                // sp 800-63b: length over composition, screen against breached lists, no forced expiry
+                "nist-63b" | "nist" => Some(PasswordPolicy {
+                    name: String::from("nist-63b"),
+                    min_length: 15,
+                    max_length: 64,
+                    required: Vec::new(),
+                    min_classes: 0,
+                    blocklist: Blocklist::Common,
+                    forbidden: Vec::new(),
+                    expiry_days: 0,
+                }),
+                // pci dss 4.0 requirement 8.3.6 and 8.3.9
+                "pci-dss" | "pci" => Some(PasswordPolicy {
+                    name: String::from("pci-dss"),
+                    min_length: 12,
+                    max_length: 0,
+                    required: vec![CharClass::Letter, CharClass::Digit],
+                    min_classes: 0,
+                    blocklist: Blocklist::None,
+                    forbidden: Vec::new(),
+                    expiry_days: 90,
+                }),
+                _ => None,
+            };
+        }
+
+        // key=value lines, # starts comment:
+        // name=corp, min_length=14, max_length=64, require=lower,upper,digit,symbol,
+        // min_classes=3, blocklist=common|none|path, forbid=acme,summer, expiry_days=180
+        pub fn parse(text: &str) -> Result<PasswordPolicy, Error> {
+            let mut policy = PasswordPolicy {
+                name: String::from("custom"),
+                min_length: 8,
+                max_length: 0,
+                required: Vec::new(),
+                min_classes: 0,
+                blocklist: Blocklist::None,
+                forbidden: Vec::new(),
+                expiry_days: 0,
+            };
+            for line in text.lines() {
+                let line = line.split('#').next().unwrap_or("").trim();
+                if line.is_empty() {
+                    continue;
+                }
+                let (key, value) = match line.split_once('=') {
+                    Some((key, value)) => (key.trim(), value.trim()),
+                    None => (line, ""),
+                };
+                let number = || -> Result<u32, Error> {
+                    return value.parse().map_err(|_e| {
+                        let message = format!("policy {} needs a number, got {}", key, value);
+                        return Error::new(ErrorKind::InvalidInput, message);
+                    });
+                };
+                match key {
+                    "name" => policy.name = String::from(value),
+                    "min_length" => policy.min_length = number()?,
+                    "max_length" => policy.max_length = number()?,
+                    "min_classes" => policy.min_classes = number()?,
+                    "expiry_days" => policy.expiry_days = number()?,
+                    "require" => {
+                        policy.required.clear();
+                        for name in value.split(',').map(|n| n.trim()).filter(|n| !n.is_empty()) {
+                            match CharClass::from(name) {
+                                Some(class) => policy.required.push(class),
+                                None => {
+                                    let message = format!("unknown character class {}", name);
+                                    return Err(Error::new(ErrorKind::InvalidInput, message));
+                                }
+                            }
+                        }
+                    }
+                    "forbid" => {
+                        policy.forbidden = value
+                            .split(',')
+                            .map(|w| String::from(w.trim()))
+                            .filter(|w| !w.is_empty())
+                            .collect();
+                    }
+                    "blocklist" => {
+                        policy.blocklist = match value {
+                            "" | "0" | "none" => Blocklist::None,
+                            "1" | "common" => Blocklist::Common,
+                            path => Blocklist::File(String::from(path)),
+                        };
+                    }
+                    _ => {
+                        let message = format!("unknown policy key {}", key);
+                        return Err(Error::new(ErrorKind::InvalidInput, message));
+                    }
+                }
+            }
+            if policy.max_length > 0 && policy.max_length < policy.min_length {
+                let message = format!(
+                    "policy max_length {} is below min_length {}",
+                    policy.max_length, policy.min_length
+                );
+                return Err(Error::new(ErrorKind::InvalidInput, message));
+            }
+            return Ok(policy);
+        }
+
+        // preset name or path of policy file
+        pub fn load(spec: &str) -> Result<PasswordPolicy, Error> {
+            if let Some(policy) = PasswordPolicy::preset(spec) {
+                return Ok(policy);
+            }
+            let text = read_to_string(spec).map_err(|e| {
+                let message = format!(
+                    "{} is neither policy preset ({}) nor readable policy file: {}",
+                    spec,
+                    PasswordPolicy::PRESETS.join(", "),
+                    e
+                );
+                return Error::new(ErrorKind::InvalidInput, message);
+            })?;
+            return PasswordPolicy::parse(&text);
+        }
+
+        // requested length moved into allowed range
+        pub fn length(&self, requested: u32) -> u32 {
+            let length = requested.max(self.min_length);
+            if self.max_length > 0 {
+                return length.min(self.max_length);
+            }
+            return length;
+        }
+
+        // rules password breaks, blocklist is checked by PolicyFilter
+        pub fn violations(&self, password: &str) -> Vec<String> {
+            let mut broken = Vec::new();
+            let length = password.chars().count() as u32;
+            if length < self.min_length {
+                broken.push(format!("shorter than {}", self.min_length));
+            }
+            if self.max_length > 0 && length > self.max_length {
+                broken.push(format!("longer than {}", self.max_length));
+            }
+            for class in self.required.iter() {
+                if !password.chars().any(|c| class.matches(c)) {
+                    broken.push(format!("no {}", class.name()));
+                }
+            }
+            let four = [
+                CharClass::Lower,
+                CharClass::Upper,
+                CharClass::Digit,
+                CharClass::Symbol,
+            ];
+            let present = four
+                .iter()
+                .filter(|class| password.chars().any(|c| class.matches(c)))
+                .count() as u32;
+            if present < self.min_classes {
+                broken.push(format!("only {} of {} classes", present, self.min_classes));
+            }
+            return broken;
+        }
+
+        // hint for whoever hands out passwords, goes to run metadata
+        pub fn expiry_hint(&self) -> String {
+            if self.expiry_days == 0 {
+                return String::from("never");
+            }
+            let days = self.expiry_days as u64 * 24 * 60 * 60 * 1000;
+            return iso_date(now_millis() + days);
+        }
+    }
+
+    // config with length the policy allows
+    pub fn apply_policy(conf: &Config) -> Result<Config, Error> {
+        let mut conf = conf.clone();
+        if conf.get_policy().is_empty() {
+            return Ok(conf);
+        }
+        let policy = PasswordPolicy::load(&conf.get_policy())?;
+        conf.set_length(policy.length(conf.get_length()));
+        return Ok(conf);
+    }
+
+    fn lowercase(s: &str) -> String {
+        return s.to_lowercase();
+    }
+
+    // rejects passwords breaking policy or found in its blocklist
+    pub struct PolicyFilter {
+        policy: PasswordPolicy,
+        blocked: HashSet<String>,
+        forbidden: ConstraintEngine,
+    }
+    impl PolicyFilter {
+        pub fn new(policy: PasswordPolicy) -> Result<PolicyFilter, Error> {
+            let blocked: HashSet<String> = match policy.blocklist {
+                Blocklist::None => HashSet::new(),
+                Blocklist::Common => COMMON_PASSWORDS
+                    .lines()
+                    .map(|l| l.trim().to_lowercase())
+                    .collect(),
+                Blocklist::File(ref path) => read_to_string(path)?
+                    .lines()
+                    .map(|l| l.trim().to_lowercase())
+                    .collect(),
+            };
+            let forbidden = ConstraintEngine::with_normalize(&[], &policy.forbidden, lowercase);
+            return Ok(PolicyFilter {
+                policy,
+                blocked,
+                forbidden,
+            });
+        }
+        pub fn is_blocked(&self, password: &str) -> bool {
+            return self.blocked.contains(&password.to_lowercase())
+                || self.forbidden.forbidden_in(password).is_some();
+        }
+    }
+    impl StringFilter for PolicyFilter {
+        fn accepts(&mut self, value: &str) -> bool {
+            return self.policy.violations(value).is_empty() && !self.is_blocked(value);
+        }
+    }
+
+    // what password is made of: least count per class, classes to draw from and
+    // how many times same character may follow itself
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct Composition {
+        pub minimums: Vec<(CharClass, usize)>,
+        pub classes: Vec<CharClass>,
+        // 0 means no limit
+        pub max_repeat: usize,
+    }
+    impl Composition {
+        // rules= of pass mode: digit=2:symbol=1:repeat=2[:classes=lower,upper,digit]
+        pub fn from(s: &str) -> Result<Composition, Error> {
+            let mut composition = Composition {
+                minimums: Vec::new(),
+                classes: vec![
+                    CharClass::Lower,
+                    CharClass::Upper,
+                    CharClass::Digit,
+                    CharClass::Symbol,
+                ],
+                max_repeat: 0,
+            };
+            for part in s.split(':').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+                let (key, value) = part.split_once('=').unwrap_or((part, "1"));
+                let number = || -> Result<usize, Error> {
+                    return value.trim().parse().map_err(|_e| {
+                        let message = format!("password {} needs a number, got {}", key, value);
+                        return Error::new(ErrorKind::InvalidInput, message);
+                    });
+                };
+                if key == "repeat" {
+                    composition.max_repeat = number()?;
+                    continue;
+                }
+                if key == "classes" {
+                    composition.classes.clear();
+                    for name in value.split(',').map(|n| n.trim()) {
+                        match CharClass::from(name) {
+                            Some(class) => composition.classes.push(class),
+                            None => {
+                                let message = format!("unknown character class {}", name);
+                                return Err(Error::new(ErrorKind::InvalidInput, message));
+                            }
+                        }
+                    }
+                    continue;
+                }
+                match CharClass::from(key) {
+                    Some(class) => composition.minimums.push((class, number()?)),
+                    None => {
+                        let message = format!("unknown password rule {}", key);
+                        return Err(Error::new(ErrorKind::InvalidInput, message));
+                    }
+                }
+            }
+            return Ok(composition);
+        }
+        // shortest password holding every minimum
+        pub fn min_length(&self) -> usize {
+            return self.minimums.iter().map(|(_class, n)| n).sum();
+        }
+        // every character of drawn classes once
+        pub fn pool(&self) -> Vec<char> {
+            let mut pool: Vec<char> = Vec::new();
+            for class in self.classes.iter() {
+                for c in class.alphabet().chars() {
+                    if !pool.contains(&c) {
+                        pool.push(c);
+                    }
+                }
+            }
+            return pool;
+        }
+        // longest run of one character
+        pub fn longest_run(password: &str) -> usize {
+            let chars: Vec<char> = password.chars().collect();
+            let mut longest = 0;
+            let mut run = 0;
+            for i in 0..chars.len() {
+                run = if i > 0 && chars[i] == chars[i - 1] {
+                    run + 1
+                } else {
+                    1
+                };
+                longest = longest.max(run);
+            }
+            return longest;
+        }
+    }
+
+    // pass mode with composition: minimums are placed first, rest comes from all classes,
+    // then everything is shuffled, so position of a digit tells nothing
+    pub struct ComposedPassword {
+        composition: Composition,
+        pool: Vec<char>,
+        lengths: (usize, usize),
+        rng: Box<dyn RngBackend>,
+        version: GeneratorVersion,
+        held_string: String,
+    }
+    impl Default for ComposedPassword {
+        fn default() -> Self {
+            return ComposedPassword::new();
+        }
+    }
+    impl ComposedPassword {
+        pub fn new() -> ComposedPassword {
+            let mut rng = RNG::new();
+            rng.seed();
+            let composition = Composition::from("").unwrap();
+            return ComposedPassword {
+                pool: composition.pool(),
+                composition,
+                lengths: (16, 16),
+                rng: Box::new(rng),
+                version: GeneratorVersion::latest(),
+                held_string: String::new(),
+            };
+        }
+        fn draw(&mut self, chars: &[char]) -> char {
+            let index = self.version.pick_index(self.rng.as_mut(), chars.len());
+            return chars[index];
+        }
+    }
+    impl Drop for ComposedPassword {
+        fn drop(&mut self) {
+            wipe(&mut self.held_string);
+        }
+    }
+    impl StringGenerator for ComposedPassword {
+        fn get(&mut self) -> String {
+            let (min, max) = self.lengths;
+            let length = in_range(self.rng.as_mut(), min as u64, max as u64) as usize;
+            let minimums = self.composition.minimums.clone();
+            let pool = self.pool.clone();
+            loop {
+                let mut chars: Vec<char> = Vec::with_capacity(length);
+                for (class, n) in minimums.iter() {
+                    let alphabet: Vec<char> = class.alphabet().chars().collect();
+                    for _ in 0..*n {
+                        let c = self.draw(&alphabet);
+                        chars.push(c);
+                    }
+                }
+                while chars.len() < length {
+                    let c = self.draw(&pool);
+                    chars.push(c);
+                }
+                // fisher-yates
+                for i in (1..chars.len()).rev() {
+                    let j = self.version.pick_index(self.rng.as_mut(), i + 1);
+                    chars.swap(i, j);
+                }
+                wipe(&mut self.held_string);
+                self.held_string.extend(chars.iter());
+                chars.iter_mut().for_each(|c| *c = '\0');
+                let max_repeat = self.composition.max_repeat;
+                if max_repeat == 0 || Composition::longest_run(&self.held_string) <= max_repeat {
+                    return self.held_string.clone();
+                }
+            }
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.backend(0)?;
+            self.version = conf.get_version();
+            self.composition = Composition::from(&conf.get_rules())?;
+            self.pool = self.composition.pool();
+            let (min, max) = conf.get_length_range();
+            let least = self.composition.min_length();
+            if (max as usize) < least {
+                let message = format!("password rules need at least {} characters", least);
+                return Err(Error::new(ErrorKind::InvalidInput, message));
+            }
+            self.lengths = ((min as usize).max(least), max as usize);
+            return Ok(());
+        }
+    }
+}
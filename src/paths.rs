@@ -0,0 +1,100 @@
+pub mod paths {
+    use std::ffi::{OsStr, OsString};
+    use std::path::{Path, PathBuf};
+
+    // directory with bundled word lists, relative to working directory
+    pub fn lists_dir() -> PathBuf {
+        return Path::new(".").join("lists");
+    }
+
+    // path with extra extension appended: strings.textout -> strings.textout.meta
+    pub fn sidecar(path: &Path, extension: &str) -> PathBuf {
+        let mut name: OsString = path.as_os_str().to_os_string();
+        name.push(".");
+        name.push(extension);
+        return PathBuf::from(name);
+    }
+
+    // splits on ascii separator without going through String, so non utf-8 paths survive
+    #[cfg(unix)]
+    pub fn split_os(s: &OsStr, sep: char) -> Vec<OsString> {
+        use std::os::unix::ffi::OsStrExt;
+        return s
+            .as_bytes()
+            .split(|b| *b == sep as u8)
+            .map(|part| OsStr::from_bytes(part).to_os_string())
+            .collect();
+    }
+    #[cfg(windows)]
+    pub fn split_os(s: &OsStr, sep: char) -> Vec<OsString> {
+        use std::os::windows::ffi::{OsStrExt, OsStringExt};
+        let wide: Vec<u16> = s.encode_wide().collect();
+        return wide
+            .split(|w| *w == sep as u16)
+            .map(OsString::from_wide)
+            .collect();
+    }
+    #[cfg(not(any(unix, windows)))]
+    pub fn split_os(s: &OsStr, sep: char) -> Vec<OsString> {
+        return s.to_string_lossy().split(sep).map(OsString::from).collect();
+    }
+
+    // "prefix" removed from start of argument, e.g. next=
+    pub fn strip_prefix_os(s: &OsStr, prefix: &str) -> Option<OsString> {
+        let key = prefix.trim_end_matches('=');
+        let parts = split_os(s, '=');
+        if parts.len() < 2 || parts[0] != OsStr::new(key) {
+            return None;
+        }
+        let mut value = parts[1].clone();
+        for part in &parts[2..] {
+            value.push("=");
+            value.push(part);
+        }
+        return Some(value);
+    }
+
+    // list files are separated by ':', but drive letters (C:\lists\a.list) are kept whole
+    pub fn split_list_files(s: &OsStr) -> Vec<PathBuf> {
+        let parts = split_os(s, ':');
+        let mut result: Vec<PathBuf> = Vec::new();
+        let mut i = 0;
+        while i < parts.len() {
+            let part = &parts[i];
+            let is_drive = part.len() == 1
+                && part
+                    .to_str()
+                    .is_some_and(|p| p.chars().all(|c| c.is_ascii_alphabetic()));
+            if is_drive && i + 1 < parts.len() {
+                let next = parts[i + 1].to_string_lossy();
+                if next.starts_with('\\') || next.starts_with('/') {
+                    let mut joined = part.clone();
+                    joined.push(":");
+                    joined.push(&parts[i + 1]);
+                    result.push(PathBuf::from(joined));
+                    i += 2;
+                    continue;
+                }
+            }
+            result.push(PathBuf::from(part));
+            i += 1;
+        }
+        return result;
+    }
+
+    // windows consoles default to legacy code page, switch to utf-8
+    // so georgian and german output is readable by other programs too
+    #[cfg(windows)]
+    pub fn utf8_console() {
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn SetConsoleOutputCP(code_page: u32) -> i32;
+        }
+        const CP_UTF8: u32 = 65001;
+        unsafe {
+            SetConsoleOutputCP(CP_UTF8);
+        }
+    }
+    #[cfg(not(windows))]
+    pub fn utf8_console() {}
+}
@@ -0,0 +1,293 @@
+pub mod pseudonyms {
+    use std::collections::HashMap;
+    use std::fs::{read, read_to_string, File};
+    use std::io::{Error, ErrorKind, Write};
+    use std::path::{Path, PathBuf};
+
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{
+        decrypt, encrypt, encryption_available, read_input, read_passphrase, wipe, wipe_bytes,
+        Config, Languages, ListType, WordList,
+    };
+
+    // key of pseudonyms when no key file is given
+    pub const KEY_VAR: &str = "STRGEN_PSEUDONYM_KEY";
+    // hmac rounds tried for one id before mapping counts as full
+    pub const PSEUDONYM_ROUNDS: u32 = 1000;
+
+    pub fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac takes key of any length");
+        mac.update(message);
+        return mac.finalize().into_bytes().into();
+    }
+
+    fn word_at(list: &WordList, bytes: &[u8]) -> String {
+        let n = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+        return list.get_at(n % list.get_list_len());
+    }
+
+    // same key and id give same pseudonym, on any machine and in any run
+    pub struct Pseudonymizer {
+        key: Vec<u8>,
+        adjectives: WordList,
+        nouns: WordList,
+        // pseudonym -> id, ids of mapping file and of this run
+        taken: HashMap<String, String>,
+        // id -> pseudonym
+        given: HashMap<String, String>,
+    }
+    impl Pseudonymizer {
+        pub fn new(key: &[u8], language: Languages) -> Result<Pseudonymizer, Error> {
+            if key.is_empty() {
+                return Err(Error::new(ErrorKind::InvalidInput, "empty pseudonym key"));
+            }
+            let mut adjectives = WordList::new(ListType::Adjectives, language.clone());
+            adjectives.fill(Path::new(""))?;
+            let mut nouns = WordList::new(ListType::Nouns, language);
+            nouns.fill(Path::new(""))?;
+            return Ok(Pseudonymizer {
+                key: key.to_vec(),
+                adjectives,
+                nouns,
+                taken: HashMap::new(),
+                given: HashMap::new(),
+            });
+        }
+        // pairs of earlier runs keep their pseudonyms
+        pub fn remember(&mut self, pseudonym: &str, id: &str) {
+            self.taken.insert(String::from(pseudonym), String::from(id));
+            self.given.insert(String::from(id), String::from(pseudonym));
+        }
+        // adjective-noun-number of given round of hmac
+        pub fn candidate(&self, id: &str, round: u32) -> String {
+            let mut message = id.as_bytes().to_vec();
+            if round > 0 {
+                message.push(0);
+                message.extend_from_slice(&round.to_be_bytes());
+            }
+            let mac = hmac_sha256(&self.key, &message);
+            let number = u32::from_be_bytes([mac[8], mac[9], mac[10], mac[11]]) % 10000;
+            return format!(
+                "{}-{}-{:04}",
+                word_at(&self.adjectives, &mac[0..4]),
+                word_at(&self.nouns, &mac[4..8]),
+                number
+            );
+        }
+        // taken pseudonym moves id to next round of hmac
+        pub fn pseudonym(&mut self, id: &str) -> Result<String, Error> {
+            if let Some(pseudonym) = self.given.get(id) {
+                return Ok(pseudonym.clone());
+            }
+            for round in 0..PSEUDONYM_ROUNDS {
+                let pseudonym = self.candidate(id, round);
+                if !self.taken.contains_key(&pseudonym) {
+                    self.remember(&pseudonym, id);
+                    return Ok(pseudonym);
+                }
+            }
+            let message = format!("no free pseudonym in {} rounds, map is full", PSEUDONYM_ROUNDS);
+            return Err(Error::other(message));
+        }
+        // pseudonym\tid lines, sorted by pseudonym
+        pub fn mapping(&self) -> String {
+            let mut pairs: Vec<(&String, &String)> = self.taken.iter().collect();
+            pairs.sort();
+            let mut text = String::new();
+            for (pseudonym, id) in pairs {
+                text.push_str(&format!("{}\t{}\n", pseudonym, id));
+            }
+            return text;
+        }
+    }
+    impl Drop for Pseudonymizer {
+        fn drop(&mut self) {
+            wipe_bytes(&mut self.key);
+        }
+    }
+
+    // 4th parameter: input[:key=file][:map=file][:lang=en], input - or empty is stdin
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct PseudonymSpec {
+        pub input: String,
+        pub key_file: String,
+        pub map: String,
+        pub language: String,
+    }
+    impl PseudonymSpec {
+        pub fn from(s: &str) -> PseudonymSpec {
+            let mut spec = PseudonymSpec {
+                input: String::new(),
+                key_file: String::new(),
+                map: String::new(),
+                language: String::from("en"),
+            };
+            let mut parts = s.split(':');
+            spec.input = String::from(parts.next().unwrap_or(""));
+            for part in parts {
+                match part.split_once('=') {
+                    Some(("key", path)) => spec.key_file = String::from(path),
+                    Some(("map", path)) => spec.map = String::from(path),
+                    Some(("lang", lang)) => spec.language = String::from(lang),
+                    _ => {}
+                }
+            }
+            return spec;
+        }
+        // key file, or STRGEN_PSEUDONYM_KEY
+        pub fn key(&self) -> Result<Vec<u8>, Error> {
+            if !self.key_file.is_empty() {
+                let key = read(&self.key_file)?;
+                return Ok(key.trim_ascii().to_vec());
+            }
+            return match std::env::var(KEY_VAR) {
+                Ok(key) if !key.is_empty() => Ok(key.into_bytes()),
+                _ => Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("pseudonyms need key: key=file or {} set", KEY_VAR),
+                )),
+            };
+        }
+        pub fn is_encrypted(&self) -> bool {
+            return self.map.ends_with(".enc");
+        }
+    }
+
+    // identifiers of real data replaced with readable stand-ins, mapping back kept in map file
+    pub struct Pseudonyms {
+        pseudonymizer: Option<Pseudonymizer>,
+        ids: Vec<String>,
+        position: usize,
+        map: Option<PathBuf>,
+        passphrase: String,
+        saved: bool,
+        // strings run asks for, mapping is saved once they are given
+        amount: usize,
+        error: Option<Error>,
+    }
+    impl Default for Pseudonyms {
+        fn default() -> Self {
+            return Pseudonyms::new();
+        }
+    }
+    impl Pseudonyms {
+        pub fn new() -> Pseudonyms {
+            return Pseudonyms {
+                pseudonymizer: None,
+                ids: Vec::new(),
+                position: 0,
+                map: None,
+                passphrase: String::new(),
+                saved: true,
+                amount: 0,
+                error: None,
+            };
+        }
+        fn load_map(&mut self, path: &Path) -> Result<(), Error> {
+            if !path.exists() {
+                return Ok(());
+            }
+            let text = if self.passphrase.is_empty() {
+                read_to_string(path)?
+            } else {
+                let plain = decrypt(&read(path)?, &self.passphrase)?;
+                String::from_utf8(plain).map_err(|e| Error::new(ErrorKind::InvalidData, e))?
+            };
+            if let Some(pseudonymizer) = self.pseudonymizer.as_mut() {
+                for line in text.lines() {
+                    if let Some((pseudonym, id)) = line.split_once('\t') {
+                        pseudonymizer.remember(pseudonym, id);
+                    }
+                }
+            }
+            return Ok(());
+        }
+        // whole mapping is written at once, encrypted with passphrase for .enc files
+        pub fn save(&mut self) -> Result<(), Error> {
+            let (Some(path), Some(pseudonymizer)) =
+                (self.map.as_ref(), self.pseudonymizer.as_ref())
+            else {
+                return Ok(());
+            };
+            let mut text = pseudonymizer.mapping();
+            let mut file = File::create(path)?;
+            if self.passphrase.is_empty() {
+                file.write_all(text.as_bytes())?;
+            } else {
+                file.write_all(&encrypt(text.as_bytes(), &self.passphrase)?)?;
+            }
+            wipe(&mut text);
+            self.saved = true;
+            return file.flush();
+        }
+    }
+    impl Drop for Pseudonyms {
+        fn drop(&mut self) {
+            // run stopped by error before last string, mapping of what was given still counts
+            if !self.saved {
+                if let Err(e) = self.save() {
+                    eprintln!("mapping not saved: {}", e);
+                }
+            }
+            wipe(&mut self.passphrase);
+        }
+    }
+    impl StringGenerator for Pseudonyms {
+        fn get(&mut self) -> String {
+            let Some(id) = self.ids.get(self.position) else {
+                return String::new();
+            };
+            self.position += 1;
+            let pseudonym = match self.pseudonymizer.as_mut().map(|p| p.pseudonym(id)) {
+                Some(Ok(pseudonym)) => pseudonym,
+                Some(Err(e)) => {
+                    self.error = Some(e);
+                    return String::new();
+                }
+                None => return String::new(),
+            };
+            self.saved = false;
+            // last one of input or of run, mapping that can't be saved fails the run
+            if self.exhausted() || self.position >= self.amount {
+                if let Err(e) = self.save() {
+                    let message = format!("mapping not saved: {}", e);
+                    self.error = Some(Error::new(e.kind(), message));
+                }
+            }
+            return pseudonym;
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            let spec = PseudonymSpec::from(&conf.get_next());
+            let mut key = spec.key()?;
+            let language = Languages::from(spec.language.as_ref());
+            self.pseudonymizer = Some(Pseudonymizer::new(&key, language)?);
+            wipe_bytes(&mut key);
+            self.ids = read_input(&spec.input)?
+                .iter()
+                .map(|id| String::from(id.trim()))
+                .filter(|id| !id.is_empty())
+                .collect();
+            self.position = 0;
+            self.amount = conf.get_amount() as usize;
+            if !spec.map.is_empty() {
+                if spec.is_encrypted() {
+                    encryption_available()?;
+                    self.passphrase = read_passphrase(!Path::new(&spec.map).exists())?;
+                }
+                let path = PathBuf::from(&spec.map);
+                self.load_map(&path)?;
+                self.map = Some(path);
+            }
+            return Ok(());
+        }
+        fn exhausted(&self) -> bool {
+            return self.position >= self.ids.len();
+        }
+        fn failure(&mut self) -> Option<Error> {
+            return self.error.take();
+        }
+    }
+}
@@ -0,0 +1,417 @@
+pub mod ranking {
+    use std::collections::HashSet;
+    use std::io::{Error, ErrorKind};
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{stringer, Config, Modes};
+
+    // english letter frequencies in percent, a to z
+    const LETTER_FREQUENCY: [f64; 26] = [
+        8.2, 1.5, 2.8, 4.3, 12.7, 2.2, 2.0, 6.1, 7.0, 0.15, 0.77, 4.0, 2.4, 6.7, 7.5, 1.9, 0.095,
+        6.0, 6.3, 9.1, 2.8, 0.98, 2.4, 0.15, 2.0, 0.074,
+    ];
+
+    fn is_vowel(c: char) -> bool {
+        return "aeiouy".contains(c);
+    }
+    fn words(value: &str) -> Vec<String> {
+        return value
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(|w| w.to_lowercase())
+            .collect();
+    }
+
+    // 1 minus share of latin letters stuck in runs of 3+ consonants or 3+ vowels
+    pub fn pronounceability(value: &str) -> f64 {
+        let mut letters = 0;
+        let mut stuck = 0;
+        for word in words(value).iter() {
+            let mut run = 0;
+            let mut vowels = false;
+            for c in word.chars().filter(|c| c.is_ascii_lowercase()) {
+                letters += 1;
+                if run > 0 && is_vowel(c) == vowels {
+                    run += 1;
+                } else {
+                    run = 1;
+                    vowels = is_vowel(c);
+                }
+                // whole run counts once it gets too long
+                if run == 3 {
+                    stuck += 3;
+                } else if run > 3 {
+                    stuck += 1;
+                }
+            }
+        }
+        if letters == 0 {
+            return 0.0;
+        }
+        return 1.0 - stuck as f64 / letters as f64;
+    }
+
+    // mean surprise of latin letters, 0 for all e's, 1 for all z's
+    pub fn rarity(value: &str) -> f64 {
+        let rarest = -(LETTER_FREQUENCY[25] / 100.0).log2();
+        let mut total = 0.0;
+        let mut letters = 0;
+        for c in value
+            .to_lowercase()
+            .chars()
+            .filter(|c| c.is_ascii_lowercase())
+        {
+            let frequency = LETTER_FREQUENCY[(c as u8 - b'a') as usize] / 100.0;
+            total += -frequency.log2() / rarest;
+            letters += 1;
+        }
+        if letters == 0 {
+            return 0.0;
+        }
+        return total / letters as f64;
+    }
+
+    // share of neighbouring words starting with same letter, 0 for single word
+    pub fn alliteration(value: &str) -> f64 {
+        let words = words(value);
+        if words.len() < 2 {
+            return 0.0;
+        }
+        let same = words
+            .windows(2)
+            .filter(|pair| pair[0].chars().next() == pair[1].chars().next())
+            .count();
+        return same as f64 / (words.len() - 1) as f64;
+    }
+
+    pub const FEATURES: [&str; 4] = ["len", "pron", "rare", "allit"];
+
+    // value of named feature for candidate
+    pub fn feature(name: &str, value: &str) -> Option<f64> {
+        return match name {
+            "len" | "length" => Some(value.chars().count() as f64),
+            "pron" => Some(pronounceability(value)),
+            "rare" => Some(rarity(value)),
+            "allit" => Some(alliteration(value)),
+            _ => None,
+        };
+    }
+
+    // scoring expression over features: numbers, + - * /, parentheses and min, max, abs
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Expr {
+        Number(f64),
+        Feature(String),
+        Negate(Box<Expr>),
+        Binary(char, Box<Expr>, Box<Expr>),
+        Call(String, Vec<Expr>),
+    }
+    impl Expr {
+        pub fn eval(&self, value: &str) -> f64 {
+            return match self {
+                Expr::Number(n) => *n,
+                Expr::Feature(name) => feature(name, value).unwrap_or(0.0),
+                Expr::Negate(e) => -e.eval(value),
+                Expr::Binary(op, a, b) => {
+                    let (a, b) = (a.eval(value), b.eval(value));
+                    match op {
+                        '+' => a + b,
+                        '-' => a - b,
+                        '*' => a * b,
+                        // division by zero scores 0 instead of spreading inf
+                        _ if b == 0.0 => 0.0,
+                        _ => a / b,
+                    }
+                }
+                Expr::Call(name, args) => {
+                    let args: Vec<f64> = args.iter().map(|a| a.eval(value)).collect();
+                    match name.as_str() {
+                        "min" => args.iter().cloned().fold(f64::INFINITY, f64::min),
+                        "max" => args.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                        _ => args[0].abs(),
+                    }
+                }
+            };
+        }
+    }
+
+    fn invalid(message: String) -> Error {
+        return Error::new(ErrorKind::InvalidInput, message);
+    }
+
+    // recursive descent: expr = term (+|- term)*, term = unary (*|/ unary)*
+    struct ExprParser {
+        chars: Vec<char>,
+        position: usize,
+    }
+    impl ExprParser {
+        fn peek(&mut self) -> Option<char> {
+            while self.position < self.chars.len() && self.chars[self.position].is_whitespace() {
+                self.position += 1;
+            }
+            return self.chars.get(self.position).cloned();
+        }
+        fn expect(&mut self, c: char) -> Result<(), Error> {
+            if self.peek() != Some(c) {
+                return Err(invalid(format!(
+                    "expected '{}' at position {} of scoring expression",
+                    c, self.position
+                )));
+            }
+            self.position += 1;
+            return Ok(());
+        }
+        fn expression(&mut self) -> Result<Expr, Error> {
+            let mut left = self.term()?;
+            while let Some(op @ ('+' | '-')) = self.peek() {
+                self.position += 1;
+                left = Expr::Binary(op, Box::new(left), Box::new(self.term()?));
+            }
+            return Ok(left);
+        }
+        fn term(&mut self) -> Result<Expr, Error> {
+            let mut left = self.unary()?;
+            while let Some(op @ ('*' | '/')) = self.peek() {
+                self.position += 1;
+                left = Expr::Binary(op, Box::new(left), Box::new(self.unary()?));
+            }
+            return Ok(left);
+        }
+        fn unary(&mut self) -> Result<Expr, Error> {
+            if self.peek() == Some('-') {
+                self.position += 1;
+                return Ok(Expr::Negate(Box::new(self.unary()?)));
+            }
+            return self.atom();
+        }
+        fn atom(&mut self) -> Result<Expr, Error> {
+            let c = match self.peek() {
+                Some(c) => c,
+                None => return Err(invalid(String::from("scoring expression ends too early"))),
+            };
+            if c == '(' {
+                self.position += 1;
+                let inner = self.expression()?;
+                self.expect(')')?;
+                return Ok(inner);
+            }
+            let start = self.position;
+            if c.is_ascii_digit() || c == '.' {
+                while self.position < self.chars.len()
+                    && (self.chars[self.position].is_ascii_digit()
+                        || self.chars[self.position] == '.')
+                {
+                    self.position += 1;
+                }
+                let text: String = self.chars[start..self.position].iter().collect();
+                return match text.parse() {
+                    Ok(n) => Ok(Expr::Number(n)),
+                    Err(_e) => Err(invalid(format!(
+                        "bad number {} in scoring expression",
+                        text
+                    ))),
+                };
+            }
+            if !c.is_ascii_alphabetic() {
+                return Err(invalid(format!(
+                    "unexpected '{}' at position {} of scoring expression",
+                    c, self.position
+                )));
+            }
+            while self.position < self.chars.len()
+                && (self.chars[self.position].is_ascii_alphanumeric()
+                    || self.chars[self.position] == '_')
+            {
+                self.position += 1;
+            }
+            let name: String = self.chars[start..self.position].iter().collect();
+            if self.peek() == Some('(') {
+                self.position += 1;
+                let mut args = vec![self.expression()?];
+                while self.peek() == Some(',') {
+                    self.position += 1;
+                    args.push(self.expression()?);
+                }
+                self.expect(')')?;
+                let arity_ok = match name.as_str() {
+                    "min" | "max" => true,
+                    "abs" => args.len() == 1,
+                    _ => {
+                        return Err(invalid(format!(
+                            "unknown function {} in scoring expression",
+                            name
+                        )))
+                    }
+                };
+                if !arity_ok {
+                    return Err(invalid(format!("{} takes one argument", name)));
+                }
+                return Ok(Expr::Call(name, args));
+            }
+            if feature(&name, "").is_none() {
+                return Err(invalid(format!(
+                    "unknown scoring feature {}, known are {}",
+                    name,
+                    FEATURES.join(", ")
+                )));
+            }
+            return Ok(Expr::Feature(name));
+        }
+    }
+
+    pub fn parse_expression(s: &str) -> Result<Expr, Error> {
+        let mut parser = ExprParser {
+            chars: s.chars().collect(),
+            position: 0,
+        };
+        let expr = parser.expression()?;
+        if parser.peek().is_some() {
+            return Err(invalid(format!(
+                "unexpected '{}' at position {} of scoring expression",
+                parser.chars[parser.position], parser.position
+            )));
+        }
+        return Ok(expr);
+    }
+
+    // weighted list pron=2,allit,len=-0.1 (weight defaults to 1)
+    // or expression 2*allit + pron - 0.5*len
+    #[derive(Clone, Debug)]
+    pub struct Scoring {
+        expr: Expr,
+    }
+    impl Scoring {
+        pub const DEFAULT: &'static str = "pron + allit + 0.5*rare - 0.05*len";
+
+        pub fn from(s: &str) -> Result<Scoring, Error> {
+            let s = if s.trim().is_empty() {
+                Scoring::DEFAULT
+            } else {
+                s
+            };
+            // commas outside of function calls mean weighted list
+            let list = s.contains('=') || (s.contains(',') && !s.contains('('));
+            if !list {
+                return Ok(Scoring {
+                    expr: parse_expression(s)?,
+                });
+            }
+            let mut sum: Option<Expr> = None;
+            for part in s.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+                let (name, weight) = part.split_once('=').unwrap_or((part, "1"));
+                if feature(name, "").is_none() {
+                    return Err(invalid(format!(
+                        "unknown scoring feature {}, known are {}",
+                        name,
+                        FEATURES.join(", ")
+                    )));
+                }
+                let weight = match weight.trim().parse() {
+                    Ok(weight) => weight,
+                    Err(_e) => {
+                        return Err(invalid(format!("scoring weight is not a number: {}", part)))
+                    }
+                };
+                let term = Expr::Binary(
+                    '*',
+                    Box::new(Expr::Number(weight)),
+                    Box::new(Expr::Feature(String::from(name))),
+                );
+                sum = match sum {
+                    Some(sum) => Some(Expr::Binary('+', Box::new(sum), Box::new(term))),
+                    None => Some(term),
+                };
+            }
+            return Ok(Scoring {
+                expr: sum.unwrap_or(Expr::Number(0.0)),
+            });
+        }
+        pub fn score(&self, value: &str) -> f64 {
+            return self.expr.eval(value);
+        }
+    }
+
+    // over-generates candidates of other mode and hands out best scoring ones first
+    pub struct RankedCandidates {
+        // (candidate, score), best last so it can be popped
+        ranked: Vec<(String, f64)>,
+        last_score: f64,
+    }
+    impl Default for RankedCandidates {
+        fn default() -> Self {
+            return RankedCandidates::new();
+        }
+    }
+    impl RankedCandidates {
+        pub fn new() -> RankedCandidates {
+            return RankedCandidates {
+                ranked: Vec::new(),
+                last_score: 0.0,
+            };
+        }
+        // ranks given candidates, duplicates are dropped
+        pub fn rank(&mut self, candidates: Vec<String>, scoring: &Scoring) {
+            let mut seen: HashSet<String> = HashSet::new();
+            let mut ranked: Vec<(String, f64)> = Vec::new();
+            for candidate in candidates.into_iter() {
+                if seen.insert(candidate.clone()) {
+                    let score = scoring.score(&candidate);
+                    ranked.push((candidate, score));
+                }
+            }
+            // lowest first, ties keep generation order when popped
+            ranked.reverse();
+            ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+            self.ranked = ranked;
+        }
+        pub fn len(&self) -> usize {
+            return self.ranked.len();
+        }
+        pub fn is_empty(&self) -> bool {
+            return self.ranked.is_empty();
+        }
+    }
+    impl StringGenerator for RankedCandidates {
+        fn get(&mut self) -> String {
+            return match self.ranked.pop() {
+                Some((candidate, score)) => {
+                    self.last_score = score;
+                    candidate
+                }
+                None => String::new(),
+            };
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            let scoring = Scoring::from(&conf.get_scoring())?;
+            let next = conf.get_next();
+            let (mode, inner_next) = next.split_once('@').unwrap_or((next.as_str(), ""));
+            if mode == "rank" {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "rank can't rank itself",
+                ));
+            }
+            let mut sub = conf.clone();
+            sub.set_mode(Modes::from(mode));
+            sub.set_next(String::from(inner_next));
+            sub.set_amount(conf.get_amount().saturating_mul(conf.get_oversample()));
+            let mut generator = stringer(sub.clone());
+            generator.setup(&sub)?;
+            let mut candidates: Vec<String> = Vec::new();
+            for _ in 0..sub.get_amount() {
+                if generator.exhausted() {
+                    break;
+                }
+                candidates.push(generator.get());
+            }
+            self.rank(candidates, &scoring);
+            return Ok(());
+        }
+        fn label(&self) -> Option<String> {
+            return Some(format!("{:.3}", self.last_score));
+        }
+        fn exhausted(&self) -> bool {
+            return self.ranked.is_empty();
+        }
+    }
+}
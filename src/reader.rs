@@ -3,17 +3,30 @@ pub mod reader {
     use std::io::{self, BufRead, ErrorKind};
     use std::path::Path;
 
+    use crate::stringer::StrgenError;
+
     //copied from rust site and modified
     pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
     where
         P: AsRef<Path>,
     {
-        match File::open(filename) {
+        match File::open(filename.as_ref()) {
             Ok(file) => return Ok(io::BufReader::new(file).lines()),
             Err(error) => match error.kind() {
-                ErrorKind::NotFound => panic!("File not found"),
-                _ => panic!("Error when opening file"),
+                ErrorKind::NotFound => {
+                    let path = filename.as_ref().to_path_buf();
+                    return Err(StrgenError::MissingListFile(path).into());
+                }
+                _ => return Err(error),
             },
         };
     }
+
+    // lines of input file, or of stdin for - and empty path
+    pub fn read_input(input: &str) -> io::Result<Vec<String>> {
+        if input.is_empty() || input == "-" {
+            return io::stdin().lock().lines().collect();
+        }
+        return read_lines(input)?.collect();
+    }
 }
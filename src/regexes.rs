@@ -0,0 +1,280 @@
+pub mod regexes {
+    use std::io::{Error, ErrorKind};
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{in_range, Config, GeneratorVersion, RNG};
+
+    // * and + repeat at most this many times more than their minimum
+    pub const MAX_REPEAT: u32 = 8;
+
+    #[derive(Clone, PartialEq, Debug)]
+    pub enum Node {
+        Literal(char),
+        // characters any one of which matches
+        Set(Vec<char>),
+        // alternatives, each a sequence
+        Group(Vec<Vec<Node>>),
+        Repeat(Box<Node>, u32, u32),
+    }
+
+    fn printable() -> Vec<char> {
+        return (' '..='~').collect();
+    }
+
+    fn escaped_set(c: char) -> Option<Vec<char>> {
+        let mut set: Vec<char> = match c.to_ascii_lowercase() {
+            'd' => ('0'..='9').collect(),
+            'w' => ('a'..='z')
+                .chain('A'..='Z')
+                .chain('0'..='9')
+                .chain(['_'])
+                .collect(),
+            's' => vec![' ', '\t'],
+            _ => return None,
+        };
+        if c.is_ascii_uppercase() {
+            set = printable()
+                .into_iter()
+                .filter(|p| !set.contains(p))
+                .collect();
+        }
+        return Some(set);
+    }
+
+    fn bad_regex(message: String) -> Error {
+        return Error::new(ErrorKind::InvalidInput, message);
+    }
+
+    // literals, ., [a-z] and [^..], \d \w \s, groups with |, ? * + {n} {n,m} {n,}
+    pub struct RegexParser {
+        chars: Vec<char>,
+        position: usize,
+    }
+    impl RegexParser {
+        pub fn parse(s: &str) -> Result<Node, Error> {
+            let mut pattern = s;
+            pattern = pattern.strip_prefix('^').unwrap_or(pattern);
+            if pattern.ends_with('$') && !pattern.ends_with("\\$") {
+                pattern = &pattern[..pattern.len() - 1];
+            }
+            let mut parser = RegexParser {
+                chars: pattern.chars().collect(),
+                position: 0,
+            };
+            let node = parser.alternation()?;
+            if parser.position < parser.chars.len() {
+                return Err(bad_regex(format!("unmatched ) in regex {}", s)));
+            }
+            return Ok(node);
+        }
+        fn peek(&self) -> Option<char> {
+            return self.chars.get(self.position).copied();
+        }
+        fn next(&mut self) -> Option<char> {
+            let c = self.peek();
+            self.position += 1;
+            return c;
+        }
+        fn alternation(&mut self) -> Result<Node, Error> {
+            let mut alternatives = vec![self.sequence()?];
+            while self.peek() == Some('|') {
+                self.position += 1;
+                alternatives.push(self.sequence()?);
+            }
+            return Ok(Node::Group(alternatives));
+        }
+        fn sequence(&mut self) -> Result<Vec<Node>, Error> {
+            let mut nodes: Vec<Node> = Vec::new();
+            while let Some(c) = self.peek() {
+                if c == '|' || c == ')' {
+                    break;
+                }
+                let atom = self.atom()?;
+                nodes.push(self.quantified(atom)?);
+            }
+            return Ok(nodes);
+        }
+        fn atom(&mut self) -> Result<Node, Error> {
+            let c = self.next().unwrap_or_default();
+            return match c {
+                '(' => {
+                    if self.chars[self.position..].starts_with(&['?', ':']) {
+                        self.position += 2;
+                    }
+                    let group = self.alternation()?;
+                    if self.next() != Some(')') {
+                        return Err(bad_regex(String::from("unclosed ( in regex")));
+                    }
+                    Ok(group)
+                }
+                '[' => self.class(),
+                '.' => Ok(Node::Set(printable())),
+                '\\' => {
+                    let escaped = self
+                        .next()
+                        .ok_or_else(|| bad_regex(String::from("regex ends with \\")))?;
+                    Ok(match escaped_set(escaped) {
+                        Some(set) => Node::Set(set),
+                        None => Node::Literal(escaped),
+                    })
+                }
+                '*' | '+' | '?' | '{' => Err(bad_regex(format!("nothing to repeat before {}", c))),
+                _ => Ok(Node::Literal(c)),
+            };
+        }
+        fn class(&mut self) -> Result<Node, Error> {
+            let negated = self.peek() == Some('^');
+            if negated {
+                self.position += 1;
+            }
+            let mut set: Vec<char> = Vec::new();
+            let mut first = true;
+            loop {
+                let c = self
+                    .next()
+                    .ok_or_else(|| bad_regex(String::from("unclosed [ in regex")))?;
+                if c == ']' && !first {
+                    break;
+                }
+                first = false;
+                let c = if c == '\\' {
+                    let escaped = self
+                        .next()
+                        .ok_or_else(|| bad_regex(String::from("unclosed [ in regex")))?;
+                    if let Some(escaped) = escaped_set(escaped) {
+                        set.extend(escaped);
+                        continue;
+                    }
+                    escaped
+                } else {
+                    c
+                };
+                let is_range = self.peek() == Some('-')
+                    && self
+                        .chars
+                        .get(self.position + 1)
+                        .is_some_and(|next| *next != ']');
+                if is_range {
+                    self.position += 1;
+                    let end = self.next().unwrap_or(c);
+                    if end < c {
+                        return Err(bad_regex(format!("bad range {}-{} in regex", c, end)));
+                    }
+                    set.extend(c..=end);
+                } else {
+                    set.push(c);
+                }
+            }
+            if negated {
+                set = printable()
+                    .into_iter()
+                    .filter(|p| !set.contains(p))
+                    .collect();
+            }
+            set.sort();
+            set.dedup();
+            if set.is_empty() {
+                return Err(bad_regex(String::from("character class matches nothing")));
+            }
+            return Ok(Node::Set(set));
+        }
+        fn quantified(&mut self, atom: Node) -> Result<Node, Error> {
+            let (min, max) = match self.peek() {
+                Some('?') => (0, 1),
+                Some('*') => (0, MAX_REPEAT),
+                Some('+') => (1, 1 + MAX_REPEAT),
+                Some('{') => return self.bounds(atom),
+                _ => return Ok(atom),
+            };
+            self.position += 1;
+            return Ok(Node::Repeat(Box::new(atom), min, max));
+        }
+        // {n}, {n,m} or {n,}
+        fn bounds(&mut self, atom: Node) -> Result<Node, Error> {
+            let start = self.position + 1;
+            let Some(length) = self.chars[start..].iter().position(|c| *c == '}') else {
+                return Err(bad_regex(String::from("unclosed { in regex")));
+            };
+            let inside: String = self.chars[start..start + length].iter().collect();
+            self.position = start + length + 1;
+            let bad = |_e| bad_regex(format!("bad repetition {{{}}} in regex", inside));
+            let (min, max) = match inside.split_once(',') {
+                Some((min, "")) => {
+                    let min = min.trim().parse::<u32>().map_err(bad)?;
+                    (min, min + MAX_REPEAT)
+                }
+                Some((min, max)) => (
+                    min.trim().parse::<u32>().map_err(bad)?,
+                    max.trim().parse::<u32>().map_err(bad)?,
+                ),
+                None => {
+                    let n = inside.trim().parse::<u32>().map_err(bad)?;
+                    (n, n)
+                }
+            };
+            if max < min {
+                return Err(bad_regex(format!("bad repetition {{{}}} in regex", inside)));
+            }
+            return Ok(Node::Repeat(Box::new(atom), min, max));
+        }
+    }
+
+    // 4th parameter is regex, every string matches it
+    pub struct RegexStrings {
+        node: Node,
+        rng: RNG,
+        version: GeneratorVersion,
+    }
+    impl Default for RegexStrings {
+        fn default() -> Self {
+            return RegexStrings::new();
+        }
+    }
+    impl RegexStrings {
+        pub fn new() -> RegexStrings {
+            return RegexStrings {
+                node: Node::Group(Vec::new()),
+                rng: RNG::new(),
+                version: GeneratorVersion::latest(),
+            };
+        }
+        fn expand(&mut self, node: &Node, result: &mut String) {
+            match node {
+                Node::Literal(c) => result.push(*c),
+                Node::Set(set) => {
+                    let index = self.version.pick_index(&mut self.rng, set.len());
+                    result.push(set[index]);
+                }
+                Node::Group(alternatives) => {
+                    if alternatives.is_empty() {
+                        return;
+                    }
+                    let index = self.version.pick_index(&mut self.rng, alternatives.len());
+                    for node in alternatives[index].iter() {
+                        self.expand(node, result);
+                    }
+                }
+                Node::Repeat(node, min, max) => {
+                    let times = in_range(&mut self.rng, *min as u64, *max as u64);
+                    for _ in 0..times {
+                        self.expand(node, result);
+                    }
+                }
+            }
+        }
+    }
+    impl StringGenerator for RegexStrings {
+        fn get(&mut self) -> String {
+            let mut result = String::new();
+            let node = self.node.clone();
+            self.expand(&node, &mut result);
+            return result;
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.node = RegexParser::parse(&conf.get_next())?;
+            self.version = conf.get_version();
+            self.rng = conf.rng(0);
+            return Ok(());
+        }
+    }
+}
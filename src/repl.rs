@@ -3,10 +3,12 @@ pub mod repl {
         use std::io::stdin;
         //crate modules
         use crate::stringer::run_generator;
+        use crate::stringer::{tr, tr_with};
         use crate::stringer::Config;
         use crate::stringer::Modes;
+        use crate::stringer::parse_number;
         //repl mode
-        println!("Welcome to REPL mode");
+        println!("{}", tr("repl.welcome"));
         let mut conf = Config::default();
         let mut exit = false;
         let mut line = String::new();
@@ -26,7 +28,7 @@ pub mod repl {
                 match run_generator(&conf) {
                     Ok(_result) => {}
                     Err(e) => {
-                        println!("Error:{}", e);
+                        println!("{}", tr_with("cli.error", &[&e.to_string()]));
                         break;
                     }
                 }
@@ -45,7 +47,7 @@ pub mod repl {
                     _ => 2,
                 };
                 if split.len() < required_length {
-                    println!("pass other parameters for this mode");
+                    println!("{}", tr("repl.missing"));
                     line.truncate(0);
                     continue;
                 }
@@ -69,16 +71,20 @@ pub mod repl {
             // number
             if line.trim().contains("num") {
                 let split: Vec<&str> = line.trim().split(" ").collect();
-                let num: u32 = split[1].parse().expect("number isnot?!");
-                conf.set_amount(num);
+                match parse_number("amount", split.get(1).copied().unwrap_or("")) {
+                    Ok(num) => conf.set_amount(num),
+                    Err(e) => println!("{}", tr_with("cli.error", &[&e.to_string()])),
+                }
                 line.truncate(0);
                 continue;
             }
             // length
             if line.trim().contains("len") {
                 let split: Vec<&str> = line.trim().split(" ").collect();
-                let num: u32 = split[1].parse().expect("number isnot?!");
-                conf.set_length(num);
+                match parse_number("length", split.get(1).copied().unwrap_or("")) {
+                    Ok(num) => conf.set_length(num),
+                    Err(e) => println!("{}", tr_with("cli.error", &[&e.to_string()])),
+                }
                 line.truncate(0);
                 continue;
             }
@@ -0,0 +1,322 @@
+pub mod reservations {
+    use std::io::Error;
+    #[cfg(not(feature = "sqlite"))]
+    use std::io::ErrorKind;
+    use std::path::{Path, PathBuf};
+
+    #[cfg(feature = "sqlite")]
+    use crate::stringer::now_millis;
+    use crate::stringer::{iso8601, StringFilter};
+
+    pub const DEFAULT_RESERVATIONS: &str = "reservations.sqlite";
+
+    // 90s, 30m, 12h, 7d or plain seconds, as milliseconds
+    pub fn parse_duration(s: &str) -> Option<u64> {
+        let s = s.trim();
+        let (number, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+            Some(at) => s.split_at(at),
+            None => (s, "s"),
+        };
+        let seconds: u64 = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 3600,
+            "d" => 86400,
+            _ => return None,
+        };
+        let number: u64 = number.parse().ok()?;
+        return number.checked_mul(seconds * 1000);
+    }
+
+    // shared db with options: names.sqlite[:ns=hosts][:owner=alice][:ttl=8h]
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct ReservationSpec {
+        pub path: PathBuf,
+        pub namespace: String,
+        pub owner: String,
+        // lease length in millis, None keeps claims forever
+        pub ttl: Option<u64>,
+    }
+    impl ReservationSpec {
+        pub fn from(s: &str) -> ReservationSpec {
+            let mut spec = ReservationSpec {
+                path: PathBuf::new(),
+                namespace: String::from("default"),
+                owner: default_owner(),
+                ttl: None,
+            };
+            let mut parts: Vec<&str> = s.split(':').collect();
+            // options come last, path may hold ':' itself
+            while parts.len() > 1 {
+                let last = parts[parts.len() - 1];
+                if let Some(namespace) = last.strip_prefix("ns=") {
+                    spec.namespace = String::from(namespace);
+                } else if let Some(owner) = last.strip_prefix("owner=") {
+                    spec.owner = String::from(owner);
+                } else if let Some(ttl) = last.strip_prefix("ttl=") {
+                    spec.ttl = parse_duration(ttl);
+                } else {
+                    break;
+                }
+                parts.pop();
+            }
+            spec.path = PathBuf::from(parts.join(":"));
+            return spec;
+        }
+    }
+
+    // login name of whoever runs generator
+    pub fn default_owner() -> String {
+        for var in ["USER", "USERNAME"] {
+            if let Ok(user) = std::env::var(var) {
+                if !user.is_empty() {
+                    return user;
+                }
+            }
+        }
+        return String::from("unknown");
+    }
+
+    // one claimed name
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct Reservation {
+        pub namespace: String,
+        pub name: String,
+        pub owner: String,
+        pub claimed_at: String,
+        // unix millis when lease runs out and name returns to pool
+        pub expires_at: Option<u64>,
+    }
+    impl Reservation {
+        // claimed_at  owner  name  [until expiry]
+        pub fn line(&self) -> String {
+            let line = format!("{}  {}  {}", self.claimed_at, self.owner, self.name);
+            return match self.expires_at {
+                Some(expires_at) => format!("{}  until {}", line, iso8601(expires_at)),
+                None => line,
+            };
+        }
+    }
+
+    #[cfg(not(feature = "sqlite"))]
+    fn no_database() -> Error {
+        return Error::new(
+            ErrorKind::Unsupported,
+            "reservations need the sqlite feature (cargo build --features sqlite)",
+        );
+    }
+    #[cfg(feature = "sqlite")]
+    fn to_io(e: rusqlite::Error) -> Error {
+        return Error::other(e);
+    }
+
+    // names claimed in shared sqlite file, sqlite locks the file so
+    // claims of concurrent processes never overlap
+    pub struct Reservations {
+        #[cfg(feature = "sqlite")]
+        connection: rusqlite::Connection,
+        namespace: String,
+        owner: String,
+        ttl: Option<u64>,
+    }
+    #[cfg(feature = "sqlite")]
+    impl Reservations {
+        pub fn open(path: &Path, namespace: &str, owner: &str) -> Result<Reservations, Error> {
+            let connection = rusqlite::Connection::open(path).map_err(to_io)?;
+            // other process holding the lock is waited for, not failed on
+            connection
+                .busy_timeout(std::time::Duration::from_secs(10))
+                .map_err(to_io)?;
+            connection
+                .execute_batch(
+                    "CREATE TABLE IF NOT EXISTS reservations (
+                        namespace TEXT NOT NULL, name TEXT NOT NULL, owner TEXT NOT NULL,
+                        claimed_at TEXT NOT NULL, expires_at INTEGER,
+                        PRIMARY KEY (namespace, name));",
+                )
+                .map_err(to_io)?;
+            // files made before leases lack the column
+            if connection
+                .prepare("SELECT expires_at FROM reservations LIMIT 0")
+                .is_err()
+            {
+                connection
+                    .execute_batch("ALTER TABLE reservations ADD COLUMN expires_at INTEGER;")
+                    .map_err(to_io)?;
+            }
+            return Ok(Reservations {
+                connection,
+                namespace: String::from(namespace),
+                owner: String::from(owner),
+                ttl: None,
+            });
+        }
+        // true when name was free (or its lease ran out) and is ours now
+        pub fn claim(&mut self, name: &str) -> Result<bool, Error> {
+            let now = now_millis();
+            let expires_at = self.ttl.map(|ttl| (now + ttl) as i64);
+            // immediate takes write lock up front, so check and insert are one step
+            let transaction = self
+                .connection
+                .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+                .map_err(to_io)?;
+            transaction
+                .execute(
+                    "DELETE FROM reservations WHERE namespace = ?1 AND name = ?2
+                     AND expires_at IS NOT NULL AND expires_at <= ?3",
+                    rusqlite::params![self.namespace, name, now as i64],
+                )
+                .map_err(to_io)?;
+            let inserted = transaction
+                .execute(
+                    "INSERT OR IGNORE INTO reservations (namespace, name, owner, claimed_at, expires_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    rusqlite::params![self.namespace, name, self.owner, iso8601(now), expires_at],
+                )
+                .map_err(to_io)?;
+            transaction.commit().map_err(to_io)?;
+            return Ok(inserted == 1);
+        }
+        // gives own claim back to pool, false when there was none
+        pub fn release(&mut self, name: &str) -> Result<bool, Error> {
+            let deleted = self
+                .connection
+                .execute(
+                    "DELETE FROM reservations WHERE namespace = ?1 AND name = ?2 AND owner = ?3",
+                    rusqlite::params![self.namespace, name, self.owner],
+                )
+                .map_err(to_io)?;
+            return Ok(deleted == 1);
+        }
+        // starts own lease over with current ttl, false when name is not (or no longer) ours
+        pub fn renew(&mut self, name: &str) -> Result<bool, Error> {
+            let now = now_millis();
+            let expires_at = self.ttl.map(|ttl| (now + ttl) as i64);
+            let updated = self
+                .connection
+                .execute(
+                    "UPDATE reservations SET expires_at = ?4 WHERE namespace = ?1 AND name = ?2
+                     AND owner = ?3 AND (expires_at IS NULL OR expires_at > ?5)",
+                    rusqlite::params![self.namespace, name, self.owner, expires_at, now as i64],
+                )
+                .map_err(to_io)?;
+            return Ok(updated == 1);
+        }
+        // removes run out leases of namespace, returns how many
+        pub fn purge_expired(&mut self) -> Result<usize, Error> {
+            let deleted = self
+                .connection
+                .execute(
+                    "DELETE FROM reservations WHERE namespace = ?1
+                     AND expires_at IS NOT NULL AND expires_at <= ?2",
+                    rusqlite::params![self.namespace, now_millis() as i64],
+                )
+                .map_err(to_io)?;
+            return Ok(deleted);
+        }
+        pub fn find(&self, name: &str) -> Result<Option<Reservation>, Error> {
+            let found = self.list()?.into_iter().find(|r| r.name == name);
+            return Ok(found);
+        }
+        // live claims of namespace, oldest first
+        pub fn list(&self) -> Result<Vec<Reservation>, Error> {
+            let mut statement = self
+                .connection
+                .prepare(
+                    "SELECT namespace, name, owner, claimed_at, expires_at FROM reservations
+                     WHERE namespace = ?1 AND (expires_at IS NULL OR expires_at > ?2)
+                     ORDER BY claimed_at, rowid",
+                )
+                .map_err(to_io)?;
+            let rows = statement
+                .query_map(
+                    rusqlite::params![self.namespace, now_millis() as i64],
+                    |row| {
+                        let expires_at: Option<i64> = row.get(4)?;
+                        return Ok(Reservation {
+                            namespace: row.get(0)?,
+                            name: row.get(1)?,
+                            owner: row.get(2)?,
+                            claimed_at: row.get(3)?,
+                            expires_at: expires_at.map(|e| e as u64),
+                        });
+                    },
+                )
+                .map_err(to_io)?;
+            let mut reservations: Vec<Reservation> = Vec::new();
+            for row in rows {
+                reservations.push(row.map_err(to_io)?);
+            }
+            return Ok(reservations);
+        }
+    }
+    #[cfg(not(feature = "sqlite"))]
+    impl Reservations {
+        pub fn open(_path: &Path, _namespace: &str, _owner: &str) -> Result<Reservations, Error> {
+            return Err(no_database());
+        }
+        pub fn claim(&mut self, _name: &str) -> Result<bool, Error> {
+            return Err(no_database());
+        }
+        pub fn release(&mut self, _name: &str) -> Result<bool, Error> {
+            return Err(no_database());
+        }
+        pub fn renew(&mut self, _name: &str) -> Result<bool, Error> {
+            return Err(no_database());
+        }
+        pub fn purge_expired(&mut self) -> Result<usize, Error> {
+            return Err(no_database());
+        }
+        pub fn find(&self, _name: &str) -> Result<Option<Reservation>, Error> {
+            return Err(no_database());
+        }
+        pub fn list(&self) -> Result<Vec<Reservation>, Error> {
+            return Err(no_database());
+        }
+    }
+    impl Reservations {
+        pub fn open_spec(spec: &ReservationSpec) -> Result<Reservations, Error> {
+            let mut reservations = Reservations::open(&spec.path, &spec.namespace, &spec.owner)?;
+            reservations.set_ttl(spec.ttl);
+            return Ok(reservations);
+        }
+        // claims made from now on are leases of this many millis
+        pub fn set_ttl(&mut self, ttl: Option<u64>) {
+            self.ttl = ttl;
+        }
+        pub fn get_namespace(&self) -> String {
+            return self.namespace.clone();
+        }
+        pub fn get_owner(&self) -> String {
+            return self.owner.clone();
+        }
+    }
+
+    // accepts only strings it managed to claim, taken ones are generated again
+    pub struct ReservationFilter {
+        reservations: Reservations,
+        error: Option<Error>,
+    }
+    impl ReservationFilter {
+        pub fn new(reservations: Reservations) -> ReservationFilter {
+            return ReservationFilter {
+                reservations,
+                error: None,
+            };
+        }
+    }
+    impl StringFilter for ReservationFilter {
+        fn accepts(&mut self, value: &str) -> bool {
+            return match self.reservations.claim(value) {
+                Ok(claimed) => claimed,
+                Err(e) => {
+                    self.error = Some(e);
+                    false
+                }
+            };
+        }
+        fn failure(&mut self) -> Option<Error> {
+            return self.error.take();
+        }
+    }
+}
@@ -1,4 +1,6 @@
 pub mod rng {
+    use std::io::Error;
+
     fn nanos() -> u32 {
         use std::time::{SystemTime, UNIX_EPOCH};
         return SystemTime::now()
@@ -12,6 +14,11 @@ pub mod rng {
         y: u32,
         z: u32,
     }
+    impl Default for RNG {
+        fn default() -> Self {
+            return RNG::new();
+        }
+    }
     impl RNG {
         pub fn new() -> RNG {
             RNG {
@@ -29,6 +36,45 @@ pub mod rng {
             self.z = nanos();
         }
 
+        // same seed gives same sequence, state is spread from seed with splitmix64
+        pub fn from_seed(seed: u64) -> RNG {
+            let mut state = seed;
+            let mut next = || {
+                state = state.wrapping_add(0x9E3779B97F4A7C15);
+                let mut z = state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+                return (z ^ (z >> 31)) as u32;
+            };
+            let mut rng = RNG {
+                w: next(),
+                x: next(),
+                y: next(),
+                z: next(),
+            };
+            // xorshift never leaves all zero state
+            if rng.w | rng.x | rng.y | rng.z == 0 {
+                rng.w = 1;
+            }
+            return rng;
+        }
+
+        // clock seeded without seed, else one of independent streams of seed,
+        // so generators of one run (adjectives, nouns..) don't draw same numbers
+        pub fn seeded(seed: Option<u64>, stream: u64) -> RNG {
+            return match seed {
+                Some(seed) => {
+                    let stream = stream.wrapping_mul(0xD1B54A32D192ED03);
+                    RNG::from_seed(seed.wrapping_add(stream))
+                }
+                None => {
+                    let mut rng = RNG::new();
+                    rng.seed();
+                    rng
+                }
+            };
+        }
+
         pub fn get(&mut self) -> u32 {
             let tmp: u32 = self.x ^ (self.x << 15);
             self.x = self.y;
@@ -39,6 +85,57 @@ pub mod rng {
         }
     }
 
+    // where generators get their numbers from
+    pub trait RngBackend {
+        fn get(&mut self) -> u32;
+    }
+    // xorshift, fast and seedable but predictable from few outputs, fine for test data
+    impl RngBackend for RNG {
+        fn get(&mut self) -> u32 {
+            return RNG::get(self);
+        }
+    }
+
+    // os csprng (getrandom, /dev/urandom, BCryptGenRandom), for passwords and tokens
+    pub struct OsEntropy {
+        buffer: [u8; 256],
+        // bytes of buffer already handed out
+        used: usize,
+    }
+    impl OsEntropy {
+        pub fn new() -> Result<OsEntropy, Error> {
+            let mut entropy = OsEntropy {
+                buffer: [0; 256],
+                used: 0,
+            };
+            entropy.refill()?;
+            return Ok(entropy);
+        }
+        fn refill(&mut self) -> Result<(), Error> {
+            getrandom::getrandom(&mut self.buffer).map_err(|e| Error::other(e.to_string()))?;
+            self.used = 0;
+            return Ok(());
+        }
+    }
+    impl RngBackend for OsEntropy {
+        fn get(&mut self) -> u32 {
+            if self.used + 4 > self.buffer.len() {
+                // source worked when opened, os doesn't take it away during run
+                self.refill().expect("os random source failed");
+            }
+            let bytes = [
+                self.buffer[self.used],
+                self.buffer[self.used + 1],
+                self.buffer[self.used + 2],
+                self.buffer[self.used + 3],
+            ];
+            // handed out numbers don't stay in memory
+            self.buffer[self.used..self.used + 4].fill(0);
+            self.used += 4;
+            return u32::from_le_bytes(bytes);
+        }
+    }
+
     pub struct RNGWheel {
         rng: RNG,
         len: usize,
@@ -50,6 +147,10 @@ pub mod rng {
             rng.seed();
             return RNGWheel { rng, len, index: 0 };
         }
+        pub fn from_seed(len: usize, seed: u64) -> RNGWheel {
+            let rng = RNG::from_seed(seed);
+            return RNGWheel { rng, len, index: 0 };
+        }
     }
     impl Iterator for RNGWheel {
         type Item = u32;
@@ -0,0 +1,96 @@
+pub mod secrets {
+    use std::io::{stdin, stdout, BufRead, Error, ErrorKind, IsTerminal, Write};
+    use std::sync::atomic::{compiler_fence, Ordering};
+
+    use crate::stringer::{tr, tr_with};
+
+    // overwrites bytes so compiler can't drop the writes as dead stores
+    pub fn wipe_bytes(bytes: &mut [u8]) {
+        for byte in bytes.iter_mut() {
+            unsafe {
+                std::ptr::write_volatile(byte, 0);
+            }
+        }
+        compiler_fence(Ordering::SeqCst);
+    }
+
+    // zeroes string in place and empties it, capacity stays for reuse
+    pub fn wipe(s: &mut String) {
+        // all zero bytes are valid utf-8
+        unsafe {
+            wipe_bytes(s.as_bytes_mut());
+        }
+        s.clear();
+    }
+
+    pub fn wipe_all(strings: &mut Vec<String>) {
+        for s in strings.iter_mut() {
+            wipe(s);
+        }
+        strings.clear();
+    }
+
+    // set by ci services, there is nobody to confirm and log is kept
+    const CI_VARS: [&str; 7] = [
+        "CI",
+        "GITHUB_ACTIONS",
+        "GITLAB_CI",
+        "BUILDKITE",
+        "JENKINS_URL",
+        "TF_BUILD",
+        "TEAMCITY_VERSION",
+    ];
+    // set by terminal recorders
+    const RECORDER_VARS: [&str; 2] = ["ASCIINEMA_REC", "VHS_RECORD"];
+
+    fn env_set(var: &str) -> bool {
+        return std::env::var_os(var).is_some_and(|v| !v.is_empty() && v != "0" && v != "false");
+    }
+
+    pub fn ci_detected() -> bool {
+        return CI_VARS.iter().any(|v| env_set(v));
+    }
+
+    // variable telling terminal session is being recorded
+    pub fn recording_hint() -> Option<&'static str> {
+        return RECORDER_VARS.iter().find(|v| env_set(v)).copied();
+    }
+
+    // warning in red unless NO_COLOR asks otherwise (no-color.org)
+    fn warning(text: &str) -> String {
+        if env_set("NO_COLOR") || !stdout().is_terminal() {
+            return String::from(text);
+        }
+        return format!("\x1b[31m{}\x1b[0m", text);
+    }
+
+    // before secrets go to screen: refused in ci, asked for on recorded terminal
+    pub fn confirm_display() -> Result<(), Error> {
+        if ci_detected() {
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
+                "secrets would end up in ci log, write them to file or encrypted output instead",
+            ));
+        }
+        if !stdout().is_terminal() {
+            // piped somewhere, not displayed
+            return Ok(());
+        }
+        let hint = match recording_hint() {
+            Some(hint) => hint,
+            None => return Ok(()),
+        };
+        println!("{}", warning(&tr_with("secrets.recorded", &[hint])));
+        print!("{}", tr("secrets.confirm"));
+        stdout().flush()?;
+        let mut answer = String::new();
+        stdin().lock().read_line(&mut answer)?;
+        if matches!(answer.trim(), "y" | "Y" | "yes") {
+            return Ok(());
+        }
+        return Err(Error::new(
+            ErrorKind::Interrupted,
+            "display of secrets cancelled",
+        ));
+    }
+}
@@ -0,0 +1,1144 @@
+pub mod sinks {
+    use std::fs::{File, OpenOptions};
+    use std::io::{BufWriter, Error, ErrorKind, Read, Stdout, Write};
+    use std::net::TcpStream;
+    #[cfg(unix)]
+    use std::os::unix::net::UnixStream;
+    use std::path::{Path, PathBuf};
+    #[cfg(unix)]
+    use std::sync::mpsc::{sync_channel, SyncSender, TrySendError};
+    use std::thread::sleep;
+    #[cfg(unix)]
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+
+    use crate::stringer::{
+        encryption_available, json_escape, read_passphrase, sidecar, tr_with, wipe, wipe_all,
+        ClipboardSink, Config, EncryptedFileSink, HistorySink, Languages, Wrap,
+    };
+
+    // one generated string with what downstream outputs may want to know about it
+    #[derive(Clone)]
+    pub struct Record {
+        pub index: u32,
+        pub value: String,
+        pub mode: String,
+        // which profile or slot produced value, empty when labels are off
+        pub label: String,
+        // named values generator attached to this string
+        pub fields: Vec<(String, String)>,
+        // noisy copy of value written next to it, see pairs=
+        pub pair: Option<String>,
+    }
+    impl Record {
+        pub fn new(index: u32, value: String, mode: String) -> Record {
+            let label = String::new();
+            return Record {
+                index,
+                value,
+                mode,
+                label,
+                fields: Vec::new(),
+                pair: None,
+            };
+        }
+        pub fn with_label(mut self, label: String) -> Record {
+            self.label = label;
+            return self;
+        }
+        pub fn with_fields(mut self, fields: Vec<(String, String)>) -> Record {
+            self.fields = fields;
+            return self;
+        }
+        // noisy copy is also noisy column of csv and jsonl outputs
+        pub fn with_pair(mut self, pair: String) -> Record {
+            self.fields.push((String::from("noisy"), pair.clone()));
+            self.pair = Some(pair);
+            return self;
+        }
+        // line for plain text outputs, noisy copy and label go after tabs
+        pub fn text(&self, wrap: Wrap) -> String {
+            let mut value = wrap.apply(&self.value);
+            if let Some(pair) = self.pair.as_ref() {
+                value = format!("{}\t{}", value, wrap.apply(pair));
+            }
+            if self.label.is_empty() {
+                return value;
+            }
+            return format!("{}\t{}", value, self.label);
+        }
+    }
+
+    // destination of generated strings, implement it to plug own outputs into run_generator_with
+    pub trait OutputSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error>;
+        // called once after last record
+        fn finish(&mut self) -> Result<(), Error> {
+            return Ok(());
+        }
+    }
+
+    // buffered, so big runs don't pay write call per line
+    pub struct StdoutSink {
+        writer: BufWriter<Stdout>,
+        write_indices: bool,
+        wrap: Wrap,
+    }
+    impl StdoutSink {
+        pub fn new(write_indices: bool, wrap: Wrap) -> StdoutSink {
+            return StdoutSink {
+                writer: BufWriter::new(std::io::stdout()),
+                write_indices,
+                wrap,
+            };
+        }
+    }
+    impl OutputSink for StdoutSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            let value = record.text(self.wrap);
+            let strong = if self.write_indices {
+                format!("{}:{}\n", value, record.index)
+            } else {
+                format!("{}\n", value)
+            };
+            writeln!(self.writer, "{}", strong)?;
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            return self.writer.flush();
+        }
+    }
+
+    // file of output, appended to or truncated; error names path instead of bare os message
+    pub fn open_output(path: &Path, append: bool) -> Result<File, Error> {
+        let mut options = OpenOptions::new();
+        options.create(true);
+        if append {
+            options.append(true);
+        } else {
+            options.write(true).truncate(true);
+        }
+        return options.open(path).map_err(|e| {
+            let shown = path.display().to_string();
+            let message = tr_with("sink.cannot_write", &[&shown, &e.to_string()]);
+            return Error::new(e.kind(), message);
+        });
+    }
+
+    // plain text, one string per line
+    pub struct FileSink {
+        writer: BufWriter<File>,
+        wrap: Wrap,
+    }
+    impl FileSink {
+        pub fn create(path: &Path, wrap: Wrap, append: bool) -> Result<FileSink, Error> {
+            let writer = BufWriter::new(open_output(path, append)?);
+            return Ok(FileSink { writer, wrap });
+        }
+    }
+    impl OutputSink for FileSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            writeln!(self.writer, "{}", record.text(self.wrap))?;
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            return self.writer.flush();
+        }
+    }
+
+    // rfc 4180 field, quoted only when needed
+    pub fn csv_field(s: &str) -> String {
+        if s.contains(['"', ',', '\n', '\r']) {
+            return format!("\"{}\"", s.replace('"', "\"\""));
+        }
+        return String::from(s);
+    }
+
+    pub struct CsvSink {
+        writer: BufWriter<File>,
+        wrap: Wrap,
+        labels: bool,
+        header: bool,
+    }
+    impl CsvSink {
+        // labels adds label column, file appended to keeps header it has
+        pub fn create(
+            path: &Path,
+            wrap: Wrap,
+            labels: bool,
+            append: bool,
+        ) -> Result<CsvSink, Error> {
+            let file = open_output(path, append)?;
+            let header = append && file.metadata()?.len() > 0;
+            return Ok(CsvSink {
+                writer: BufWriter::new(file),
+                wrap,
+                labels,
+                header,
+            });
+        }
+        // written with first record, its fields become extra columns
+        fn write_header(&mut self, record: &Record) -> Result<(), Error> {
+            write!(self.writer, "index,value,mode")?;
+            if self.labels {
+                write!(self.writer, ",label")?;
+            }
+            for (name, _value) in record.fields.iter() {
+                write!(self.writer, ",{}", csv_field(name))?;
+            }
+            writeln!(self.writer)?;
+            self.header = true;
+            return Ok(());
+        }
+    }
+    impl OutputSink for CsvSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            if !self.header {
+                self.write_header(record)?;
+            }
+            let value = self.wrap.apply(&record.value);
+            write!(
+                self.writer,
+                "{},{},{}",
+                record.index,
+                csv_field(&value),
+                csv_field(&record.mode)
+            )?;
+            if self.labels {
+                write!(self.writer, ",{}", csv_field(&record.label))?;
+            }
+            for (_name, value) in record.fields.iter() {
+                write!(self.writer, ",{}", csv_field(value))?;
+            }
+            writeln!(self.writer)?;
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            return self.writer.flush();
+        }
+    }
+
+    pub struct JsonlSink {
+        writer: BufWriter<File>,
+        wrap: Wrap,
+    }
+    impl JsonlSink {
+        pub fn create(path: &Path, wrap: Wrap, append: bool) -> Result<JsonlSink, Error> {
+            let writer = BufWriter::new(open_output(path, append)?);
+            return Ok(JsonlSink { writer, wrap });
+        }
+    }
+    impl OutputSink for JsonlSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            let value = self.wrap.apply(&record.value);
+            write!(
+                self.writer,
+                "{{\"index\":{},\"value\":\"{}\",\"mode\":\"{}\"",
+                record.index,
+                json_escape(&value),
+                json_escape(&record.mode)
+            )?;
+            if !record.label.is_empty() {
+                write!(self.writer, ",\"label\":\"{}\"", json_escape(&record.label))?;
+            }
+            for (name, value) in record.fields.iter() {
+                write!(
+                    self.writer,
+                    ",\"{}\":\"{}\"",
+                    json_escape(name),
+                    json_escape(value)
+                )?;
+            }
+            writeln!(self.writer, "}}")?;
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            return self.writer.flush();
+        }
+    }
+
+    // how stdout and text file outputs write records, other kinds keep their own format
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum OutputFormat {
+        Plain,
+        // one json array of objects
+        Json,
+        Jsonl,
+        Csv,
+        Tsv,
+    }
+    impl OutputFormat {
+        pub fn from(s: &str) -> Option<OutputFormat> {
+            return match s {
+                "plain" | "text" => Some(OutputFormat::Plain),
+                "json" => Some(OutputFormat::Json),
+                "jsonl" => Some(OutputFormat::Jsonl),
+                "csv" => Some(OutputFormat::Csv),
+                "tsv" => Some(OutputFormat::Tsv),
+                _ => None,
+            };
+        }
+    }
+
+    // what structured formats can say about every string
+    pub const FORMAT_FIELDS: [&str; 5] = ["index", "value", "mode", "lang", "label"];
+
+    // format[:field,field..], fields in order of output, index, value, mode and lang by default
+    pub fn parse_format(s: &str) -> Result<(OutputFormat, Vec<String>), Error> {
+        let (name, fields) = match s.split_once(':') {
+            Some((name, fields)) => (name, fields.split(',').map(|f| f.trim()).collect()),
+            None => (s, vec!["index", "value", "mode", "lang"]),
+        };
+        let Some(format) = OutputFormat::from(name.trim()) else {
+            let message = format!(
+                "unknown format {}, expected plain, json, jsonl, csv or tsv",
+                name
+            );
+            return Err(Error::new(ErrorKind::InvalidInput, message));
+        };
+        if let Some(field) = fields.iter().find(|f| !FORMAT_FIELDS.contains(f)) {
+            let message = format!(
+                "unknown format field {}, expected {}",
+                field,
+                FORMAT_FIELDS.join(", ")
+            );
+            return Err(Error::new(ErrorKind::InvalidInput, message));
+        }
+        return Ok((format, fields.into_iter().map(String::from).collect()));
+    }
+
+    // tsv has no quoting, tabs and line breaks in cell become spaces
+    pub fn tsv_field(s: &str) -> String {
+        return s.replace(['\t', '\n', '\r'], " ");
+    }
+
+    // records as json, json lines, csv or tsv with chosen fields, then fields generator attached
+    pub struct FormattedSink {
+        writer: Box<dyn Write>,
+        format: OutputFormat,
+        fields: Vec<String>,
+        // language of word lists, empty for modes without them
+        language: String,
+        wrap: Wrap,
+        written: usize,
+    }
+    impl FormattedSink {
+        pub fn new(
+            writer: Box<dyn Write>,
+            format: OutputFormat,
+            fields: Vec<String>,
+            language: String,
+            wrap: Wrap,
+        ) -> FormattedSink {
+            return FormattedSink {
+                writer,
+                format,
+                fields,
+                language,
+                wrap,
+                written: 0,
+            };
+        }
+        fn cell(&self, field: &str, record: &Record) -> String {
+            return match field {
+                "index" => record.index.to_string(),
+                "value" => self.wrap.apply(&record.value),
+                "mode" => record.mode.clone(),
+                "lang" => self.language.clone(),
+                "label" => record.label.clone(),
+                _ => String::new(),
+            };
+        }
+        // csv or tsv line, header of field names goes before first one
+        fn write_row(&mut self, record: &Record) -> Result<(), Error> {
+            let (field, separator): (fn(&str) -> String, &str) = match self.format {
+                OutputFormat::Tsv => (tsv_field, "\t"),
+                _ => (csv_field, ","),
+            };
+            if self.written == 0 {
+                let mut header: Vec<String> = self.fields.clone();
+                header.extend(record.fields.iter().map(|(name, _v)| name.clone()));
+                let header: Vec<String> = header.iter().map(|h| field(h)).collect();
+                writeln!(self.writer, "{}", header.join(separator))?;
+            }
+            let mut row: Vec<String> = self.fields.iter().map(|f| self.cell(f, record)).collect();
+            row.extend(record.fields.iter().map(|(_name, value)| value.clone()));
+            let row: Vec<String> = row.iter().map(|c| field(c)).collect();
+            writeln!(self.writer, "{}", row.join(separator))?;
+            return Ok(());
+        }
+        // empty language and label are left out
+        fn json_object(&self, record: &Record) -> String {
+            let mut members: Vec<String> = Vec::new();
+            for field in self.fields.iter() {
+                let cell = self.cell(field, record);
+                if field == "index" {
+                    members.push(format!("\"index\":{}", cell));
+                } else if !cell.is_empty() || (field != "lang" && field != "label") {
+                    members.push(format!("\"{}\":\"{}\"", field, json_escape(&cell)));
+                }
+            }
+            for (name, value) in record.fields.iter() {
+                members.push(format!(
+                    "\"{}\":\"{}\"",
+                    json_escape(name),
+                    json_escape(value)
+                ));
+            }
+            return format!("{{{}}}", members.join(","));
+        }
+    }
+    impl OutputSink for FormattedSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            match self.format {
+                OutputFormat::Plain => writeln!(self.writer, "{}", record.text(self.wrap))?,
+                OutputFormat::Jsonl => writeln!(self.writer, "{}", self.json_object(record))?,
+                OutputFormat::Json => {
+                    let separator = if self.written == 0 { "[\n" } else { ",\n" };
+                    write!(self.writer, "{}{}", separator, self.json_object(record))?;
+                }
+                OutputFormat::Csv | OutputFormat::Tsv => self.write_row(record)?,
+            }
+            self.written += 1;
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            if self.format == OutputFormat::Json {
+                let end = if self.written == 0 { "[]\n" } else { "\n]\n" };
+                write!(self.writer, "{}", end)?;
+            }
+            return self.writer.flush();
+        }
+    }
+
+    // cell cut or padded to exactly width characters
+    pub fn fixed_cell(s: &str, width: usize, right: bool) -> String {
+        let cut: String = s.chars().take(width).collect();
+        if right {
+            return format!("{:>width$}", cut, width = width);
+        }
+        return format!("{:<width$}", cut, width = width);
+    }
+
+    // aligned plain text: index right aligned, value, mode, label and fields left aligned,
+    // columns separated by one space
+    pub struct FixedWidthSink {
+        writer: BufWriter<File>,
+        wrap: Wrap,
+        labels: bool,
+        // width per column in order above, missing or 0 is width of longest cell
+        widths: Vec<usize>,
+        // rows held back until finish when some width comes from cells
+        rows: Vec<Vec<String>>,
+        streaming: Option<bool>,
+    }
+    impl FixedWidthSink {
+        pub fn create(
+            path: &Path,
+            wrap: Wrap,
+            labels: bool,
+            widths: Vec<usize>,
+            append: bool,
+        ) -> Result<FixedWidthSink, Error> {
+            let writer = BufWriter::new(open_output(path, append)?);
+            return Ok(FixedWidthSink {
+                writer,
+                wrap,
+                labels,
+                widths,
+                rows: Vec::new(),
+                streaming: None,
+            });
+        }
+        fn write_row(&mut self, row: &[String], widths: &[usize]) -> Result<(), Error> {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths.iter())
+                .enumerate()
+                .map(|(i, (cell, width))| fixed_cell(cell, *width, i == 0))
+                .collect();
+            writeln!(self.writer, "{}", cells.join(" "))?;
+            return Ok(());
+        }
+    }
+    impl OutputSink for FixedWidthSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            let mut row = vec![
+                record.index.to_string(),
+                self.wrap.apply(&record.value),
+                record.mode.clone(),
+            ];
+            if self.labels {
+                row.push(record.label.clone());
+            }
+            for (_name, value) in record.fields.iter() {
+                row.push(value.clone());
+            }
+            // first record tells how many columns there are
+            let streaming = *self.streaming.get_or_insert_with(|| {
+                return row.len() <= self.widths.len() && self.widths.iter().all(|w| *w > 0);
+            });
+            if streaming {
+                let widths = self.widths.clone();
+                self.write_row(&row, &widths)?;
+                wipe_all(&mut row);
+                return Ok(());
+            }
+            self.rows.push(row);
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            let columns = self.rows.iter().map(|row| row.len()).max().unwrap_or(0);
+            let mut widths: Vec<usize> = Vec::with_capacity(columns);
+            for column in 0..columns {
+                let width = match self.widths.get(column) {
+                    Some(width) if *width > 0 => *width,
+                    _ => self
+                        .rows
+                        .iter()
+                        .filter_map(|row| row.get(column))
+                        .map(|cell| cell.chars().count())
+                        .max()
+                        .unwrap_or(0),
+                };
+                widths.push(width);
+            }
+            let mut rows = std::mem::take(&mut self.rows);
+            for row in rows.iter_mut() {
+                self.write_row(row, &widths)?;
+                wipe_all(row);
+            }
+            return self.writer.flush();
+        }
+    }
+
+    // rows go to table strings(idx, value, mode, label), whole run is one transaction
+    #[cfg(feature = "sqlite")]
+    pub struct SqliteSink {
+        connection: rusqlite::Connection,
+        wrap: Wrap,
+    }
+    #[cfg(feature = "sqlite")]
+    impl SqliteSink {
+        fn to_io(e: rusqlite::Error) -> Error {
+            return Error::other(e);
+        }
+        pub fn create(path: &Path, wrap: Wrap) -> Result<SqliteSink, Error> {
+            let connection = rusqlite::Connection::open(path).map_err(SqliteSink::to_io)?;
+            connection
+                .execute_batch(
+                    "CREATE TABLE IF NOT EXISTS strings (idx INTEGER, value TEXT, mode TEXT, label TEXT); BEGIN;",
+                )
+                .map_err(SqliteSink::to_io)?;
+            return Ok(SqliteSink { connection, wrap });
+        }
+    }
+    #[cfg(feature = "sqlite")]
+    impl OutputSink for SqliteSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            let value = self.wrap.apply(&record.value);
+            self.connection
+                .execute(
+                    "INSERT INTO strings (idx, value, mode, label) VALUES (?1, ?2, ?3, ?4)",
+                    rusqlite::params![record.index, value, record.mode, record.label],
+                )
+                .map_err(SqliteSink::to_io)?;
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            self.connection
+                .execute_batch("COMMIT;")
+                .map_err(SqliteSink::to_io)?;
+            return Ok(());
+        }
+    }
+
+    // posts batches of strings as json arrays, plain http only,
+    // labelled strings are sent as {"value":..,"label":..}
+    pub struct HttpPostSink {
+        host: String,
+        port: u16,
+        path: String,
+        batch_size: usize,
+        retries: u32,
+        batch: Vec<String>,
+        wrap: Wrap,
+    }
+    impl HttpPostSink {
+        // http://host[:port]/path
+        pub fn new(url: &str, batch_size: usize, wrap: Wrap) -> Result<HttpPostSink, Error> {
+            let rest = match url.strip_prefix("http://") {
+                Some(rest) => rest,
+                None => {
+                    let message = format!("only http:// urls are supported: {}", url);
+                    return Err(Error::new(ErrorKind::InvalidInput, message));
+                }
+            };
+            let (authority, path) = match rest.find('/') {
+                Some(i) => (&rest[..i], &rest[i..]),
+                None => (rest, "/"),
+            };
+            let (host, port) = match authority.rsplit_once(':') {
+                Some((host, port)) => match port.parse() {
+                    Ok(port) => (host, port),
+                    Err(_e) => {
+                        let message = format!("bad port in url: {}", url);
+                        return Err(Error::new(ErrorKind::InvalidInput, message));
+                    }
+                },
+                None => (authority, 80),
+            };
+            return Ok(HttpPostSink {
+                host: String::from(host),
+                port,
+                path: String::from(path),
+                batch_size: batch_size.max(1),
+                retries: 5,
+                batch: Vec::new(),
+                wrap,
+            });
+        }
+        pub fn set_retries(&mut self, retries: u32) {
+            self.retries = retries;
+        }
+        // one attempt, returns http status code
+        fn post(&self, body: &str) -> Result<u32, Error> {
+            let mut stream = TcpStream::connect((self.host.as_ref(), self.port))?;
+            stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+            let request = format!(
+                "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                self.path,
+                self.host,
+                body.len(),
+                body
+            );
+            stream.write_all(request.as_bytes())?;
+            let mut response = String::new();
+            stream.read_to_string(&mut response)?;
+            // HTTP/1.1 200 OK
+            let status = response
+                .split(' ')
+                .nth(1)
+                .and_then(|code| code.parse().ok())
+                .unwrap_or(0);
+            return Ok(status);
+        }
+        fn flush_batch(&mut self) -> Result<(), Error> {
+            if self.batch.is_empty() {
+                return Ok(());
+            }
+            // items are json already
+            let mut body = format!("[{}]", self.batch.join(","));
+            let mut delay = Duration::from_millis(200);
+            let mut attempt = 0;
+            loop {
+                let failure = match self.post(&body) {
+                    Ok(status) if (200..300).contains(&status) => {
+                        wipe_all(&mut self.batch);
+                        wipe(&mut body);
+                        return Ok(());
+                    }
+                    Ok(status) => format!("server answered {}", status),
+                    Err(e) => e.to_string(),
+                };
+                attempt += 1;
+                if attempt > self.retries {
+                    let message = format!(
+                        "posting batch failed after {} attempts: {}",
+                        attempt, failure
+                    );
+                    return Err(Error::other(message));
+                }
+                // exponential backoff
+                sleep(delay);
+                delay *= 2;
+            }
+        }
+    }
+    impl OutputSink for HttpPostSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            let value = format!("\"{}\"", json_escape(&self.wrap.apply(&record.value)));
+            if record.label.is_empty() {
+                self.batch.push(value);
+            } else {
+                let label = json_escape(&record.label);
+                let item = format!("{{\"value\":{},\"label\":\"{}\"}}", value, label);
+                self.batch.push(item);
+            }
+            if self.batch.len() >= self.batch_size {
+                self.flush_batch()?;
+            }
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            return self.flush_batch();
+        }
+    }
+
+    // kafka producer, the generated string is message key too,
+    // so equal strings always land in the same partition
+    #[cfg(feature = "kafka")]
+    pub struct KafkaSink {
+        producer: kafka::producer::Producer,
+        topic: String,
+        batch_size: usize,
+        batch: Vec<String>,
+        wrap: Wrap,
+    }
+    #[cfg(feature = "kafka")]
+    impl KafkaSink {
+        fn to_io(e: kafka::Error) -> Error {
+            return Error::other(e);
+        }
+        // host:port[,host:port]/topic
+        pub fn connect(target: &str, batch_size: usize, wrap: Wrap) -> Result<KafkaSink, Error> {
+            let (hosts, topic) = match target.split_once('/') {
+                Some((hosts, topic)) if !topic.is_empty() => (hosts, topic),
+                _ => {
+                    let message = format!("kafka output needs host:port/topic, got: {}", target);
+                    return Err(Error::new(ErrorKind::InvalidInput, message));
+                }
+            };
+            let hosts: Vec<String> = hosts.split(',').map(String::from).collect();
+            let producer = kafka::producer::Producer::from_hosts(hosts)
+                .with_ack_timeout(Duration::from_secs(5))
+                .with_required_acks(kafka::producer::RequiredAcks::One)
+                .create()
+                .map_err(KafkaSink::to_io)?;
+            return Ok(KafkaSink {
+                producer,
+                topic: String::from(topic),
+                batch_size: batch_size.max(1),
+                batch: Vec::new(),
+                wrap,
+            });
+        }
+        fn flush_batch(&mut self) -> Result<(), Error> {
+            use kafka::producer::Record as KafkaRecord;
+            if self.batch.is_empty() {
+                return Ok(());
+            }
+            let records: Vec<KafkaRecord<&str, &str>> = self
+                .batch
+                .iter()
+                .map(|value| {
+                    KafkaRecord::from_key_value(&self.topic, value.as_ref(), value.as_ref())
+                })
+                .collect();
+            let confirms = self.producer.send_all(&records).map_err(KafkaSink::to_io)?;
+            for confirm in confirms.iter() {
+                for partition in confirm.partition_confirms.iter() {
+                    if let Err(code) = partition.offset {
+                        let message = format!(
+                            "kafka rejected batch on {}/{}: {:?}",
+                            confirm.topic, partition.partition, code
+                        );
+                        return Err(Error::other(message));
+                    }
+                }
+            }
+            wipe_all(&mut self.batch);
+            return Ok(());
+        }
+    }
+    #[cfg(feature = "kafka")]
+    impl OutputSink for KafkaSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            self.batch.push(self.wrap.apply(&record.value));
+            if self.batch.len() >= self.batch_size {
+                self.flush_batch()?;
+            }
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            return self.flush_batch();
+        }
+    }
+
+    // what to do with strings when local reader can't keep up
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum SlowReader {
+        // generation waits for reader
+        Block,
+        // strings that don't fit into buffer are thrown away and counted
+        Drop,
+    }
+    impl SlowReader {
+        pub fn from(s: &str) -> SlowReader {
+            return match s {
+                "drop" => SlowReader::Drop,
+                _ => SlowReader::Block,
+            };
+        }
+    }
+
+    // lines buffered between generator and reader
+    pub const STREAM_BUFFER: usize = 1024;
+
+    // fifo or unix socket, lines are handed to writer thread through bounded channel
+    #[cfg(unix)]
+    pub struct StreamSink {
+        sender: Option<SyncSender<String>>,
+        writer: Option<JoinHandle<Result<(), Error>>>,
+        policy: SlowReader,
+        dropped: u64,
+        wrap: Wrap,
+    }
+    #[cfg(unix)]
+    impl StreamSink {
+        fn start<F>(connect: F, policy: SlowReader, wrap: Wrap) -> StreamSink
+        where
+            F: FnOnce() -> Result<Box<dyn Write + Send>, Error> + Send + 'static,
+        {
+            let (sender, receiver) = sync_channel::<String>(STREAM_BUFFER);
+            // opening fifo blocks until someone reads it, so it happens on writer thread
+            let writer = thread::spawn(move || {
+                let mut out = BufWriter::new(connect()?);
+                for line in receiver.iter() {
+                    out.write_all(line.as_bytes())?;
+                    // whatever is queued goes out together, then reader sees it right away
+                    while let Ok(more) = receiver.try_recv() {
+                        out.write_all(more.as_bytes())?;
+                    }
+                    out.flush()?;
+                }
+                return out.flush();
+            });
+            return StreamSink {
+                sender: Some(sender),
+                writer: Some(writer),
+                policy,
+                dropped: 0,
+                wrap,
+            };
+        }
+        // named pipe, created with mkfifo beforehand
+        pub fn fifo(path: &Path, policy: SlowReader, wrap: Wrap) -> StreamSink {
+            let path = path.to_path_buf();
+            let connect = move || -> Result<Box<dyn Write + Send>, Error> {
+                return Ok(Box::new(File::options().write(true).open(&path)?));
+            };
+            return StreamSink::start(connect, policy, wrap);
+        }
+        // connects to listening unix domain socket
+        pub fn socket(path: &Path, policy: SlowReader, wrap: Wrap) -> StreamSink {
+            let path = path.to_path_buf();
+            let connect = move || -> Result<Box<dyn Write + Send>, Error> {
+                return Ok(Box::new(UnixStream::connect(&path)?));
+            };
+            return StreamSink::start(connect, policy, wrap);
+        }
+        pub fn get_dropped(&self) -> u64 {
+            return self.dropped;
+        }
+        // writer thread stopped, its error says why
+        fn writer_error(&mut self) -> Error {
+            self.sender = None;
+            return match self.writer.take().map(|writer| writer.join()) {
+                Some(Ok(Err(e))) => e,
+                _ => Error::new(ErrorKind::BrokenPipe, "reader went away"),
+            };
+        }
+    }
+    #[cfg(unix)]
+    impl OutputSink for StreamSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            let line = format!("{}\n", record.text(self.wrap));
+            let sender = match &self.sender {
+                Some(sender) => sender,
+                None => return Err(Error::new(ErrorKind::BrokenPipe, "output closed")),
+            };
+            let sent = match self.policy {
+                SlowReader::Block => sender.send(line).is_ok(),
+                SlowReader::Drop => match sender.try_send(line) {
+                    Ok(()) => true,
+                    Err(TrySendError::Full(_line)) => {
+                        self.dropped += 1;
+                        true
+                    }
+                    Err(TrySendError::Disconnected(_line)) => false,
+                },
+            };
+            if !sent {
+                return Err(self.writer_error());
+            }
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            // closing channel lets writer drain and exit
+            self.sender = None;
+            if let Some(writer) = self.writer.take() {
+                match writer.join() {
+                    Ok(result) => result?,
+                    Err(_panic) => return Err(Error::other("output writer panicked")),
+                }
+            }
+            if self.dropped > 0 {
+                eprintln!("{}", tr_with("sink.dropped", &[&self.dropped.to_string()]));
+            }
+            return Ok(());
+        }
+    }
+
+    // holds records back and passes them on grouped by label, order inside group is kept
+    pub struct GroupingSink {
+        inner: Box<dyn OutputSink>,
+        records: Vec<Record>,
+    }
+    impl GroupingSink {
+        pub fn new(inner: Box<dyn OutputSink>) -> GroupingSink {
+            return GroupingSink {
+                inner,
+                records: Vec::new(),
+            };
+        }
+    }
+    impl OutputSink for GroupingSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            self.records.push(record.clone());
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            // stable sort, groups come in order of first appearance
+            let mut order: Vec<String> = Vec::new();
+            for record in self.records.iter() {
+                if !order.contains(&record.label) {
+                    order.push(record.label.clone());
+                }
+            }
+            for label in order.iter() {
+                for record in self.records.iter().filter(|r| &r.label == label) {
+                    self.inner.write(record)?;
+                }
+            }
+            for record in self.records.iter_mut() {
+                wipe(&mut record.value);
+            }
+            self.records.clear();
+            return self.inner.finish();
+        }
+    }
+
+    // same records to several outputs, e.g. file and stdout
+    pub struct TeeSink {
+        sinks: Vec<Box<dyn OutputSink>>,
+    }
+    impl TeeSink {
+        pub fn new(sinks: Vec<Box<dyn OutputSink>>) -> TeeSink {
+            return TeeSink { sinks };
+        }
+    }
+    impl OutputSink for TeeSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            for sink in self.sinks.iter_mut() {
+                sink.write(record)?;
+            }
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            // every output gets finished, first error is reported
+            let mut result = Ok(());
+            for sink in self.sinks.iter_mut() {
+                let finished = sink.finish();
+                if result.is_ok() {
+                    result = finished;
+                }
+            }
+            return result;
+        }
+    }
+
+    const SINK_KINDS: [&str; 13] = [
+        "stdout", "file", "csv", "jsonl", "sqlite", "http", "https", "kafka", "fifo", "unix",
+        "enc", "clip", "fixed",
+    ];
+
+    // --out value to sink spec: - is stdout, kind[:path] stays, anything else is text file path
+    pub fn out_spec(value: &str) -> String {
+        if value == "-" {
+            return String::from("stdout");
+        }
+        let kind = value.split_once(':').map(|(k, _p)| k).unwrap_or(value);
+        if SINK_KINDS.contains(&kind) {
+            return String::from(value);
+        }
+        return format!("file:{}", value);
+    }
+
+    pub fn open_sinks(specs: &[String], conf: &Config) -> Result<Box<dyn OutputSink>, Error> {
+        let mut sinks: Vec<Box<dyn OutputSink>> = Vec::new();
+        for spec in specs.iter() {
+            sinks.push(open_sink(spec, conf)?);
+        }
+        // history is one more output
+        if !conf.get_history().is_empty() {
+            let path = PathBuf::from(conf.get_history());
+            let mut history = HistorySink::open(&path)?;
+            history.set_redact(conf.get_mode().is_secret());
+            sinks.push(Box::new(history));
+        }
+        let sink = if sinks.len() == 1 {
+            sinks.remove(0)
+        } else {
+            Box::new(TeeSink::new(sinks))
+        };
+        if conf.get_group() {
+            return Ok(Box::new(GroupingSink::new(sink)));
+        }
+        return Ok(sink);
+    }
+
+    // kind[:path], e.g. csv or jsonl:out.jsonl
+    pub fn open_sink(spec: &str, conf: &Config) -> Result<Box<dyn OutputSink>, Error> {
+        let (kind, path) = match spec.split_once(':') {
+            Some((kind, path)) => (kind, Some(PathBuf::from(path))),
+            None => (spec, None),
+        };
+        let default_name = match kind {
+            "csv" => "strings.csv",
+            "jsonl" => "strings.jsonl",
+            "sqlite" => "strings.sqlite",
+            "enc" => "strings.enc",
+            "fixed" => "strings.txt",
+            _ => "strings.textout",
+        };
+        let path = path.unwrap_or_else(|| PathBuf::from(default_name));
+        let wrap = conf.get_wrap(kind);
+        let append = conf.get_append();
+        // rows of columns= are csv unless format says otherwise, with columns as only fields
+        let columns = !conf.get_columns().is_empty();
+        let format = match conf.get_format() {
+            OutputFormat::Plain if columns => OutputFormat::Csv,
+            _ if columns && kind == "csv" => OutputFormat::Csv,
+            format => format,
+        };
+        let fields = if columns {
+            Vec::new()
+        } else {
+            conf.get_format_fields()
+        };
+        if format == OutputFormat::Json && append && !matches!(kind, "stdout" | "-") {
+            let message = "json array can't be appended to, use format=jsonl";
+            return Err(Error::new(ErrorKind::InvalidInput, message));
+        }
+        // structured formats tell language of word list modes
+        let language = if conf.get_mode().uses_word_lists() {
+            Languages::from(conf.get_next().as_ref()).abbr()
+        } else {
+            String::new()
+        };
+        // kept as kind so --out https://.. is not taken for file name
+        if kind == "https" {
+            let message = format!("https output is not supported, use http:// url: {}", spec);
+            return Err(Error::new(ErrorKind::Unsupported, message));
+        }
+        if kind == "http" {
+            // spec is the url itself, http://host/path
+            let sink = HttpPostSink::new(spec, conf.get_batch_size() as usize, wrap)?;
+            return Ok(Box::new(sink));
+        }
+        if kind == "fifo" || kind == "unix" {
+            // fifo:path[:drop]
+            let target = spec.split_once(':').map(|(_k, t)| t).unwrap_or("");
+            let (target, policy) = match target.rsplit_once(':') {
+                Some((path, "drop")) => (path, SlowReader::Drop),
+                Some((path, "block")) => (path, SlowReader::Block),
+                _ => (target, SlowReader::Block),
+            };
+            if target.is_empty() {
+                let message = format!("{} output needs a path", kind);
+                return Err(Error::new(ErrorKind::InvalidInput, message));
+            }
+            #[cfg(unix)]
+            {
+                let sink = if kind == "fifo" {
+                    StreamSink::fifo(Path::new(target), policy, wrap)
+                } else {
+                    StreamSink::socket(Path::new(target), policy, wrap)
+                };
+                return Ok(Box::new(sink));
+            }
+            #[cfg(not(unix))]
+            {
+                let _policy = policy;
+                return Err(Error::other(
+                    "fifo and unix socket outputs need a unix system",
+                ));
+            }
+        }
+        if kind == "kafka" {
+            let target = spec.split_once(':').map(|(_k, t)| t).unwrap_or("");
+            #[cfg(feature = "kafka")]
+            {
+                let sink = KafkaSink::connect(target, conf.get_batch_size() as usize, wrap)?;
+                return Ok(Box::new(sink));
+            }
+            #[cfg(not(feature = "kafka"))]
+            {
+                let message = format!(
+                    "kafka output to {} needs the kafka feature (cargo build --features kafka)",
+                    target
+                );
+                return Err(Error::other(message));
+            }
+        }
+        let sink: Box<dyn OutputSink> = match kind {
+            "stdout" | "-" => {
+                let wrap = conf.get_wrap("stdout");
+                if format != OutputFormat::Plain {
+                    let writer = Box::new(std::io::stdout());
+                    let sink = FormattedSink::new(writer, format, fields, language, wrap);
+                    return Ok(Box::new(sink));
+                }
+                return Ok(Box::new(StdoutSink::new(!conf.get_write_indices(), wrap)));
+            }
+            "csv" if !columns => Box::new(CsvSink::create(&path, wrap, conf.get_labels(), append)?),
+            "jsonl" => Box::new(JsonlSink::create(&path, wrap, append)?),
+            "fixed" => Box::new(FixedWidthSink::create(
+                &path,
+                wrap,
+                conf.get_labels(),
+                conf.get_widths(),
+                append,
+            )?),
+            "clip" => {
+                // only secrets get cleared from clipboard
+                let clear = if conf.get_mode().is_secret() {
+                    conf.get_clip_clear()
+                } else {
+                    0
+                };
+                // no file, so no run metadata either
+                return Ok(Box::new(ClipboardSink::new(wrap, clear)));
+            }
+            "enc" => {
+                encryption_available()?;
+                if append {
+                    let message = "encrypted output can't be appended to, give new path";
+                    return Err(Error::new(ErrorKind::InvalidInput, message));
+                }
+                let passphrase = read_passphrase(true)?;
+                // plaintext metadata would tell mode and length of encrypted secrets
+                return Ok(Box::new(EncryptedFileSink::create(&path, passphrase, wrap)?));
+            }
+            #[cfg(feature = "sqlite")]
+            "sqlite" => Box::new(SqliteSink::create(&path, wrap)?),
+            #[cfg(not(feature = "sqlite"))]
+            "sqlite" => {
+                let message =
+                    "sqlite output needs the sqlite feature (cargo build --features sqlite)";
+                return Err(Error::other(message));
+            }
+            _ if format != OutputFormat::Plain => {
+                let writer = Box::new(BufWriter::new(open_output(&path, append)?));
+                let wrap = conf.get_wrap(if kind == "csv" { "csv" } else { "file" });
+                Box::new(FormattedSink::new(writer, format, fields, language, wrap))
+            }
+            _ => Box::new(FileSink::create(&path, conf.get_wrap("file"), append)?),
+        };
+        // file outputs get run metadata next to them, line per run when appending
+        let mut meta = open_output(&sidecar(&path, "meta"), append)?;
+        writeln!(meta, "{}", conf.metadata())?;
+        return Ok(sink);
+    }
+}
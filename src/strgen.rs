@@ -1,21 +1,76 @@
 pub mod string_generator_module {
 
+    use std::collections::HashMap;
     use std::fs::read_to_string;
-    use std::io::Error;
+    use std::io::{Error, ErrorKind};
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
 
+    use crate::stringer::in_range;
     use crate::stringer::read_lines;
-    use crate::stringer::{Config, GermanNounList, Languages, ListType, Modes, RNG};
+    use crate::stringer::tr;
+    use crate::stringer::wipe;
+    use crate::stringer::{lists_dir, quotas, split_list_files, TIERS};
+    use crate::stringer::{
+        Config, GeneratorVersion, GermanCase, GermanNounList, Languages, ListType, Modes,
+        RngBackend, StrgenError, RNG,
+    };
 
     pub trait StringGenerator {
         fn get(&mut self) -> String;
         fn setup(&mut self, conf: &Config) -> Result<(), Error>;
+        // label of last string, for generators mixing several profiles or slots
+        fn label(&self) -> Option<String> {
+            return None;
+        }
+        // true when there is nothing left to produce, e.g. all combinations enumerated
+        fn exhausted(&self) -> bool {
+            return false;
+        }
+        // error that made last string unusable, e.g. lost counter file, stops the run
+        fn failure(&mut self) -> Option<Error> {
+            return None;
+        }
+        // extra named values of last string, e.g. batch and expiry, become output columns
+        fn fields(&self) -> Vec<(String, String)> {
+            return Vec::new();
+        }
+        // up to n strings at once, fewer when exhausted; label and fields are of last one only
+        fn generate_batch(&mut self, n: usize) -> Vec<String> {
+            let mut batch: Vec<String> = Vec::with_capacity(n);
+            while batch.len() < n && !self.exhausted() {
+                batch.push(self.get());
+            }
+            return batch;
+        }
+        // next string written over out, so one buffer serves every call
+        fn generate_into(&mut self, out: &mut String) {
+            let mut value = self.get();
+            wipe(out);
+            out.push_str(&value);
+            wipe(&mut value);
+        }
+    }
+
+    // set up generators give strings until exhausted: sg.by_ref().take(100).collect()
+    impl Iterator for dyn StringGenerator {
+        type Item = String;
+        fn next(&mut self) -> Option<String> {
+            if self.exhausted() {
+                return None;
+            }
+            return Some(self.get());
+        }
     }
 
     pub struct LettterSequence {
         alphabet: Vec<char>,
         held_string: String,
+        // each string gets length between min_length and length
+        min_length: usize,
         length: usize,
-        rng: RNG,
+        rng: Box<dyn RngBackend>,
+        version: GeneratorVersion,
     }
 
     impl LettterSequence {
@@ -40,8 +95,10 @@ pub mod string_generator_module {
             return LettterSequence {
                 held_string,
                 alphabet,
+                min_length: length,
                 length,
-                rng,
+                rng: Box::new(rng),
+                version: GeneratorVersion::latest(),
             };
         }
         pub fn set_alphabet(&mut self, s: &str) {
@@ -49,52 +106,70 @@ pub mod string_generator_module {
             self.alphabet = abc;
         }
         pub fn set_length(&mut self, n: usize) {
+            self.min_length = n;
             self.length = n;
         }
+        pub fn set_length_range(&mut self, min: usize, max: usize) {
+            self.min_length = min;
+            self.length = max;
+        }
         fn setup_rlaf(&mut self, conf: &Config) -> Result<(), Error> {
-            // let contents = read_to_string(conf.get_next())?;
-            // we sorta need to handle error there
-            let read_text = read_to_string(conf.get_next());
-
-            if read_text.is_err() {
-                println!("Error reading, reverting to latin");
-                self.set_alphabet(Languages::English.get_alphabet().as_ref());
-                return Ok(());
-            }
+            let path = conf.get_next_path();
+            let alphabet = match read_to_string(&path) {
+                Ok(alphabet) => alphabet,
+                Err(e) if e.kind() == ErrorKind::NotFound => {
+                    return Err(StrgenError::MissingListFile(path).into());
+                }
+                Err(e) => return Err(e),
+            };
 
             //remove spaces and line returns
-            //trim_matches?
-
-            let alphabet = read_text.unwrap();
-            
             let mut alpha = String::from(alphabet.trim());
             alpha.retain(char::is_alphanumeric);
+            if alpha.is_empty() {
+                return Err(StrgenError::EmptyList(path).into());
+            }
 
             self.set_alphabet(alpha.as_ref());
 
             return Ok(());
         }
     }
+    impl Drop for LettterSequence {
+        fn drop(&mut self) {
+            wipe(&mut self.held_string);
+        }
+    }
     impl StringGenerator for LettterSequence {
         fn get(&mut self) -> String {
+            let mut held = std::mem::take(&mut self.held_string);
+            self.generate_into(&mut held);
+            self.held_string = held;
+            return self.held_string.clone();
+        }
+        fn generate_into(&mut self, out: &mut String) {
             let len = self.alphabet.len();
-            self.held_string = String::new();
-            for _i in 0..self.length {
-                let num = self.rng.get();
-                let index = num as usize % len;
-                self.held_string.push(self.alphabet[index]);
+            wipe(out);
+            let length = in_range(self.rng.as_mut(), self.min_length as u64, self.length as u64);
+            for _i in 0..length {
+                let index = self.version.pick_index(self.rng.as_mut(), len);
+                out.push(self.alphabet[index]);
             }
-            return self.held_string.clone();
         }
         fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.backend(0)?;
             match conf.get_mode() {
                 Modes::RandomLettersFromCustomAlphabet => {
                     if conf.get_next().is_empty() {
-                        println!("Alphabet paremeter empty, defaulting to latin");
+                        println!("{}", tr("alphabet.empty"));
                         self.set_alphabet("abcdefghijklmnopqrstuvwxyz");
                     } else {
                         self.set_alphabet(conf.get_next().as_ref());
                     }
+                    // alphabet of spaces only
+                    if self.alphabet.is_empty() {
+                        return Err(StrgenError::EmptyList(conf.get_next_path()).into());
+                    }
                 }
                 Modes::RandomLettersFromAlphabetFile => {
                     self.setup_rlaf(conf)?;
@@ -106,16 +181,101 @@ pub mod string_generator_module {
                 }
                 _ => {}
             }
-            self.set_length(conf.get_length() as usize);
+            let (min, max) = conf.get_length_range();
+            self.set_length_range(min as usize, max as usize);
+            self.version = conf.get_version();
             return Ok(());
         }
     }
 
+    // quotas over groups of list words, enforced across the run
+    #[derive(Clone, PartialEq, Debug)]
+    pub enum Strata {
+        None,
+        // tag=weight, tags are written in lists as word#tag
+        Tags(Vec<(String, u32)>),
+        // length=weight, empty means equal counts for every length in the list
+        Lengths(Vec<(usize, u32)>),
+    }
+    impl Strata {
+        // tag:animal=30,object=70 or len or len:4=1,5=2
+        pub fn from(s: &str) -> Strata {
+            let (kind, spec) = s.split_once(':').unwrap_or((s, ""));
+            let mut pairs: Vec<(String, u32)> = Vec::new();
+            for part in spec.split(',').filter(|p| !p.is_empty()) {
+                let (key, weight) = part.split_once('=').unwrap_or((part, "1"));
+                pairs.push((String::from(key.trim()), weight.trim().parse().unwrap_or(0)));
+            }
+            return match kind {
+                "tag" => Strata::Tags(pairs),
+                "len" => Strata::Lengths(
+                    pairs
+                        .iter()
+                        .filter_map(|(len, w)| len.parse().ok().map(|len| (len, *w)))
+                        .collect(),
+                ),
+                _ => Strata::None,
+            };
+        }
+    }
+
+    // word choice of word modes: easy is short common words, hard long and rare ones
+    #[derive(Clone, PartialEq, Debug)]
+    pub enum Difficulty {
+        Any,
+        Easy,
+        Medium,
+        Hard,
+    }
+    impl Difficulty {
+        pub fn from(s: &str) -> Result<Difficulty, Error> {
+            return match s.trim() {
+                "" | "any" => Ok(Difficulty::Any),
+                "easy" => Ok(Difficulty::Easy),
+                "medium" => Ok(Difficulty::Medium),
+                "hard" => Ok(Difficulty::Hard),
+                _ => {
+                    let message =
+                        format!("unknown difficulty {}, expected easy, medium or hard", s);
+                    Err(Error::new(ErrorKind::InvalidInput, message))
+                }
+            };
+        }
+        // frequency tiers of lists tiers (word#common), lists without tiers are taken whole
+        pub fn tiers(&self) -> &'static [&'static str] {
+            return match *self {
+                Difficulty::Any => &[],
+                Difficulty::Easy => &["common"],
+                Difficulty::Medium => &["common", "uncommon"],
+                Difficulty::Hard => &["uncommon", "rare"],
+            };
+        }
+        // shortest and longest word in characters
+        pub fn lengths(&self) -> (usize, usize) {
+            return match *self {
+                Difficulty::Any => (0, usize::MAX),
+                Difficulty::Easy => (3, 7),
+                Difficulty::Medium => (3, 10),
+                Difficulty::Hard => (6, usize::MAX),
+            };
+        }
+    }
+
     pub struct WordList {
         list: Vec<String>,
+        // tag of each word, empty when untagged
+        tags: Vec<String>,
+        // (member indices, weight), remaining counts per stratum are kept aside
+        strata: Vec<(Vec<usize>, u32)>,
+        strata_left: Vec<u32>,
         list_type: ListType,
         language: Languages,
         rng: RNG,
+        version: GeneratorVersion,
+        // every word once before any repeats
+        coverage: bool,
+        // indices not drawn in current round of coverage
+        bag: Vec<usize>,
     }
 
     impl WordList {
@@ -125,13 +285,116 @@ pub mod string_generator_module {
             rng.seed();
             return WordList {
                 list,
+                tags: Vec::new(),
+                strata: Vec::new(),
+                strata_left: Vec::new(),
                 list_type,
                 language,
                 rng,
+                version: GeneratorVersion::latest(),
+                coverage: false,
+                bag: Vec::new(),
             };
         }
+        pub fn set_version(&mut self, version: GeneratorVersion) {
+            self.version = version;
+        }
+        pub fn set_coverage(&mut self, coverage: bool) {
+            self.coverage = coverage;
+        }
+        pub fn set_rng(&mut self, rng: RNG) {
+            self.rng = rng;
+        }
         pub fn add_word(&mut self, s: String) {
             self.list.push(s);
+            self.tags.push(String::new());
+        }
+        pub fn add_tagged_word(&mut self, s: String, tag: String) {
+            self.list.push(s);
+            self.tags.push(tag);
+        }
+        // words outside tiers and lengths of difficulty are dropped, error when none is left
+        pub fn set_difficulty(&mut self, difficulty: &Difficulty) -> Result<(), Error> {
+            if *difficulty == Difficulty::Any {
+                return Ok(());
+            }
+            let tiers = difficulty.tiers();
+            let tiered = self.tags.iter().any(|tag| TIERS.contains(&tag.as_str()));
+            let (min, max) = difficulty.lengths();
+            let keep: Vec<bool> = (0..self.list.len())
+                .map(|i| {
+                    let length = self.list[i].chars().count();
+                    return (!tiered || tiers.contains(&self.tags[i].as_str()))
+                        && length >= min
+                        && length <= max;
+                })
+                .collect();
+            let mut i = 0;
+            self.list.retain(|_w| {
+                i += 1;
+                return keep[i - 1];
+            });
+            let mut i = 0;
+            self.tags.retain(|_t| {
+                i += 1;
+                return keep[i - 1];
+            });
+            if self.list.is_empty() {
+                let message = format!(
+                    "no words of {} fit difficulty {:?}",
+                    self.get_file_name().display(),
+                    difficulty
+                );
+                return Err(Error::new(ErrorKind::InvalidData, message));
+            }
+            return Ok(());
+        }
+        // entropy one word drawn from list adds
+        pub fn bits(&self) -> f64 {
+            return (self.list.len().max(1) as f64).log2();
+        }
+        // amount is split between strata by their weights
+        pub fn set_strata(&mut self, strata: &Strata, amount: u32) -> Result<(), Error> {
+            let mut groups: Vec<(Vec<usize>, u32)> = Vec::new();
+            match strata {
+                Strata::None => {}
+                Strata::Tags(tags) => {
+                    for (tag, weight) in tags.iter() {
+                        let members = (0..self.list.len()).filter(|i| &self.tags[*i] == tag);
+                        groups.push((members.collect(), *weight));
+                    }
+                }
+                Strata::Lengths(lengths) => {
+                    let length_of = |i: &usize| self.list[*i].chars().count();
+                    let mut lengths = lengths.clone();
+                    if lengths.is_empty() {
+                        lengths = (0..self.list.len()).map(|i| (length_of(&i), 1)).collect();
+                        lengths.sort();
+                        lengths.dedup();
+                    }
+                    for (len, weight) in lengths.iter() {
+                        let members = (0..self.list.len()).filter(|i| length_of(i) == *len);
+                        groups.push((members.collect(), *weight));
+                    }
+                }
+            }
+            for (members, weight) in groups.iter() {
+                if members.is_empty() && *weight > 0 {
+                    let message = format!("no words for stratum of {:?}", strata);
+                    return Err(Error::new(ErrorKind::InvalidInput, message));
+                }
+            }
+            if !groups.is_empty() && groups.iter().all(|(_m, w)| *w == 0) {
+                return Err(Error::new(ErrorKind::InvalidInput, "strata weights are all zero"));
+            }
+            let weights: Vec<u32> = groups.iter().map(|(_m, w)| *w).collect();
+            self.strata_left = if groups.is_empty() {
+                Vec::new()
+            } else {
+                quotas(&weights, amount)
+            };
+            self.strata = groups;
+            return Ok(());
         }
         pub fn get_language(&self) -> Languages {
             return self.language.clone();
@@ -139,7 +402,7 @@ pub mod string_generator_module {
         pub fn get_list_type(&self) -> ListType {
             return self.list_type.clone();
         }
-        pub fn get_file_name(&self) -> String {
+        pub fn get_file_name(&self) -> PathBuf {
             let list_type = &self.get_list_type();
             let lang = &self.get_language();
             let head = match list_type {
@@ -149,34 +412,75 @@ pub mod string_generator_module {
                 ListType::Names => "names",
             };
             let lang = lang.abbr();
-            return format!("./lists/{}.{}.list", head, lang);
+            return lists_dir().join(format!("{}.{}.list", head, lang));
         }
         pub fn get_list_len(&self) -> usize {
             return self.list.len();
         }
-        pub fn fill(&mut self, s: &str) -> Result<(), Error> {
-            let filename = if s == "" {
+        // empty path means the bundled list for type and language
+        pub fn fill(&mut self, path: &Path) -> Result<(), Error> {
+            let filename = if path.as_os_str().is_empty() {
                 self.get_file_name()
             } else {
-                String::from(s)
+                path.to_path_buf()
             };
-            let lines = read_lines(filename)?;
+            let lines = read_lines(&filename)?;
             for line in lines {
                 let ip = line?;
                 let chazar = ip.split(",");
                 for chaz in chazar {
-                    if chaz == "" {
+                    if chaz.trim().is_empty() {
                         continue;
                     }
-                    self.add_word(String::from(chaz.trim()))
+                    match chaz.trim().split_once('#') {
+                        Some((word, tag)) => {
+                            self.add_tagged_word(String::from(word), String::from(tag))
+                        }
+                        None => self.add_word(String::from(chaz.trim())),
+                    }
                 }
             }
+            // every draw would divide by zero
+            if self.list.is_empty() {
+                return Err(StrgenError::EmptyList(filename).into());
+            }
             return Ok(());
         }
 
-        fn get(&mut self) -> String {
+        pub fn get_at(&self, index: usize) -> String {
+            return self.list[index].clone();
+        }
+
+        pub fn get(&mut self) -> String {
             let diclen = self.list.len();
-            let index = self.rng.get() as usize % diclen;
+            // never filled, setup reports why
+            if diclen == 0 {
+                return String::new();
+            }
+            if !self.strata.is_empty() {
+                // stratum by what is left of its quota, past the run by plain weights
+                let stratum = if self.strata_left.iter().sum::<u32>() > 0 {
+                    let picked = self.version.pick_weighted(&mut self.rng, &self.strata_left);
+                    self.strata_left[picked] -= 1;
+                    picked
+                } else {
+                    let weights: Vec<u32> = self.strata.iter().map(|(_m, w)| *w).collect();
+                    self.version.pick_weighted(&mut self.rng, &weights)
+                };
+                let members = &self.strata[stratum].0;
+                let index = members[self.version.pick_index(&mut self.rng, members.len())];
+                return self.list[index].clone();
+            }
+            if self.coverage {
+                // shuffle bag, refilled when every word was used
+                if self.bag.is_empty() {
+                    self.bag = (0..diclen).collect();
+                }
+                let pick = self.version.pick_index(&mut self.rng, self.bag.len());
+                let index = self.bag.swap_remove(pick);
+                return self.list[index].clone();
+            }
+            let index = self.version.pick_index(&mut self.rng, diclen);
             return self.list[index].clone();
         }
     }
@@ -186,6 +490,10 @@ pub mod string_generator_module {
         second_type: ListType,
         language: Languages,
         type_list: WordList,
+        // shared, empty unless language is german
+        nounlist: Arc<GermanNounList>,
+        // entropy of every string, reported with difficulty only
+        bits: Option<f64>,
     }
     impl CoupledWords {
         pub fn new(second_type: ListType, language: Languages) -> CoupledWords {
@@ -196,15 +504,13 @@ pub mod string_generator_module {
                 second_type,
                 language,
                 type_list,
+                nounlist: Arc::new(GermanNounList::new()),
+                bits: None,
             };
         }
     }
     impl StringGenerator for CoupledWords {
         fn get(&mut self) -> String {
-            let mut nounlist: GermanNounList = GermanNounList::new();
-            if self.language.is_german() {
-                nounlist.fill();
-            }
             let adj = self.adjectives.get();
             let s2 = self.type_list.get();
 
@@ -212,26 +518,141 @@ pub mod string_generator_module {
 
             if self.language.is_german() && self.second_type.is_noun() {
                 //noun adjective
-                strong = nounlist.get_adapted(s2, adj);
+                strong = self.nounlist.get_adapted(s2, adj);
             }
             return strong;
         }
         fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.adjectives.set_version(conf.get_version());
+            self.type_list.set_version(conf.get_version());
+            self.adjectives.set_coverage(conf.get_coverage());
+            self.type_list.set_coverage(conf.get_coverage());
+            self.adjectives.set_rng(conf.rng(1));
+            self.type_list.set_rng(conf.rng(2));
             match conf.get_mode() {
                 Modes::CoupledWordsNouns | Modes::CoupledWordsNames => {
-                    self.adjectives.fill("")?;
-                    self.type_list.fill("")?;
-                    Ok(())
+                    self.adjectives.fill(Path::new(""))?;
+                    self.type_list.fill(Path::new(""))?;
                 }
                 Modes::CoupledWordsListFiles => {
-                    let nxt = conf.get_next();
-                    let names: Vec<&str> = nxt.split(":").collect();
-                    self.adjectives.fill(names[0])?;
-                    self.type_list.fill(names[1])?;
-                    Ok(())
+                    let nxt = conf.get_next_path();
+                    let names = split_list_files(nxt.as_os_str());
+                    self.adjectives.fill(&names[0])?;
+                    self.type_list.fill(&names[1])?;
                 }
-                _ => Ok(()),
+                _ => {}
+            }
+            if self.language.is_german() {
+                self.nounlist = GermanNounList::shared(&self.language);
+            }
+            let difficulty = Difficulty::from(&conf.get_difficulty())?;
+            self.adjectives.set_difficulty(&difficulty)?;
+            self.type_list.set_difficulty(&difficulty)?;
+            self.bits = (difficulty != Difficulty::Any)
+                .then(|| self.adjectives.bits() + self.type_list.bits());
+            // strata apply to second word, the noun or name
+            let strata = Strata::from(conf.get_strata().as_ref());
+            self.type_list.set_strata(&strata, conf.get_amount())?;
+            return Ok(());
+        }
+        fn label(&self) -> Option<String> {
+            return self.bits.map(|bits| format!("{:.1} bits", bits));
+        }
+    }
+
+    // every adjective x noun pair once, in list order or shuffled
+    pub struct CartesianPairs {
+        adjectives: WordList,
+        nouns: WordList,
+        nounlist: Arc<GermanNounList>,
+        shuffled: bool,
+        // pairs handed out so far
+        position: u64,
+        // sparse fisher-yates, only swapped positions are stored
+        swaps: HashMap<u64, u64>,
+        rng: RNG,
+        version: GeneratorVersion,
+    }
+    impl CartesianPairs {
+        pub fn new(language: Languages) -> CartesianPairs {
+            let mut rng = RNG::new();
+            rng.seed();
+            return CartesianPairs {
+                adjectives: WordList::new(ListType::Adjectives, language.clone()),
+                nouns: WordList::new(ListType::Nouns, language),
+                nounlist: Arc::new(GermanNounList::new()),
+                shuffled: false,
+                position: 0,
+                swaps: HashMap::new(),
+                rng,
+                version: GeneratorVersion::latest(),
+            };
+        }
+        pub fn set_shuffled(&mut self, shuffled: bool) {
+            self.shuffled = shuffled;
+        }
+        pub fn total(&self) -> u64 {
+            return self.adjectives.get_list_len() as u64 * self.nouns.get_list_len() as u64;
+        }
+        fn next_pair(&mut self) -> u64 {
+            let i = self.position;
+            self.position += 1;
+            if !self.shuffled {
+                return i;
             }
+            let left = (self.total() - i) as usize;
+            let j = i + self.version.pick_index(&mut self.rng, left) as u64;
+            let at_j = *self.swaps.get(&j).unwrap_or(&j);
+            let at_i = *self.swaps.get(&i).unwrap_or(&i);
+            self.swaps.insert(j, at_i);
+            self.swaps.remove(&i);
+            return at_j;
+        }
+    }
+    impl StringGenerator for CartesianPairs {
+        fn get(&mut self) -> String {
+            if self.exhausted() {
+                return String::new();
+            }
+            let pair = self.next_pair();
+            let nouns_len = self.nouns.get_list_len() as u64;
+            let adj = self.adjectives.get_at((pair / nouns_len) as usize);
+            let noun = self.nouns.get_at((pair % nouns_len) as usize);
+            if self.nouns.get_language().is_german() {
+                return self.nounlist.get_adapted(noun, adj);
+            }
+            return format!("{}_{}", adj, noun);
+        }
+        // [language | adjectives file:nouns file][:shuffle]
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            self.version = conf.get_version();
+            let next = conf.get_next_path();
+            let mut files = split_list_files(next.as_os_str());
+            if files.last().is_some_and(|f| f.as_os_str() == "shuffle") {
+                self.shuffled = true;
+                files.pop();
+            }
+            if files.len() >= 2 {
+                self.adjectives.fill(&files[0])?;
+                self.nouns.fill(&files[1])?;
+            } else {
+                let language = match files.first() {
+                    Some(lang) => Languages::from(lang.to_string_lossy().as_ref()),
+                    None => Languages::English,
+                };
+                self.adjectives = WordList::new(ListType::Adjectives, language.clone());
+                self.nouns = WordList::new(ListType::Nouns, language.clone());
+                self.adjectives.fill(Path::new(""))?;
+                self.nouns.fill(Path::new(""))?;
+                if language.is_german() {
+                    self.nounlist = GermanNounList::shared(&language);
+                }
+            }
+            return Ok(());
+        }
+        fn exhausted(&self) -> bool {
+            return self.position >= self.total();
         }
     }
 
@@ -241,6 +662,8 @@ pub mod string_generator_module {
         verbs: WordList,
         language: Languages,
         verb_prepositions: Vec<String>,
+        // entropy of every string, reported with difficulty only
+        bits: Option<f64>,
     }
 
     impl SimpleSentences {
@@ -254,25 +677,27 @@ pub mod string_generator_module {
                 verbs,
                 language,
                 verb_prepositions: Vec::new(),
+                bits: None,
             };
         }
+        pub fn get_language(&self) -> Languages {
+            return self.language.clone();
+        }
 
         pub fn fill_preps(&mut self, word: &str) {
-            let filename = "./lists/verbs.to.en.dic";
+            let filename = lists_dir().join("verbs.to.en.dic");
             if let Ok(lines) = read_lines(filename) {
-                for line in lines {
-                    if let Ok(ip) = line {
-                        let verbs = ip.split(";");
-                        for verb in verbs {
-                            if verb == "" {
-                                continue;
-                            }
-                            let split: Vec<&str> = verb.trim().split("=>").collect();
-                            if split[0] == word {
-                                let preps: Vec<&str> = split[1].trim().split(",").collect();
-                                for prep in preps {
-                                    self.verb_prepositions.push(String::from(prep));
-                                }
+                for ip in lines.map_while(Result::ok) {
+                    let verbs = ip.split(";");
+                    for verb in verbs {
+                        if verb.is_empty() {
+                            continue;
+                        }
+                        let split: Vec<&str> = verb.trim().split("=>").collect();
+                        if split[0] == word {
+                            let preps: Vec<&str> = split[1].trim().split(",").collect();
+                            for prep in preps {
+                                self.verb_prepositions.push(String::from(prep));
                             }
                         }
                     }
@@ -297,7 +722,7 @@ pub mod string_generator_module {
 
             let mut strong = format!("{} {} {} {} {}", adj1, noun1, verb, adj2, noun2);
 
-            if self.verb_prepositions.len() > 0 {
+            if !self.verb_prepositions.is_empty() {
                 let prep: &str = self.verb_prepositions[0].as_ref();
                 strong = format!("{} {} {} {} {} {}", adj1, noun1, verb, prep, adj2, noun2);
             }
@@ -308,11 +733,154 @@ pub mod string_generator_module {
             return strong;
         }
         fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.adjectives.set_version(conf.get_version());
+            self.nouns.set_version(conf.get_version());
+            self.verbs.set_version(conf.get_version());
+            self.adjectives.set_coverage(conf.get_coverage());
+            self.nouns.set_coverage(conf.get_coverage());
+            self.verbs.set_coverage(conf.get_coverage());
+            self.adjectives.set_rng(conf.rng(1));
+            self.nouns.set_rng(conf.rng(2));
+            self.verbs.set_rng(conf.rng(3));
             //propagates error
-            self.adjectives.fill("")?;
-            self.nouns.fill("")?;
-            self.verbs.fill("")?;
+            self.adjectives.fill(Path::new(""))?;
+            self.nouns.fill(Path::new(""))?;
+            self.verbs.fill(Path::new(""))?;
+            let difficulty = Difficulty::from(&conf.get_difficulty())?;
+            self.adjectives.set_difficulty(&difficulty)?;
+            self.nouns.set_difficulty(&difficulty)?;
+            self.verbs.set_difficulty(&difficulty)?;
+            // two adjectives, two nouns and verb
+            self.bits = (difficulty != Difficulty::Any).then(|| {
+                return 2.0 * (self.adjectives.bits() + self.nouns.bits()) + self.verbs.bits();
+            });
+            let strata = Strata::from(conf.get_strata().as_ref());
+            self.nouns.set_strata(&strata, conf.get_amount())?;
             Ok(())
         }
+        fn label(&self) -> Option<String> {
+            return self.bits.map(|bits| format!("{:.1} bits", bits));
+        }
+    }
+
+    // the brave tree builds the old house., subject and object with article and adjective
+    pub struct SvoSentences {
+        adjectives: WordList,
+        nouns: WordList,
+        verbs: WordList,
+        language: Languages,
+        // german nouns with their gender, articles and endings follow from it
+        german: Arc<GermanNounList>,
+        // verb -> preposition it takes before object
+        prepositions: HashMap<String, String>,
+        rng: RNG,
+        version: GeneratorVersion,
+        // entropy of every string, reported with difficulty only
+        bits: Option<f64>,
+    }
+    impl SvoSentences {
+        pub fn new(language: Languages) -> SvoSentences {
+            let mut rng = RNG::new();
+            rng.seed();
+            return SvoSentences {
+                adjectives: WordList::new(ListType::Adjectives, language.clone()),
+                nouns: WordList::new(ListType::Nouns, language.clone()),
+                verbs: WordList::new(ListType::Verbs, language.clone()),
+                language,
+                german: Arc::new(GermanNounList::new()),
+                prepositions: HashMap::new(),
+                rng,
+                version: GeneratorVersion::latest(),
+                bits: None,
+            };
+        }
+        fn noun_phrase(&mut self, case: GermanCase) -> String {
+            let adjective = self.adjectives.get();
+            if self.language.is_german() {
+                let index = self.version.pick_index(&mut self.rng, self.german.len());
+                if let Some(noun) = self.german.get_at(index) {
+                    return noun.with_adjective(&adjective, case);
+                }
+            }
+            return format!("the {} {}", adjective, self.nouns.get());
+        }
+        // first preposition of every verb in verbs.to.en.dic
+        fn fill_prepositions(&mut self) {
+            let filename = lists_dir().join("verbs.to.en.dic");
+            if let Ok(lines) = read_lines(filename) {
+                for line in lines.map_while(Result::ok) {
+                    for entry in line.split(';').filter(|e| !e.trim().is_empty()) {
+                        if let Some((verb, preps)) = entry.trim().split_once("=>") {
+                            let prep = preps.split(',').next().unwrap_or("").trim();
+                            self.prepositions
+                                .insert(String::from(verb.trim()), String::from(prep));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    impl StringGenerator for SvoSentences {
+        fn get(&mut self) -> String {
+            let subject = self.noun_phrase(GermanCase::Nominative);
+            let verb = self.verbs.get();
+            let object = self.noun_phrase(GermanCase::Accusative);
+            let sentence = match self.prepositions.get(&verb) {
+                Some(prep) => format!("{} {} {} {}.", subject, verb, prep, object),
+                None => format!("{} {} {}.", subject, verb, object),
+            };
+            let mut chars = sentence.chars();
+            return match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => sentence,
+            };
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            if !matches!(self.language, Languages::English | Languages::German) {
+                let message = format!(
+                    "sentences are english (en) or german (de), not {}",
+                    self.language.abbr()
+                );
+                return Err(Error::new(ErrorKind::InvalidInput, message));
+            }
+            self.adjectives.set_version(conf.get_version());
+            self.nouns.set_version(conf.get_version());
+            self.verbs.set_version(conf.get_version());
+            self.adjectives.set_coverage(conf.get_coverage());
+            self.nouns.set_coverage(conf.get_coverage());
+            self.verbs.set_coverage(conf.get_coverage());
+            self.adjectives.set_rng(conf.rng(1));
+            self.nouns.set_rng(conf.rng(2));
+            self.verbs.set_rng(conf.rng(3));
+            self.rng = conf.rng(4);
+            self.version = conf.get_version();
+            self.adjectives.fill(Path::new(""))?;
+            self.nouns.fill(Path::new(""))?;
+            self.verbs.fill(Path::new(""))?;
+            if self.language.is_german() {
+                self.german = GermanNounList::shared(&self.language);
+                if self.german.is_empty() {
+                    return Err(StrgenError::EmptyList(lists_dir().join("nouns.de.dic")).into());
+                }
+            } else {
+                self.fill_prepositions();
+            }
+            let difficulty = Difficulty::from(&conf.get_difficulty())?;
+            self.adjectives.set_difficulty(&difficulty)?;
+            self.nouns.set_difficulty(&difficulty)?;
+            self.verbs.set_difficulty(&difficulty)?;
+            // german nouns come with gender from their own list
+            let nouns = if self.language.is_german() {
+                (self.german.len().max(1) as f64).log2()
+            } else {
+                self.nouns.bits()
+            };
+            self.bits = (difficulty != Difficulty::Any)
+                .then(|| 2.0 * (self.adjectives.bits() + nouns) + self.verbs.bits());
+            return Ok(());
+        }
+        fn label(&self) -> Option<String> {
+            return self.bits.map(|bits| format!("{:.1} bits", bits));
+        }
     }
 }
@@ -0,0 +1,703 @@
+pub mod structured {
+    use std::io::Error;
+    use std::path::Path;
+
+    use crate::strgen::string_generator_module::*;
+    use crate::stringer::{Config, Languages, ListType, Modes, RngBackend, RNG};
+
+    // random integer in [min, max]
+    pub fn in_range(rng: &mut dyn RngBackend, min: u64, max: u64) -> u64 {
+        if max <= min {
+            return min;
+        }
+        let span = max - min + 1;
+        let num = ((rng.get() as u64) << 32) | rng.get() as u64;
+        return min + num % span;
+    }
+
+    // semver identifiers allow only ascii alphanumerics and hyphen
+    fn semver_identifier(s: &str) -> String {
+        let mut ident: String = s
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+            .collect();
+        if ident.is_empty() {
+            ident = String::from("x");
+        }
+        return ident.to_lowercase();
+    }
+
+    // major.minor.patch with optional -prerelease and +build parts
+    pub struct SemVer {
+        min: [u64; 3],
+        max: [u64; 3],
+        prerelease: bool,
+        build: bool,
+        adjectives: WordList,
+        nouns: WordList,
+        rng: RNG,
+    }
+    impl Default for SemVer {
+        fn default() -> Self {
+            return SemVer::new();
+        }
+    }
+    impl SemVer {
+        const TAGS: [&'static str; 3] = ["alpha", "beta", "rc"];
+
+        pub fn new() -> SemVer {
+            let mut rng = RNG::new();
+            rng.seed();
+            return SemVer {
+                min: [0, 0, 0],
+                max: [9, 20, 50],
+                prerelease: false,
+                build: false,
+                adjectives: WordList::new(ListType::Adjectives, Languages::English),
+                nouns: WordList::new(ListType::Nouns, Languages::English),
+                rng,
+            };
+        }
+        fn parse_version(s: &str) -> Option<[u64; 3]> {
+            let parts: Vec<&str> = s.trim().split('.').collect();
+            if parts.len() != 3 {
+                return None;
+            }
+            let mut version = [0u64; 3];
+            for (i, part) in parts.iter().enumerate() {
+                version[i] = part.parse().ok()?;
+            }
+            return Some(version);
+        }
+        // 1.0.0-3.10.20 sets ranges for each component
+        pub fn set_range(&mut self, s: &str) {
+            if let Some((min, max)) = s.split_once('-') {
+                if let (Some(min), Some(max)) =
+                    (SemVer::parse_version(min), SemVer::parse_version(max))
+                {
+                    self.min = min;
+                    self.max = max;
+                }
+            }
+        }
+        pub fn set_prerelease(&mut self, prerelease: bool) {
+            self.prerelease = prerelease;
+        }
+        pub fn set_build(&mut self, build: bool) {
+            self.build = build;
+        }
+    }
+    impl StringGenerator for SemVer {
+        fn get(&mut self) -> String {
+            let mut strong = String::new();
+            for i in 0..3 {
+                let component = in_range(&mut self.rng, self.min[i], self.max[i]);
+                if i > 0 {
+                    strong.push('.');
+                }
+                strong.push_str(&component.to_string());
+            }
+            // tags and metadata on about half of versions
+            if self.prerelease && self.rng.get().is_multiple_of(2) {
+                let tag = if self.rng.get().is_multiple_of(4) {
+                    semver_identifier(&self.adjectives.get())
+                } else {
+                    String::from(SemVer::TAGS[self.rng.get() as usize % SemVer::TAGS.len()])
+                };
+                strong = format!("{}-{}.{}", strong, tag, self.rng.get() % 10);
+            }
+            if self.build && self.rng.get().is_multiple_of(2) {
+                let noun = semver_identifier(&self.nouns.get());
+                strong = format!("{}+{}.{:07x}", strong, noun, self.rng.get() & 0xfffffff);
+            }
+            return strong;
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            // next: min-max[:pre][:build]
+            let next = conf.get_next();
+            let parts: Vec<&str> = next.split(':').collect();
+            self.set_range(parts[0]);
+            for part in &parts[1..] {
+                match *part {
+                    "pre" => self.set_prerelease(true),
+                    "build" => self.set_build(true),
+                    _ => {}
+                }
+            }
+            self.adjectives.set_version(conf.get_version());
+            self.nouns.set_version(conf.get_version());
+            if self.prerelease {
+                self.adjectives.fill(Path::new(""))?;
+            }
+            if self.build {
+                self.nouns.fill(Path::new(""))?;
+            }
+            return Ok(());
+        }
+    }
+
+    // 1234567 -> 1,234,567 with given separator
+    pub fn group_digits(digits: &str, separator: char) -> String {
+        let mut grouped = String::new();
+        let len = digits.len();
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (len - i).is_multiple_of(3) {
+                grouped.push(separator);
+            }
+            grouped.push(c);
+        }
+        return grouped;
+    }
+
+    // money amounts formatted per language: $1,234.56 or 1.234,56 €
+    pub struct CurrencyAmount {
+        language: Languages,
+        max: u64,
+        rng: RNG,
+    }
+    impl Default for CurrencyAmount {
+        fn default() -> Self {
+            return CurrencyAmount::new(Languages::English);
+        }
+    }
+    impl CurrencyAmount {
+        pub fn new(language: Languages) -> CurrencyAmount {
+            let mut rng = RNG::new();
+            rng.seed();
+            return CurrencyAmount {
+                language,
+                max: 10000,
+                rng,
+            };
+        }
+        pub fn set_max(&mut self, max: u64) {
+            self.max = max;
+        }
+        pub fn format(&self, cents: u64) -> String {
+            let whole = group_digits(&(cents / 100).to_string(), self.language.group_separator());
+            let number = format!(
+                "{}{}{:02}",
+                whole,
+                self.language.decimal_separator(),
+                cents % 100
+            );
+            let (symbol, before) = self.language.currency();
+            if before {
+                return format!("{}{}", symbol, number);
+            }
+            return format!("{} {}", number, symbol);
+        }
+    }
+    impl StringGenerator for CurrencyAmount {
+        fn get(&mut self) -> String {
+            let cents = in_range(&mut self.rng, 0, self.max * 100);
+            return self.format(cents);
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            // next: language[:max amount]
+            let next = conf.get_next();
+            let parts: Vec<&str> = next.split(':').collect();
+            self.language = Languages::from(parts[0]);
+            if parts.len() > 1 {
+                self.set_max(parts[1].parse().unwrap_or(10000));
+            }
+            return Ok(());
+        }
+    }
+
+    // bban layout per country: n digit, a uppercase letter, c alphanumeric
+    const IBAN_FORMATS: [(&str, &str); 12] = [
+        ("AT", "16n"),
+        ("BE", "12n"),
+        ("CH", "5n12c"),
+        ("DE", "18n"),
+        ("ES", "20n"),
+        ("FR", "10n11c2n"),
+        ("GB", "4a14n"),
+        ("GE", "2a16n"),
+        ("IT", "1a10n12c"),
+        ("NL", "4a10n"),
+        ("PL", "24n"),
+        ("SE", "20n"),
+    ];
+
+    fn iban_mod97(s: &str) -> u32 {
+        let mut rem: u32 = 0;
+        for c in s.chars() {
+            let value = match c.to_digit(36) {
+                Some(value) => value,
+                None => continue,
+            };
+            if value >= 10 {
+                rem = (rem * 100 + value) % 97;
+            } else {
+                rem = (rem * 10 + value) % 97;
+            }
+        }
+        return rem;
+    }
+    pub fn iban_check_digits(country: &str, bban: &str) -> String {
+        let rearranged = format!("{}{}00", bban, country);
+        return format!("{:02}", 98 - iban_mod97(&rearranged));
+    }
+    pub fn is_valid_iban(iban: &str) -> bool {
+        let iban: String = iban.chars().filter(|c| !c.is_whitespace()).collect();
+        if iban.len() < 5 || !iban.is_ascii() {
+            return false;
+        }
+        let rearranged = format!("{}{}", &iban[4..], &iban[..4]);
+        return iban_mod97(&rearranged) == 1;
+    }
+
+    // iban shaped test values with valid check digits, bank parts are random
+    pub struct Iban {
+        countries: Vec<(String, String)>,
+        rng: RNG,
+    }
+    impl Default for Iban {
+        fn default() -> Self {
+            return Iban::new();
+        }
+    }
+    impl Iban {
+        pub fn new() -> Iban {
+            let mut rng = RNG::new();
+            rng.seed();
+            let mut iban = Iban {
+                countries: Vec::new(),
+                rng,
+            };
+            iban.set_countries("DE");
+            return iban;
+        }
+        // comma separated country codes, unknown ones are skipped
+        pub fn set_countries(&mut self, s: &str) {
+            self.countries.clear();
+            for code in s.split(',') {
+                let code = code.trim().to_uppercase();
+                for (country, layout) in IBAN_FORMATS.iter() {
+                    if *country == code {
+                        self.countries
+                            .push((String::from(*country), String::from(*layout)));
+                    }
+                }
+            }
+            if self.countries.is_empty() {
+                self.countries
+                    .push((String::from("DE"), String::from("18n")));
+            }
+        }
+        fn bban(&mut self, layout: &str) -> String {
+            const DIGITS: &[u8] = b"0123456789";
+            const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+            const ALNUM: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+            let mut bban = String::new();
+            let mut count = String::new();
+            for c in layout.chars() {
+                if c.is_ascii_digit() {
+                    count.push(c);
+                    continue;
+                }
+                let set = match c {
+                    'a' => LETTERS,
+                    'c' => ALNUM,
+                    _ => DIGITS,
+                };
+                let n: usize = count.parse().unwrap_or(1);
+                for _i in 0..n {
+                    bban.push(set[self.rng.get() as usize % set.len()] as char);
+                }
+                count.clear();
+            }
+            return bban;
+        }
+    }
+    impl StringGenerator for Iban {
+        fn get(&mut self) -> String {
+            let index = self.rng.get() as usize % self.countries.len();
+            let (country, layout) = self.countries[index].clone();
+            let bban = self.bban(&layout);
+            let check = iban_check_digits(&country, &bban);
+            return format!("{}{}{}", country, check, bban);
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            if !conf.get_next().is_empty() {
+                self.set_countries(conf.get_next().as_ref());
+            }
+            return Ok(());
+        }
+    }
+
+    const GEOHASH_ALPHABET: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+    pub fn geohash(lat: f64, lon: f64, precision: usize) -> String {
+        let mut lat_range = (-90.0, 90.0);
+        let mut lon_range = (-180.0, 180.0);
+        let mut hash = String::new();
+        let mut even = true;
+        let mut bit = 0;
+        let mut ch = 0;
+        while hash.len() < precision {
+            // bits alternate between longitude and latitude, longitude first
+            let (range, value) = if even {
+                (&mut lon_range, lon)
+            } else {
+                (&mut lat_range, lat)
+            };
+            let mid = (range.0 + range.1) / 2.0;
+            if value >= mid {
+                ch |= 1 << (4 - bit);
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            even = !even;
+            bit += 1;
+            if bit == 5 {
+                hash.push(GEOHASH_ALPHABET[ch] as char);
+                bit = 0;
+                ch = 0;
+            }
+        }
+        return hash;
+    }
+
+    #[derive(Clone)]
+    pub enum GeoFormat {
+        Csv,
+        GeoJson,
+    }
+
+    // latitude/longitude pairs inside bounding box with their geohash
+    pub struct Coordinates {
+        // min lat, min lon, max lat, max lon
+        bbox: [f64; 4],
+        precision: usize,
+        format: GeoFormat,
+        rng: RNG,
+    }
+    impl Default for Coordinates {
+        fn default() -> Self {
+            return Coordinates::new();
+        }
+    }
+    impl Coordinates {
+        pub fn new() -> Coordinates {
+            let mut rng = RNG::new();
+            rng.seed();
+            return Coordinates {
+                bbox: [-90.0, -180.0, 90.0, 180.0],
+                precision: 9,
+                format: GeoFormat::Csv,
+                rng,
+            };
+        }
+        // min lat,min lon,max lat,max lon
+        pub fn set_bbox(&mut self, s: &str) -> bool {
+            let values: Vec<f64> = s.split(',').filter_map(|v| v.trim().parse().ok()).collect();
+            if values.len() != 4 || values[0] > values[2] || values[1] > values[3] {
+                return false;
+            }
+            self.bbox = [values[0], values[1], values[2], values[3]];
+            return true;
+        }
+        pub fn set_precision(&mut self, precision: usize) {
+            self.precision = precision.clamp(1, 12);
+        }
+        pub fn set_format(&mut self, format: GeoFormat) {
+            self.format = format;
+        }
+        fn uniform(&mut self, min: f64, max: f64) -> f64 {
+            let fraction = self.rng.get() as f64 / u32::MAX as f64;
+            return min + (max - min) * fraction;
+        }
+    }
+    impl StringGenerator for Coordinates {
+        fn get(&mut self) -> String {
+            let lat = self.uniform(self.bbox[0], self.bbox[2]);
+            let lon = self.uniform(self.bbox[1], self.bbox[3]);
+            let hash = geohash(lat, lon, self.precision);
+            return match self.format {
+                GeoFormat::Csv => format!("{:.6},{:.6},{}", lat, lon, hash),
+                GeoFormat::GeoJson => format!(
+                    "{{\"type\":\"Feature\",\"geometry\":{{\"type\":\"Point\",\"coordinates\":[{:.6},{:.6}]}},\"properties\":{{\"geohash\":\"{}\"}}}}",
+                    lon, lat, hash
+                ),
+            };
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            // next: [bbox][:csv|geojson], length is geohash precision
+            for part in conf.get_next().split(':') {
+                match part {
+                    "csv" => self.set_format(GeoFormat::Csv),
+                    "geojson" => self.set_format(GeoFormat::GeoJson),
+                    "" => {}
+                    bbox => {
+                        if !self.set_bbox(bbox) {
+                            println!(
+                                "bounding box is not min lat,min lon,max lat,max lon: {}",
+                                bbox
+                            );
+                        }
+                    }
+                }
+            }
+            self.set_precision(conf.get_length() as usize);
+            return Ok(());
+        }
+    }
+
+    // hue in degrees, saturation and lightness in 0..1
+    pub fn hsl_to_hex(hue: f64, saturation: f64, lightness: f64) -> String {
+        let c = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let h = (hue.rem_euclid(360.0)) / 60.0;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = lightness - c / 2.0;
+        let channel = |v: f64| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+        return format!("#{:02x}{:02x}{:02x}", channel(r), channel(g), channel(b));
+    }
+
+    // fnv-1a, stable across platforms and releases
+    pub fn fnv1a(s: &str) -> u64 {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for b in s.bytes() {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        return hash;
+    }
+
+    #[derive(Clone)]
+    pub enum PaletteFormat {
+        Hex,
+        Json,
+        Css,
+    }
+
+    // single hex colors or whole palettes made by rotating hue around a named seed
+    pub struct ColorPalette {
+        size: usize,
+        format: PaletteFormat,
+        adjectives: WordList,
+        nouns: WordList,
+        rng: RNG,
+    }
+    impl ColorPalette {
+        pub fn new(size: usize, format: PaletteFormat) -> ColorPalette {
+            let mut rng = RNG::new();
+            rng.seed();
+            return ColorPalette {
+                size,
+                format,
+                adjectives: WordList::new(ListType::Adjectives, Languages::English),
+                nouns: WordList::new(ListType::Nouns, Languages::English),
+                rng,
+            };
+        }
+        // same name always gives same palette
+        pub fn palette(&self, name: &str) -> Vec<String> {
+            let hash = fnv1a(name);
+            let base = (hash % 360) as f64;
+            let saturation = 0.45 + ((hash >> 16) % 40) as f64 / 100.0;
+            let step = 360.0 / self.size.max(1) as f64;
+            let mut colors = Vec::new();
+            for i in 0..self.size {
+                // alternate lightness so neighbours stay distinguishable
+                let lightness = if i % 2 == 0 { 0.45 } else { 0.62 };
+                colors.push(hsl_to_hex(base + step * i as f64, saturation, lightness));
+            }
+            return colors;
+        }
+        fn name(&mut self) -> String {
+            let name = format!("{}-{}", self.adjectives.get(), self.nouns.get());
+            return name.to_lowercase().replace(' ', "-");
+        }
+    }
+    impl StringGenerator for ColorPalette {
+        fn get(&mut self) -> String {
+            if matches!(self.format, PaletteFormat::Hex) {
+                let hue = (self.rng.get() % 360) as f64;
+                let saturation = 0.4 + (self.rng.get() % 50) as f64 / 100.0;
+                let lightness = 0.35 + (self.rng.get() % 35) as f64 / 100.0;
+                return hsl_to_hex(hue, saturation, lightness);
+            }
+            let name = self.name();
+            let colors = self.palette(&name);
+            if matches!(self.format, PaletteFormat::Css) {
+                let mut props = String::new();
+                for (i, color) in colors.iter().enumerate() {
+                    props.push_str(&format!("--{}-{}:{};", name, i + 1, color));
+                }
+                return format!(":root{{{}}}", props);
+            }
+            let quoted: Vec<String> = colors.iter().map(|c| format!("\"{}\"", c)).collect();
+            return format!(
+                "{{\"name\":\"{}\",\"colors\":[{}]}}",
+                name,
+                quoted.join(",")
+            );
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            if matches!(conf.get_mode(), Modes::Color) {
+                self.format = PaletteFormat::Hex;
+                return Ok(());
+            }
+            // next: json|css[:number of colors]
+            let next = conf.get_next();
+            let parts: Vec<&str> = next.split(':').collect();
+            self.format = match parts[0] {
+                "css" => PaletteFormat::Css,
+                _ => PaletteFormat::Json,
+            };
+            if parts.len() > 1 {
+                self.size = parts[1].parse().unwrap_or(5);
+            }
+            self.adjectives.set_version(conf.get_version());
+            self.nouns.set_version(conf.get_version());
+            self.adjectives.fill(Path::new(""))?;
+            self.nouns.fill(Path::new(""))?;
+            return Ok(());
+        }
+    }
+
+    // valid five field cron expressions, optionally with tricky but legal forms
+    pub struct CronExpression {
+        // minute, hour, day of month, month, day of week
+        fields: [bool; 5],
+        tricky: bool,
+        rng: RNG,
+    }
+    impl Default for CronExpression {
+        fn default() -> Self {
+            return CronExpression::new();
+        }
+    }
+    impl CronExpression {
+        const NAMES: [&'static str; 5] = ["min", "hour", "dom", "mon", "dow"];
+        const BOUNDS: [(u64, u64); 5] = [(0, 59), (0, 23), (1, 31), (1, 12), (0, 6)];
+        const MONTHS: [&'static str; 12] = [
+            "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+        ];
+        const DAYS: [&'static str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+        const MACROS: [&'static str; 6] = [
+            "@yearly", "@monthly", "@weekly", "@daily", "@hourly", "@reboot",
+        ];
+
+        pub fn new() -> CronExpression {
+            let mut rng = RNG::new();
+            rng.seed();
+            return CronExpression {
+                fields: [true; 5],
+                tricky: false,
+                rng,
+            };
+        }
+        // comma separated subset of min,hour,dom,mon,dow, others stay *
+        pub fn set_fields(&mut self, s: &str) {
+            self.fields = [false; 5];
+            for name in s.split(',') {
+                for (i, known) in CronExpression::NAMES.iter().enumerate() {
+                    if name.trim() == *known {
+                        self.fields[i] = true;
+                    }
+                }
+            }
+        }
+        pub fn set_tricky(&mut self, tricky: bool) {
+            self.tricky = tricky;
+        }
+        // 7 for sunday only where it can't end up as start of a range
+        fn value(&mut self, field: usize, min: u64, max: u64, single: bool) -> String {
+            let value = in_range(&mut self.rng, min, max);
+            if self.tricky && self.rng.get().is_multiple_of(4) {
+                // month and weekday names, 7 as sunday
+                if field == 3 {
+                    return String::from(CronExpression::MONTHS[(value - 1) as usize]);
+                }
+                if field == 4 {
+                    if single && value == 0 && self.rng.get().is_multiple_of(2) {
+                        return String::from("7");
+                    }
+                    return String::from(CronExpression::DAYS[value as usize]);
+                }
+            }
+            return value.to_string();
+        }
+        fn field(&mut self, field: usize) -> String {
+            let (min, max) = CronExpression::BOUNDS[field];
+            let form = self.rng.get() % if self.tricky { 7 } else { 5 };
+            return match form {
+                0 => String::from("*"),
+                1 => self.value(field, min, max, true),
+                2 => {
+                    let low = in_range(&mut self.rng, min, max);
+                    let high = in_range(&mut self.rng, low, max);
+                    let low_value = self.value(field, low, low, false);
+                    format!("{}-{}", low_value, self.value(field, high, high, false))
+                }
+                3 => format!("*/{}", in_range(&mut self.rng, 2, (max - min).div_ceil(2))),
+                4 => {
+                    let count = in_range(&mut self.rng, 2, 4);
+                    let mut values: Vec<u64> = (0..count)
+                        .map(|_i| in_range(&mut self.rng, min, max))
+                        .collect();
+                    values.sort();
+                    values.dedup();
+                    let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+                    values.join(",")
+                }
+                5 => {
+                    // range with step
+                    let low = in_range(&mut self.rng, min, max);
+                    format!("{}-{}/{}", low, max, in_range(&mut self.rng, 2, 10))
+                }
+                _ => {
+                    // step larger than the range, only first value matches
+                    format!("*/{}", in_range(&mut self.rng, max + 1, max + 40))
+                }
+            };
+        }
+    }
+    impl StringGenerator for CronExpression {
+        fn get(&mut self) -> String {
+            if self.tricky && self.rng.get().is_multiple_of(10) {
+                let index = self.rng.get() as usize % CronExpression::MACROS.len();
+                return String::from(CronExpression::MACROS[index]);
+            }
+            let mut parts: Vec<String> = Vec::new();
+            for i in 0..5 {
+                if self.fields[i] {
+                    parts.push(self.field(i));
+                } else {
+                    parts.push(String::from("*"));
+                }
+            }
+            return parts.join(" ");
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            // next: [fields][:tricky]
+            for part in conf.get_next().split(':') {
+                match part {
+                    "tricky" => self.set_tricky(true),
+                    "" => {}
+                    fields => self.set_fields(fields),
+                }
+            }
+            return Ok(());
+        }
+    }
+}
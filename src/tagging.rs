@@ -0,0 +1,341 @@
+pub mod tagging {
+    use std::collections::HashMap;
+    use std::io::{Error, ErrorKind};
+    use std::path::Path;
+
+    use crate::paths::paths::lists_dir;
+    use crate::stringer::{read_lines, read_list, Languages};
+
+    // votes lexicon word gets over suffix and context guesses
+    const LEXICON_VOTES: u32 = 3;
+    // stem left after suffix, so -al of "al" or -er of "her" are no endings
+    const MIN_STEM: usize = 3;
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum Tag {
+        Noun,
+        Adjective,
+        Verb,
+        // articles and demonstratives, they open noun phrases
+        Determiner,
+        // pronouns, prepositions, conjunctions and auxiliaries, never list words
+        Other,
+    }
+    impl Tag {
+        pub fn from(s: &str) -> Result<Tag, Error> {
+            return match s.trim() {
+                "noun" | "nouns" | "n" => Ok(Tag::Noun),
+                "adjective" | "adjectives" | "adj" | "a" => Ok(Tag::Adjective),
+                "verb" | "verbs" | "v" => Ok(Tag::Verb),
+                "determiner" | "det" | "d" => Ok(Tag::Determiner),
+                "other" | "o" => Ok(Tag::Other),
+                _ => {
+                    let message = format!(
+                        "unknown part of speech {}, expected noun, adjective, verb, determiner \
+                        or other",
+                        s
+                    );
+                    Err(Error::new(ErrorKind::InvalidInput, message))
+                }
+            };
+        }
+    }
+
+    type Words = &'static [&'static str];
+
+    pub fn determiners(language: &Languages) -> Words {
+        return match *language {
+            Languages::English => &[
+                "the", "a", "an", "this", "that", "these", "those", "my", "your", "his", "her",
+                "its", "our", "their", "every", "each", "some", "no",
+            ],
+            Languages::German => &[
+                "der", "die", "das", "den", "dem", "des", "ein", "eine", "einen", "einem", "einer",
+                "eines", "kein", "keine", "mein", "meine", "dein", "sein", "seine", "ihr", "ihre",
+                "unser", "dieser", "diese", "dieses", "jeder", "jede", "jedes",
+            ],
+            Languages::French => &[
+                "le", "la", "les", "un", "une", "des", "du", "ce", "cet", "cette", "ces", "mon",
+                "ma", "mes", "ton", "ta", "tes", "son", "sa", "ses", "notre", "nos", "votre",
+                "vos", "leur", "leurs",
+            ],
+            Languages::Spanish => &[
+                "el", "la", "los", "las", "un", "una", "unos", "unas", "este", "esta", "estos",
+                "estas", "ese", "esa", "mi", "mis", "tu", "tus", "su", "sus",
+            ],
+            // no articles, demonstratives only
+            Languages::Georgian => &["ეს", "ის", "ეგ", "ამ", "იმ", "ყველა"],
+        };
+    }
+
+    // most common closed class words, so they never vote or get voted into lists
+    pub fn function_words(language: &Languages) -> Words {
+        return match *language {
+            Languages::English => &[
+                "and", "or", "but", "if", "of", "in", "on", "at", "to", "for", "with", "from",
+                "by", "as", "is", "are", "was", "were", "be", "been", "has", "have", "had", "do",
+                "does", "not", "it", "he", "she", "we", "they", "you", "i", "there", "then",
+                "than", "when", "which", "who", "what", "also", "only", "very", "into", "out",
+                "up", "can", "will", "would", "should", "may", "must",
+            ],
+            Languages::German => &[
+                "und", "oder", "aber", "wenn", "von", "vom", "in", "im", "an", "am", "auf", "zu",
+                "zum", "zur", "für", "mit", "aus", "bei", "nach", "über", "unter", "ist", "sind",
+                "war", "waren", "hat", "haben", "wird", "werden", "nicht", "es", "er", "sie",
+                "wir", "ich", "du", "man", "sich", "auch", "nur", "sehr", "dann", "als", "wie",
+                "kann", "muss", "soll",
+            ],
+            Languages::French => &[
+                "et", "ou", "mais", "si", "de", "en", "dans", "sur", "à", "au", "aux", "pour",
+                "avec", "par", "est", "sont", "était", "a", "ont", "pas", "ne", "il", "elle",
+                "nous", "vous", "ils", "elles", "je", "on", "se", "qui", "que", "aussi", "très",
+                "plus",
+            ],
+            Languages::Spanish => &[
+                "y", "o", "pero", "si", "de", "del", "en", "a", "al", "por", "para", "con", "sin",
+                "es", "son", "era", "fue", "ha", "han", "no", "él", "ella", "nosotros", "ellos",
+                "yo", "se", "que", "quien", "también", "muy", "más",
+            ],
+            Languages::Georgian => &[
+                "და",
+                "ან",
+                "მაგრამ",
+                "თუ",
+                "არის",
+                "იყო",
+                "არ",
+                "ვერ",
+                "მე",
+                "შენ",
+                "ჩვენ",
+                "თქვენ",
+                "ისინი",
+                "რომ",
+                "როცა",
+                "ძალიან",
+                "კი",
+                "ც",
+            ],
+        };
+    }
+
+    // endings that make word noun or adjective, longest first within each
+    pub fn suffixes(language: &Languages) -> (Words, Words) {
+        return match *language {
+            Languages::English => (
+                &[
+                    "ship", "hood", "ment", "ness", "tion", "sion", "ance", "ence", "ism", "ity",
+                    "ist", "dom", "er", "or",
+                ],
+                &[
+                    "ical", "able", "ible", "less", "ful", "ous", "ive", "ish", "ic", "al",
+                ],
+            ),
+            Languages::German => (
+                &[
+                    "schaft", "ismus", "heit", "keit", "chen", "lein", "ling", "ung", "tum", "nis",
+                    "ion", "tät",
+                ],
+                &[
+                    "artig", "lich", "isch", "haft", "voll", "bar", "sam", "los", "ig",
+                ],
+            ),
+            Languages::French => (
+                &[
+                    "isme", "ment", "tion", "ance", "ence", "age", "eur", "ité", "ure",
+                ],
+                &[
+                    "euse", "ique", "able", "ible", "elle", "eux", "ive", "if", "al",
+                ],
+            ),
+            Languages::Spanish => (
+                &[
+                    "miento", "ción", "sión", "ismo", "anza", "encia", "dad", "ura", "ero",
+                ],
+                &["oso", "osa", "ivo", "iva", "ble", "ico", "ica", "al"],
+            ),
+            Languages::Georgian => (&["ობა", "ება"], &["ოვანი", "იანი", "ური", "ული"]),
+        };
+    }
+
+    // lexicon of bundled lists and closed words, suffix rules and context of noun phrases;
+    // crude, but sorting corpus words into nouns and adjectives needs no more
+    pub struct Tagger {
+        language: Languages,
+        // lower case words
+        lexicon: HashMap<String, Tag>,
+    }
+    impl Tagger {
+        // determiners, function words and words of bundled lists of language
+        pub fn new(language: &Languages) -> Tagger {
+            let mut tagger = Tagger {
+                language: language.clone(),
+                lexicon: HashMap::new(),
+            };
+            let kinds = [
+                ("verbs", Tag::Verb),
+                ("adjectives", Tag::Adjective),
+                ("nouns", Tag::Noun),
+            ];
+            for (head, tag) in kinds {
+                let path = lists_dir().join(format!("{}.{}.list", head, language.abbr()));
+                // new language has no lists yet
+                let Ok(words) = read_list(&path) else {
+                    continue;
+                };
+                for word in words {
+                    tagger.add(&word, tag);
+                }
+            }
+            for word in function_words(language) {
+                tagger.add(word, Tag::Other);
+            }
+            for word in determiners(language) {
+                tagger.add(word, Tag::Determiner);
+            }
+            return tagger;
+        }
+        pub fn add(&mut self, word: &str, tag: Tag) {
+            self.lexicon.insert(word.trim().to_lowercase(), tag);
+        }
+        pub fn lookup(&self, word: &str) -> Option<Tag> {
+            return self.lexicon.get(&word.to_lowercase()).copied();
+        }
+        // noun or adjective by ending, none when no ending matches
+        pub fn guess(&self, word: &str) -> Option<Tag> {
+            let word = word.to_lowercase();
+            let (nouns, adjectives) = suffixes(&self.language);
+            let ending = |list: &[&str]| {
+                return list
+                    .iter()
+                    .filter(|s| word.ends_with(*s))
+                    .filter(|s| word.chars().count() >= s.chars().count() + MIN_STEM)
+                    .map(|s| s.chars().count())
+                    .max();
+            };
+            return match (ending(nouns), ending(adjectives)) {
+                (Some(n), Some(a)) if n > a => Some(Tag::Noun),
+                (Some(n), Some(a)) if a > n => Some(Tag::Adjective),
+                (Some(_n), None) => Some(Tag::Noun),
+                (None, Some(_a)) => Some(Tag::Adjective),
+                _ => None,
+            };
+        }
+        // lexicon, else ending, else capital letter of german noun inside sentence
+        fn known(&self, word: &str, sentence_start: bool) -> Option<Tag> {
+            if let Some(tag) = self.lookup(word) {
+                return Some(tag);
+            }
+            if let Some(tag) = self.guess(word) {
+                return Some(tag);
+            }
+            let capital = word.chars().next().is_some_and(|c| c.is_uppercase());
+            if self.language.is_german() && capital && !sentence_start {
+                return Some(Tag::Noun);
+            }
+            return None;
+        }
+        // part of speech of every word of text, lower case; every occurrence votes and
+        // words with as many votes for noun as for adjective are left out
+        pub fn tags(&self, text: &str) -> HashMap<String, Tag> {
+            let mut votes: HashMap<String, (u32, u32)> = HashMap::new();
+            let mut sentence_start = true;
+            // clauses end at punctuation, noun phrase never spans one
+            for clause in text.split_inclusive(|c: char| ".,;:!?()\"\n".contains(c)) {
+                let words: Vec<&str> = clause
+                    .split(|c: char| !c.is_alphabetic())
+                    .filter(|w| !w.is_empty())
+                    .collect();
+                let tags: Vec<Option<Tag>> = words
+                    .iter()
+                    .enumerate()
+                    .map(|(i, w)| self.known(w, sentence_start && i == 0))
+                    .collect();
+                for (i, word) in words.iter().enumerate() {
+                    let vote = match self.lookup(word) {
+                        Some(Tag::Noun) => (LEXICON_VOTES, 0),
+                        Some(Tag::Adjective) => (0, LEXICON_VOTES),
+                        Some(_closed) => continue,
+                        None => self.context(&tags, i),
+                    };
+                    let entry = votes.entry(word.to_lowercase()).or_default();
+                    entry.0 += vote.0;
+                    entry.1 += vote.1;
+                }
+                if clause.ends_with(['.', '!', '?']) {
+                    sentence_start = true;
+                } else if !words.is_empty() {
+                    sentence_start = false;
+                }
+            }
+            return votes
+                .into_iter()
+                .filter_map(|(word, (noun, adjective))| {
+                    return match noun.cmp(&adjective) {
+                        std::cmp::Ordering::Greater => Some((word, Tag::Noun)),
+                        std::cmp::Ordering::Less => Some((word, Tag::Adjective)),
+                        std::cmp::Ordering::Equal => None,
+                    };
+                })
+                .collect();
+        }
+        // (noun, adjective) votes of word at i out of its own ending and its neighbours
+        fn context(&self, tags: &[Option<Tag>], i: usize) -> (u32, u32) {
+            let mut vote = match tags[i] {
+                Some(Tag::Noun) => (1, 0),
+                Some(Tag::Adjective) => (0, 1),
+                _ => (0, 0),
+            };
+            let previous = if i > 0 { tags[i - 1] } else { None };
+            let before = if i > 1 { tags[i - 2] } else { None };
+            let next = tags.get(i + 1).copied().flatten();
+            let romance = matches!(self.language, Languages::French | Languages::Spanish);
+            if previous == Some(Tag::Determiner) {
+                // the old tree: word between determiner and noun describes it
+                match next {
+                    Some(Tag::Noun) => vote.1 += 2,
+                    Some(Tag::Adjective) if romance => vote.0 += 2,
+                    Some(Tag::Adjective) => vote.1 += 1,
+                    // the tree grows, the tree and
+                    Some(_verb_or_other) => vote.0 += 2,
+                    None if i + 1 == tags.len() => vote.0 += 2,
+                    // the dark lake. but la maison blanche.
+                    None if i + 2 == tags.len() && !romance => vote.1 += 1,
+                    // right after determiner is mostly noun
+                    None => vote.0 += 1,
+                }
+            } else if previous == Some(Tag::Adjective) && before == Some(Tag::Determiner) {
+                vote.0 += 2;
+            } else if romance && previous == Some(Tag::Noun) && before == Some(Tag::Determiner) {
+                // la maison blanche, el árbol viejo
+                vote.1 += 2;
+            } else if previous.is_none() && before == Some(Tag::Determiner) && i + 1 == tags.len() {
+                if romance {
+                    vote.1 += 1;
+                } else {
+                    vote.0 += 1;
+                }
+            }
+            return vote;
+        }
+    }
+
+    // word and tag per line, tab or space separated, # comments; tag is noun, adjective, verb,
+    // determiner or other
+    pub fn read_lexicon(path: &Path) -> Result<Vec<(String, Tag)>, Error> {
+        let mut lexicon: Vec<(String, Tag)> = Vec::new();
+        for line in read_lines(path)? {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((word, tag)) = line.split_once(char::is_whitespace) else {
+                let message = format!("bad lexicon line {}, expected word and tag", line);
+                return Err(Error::new(ErrorKind::InvalidData, message));
+            };
+            lexicon.push((String::from(word), Tag::from(tag)?));
+        }
+        return Ok(lexicon);
+    }
+}
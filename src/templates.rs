@@ -0,0 +1,186 @@
+pub mod templates {
+    use std::io::{Error, ErrorKind};
+    use std::path::Path;
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{
+        CharClass, Config, GeneratorVersion, Languages, ListType, WordList, RNG,
+    };
+
+    // characters of classes passwords have no use for
+    pub const HEX: &str = "0123456789abcdef";
+    pub const ALNUM: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+    #[derive(Clone, PartialEq, Debug)]
+    pub enum Part {
+        Literal(String),
+        // list and language of word
+        Word(String, String),
+        // alphabet and how many characters of it
+        Chars(String, usize),
+    }
+
+    fn list_type(name: &str) -> Option<ListType> {
+        return match name {
+            "adjective" | "adj" => Some(ListType::Adjectives),
+            "noun" => Some(ListType::Nouns),
+            "verb" => Some(ListType::Verbs),
+            "name" => Some(ListType::Names),
+            _ => None,
+        };
+    }
+
+    fn alphabet(name: &str) -> Option<String> {
+        let name = match name {
+            "digits" => "digit",
+            "letters" => "letter",
+            _ => name,
+        };
+        return match name {
+            "hex" => Some(String::from(HEX)),
+            "alnum" => Some(String::from(ALNUM)),
+            _ => CharClass::from(name).map(|class| String::from(class.alphabet())),
+        };
+    }
+
+    fn bad_template(message: String) -> Error {
+        return Error::new(ErrorKind::InvalidInput, message);
+    }
+
+    // {adjective}-{noun}-{digits:4}, {{ and }} are literal braces
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct Template {
+        pub parts: Vec<Part>,
+    }
+    impl Template {
+        pub fn parse(s: &str) -> Result<Template, Error> {
+            let mut parts: Vec<Part> = Vec::new();
+            let mut literal = String::new();
+            let mut chars = s.chars().peekable();
+            while let Some(c) = chars.next() {
+                if c == '}' {
+                    if chars.next_if_eq(&'}').is_none() {
+                        return Err(bad_template(format!("unmatched }} in template {}", s)));
+                    }
+                    literal.push('}');
+                    continue;
+                }
+                if c != '{' {
+                    literal.push(c);
+                    continue;
+                }
+                if chars.next_if_eq(&'{').is_some() {
+                    literal.push('{');
+                    continue;
+                }
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => return Err(bad_template(format!("unclosed {{ in template {}", s))),
+                    }
+                }
+                if !literal.is_empty() {
+                    parts.push(Part::Literal(literal.clone()));
+                    literal.clear();
+                }
+                parts.push(Template::placeholder(&placeholder)?);
+            }
+            if !literal.is_empty() {
+                parts.push(Part::Literal(literal));
+            }
+            if parts.is_empty() {
+                return Err(bad_template(String::from("empty template")));
+            }
+            return Ok(Template { parts });
+        }
+        // name[:argument], word lists take language, classes take count
+        fn placeholder(s: &str) -> Result<Part, Error> {
+            let (name, argument) = match s.split_once(':') {
+                Some((name, argument)) => (name.trim(), Some(argument.trim())),
+                None => (s.trim(), None),
+            };
+            if list_type(name).is_some() {
+                let language = String::from(argument.unwrap_or("en"));
+                return Ok(Part::Word(String::from(name), language));
+            }
+            let Some(alphabet) = alphabet(name) else {
+                return Err(bad_template(format!("unknown placeholder {{{}}}", s)));
+            };
+            let count = match argument {
+                Some(n) => n
+                    .parse::<usize>()
+                    .map_err(|_e| bad_template(format!("count of {{{}}} must be a number", s)))?,
+                None => 1,
+            };
+            return Ok(Part::Chars(alphabet, count));
+        }
+    }
+
+    // 4th parameter is template, expanded once per string
+    pub struct TemplateStrings {
+        template: Template,
+        // one list per word placeholder, in order of template
+        lists: Vec<WordList>,
+        rng: RNG,
+        version: GeneratorVersion,
+    }
+    impl Default for TemplateStrings {
+        fn default() -> Self {
+            return TemplateStrings::new();
+        }
+    }
+    impl TemplateStrings {
+        pub fn new() -> TemplateStrings {
+            return TemplateStrings {
+                template: Template { parts: Vec::new() },
+                lists: Vec::new(),
+                rng: RNG::new(),
+                version: GeneratorVersion::latest(),
+            };
+        }
+    }
+    impl StringGenerator for TemplateStrings {
+        fn get(&mut self) -> String {
+            let mut result = String::new();
+            let mut lists = self.lists.iter_mut();
+            for part in self.template.parts.iter() {
+                match part {
+                    Part::Literal(text) => result.push_str(text),
+                    Part::Word(_name, _language) => {
+                        if let Some(list) = lists.next() {
+                            result.push_str(&list.get());
+                        }
+                    }
+                    Part::Chars(alphabet, count) => {
+                        let alphabet: Vec<char> = alphabet.chars().collect();
+                        for _ in 0..*count {
+                            let index = self.version.pick_index(&mut self.rng, alphabet.len());
+                            result.push(alphabet[index]);
+                        }
+                    }
+                }
+            }
+            return result;
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.template = Template::parse(&conf.get_next())?;
+            self.version = conf.get_version();
+            self.rng = conf.rng(0);
+            self.lists.clear();
+            for part in self.template.parts.iter() {
+                if let Part::Word(name, language) = part {
+                    let list_type = list_type(name).expect("checked when parsed");
+                    let mut list = WordList::new(list_type, Languages::from(language.as_ref()));
+                    list.fill(Path::new(""))?;
+                    list.set_version(conf.get_version());
+                    // own stream per list, same words whatever other placeholders draw
+                    list.set_rng(conf.rng(self.lists.len() as u64 + 1));
+                    self.lists.push(list);
+                }
+            }
+            return Ok(());
+        }
+    }
+}
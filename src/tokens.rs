@@ -0,0 +1,188 @@
+pub mod tokens {
+    use std::io::{Error, ErrorKind};
+    use std::path::Path;
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{base62, crc32, read_lines, Config, RngBackend, RNG};
+
+    // registry read from working directory when none is given
+    pub const DEFAULT_TOKEN_REGISTRY: &str = "strgen.tokens";
+    const BASE62: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    // crc32 always fits in 6 base62 digits
+    const CHECK_DIGITS: usize = 6;
+
+    // prefix says whose token and that it is fake, checksum catches typos without lookup
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct TokenScheme {
+        pub name: String,
+        pub prefix: String,
+        // random base62 characters after prefix
+        pub length: usize,
+        pub checksum: bool,
+    }
+    impl TokenScheme {
+        // name=prefix:length[:nocheck]
+        pub fn from(line: &str) -> Result<TokenScheme, Error> {
+            let bad = || {
+                let message = format!("bad token scheme {}, expected name=prefix:length", line);
+                return Error::new(ErrorKind::InvalidInput, message);
+            };
+            let (name, rest) = line.split_once('=').ok_or_else(bad)?;
+            let mut parts = rest.split(':');
+            let prefix = parts.next().unwrap_or("");
+            let length = parts.next().and_then(|n| n.trim().parse::<usize>().ok());
+            let length = length.filter(|n| *n > 0).ok_or_else(bad)?;
+            let checksum = match parts.next() {
+                None | Some("crc32") => true,
+                Some("nocheck") => false,
+                Some(_) => return Err(bad()),
+            };
+            if name.trim().is_empty() || prefix.is_empty() {
+                return Err(bad());
+            }
+            return Ok(TokenScheme {
+                name: String::from(name.trim()),
+                prefix: String::from(prefix),
+                length,
+                checksum,
+            });
+        }
+        // base62 of crc32 of prefix and random part, zero padded
+        pub fn check(&self, random: &str) -> String {
+            let crc = crc32(format!("{}{}", self.prefix, random).as_bytes());
+            return format!("{:0>width$}", base62(crc as u64), width = CHECK_DIGITS);
+        }
+        pub fn make(&self, random: &str) -> String {
+            if !self.checksum {
+                return format!("{}{}", self.prefix, random);
+            }
+            return format!("{}{}{}", self.prefix, random, self.check(random));
+        }
+        pub fn verify(&self, token: &str) -> bool {
+            let Some(body) = token.strip_prefix(&self.prefix) else {
+                return false;
+            };
+            let check_length = if self.checksum { CHECK_DIGITS } else { 0 };
+            if body.len() != self.length + check_length
+                || !body.chars().all(|c| c.is_ascii_alphanumeric())
+            {
+                return false;
+            }
+            if !self.checksum {
+                return true;
+            }
+            let (random, check) = body.split_at(self.length);
+            return self.check(random) == check;
+        }
+    }
+
+    pub struct TokenRegistry {
+        pub schemes: Vec<TokenScheme>,
+    }
+    impl Default for TokenRegistry {
+        fn default() -> Self {
+            return TokenRegistry::new();
+        }
+    }
+    impl TokenRegistry {
+        // only built in test scheme
+        pub fn new() -> TokenRegistry {
+            let test = TokenScheme {
+                name: String::from("test"),
+                prefix: String::from("strgen_test_"),
+                length: 30,
+                checksum: true,
+            };
+            return TokenRegistry {
+                schemes: vec![test],
+            };
+        }
+        // one scheme per line, # comments, schemes of file override built in ones of same name
+        pub fn load(path: &Path) -> Result<TokenRegistry, Error> {
+            let mut registry = TokenRegistry::new();
+            for line in read_lines(path)? {
+                let line = line?;
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                registry.add(TokenScheme::from(line)?);
+            }
+            return Ok(registry);
+        }
+        // given file, or default one when it exists
+        pub fn open(path: &str) -> Result<TokenRegistry, Error> {
+            if !path.is_empty() {
+                return TokenRegistry::load(Path::new(path));
+            }
+            if Path::new(DEFAULT_TOKEN_REGISTRY).exists() {
+                return TokenRegistry::load(Path::new(DEFAULT_TOKEN_REGISTRY));
+            }
+            return Ok(TokenRegistry::new());
+        }
+        pub fn add(&mut self, scheme: TokenScheme) {
+            self.schemes.retain(|s| s.name != scheme.name);
+            self.schemes.push(scheme);
+        }
+        pub fn find(&self, name: &str) -> Option<&TokenScheme> {
+            return self.schemes.iter().find(|s| s.name == name);
+        }
+        // scheme token was made with, longest matching prefix first
+        pub fn verify(&self, token: &str) -> Option<&TokenScheme> {
+            let mut schemes: Vec<&TokenScheme> = self.schemes.iter().collect();
+            schemes.sort_by_key(|s| std::cmp::Reverse(s.prefix.len()));
+            return schemes.into_iter().find(|s| s.verify(token));
+        }
+    }
+
+    // 4th parameter: scheme[:registry=file], test scheme when empty
+    pub struct TestTokens {
+        scheme: TokenScheme,
+        rng: Box<dyn RngBackend>,
+    }
+    impl Default for TestTokens {
+        fn default() -> Self {
+            return TestTokens::new();
+        }
+    }
+    impl TestTokens {
+        pub fn new() -> TestTokens {
+            let mut rng = RNG::new();
+            rng.seed();
+            return TestTokens {
+                scheme: TokenRegistry::new().schemes[0].clone(),
+                rng: Box::new(rng),
+            };
+        }
+    }
+    impl StringGenerator for TestTokens {
+        fn get(&mut self) -> String {
+            let alphabet = BASE62.as_bytes();
+            let random: String = (0..self.scheme.length)
+                .map(|_| alphabet[self.rng.get() as usize % alphabet.len()] as char)
+                .collect();
+            return self.scheme.make(&random);
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            let next = conf.get_next();
+            let mut parts = next.split(':');
+            let name = parts.next().filter(|n| !n.is_empty()).unwrap_or("test");
+            let mut path = "";
+            for part in parts {
+                if let Some(("registry", file)) = part.split_once('=') {
+                    path = file;
+                }
+            }
+            let registry = TokenRegistry::open(path)?;
+            self.scheme = match registry.find(name) {
+                Some(scheme) => scheme.clone(),
+                None => {
+                    let message = format!("no token scheme {} in registry", name);
+                    return Err(Error::new(ErrorKind::NotFound, message));
+                }
+            };
+            self.rng = conf.backend(0)?;
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,468 @@
+pub mod transforms {
+    use std::io::{Error, ErrorKind};
+
+    use crate::stringer::{json_escape, unit_float, KeyboardLayout, RngBackend, StrgenError};
+
+    // escaping for the context generated strings are embedded into
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum Escape {
+        None,
+        XmlAttribute,
+        HtmlText,
+        Shell,
+        Json,
+    }
+    impl Escape {
+        pub fn from(s: &str) -> Escape {
+            return Escape::parse(s).unwrap_or(Escape::None);
+        }
+        // like from, but unknown escape is none instead of no escaping
+        pub fn parse(s: &str) -> Option<Escape> {
+            return match s {
+                "xml" | "xmlattr" => Some(Escape::XmlAttribute),
+                "html" => Some(Escape::HtmlText),
+                "shell" | "sh" => Some(Escape::Shell),
+                "json" => Some(Escape::Json),
+                "" | "none" => Some(Escape::None),
+                _ => None,
+            };
+        }
+        pub fn apply(&self, s: &str) -> String {
+            return match *self {
+                Escape::None => String::from(s),
+                Escape::XmlAttribute => {
+                    let mut escaped = String::with_capacity(s.len());
+                    for c in s.chars() {
+                        match c {
+                            '&' => escaped.push_str("&amp;"),
+                            '<' => escaped.push_str("&lt;"),
+                            '>' => escaped.push_str("&gt;"),
+                            '"' => escaped.push_str("&quot;"),
+                            '\'' => escaped.push_str("&apos;"),
+                            '\n' => escaped.push_str("&#10;"),
+                            '\t' => escaped.push_str("&#9;"),
+                            c => escaped.push(c),
+                        }
+                    }
+                    escaped
+                }
+                Escape::HtmlText => {
+                    let mut escaped = String::with_capacity(s.len());
+                    for c in s.chars() {
+                        match c {
+                            '&' => escaped.push_str("&amp;"),
+                            '<' => escaped.push_str("&lt;"),
+                            '>' => escaped.push_str("&gt;"),
+                            c => escaped.push(c),
+                        }
+                    }
+                    escaped
+                }
+                // single quoted, quote itself is closed, escaped and reopened
+                Escape::Shell => format!("'{}'", s.replace('\'', "'\\''")),
+                Escape::Json => format!("\"{}\"", json_escape(s)),
+            };
+        }
+    }
+
+    // rfc 3492 punycode of a single label, without the xn-- prefix; none on overflow
+    pub fn punycode(input: &str) -> Option<String> {
+        const BASE: u32 = 36;
+        const TMIN: u32 = 1;
+        const TMAX: u32 = 26;
+        const SKEW: u32 = 38;
+        const DAMP: u32 = 700;
+        fn adapt(mut delta: u32, points: u32, first: bool) -> u32 {
+            delta = if first { delta / DAMP } else { delta / 2 };
+            delta += delta / points;
+            let mut k = 0;
+            while delta > ((BASE - TMIN) * TMAX) / 2 {
+                delta /= BASE - TMIN;
+                k += BASE;
+            }
+            return k + (((BASE - TMIN + 1) * delta) / (delta + SKEW));
+        }
+        fn digit(d: u32) -> char {
+            if d < 26 {
+                return (b'a' + d as u8) as char;
+            }
+            return (b'0' + (d - 26) as u8) as char;
+        }
+        let code_points: Vec<u32> = input.chars().map(|c| c as u32).collect();
+        let mut output: String = input.chars().filter(|c| c.is_ascii()).collect();
+        let basic = output.len() as u32;
+        let mut handled = basic;
+        if basic > 0 {
+            output.push('-');
+        }
+        let mut n: u32 = 128;
+        let mut delta: u32 = 0;
+        let mut bias: u32 = 72;
+        while (handled as usize) < code_points.len() {
+            let m = *code_points.iter().filter(|c| **c >= n).min().unwrap();
+            delta = delta.checked_add((m - n).checked_mul(handled + 1)?)?;
+            n = m;
+            for c in code_points.iter() {
+                if *c < n {
+                    delta = delta.checked_add(1)?;
+                }
+                if *c == n {
+                    let mut q = delta;
+                    let mut k = BASE;
+                    loop {
+                        let t = if k <= bias {
+                            TMIN
+                        } else if k >= bias + TMAX {
+                            TMAX
+                        } else {
+                            k - bias
+                        };
+                        if q < t {
+                            break;
+                        }
+                        output.push(digit(t + (q - t) % (BASE - t)));
+                        q = (q - t) / (BASE - t);
+                        k += BASE;
+                    }
+                    output.push(digit(q));
+                    bias = adapt(delta, handled + 1, handled == basic);
+                    delta = 0;
+                    handled += 1;
+                }
+            }
+            delta = delta.checked_add(1)?;
+            n = n.checked_add(1)?;
+        }
+        return Some(output);
+    }
+
+    // idn style: every label with non ascii characters becomes xn--punycode
+    pub fn idn_encode(s: &str) -> String {
+        let labels: Vec<String> = s
+            .split('.')
+            .map(|label| {
+                if label.is_ascii() {
+                    return String::from(label);
+                }
+                // label too long for punycode is still kept ascii
+                match punycode(&label.to_lowercase()) {
+                    Some(encoded) => format!("xn--{}", encoded),
+                    None => percent_encode(label),
+                }
+            })
+            .collect();
+        return labels.join(".");
+    }
+
+    // rfc 3986 unreserved characters are kept, everything else is %XX per utf-8 byte
+    pub fn percent_encode(s: &str) -> String {
+        let mut encoded = String::with_capacity(s.len());
+        for b in s.bytes() {
+            if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+                encoded.push(b as char);
+            } else {
+                encoded.push_str(&format!("%{:02X}", b));
+            }
+        }
+        return encoded;
+    }
+
+    pub fn backslash_escape(s: &str) -> String {
+        let mut escaped = String::with_capacity(s.len());
+        for c in s.chars() {
+            match c {
+                '\\' => escaped.push_str("\\\\"),
+                '"' => escaped.push_str("\\\""),
+                '\'' => escaped.push_str("\\'"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\t' => escaped.push_str("\\t"),
+                '\0' => escaped.push_str("\\0"),
+                c => escaped.push(c),
+            }
+        }
+        return escaped;
+    }
+
+    // applied when a string is written, may differ per output target
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum Wrap {
+        None,
+        DoubleQuote,
+        SingleQuote,
+        Percent,
+        Punycode,
+        Backslash,
+    }
+    impl Wrap {
+        pub fn from(s: &str) -> Wrap {
+            return Wrap::parse(s).unwrap_or(Wrap::None);
+        }
+        // like from, but unknown wrap is none instead of no wrapping
+        pub fn parse(s: &str) -> Option<Wrap> {
+            return match s {
+                "quote" | "dquote" => Some(Wrap::DoubleQuote),
+                "squote" => Some(Wrap::SingleQuote),
+                "pct" | "percent" => Some(Wrap::Percent),
+                "puny" | "punycode" => Some(Wrap::Punycode),
+                "bs" | "backslash" => Some(Wrap::Backslash),
+                "" | "none" => Some(Wrap::None),
+                _ => None,
+            };
+        }
+        pub fn apply(&self, s: &str) -> String {
+            return match *self {
+                Wrap::None => String::from(s),
+                // quote inside is backslash escaped, and backslash itself
+                Wrap::DoubleQuote => {
+                    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+                }
+                Wrap::SingleQuote => {
+                    format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'"))
+                }
+                Wrap::Percent => percent_encode(s),
+                Wrap::Punycode => idn_encode(s),
+                Wrap::Backslash => backslash_escape(s),
+            };
+        }
+    }
+
+    // wrap=quote for every target or wrap=file:pct,stdout:quote per target
+    pub fn parse_wraps(s: &str) -> Vec<(String, Wrap)> {
+        let mut wraps = Vec::new();
+        for part in s.split(',') {
+            match part.split_once(':') {
+                Some((target, wrap)) => wraps.push((String::from(target), Wrap::from(wrap))),
+                None => wraps.push((String::from("*"), Wrap::from(part))),
+            }
+        }
+        return wraps;
+    }
+
+    // messy user typed look: random case flips (sponge case), doubled letters and
+    // swapped neighbours, each a chance per character
+    #[derive(Clone, Copy, PartialEq, Debug, Default)]
+    pub struct Noise {
+        pub sponge: f64,
+        pub double: f64,
+        pub swap: f64,
+    }
+    impl Noise {
+        // random stream of noise, apart from streams of generators
+        pub const STREAM: u64 = 8;
+
+        // sponge=0.5,double=0.05,swap=0.02, kind without chance gets 0.5 for sponge, 0.05 else
+        pub fn from(s: &str) -> Noise {
+            let mut noise = Noise::default();
+            for part in s.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+                let (kind, chance) = part.split_once('=').unwrap_or((part, ""));
+                let chance = |default: f64| -> f64 {
+                    let chance: f64 = chance.trim().parse().unwrap_or(default);
+                    return chance.clamp(0.0, 1.0);
+                };
+                match kind.trim() {
+                    "sponge" | "case" => noise.sponge = chance(0.5),
+                    "double" | "dup" => noise.double = chance(0.05),
+                    "swap" => noise.swap = chance(0.05),
+                    _ => {}
+                }
+            }
+            return noise;
+        }
+        // from, but unknown kind or chance outside 0..1 is error instead of ignored
+        pub fn parse(s: &str) -> Result<Noise, StrgenError> {
+            for part in s.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+                let (kind, chance) = part.split_once('=').unwrap_or((part, ""));
+                if !["sponge", "case", "double", "dup", "swap"].contains(&kind.trim()) {
+                    return Err(StrgenError::BadValue(String::from("noise"), String::from(part)));
+                }
+                let chance = chance.trim();
+                let fits = chance.parse::<f64>().is_ok_and(|c| (0.0..=1.0).contains(&c));
+                if !chance.is_empty() && !fits {
+                    let what = format!("noise chance of {}", kind.trim());
+                    return Err(StrgenError::BadNumber(what, String::from(chance)));
+                }
+            }
+            return Ok(Noise::from(s));
+        }
+        pub fn is_empty(&self) -> bool {
+            return self.sponge == 0.0 && self.double == 0.0 && self.swap == 0.0;
+        }
+        pub fn apply(&self, s: &str, rng: &mut dyn RngBackend) -> String {
+            let mut chars: Vec<char> = s.chars().collect();
+            if self.swap > 0.0 {
+                let mut i = 0;
+                while i + 1 < chars.len() {
+                    if unit_float(rng) < self.swap {
+                        chars.swap(i, i + 1);
+                        // swapped pair is not swapped back
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            let mut noisy = String::with_capacity(s.len());
+            for c in chars {
+                let c = if self.sponge > 0.0 && unit_float(rng) < self.sponge {
+                    flip_case(c)
+                } else {
+                    c
+                };
+                noisy.push(c);
+                if self.double > 0.0 && c.is_alphabetic() && unit_float(rng) < self.double {
+                    noisy.push(c);
+                }
+            }
+            return noisy;
+        }
+    }
+
+    // letter in other case, characters without case as they are
+    pub fn flip_case(c: char) -> char {
+        let flipped: Vec<char> = if c.is_lowercase() {
+            c.to_uppercase().collect()
+        } else {
+            c.to_lowercase().collect()
+        };
+        // ß to SS is two characters, left alone
+        if flipped.len() == 1 {
+            return flipped[0];
+        }
+        return c;
+    }
+
+    // slips of fingers on real keyboard: neighbouring key hit instead, two keys
+    // typed in wrong order, key missed
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub struct Typos {
+        // chance of typo per character
+        pub rate: f64,
+        pub layout: KeyboardLayout,
+    }
+    impl Default for Typos {
+        fn default() -> Self {
+            return Typos {
+                rate: 0.05,
+                layout: KeyboardLayout::Qwerty,
+            };
+        }
+    }
+    impl Typos {
+        pub const STREAM: u64 = 9;
+
+        // typos=0.05[:qwertz], rate alone is on qwerty
+        pub fn from(s: &str) -> Result<Typos, Error> {
+            let (rate, layout) = s.split_once(':').unwrap_or((s, "qwerty"));
+            let rate: f64 = match rate.trim().parse() {
+                Ok(rate) if (0.0..=1.0).contains(&rate) => rate,
+                _ => {
+                    let message = format!("typo rate is chance between 0 and 1, got {}", rate);
+                    return Err(Error::new(ErrorKind::InvalidInput, message));
+                }
+            };
+            let layout = match KeyboardLayout::from(layout.trim()) {
+                Some(layout) => layout,
+                None => {
+                    let message = format!("unknown keyboard layout {}", layout);
+                    return Err(Error::new(ErrorKind::InvalidInput, message));
+                }
+            };
+            return Ok(Typos { rate, layout });
+        }
+        pub fn apply(&self, s: &str, rng: &mut dyn RngBackend) -> String {
+            let chars: Vec<char> = s.chars().collect();
+            let mut typed = String::with_capacity(s.len());
+            let mut i = 0;
+            while i < chars.len() {
+                let c = chars[i];
+                i += 1;
+                if self.rate == 0.0 || unit_float(rng) >= self.rate {
+                    typed.push(c);
+                    continue;
+                }
+                // most typos are neighbouring keys, rest split between order and misses
+                let kind = rng.get() % 10;
+                let neighbours = self.layout.neighbours(c);
+                if kind < 6 && !neighbours.is_empty() {
+                    typed.push(neighbours[rng.get() as usize % neighbours.len()]);
+                } else if kind < 8 && i < chars.len() {
+                    typed.push(chars[i]);
+                    typed.push(c);
+                    i += 1;
+                } else if chars.len() == 1 {
+                    // nothing left of string is no typo
+                    typed.push(c);
+                }
+            }
+            return typed;
+        }
+    }
+
+    // every letter and digit swapped for random one of same kind, everything else kept:
+    // shape of emails, ids and log lines stays, what they said is gone
+    pub fn scramble(s: &str, rng: &mut dyn RngBackend) -> String {
+        const LOWER: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+        const UPPER: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        const DIGITS: &[u8] = b"0123456789";
+        let mut scrambled = String::with_capacity(s.len());
+        for c in s.chars() {
+            let set = if c.is_ascii_digit() {
+                DIGITS
+            } else if c.is_uppercase() {
+                UPPER
+            } else if c.is_lowercase() {
+                LOWER
+            } else {
+                scrambled.push(c);
+                continue;
+            };
+            scrambled.push(set[rng.get() as usize % set.len()] as char);
+        }
+        return scrambled;
+    }
+
+    // word core cut or filled up to exact width, for systems taking fixed width identifiers
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct Padding {
+        pub width: usize,
+        pub filler: Vec<char>,
+    }
+    impl Padding {
+        // random stream of filler characters
+        pub const STREAM: u64 = 10;
+
+        // pad=16[:digits|letters|hex|own alphabet], digits when left out
+        pub fn from(s: &str) -> Result<Padding, Error> {
+            let (width, filler) = s.split_once(':').unwrap_or((s, "digits"));
+            let width: usize = match width.trim().parse() {
+                Ok(width) if width > 0 => width,
+                _ => {
+                    let message = format!("pad width must be a number above 0, got {}", width);
+                    return Err(Error::new(ErrorKind::InvalidInput, message));
+                }
+            };
+            let filler = match filler {
+                "digits" | "digit" => "0123456789",
+                "letters" | "lower" => "abcdefghijklmnopqrstuvwxyz",
+                "hex" => "0123456789abcdef",
+                "" => {
+                    let message = String::from("pad filler alphabet is empty");
+                    return Err(Error::new(ErrorKind::InvalidInput, message));
+                }
+                own => own,
+            };
+            return Ok(Padding {
+                width,
+                filler: filler.chars().collect(),
+            });
+        }
+        // longer strings lose their end, shorter get random filler characters after them
+        pub fn apply(&self, s: &str, rng: &mut dyn RngBackend) -> String {
+            let mut padded: String = s.chars().take(self.width).collect();
+            for _ in padded.chars().count()..self.width {
+                padded.push(self.filler[rng.get() as usize % self.filler.len()]);
+            }
+            return padded;
+        }
+    }
+}
@@ -0,0 +1,538 @@
+pub mod variants {
+    use std::collections::HashSet;
+    use std::io::{Error, ErrorKind};
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{Config, Phonetic, RNG};
+
+    // single character edits, swap is of two neighbours
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum EditOp {
+        Substitute,
+        Insert,
+        Delete,
+        Swap,
+    }
+    impl EditOp {
+        pub const ALL: [EditOp; 4] = [
+            EditOp::Substitute,
+            EditOp::Insert,
+            EditOp::Delete,
+            EditOp::Swap,
+        ];
+        pub fn from(s: &str) -> Option<EditOp> {
+            return match s {
+                "sub" | "substitute" => Some(EditOp::Substitute),
+                "ins" | "insert" => Some(EditOp::Insert),
+                "del" | "delete" => Some(EditOp::Delete),
+                "swap" | "transpose" => Some(EditOp::Swap),
+                _ => None,
+            };
+        }
+        // every string one edit of this kind away
+        pub fn apply_all(&self, s: &[char], alphabet: &[char]) -> Vec<Vec<char>> {
+            let mut result = Vec::new();
+            match *self {
+                EditOp::Substitute => {
+                    for i in 0..s.len() {
+                        for c in alphabet.iter().filter(|c| **c != s[i]) {
+                            let mut v = s.to_vec();
+                            v[i] = *c;
+                            result.push(v);
+                        }
+                    }
+                }
+                EditOp::Insert => {
+                    for i in 0..=s.len() {
+                        for c in alphabet.iter() {
+                            let mut v = s.to_vec();
+                            v.insert(i, *c);
+                            result.push(v);
+                        }
+                    }
+                }
+                EditOp::Delete => {
+                    for i in 0..s.len() {
+                        let mut v = s.to_vec();
+                        v.remove(i);
+                        result.push(v);
+                    }
+                }
+                EditOp::Swap => {
+                    for i in 1..s.len() {
+                        if s[i] != s[i - 1] {
+                            let mut v = s.to_vec();
+                            v.swap(i - 1, i);
+                            result.push(v);
+                        }
+                    }
+                }
+            }
+            return result;
+        }
+    }
+
+    // 4th parameter: target[:dist=2][:ops=sub,ins,del,swap][:abc=letters][:all]
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct Neighborhood {
+        pub target: String,
+        pub distance: usize,
+        pub ops: Vec<EditOp>,
+        // characters substituted and inserted
+        pub alphabet: String,
+        // every string of neighborhood once, instead of random ones
+        pub enumerate: bool,
+    }
+    impl Neighborhood {
+        pub const ALPHABET: &'static str = "abcdefghijklmnopqrstuvwxyz0123456789";
+        // enumeration beyond this asks for sampling instead
+        pub const MAX_SIZE: usize = 1_000_000;
+
+        pub fn from(s: &str) -> Result<Neighborhood, Error> {
+            let mut hood = Neighborhood {
+                target: String::new(),
+                distance: 1,
+                ops: EditOp::ALL.to_vec(),
+                alphabet: String::from(Neighborhood::ALPHABET),
+                enumerate: false,
+            };
+            let mut parts: Vec<&str> = s.split(':').collect();
+            // options come last, target may hold ':' itself
+            while parts.len() > 1 {
+                let last = parts[parts.len() - 1];
+                if let Some(distance) = last.strip_prefix("dist=") {
+                    hood.distance = distance.parse().unwrap_or(1);
+                } else if let Some(ops) = last.strip_prefix("ops=") {
+                    hood.ops.clear();
+                    for name in ops.split(',').filter(|n| !n.is_empty()) {
+                        match EditOp::from(name) {
+                            Some(op) => hood.ops.push(op),
+                            None => {
+                                let message =
+                                    format!("unknown edit {}, there are sub, ins, del, swap", name);
+                                return Err(Error::new(ErrorKind::InvalidInput, message));
+                            }
+                        }
+                    }
+                } else if let Some(alphabet) = last.strip_prefix("abc=") {
+                    hood.alphabet = String::from(alphabet);
+                } else if last == "all" {
+                    hood.enumerate = true;
+                } else {
+                    break;
+                }
+                parts.pop();
+            }
+            hood.target = parts.join(":");
+            if hood.target.is_empty() || hood.ops.is_empty() || hood.distance == 0 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "similar mode needs target, at least one edit and dist of 1 or more",
+                ));
+            }
+            return Ok(hood);
+        }
+
+        // (string, fewest edits from target), closest first
+        pub fn enumerate(&self) -> Result<Vec<(String, usize)>, Error> {
+            let alphabet: Vec<char> = self.alphabet.chars().collect();
+            let target: Vec<char> = self.target.chars().collect();
+            let mut seen: HashSet<Vec<char>> = HashSet::new();
+            seen.insert(target.clone());
+            let mut frontier = vec![target];
+            let mut result: Vec<(String, usize)> = Vec::new();
+            for edits in 1..=self.distance {
+                let mut next: Vec<Vec<char>> = Vec::new();
+                for s in frontier.iter() {
+                    for op in self.ops.iter() {
+                        for variant in op.apply_all(s, &alphabet) {
+                            if seen.insert(variant.clone()) {
+                                result.push((variant.iter().collect(), edits));
+                                next.push(variant);
+                            }
+                        }
+                    }
+                    if result.len() > Neighborhood::MAX_SIZE {
+                        let message = format!(
+                            "more than {} strings within {} edits of {}, leave out all to sample them",
+                            Neighborhood::MAX_SIZE,
+                            self.distance,
+                            self.target
+                        );
+                        return Err(Error::new(ErrorKind::InvalidInput, message));
+                    }
+                }
+                frontier = next;
+            }
+            return Ok(result);
+        }
+    }
+
+    // strings a few edits away from target: typosquatting domains, fuzzy matching test sets
+    pub struct SimilarStrings {
+        hood: Neighborhood,
+        rng: RNG,
+        // whole neighborhood when enumerating
+        all: Vec<(String, usize)>,
+        position: usize,
+        last_edits: usize,
+    }
+    impl Default for SimilarStrings {
+        fn default() -> Self {
+            return SimilarStrings::new();
+        }
+    }
+    impl SimilarStrings {
+        pub fn new() -> SimilarStrings {
+            let mut rng = RNG::new();
+            rng.seed();
+            return SimilarStrings {
+                hood: Neighborhood {
+                    target: String::new(),
+                    distance: 1,
+                    ops: EditOp::ALL.to_vec(),
+                    alphabet: String::from(Neighborhood::ALPHABET),
+                    enumerate: false,
+                },
+                rng,
+                all: Vec::new(),
+                position: 0,
+                last_edits: 0,
+            };
+        }
+        // random edits, 1 to distance of them, never target itself
+        fn sample(&mut self) -> String {
+            let alphabet: Vec<char> = self.hood.alphabet.chars().collect();
+            let target: Vec<char> = self.hood.target.chars().collect();
+            loop {
+                let edits = 1 + self.rng.get() as usize % self.hood.distance;
+                let mut s = target.clone();
+                for _ in 0..edits {
+                    let op = self.hood.ops[self.rng.get() as usize % self.hood.ops.len()];
+                    let variants = op.apply_all(&s, &alphabet);
+                    if !variants.is_empty() {
+                        s = variants[self.rng.get() as usize % variants.len()].clone();
+                    }
+                }
+                if s != target {
+                    self.last_edits = edits;
+                    return s.iter().collect();
+                }
+            }
+        }
+    }
+    impl StringGenerator for SimilarStrings {
+        fn get(&mut self) -> String {
+            if !self.hood.enumerate {
+                return self.sample();
+            }
+            let (s, edits) = match self.all.get(self.position) {
+                Some(entry) => entry.clone(),
+                None => return String::new(),
+            };
+            self.position += 1;
+            self.last_edits = edits;
+            return s;
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            self.hood = Neighborhood::from(&conf.get_next())?;
+            if self.hood.enumerate {
+                self.all = self.hood.enumerate()?;
+                self.position = 0;
+                return Ok(());
+            }
+            // sampling would look for ever
+            let alphabet: Vec<char> = self.hood.alphabet.chars().collect();
+            let target: Vec<char> = self.hood.target.chars().collect();
+            if self
+                .hood
+                .ops
+                .iter()
+                .all(|op| op.apply_all(&target, &alphabet).is_empty())
+            {
+                let message = format!("no string is one edit away from {}", self.hood.target);
+                return Err(Error::new(ErrorKind::InvalidInput, message));
+            }
+            return Ok(());
+        }
+        fn exhausted(&self) -> bool {
+            return self.hood.enumerate && self.position >= self.all.len();
+        }
+        fn fields(&self) -> Vec<(String, String)> {
+            return vec![(String::from("edits"), self.last_edits.to_string())];
+        }
+    }
+
+    // spellings english reads the same, kept only when metaphone code stays
+    pub const SPELLINGS: [(&str, &str); 24] = [
+        ("ph", "f"),
+        ("ck", "k"),
+        ("c", "k"),
+        ("ee", "ea"),
+        ("ee", "i"),
+        ("ie", "y"),
+        ("y", "i"),
+        ("z", "s"),
+        ("x", "ks"),
+        ("qu", "kw"),
+        ("ou", "ow"),
+        ("ai", "ay"),
+        ("ei", "ey"),
+        ("sch", "sh"),
+        ("dg", "j"),
+        ("ll", "l"),
+        ("nn", "n"),
+        ("tt", "t"),
+        ("ss", "s"),
+        ("rr", "r"),
+        ("mm", "m"),
+        ("ff", "f"),
+        ("a", "e"),
+        ("o", "u"),
+    ];
+    // ways same name gets written in latin letters, from cyrillic, arabic, german..
+    pub const TRANSLITERATIONS: [(&str, &str); 14] = [
+        ("v", "w"),
+        ("yo", "jo"),
+        ("ya", "ja"),
+        ("yu", "ju"),
+        ("ts", "tz"),
+        ("tz", "z"),
+        ("kh", "h"),
+        ("ov", "off"),
+        ("ev", "eff"),
+        ("ski", "sky"),
+        ("sh", "sch"),
+        ("ch", "tch"),
+        ("ou", "u"),
+        ("mm", "m"),
+    ];
+
+    fn is_letter_at(s: &str, i: usize) -> bool {
+        return s[i..].chars().next().is_some_and(|c| c.is_alphabetic());
+    }
+
+    const DIGRAPHS: [&str; 8] = ["ph", "sh", "ch", "th", "gh", "wh", "kh", "ck"];
+
+    // rewriting h of philip reads different, rewrite may not cut digraph in half
+    fn splits_digraph(s: &str, start: usize, end: usize) -> bool {
+        let before = s[..start].chars().next_back();
+        let first = s[start..end].chars().next();
+        let last = s[start..end].chars().next_back();
+        let after = s[end..].chars().next();
+        return DIGRAPHS.iter().any(|d| {
+            let pair = |a: Option<char>, b: Option<char>| match (a, b) {
+                (Some(a), Some(b)) => format!("{}{}", a, b) == *d,
+                _ => false,
+            };
+            pair(before, first) || pair(last, after)
+        });
+    }
+
+    // every string one rewrite away, with rewrite as from>to, both directions of each pair
+    fn rewrites(s: &str, rules: &[(&str, &str)]) -> Vec<(String, String)> {
+        let mut result = Vec::new();
+        for (a, b) in rules.iter() {
+            for (from, to) in [(a, b), (b, a)] {
+                for (i, _m) in s.match_indices(*from) {
+                    if splits_digraph(s, i, i + from.len()) {
+                        continue;
+                    }
+                    let mut variant = String::from(&s[..i]);
+                    variant.push_str(to);
+                    variant.push_str(&s[i + from.len()..]);
+                    result.push((variant, format!("{}>{}", from, to)));
+                }
+            }
+        }
+        // final e, clark and clarke
+        for (i, c) in s.char_indices() {
+            let end = i + c.len_utf8();
+            let word_end = !is_letter_at(s, end);
+            if !word_end || !c.is_alphabetic() {
+                continue;
+            }
+            if c == 'e' && i > 0 {
+                result.push((format!("{}{}", &s[..i], &s[end..]), String::from("e>")));
+            } else if !"aeiouy".contains(c) {
+                result.push((format!("{}e{}", &s[..end], &s[end..]), String::from(">e")));
+            }
+        }
+        return result;
+    }
+
+    // 4th parameter: name[:depth=2][:translit=0][:all]
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct SoundAlikeSpec {
+        pub name: String,
+        // rewrites applied one after other
+        pub depth: usize,
+        pub transliterations: bool,
+        pub enumerate: bool,
+    }
+    impl SoundAlikeSpec {
+        pub fn from(s: &str) -> Result<SoundAlikeSpec, Error> {
+            let mut spec = SoundAlikeSpec {
+                name: String::new(),
+                depth: 2,
+                transliterations: true,
+                enumerate: false,
+            };
+            let mut parts: Vec<&str> = s.split(':').collect();
+            while parts.len() > 1 {
+                let last = parts[parts.len() - 1];
+                if let Some(depth) = last.strip_prefix("depth=") {
+                    spec.depth = depth.parse().unwrap_or(2);
+                } else if let Some(translit) = last.strip_prefix("translit=") {
+                    spec.transliterations = translit != "0";
+                } else if last == "all" {
+                    spec.enumerate = true;
+                } else {
+                    break;
+                }
+                parts.pop();
+            }
+            spec.name = parts.join(":");
+            if spec.name.trim().is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "sound mode needs name as 4th parameter",
+                ));
+            }
+            return Ok(spec);
+        }
+
+        // (variant, rewrites that made it), fewest rewrites first
+        pub fn variants(&self) -> Result<Vec<(String, String)>, Error> {
+            let name = self.name.to_lowercase();
+            let code = Phonetic::Metaphone.encode(&name);
+            let mut seen: HashSet<String> = HashSet::new();
+            seen.insert(name.clone());
+            // variant, changes, code its spelling rewrites must keep
+            let mut frontier: Vec<(String, String, String)> = vec![(name, String::new(), code)];
+            let mut result: Vec<(String, String)> = Vec::new();
+            for _ in 0..self.depth {
+                let mut next = Vec::new();
+                for (s, changes, code) in frontier.iter() {
+                    let mut candidates: Vec<(String, String, bool)> = rewrites(s, &SPELLINGS)
+                        .into_iter()
+                        .map(|(v, c)| (v, c, false))
+                        .collect();
+                    if self.transliterations {
+                        // other script, spelling may read different in english and still be same name
+                        for (v, c) in rewrites(s, &TRANSLITERATIONS) {
+                            candidates.push((v, c, true));
+                        }
+                    }
+                    for (variant, change, transliterated) in candidates {
+                        if variant.trim().is_empty() || seen.contains(&variant) {
+                            continue;
+                        }
+                        let variant_code = Phonetic::Metaphone.encode(&variant);
+                        if !transliterated && variant_code != *code {
+                            continue;
+                        }
+                        seen.insert(variant.clone());
+                        let changes = if changes.is_empty() {
+                            change
+                        } else {
+                            format!("{}+{}", changes, change)
+                        };
+                        result.push((variant.clone(), changes.clone()));
+                        next.push((variant, changes, variant_code));
+                    }
+                    if result.len() > Neighborhood::MAX_SIZE {
+                        let message = format!("too many variants of {}, lower depth", self.name);
+                        return Err(Error::new(ErrorKind::InvalidInput, message));
+                    }
+                }
+                frontier = next;
+            }
+            return Ok(result);
+        }
+    }
+
+    // dupont as Dupont when name was written so
+    fn like_name(variant: &str, name: &str) -> String {
+        if !name.chars().next().is_some_and(|c| c.is_uppercase()) {
+            return String::from(variant);
+        }
+        let mut result = String::new();
+        let mut word_start = true;
+        for c in variant.chars() {
+            if word_start {
+                result.extend(c.to_uppercase());
+            } else {
+                result.push(c);
+            }
+            word_start = !c.is_alphabetic();
+        }
+        return result;
+    }
+
+    // spellings of name that sound the same, for identity matching and deduplication tests
+    pub struct SoundAlikes {
+        spec: SoundAlikeSpec,
+        rng: RNG,
+        variants: Vec<(String, String)>,
+        position: usize,
+        last_changes: String,
+    }
+    impl Default for SoundAlikes {
+        fn default() -> Self {
+            return SoundAlikes::new();
+        }
+    }
+    impl SoundAlikes {
+        pub fn new() -> SoundAlikes {
+            let mut rng = RNG::new();
+            rng.seed();
+            return SoundAlikes {
+                spec: SoundAlikeSpec {
+                    name: String::new(),
+                    depth: 2,
+                    transliterations: true,
+                    enumerate: false,
+                },
+                rng,
+                variants: Vec::new(),
+                position: 0,
+                last_changes: String::new(),
+            };
+        }
+    }
+    impl StringGenerator for SoundAlikes {
+        fn get(&mut self) -> String {
+            let index = if self.spec.enumerate {
+                self.position += 1;
+                self.position - 1
+            } else {
+                self.rng.get() as usize % self.variants.len().max(1)
+            };
+            let (variant, changes) = match self.variants.get(index) {
+                Some(entry) => entry.clone(),
+                None => return String::new(),
+            };
+            self.last_changes = changes;
+            return like_name(&variant, &self.spec.name);
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            self.rng = conf.rng(0);
+            self.spec = SoundAlikeSpec::from(&conf.get_next())?;
+            self.variants = self.spec.variants()?;
+            self.position = 0;
+            if self.variants.is_empty() {
+                let message = format!("no variants sounding like {} found", self.spec.name);
+                return Err(Error::new(ErrorKind::InvalidInput, message));
+            }
+            return Ok(());
+        }
+        fn exhausted(&self) -> bool {
+            return self.spec.enumerate && self.position >= self.variants.len();
+        }
+        fn fields(&self) -> Vec<(String, String)> {
+            return vec![(String::from("changes"), self.last_changes.clone())];
+        }
+    }
+}
@@ -0,0 +1,227 @@
+pub mod vault {
+    use std::fs::File;
+    use std::io::{stdin, stdout, BufRead, Error, ErrorKind, Write};
+    use std::path::{Path, PathBuf};
+
+    use crate::stringer::{tr, wipe, wipe_bytes, OutputSink, Record, Wrap};
+
+    // file starts with magic, scrypt log_n, r and p (big endian), salt and nonce
+    pub const MAGIC: &[u8; 8] = b"STRGENC1";
+    pub const SALT_LEN: usize = 16;
+    pub const NONCE_LEN: usize = 12;
+    pub const HEADER_LEN: usize = 8 + 1 + 4 + 4 + SALT_LEN + NONCE_LEN;
+    // scrypt cost, 2^15 with r=8 takes 32MB and a fraction of second
+    pub const DEFAULT_LOG_N: u8 = 15;
+    // highest cost accepted from file header, 2^20 takes 1GB, more would let file exhaust memory
+    pub const MAX_LOG_N: u8 = 20;
+    pub const PASSPHRASE_VAR: &str = "STRGEN_PASSPHRASE";
+
+    fn no_encryption() -> Error {
+        return Error::new(
+            ErrorKind::Unsupported,
+            "encrypted output needs the encrypt feature (cargo build --features encrypt)",
+        );
+    }
+    // error when binary was built without encryption, checked before asking for passphrase
+    pub fn encryption_available() -> Result<(), Error> {
+        if cfg!(feature = "encrypt") {
+            return Ok(());
+        }
+        return Err(no_encryption());
+    }
+
+    #[cfg(feature = "encrypt")]
+    fn derive_key(
+        passphrase: &str,
+        salt: &[u8],
+        log_n: u8,
+        r: u32,
+        p: u32,
+    ) -> Result<[u8; 32], Error> {
+        let params = scrypt::Params::new(log_n, r, p, 32)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+        let mut key = [0u8; 32];
+        scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+        return Ok(key);
+    }
+
+    // aes-256-gcm under key derived from passphrase with scrypt of cost 2^log_n
+    #[cfg(feature = "encrypt")]
+    pub fn encrypt_with(plain: &[u8], passphrase: &str, log_n: u8) -> Result<Vec<u8>, Error> {
+        use aes_gcm::aead::rand_core::RngCore;
+        use aes_gcm::aead::{Aead, KeyInit, OsRng};
+        use aes_gcm::{Aes256Gcm, Key, Nonce};
+        let (r, p) = (8u32, 1u32);
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+        let key = derive_key(passphrase, &salt, log_n, r, p)?;
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+        let sealed = cipher
+            .encrypt(Nonce::from_slice(&nonce), plain)
+            .map_err(|_e| Error::other("encryption failed"))?;
+        let mut out: Vec<u8> = Vec::with_capacity(HEADER_LEN + sealed.len());
+        out.extend_from_slice(MAGIC);
+        out.push(log_n);
+        out.extend_from_slice(&r.to_be_bytes());
+        out.extend_from_slice(&p.to_be_bytes());
+        out.extend_from_slice(&salt);
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&sealed);
+        return Ok(out);
+    }
+    #[cfg(not(feature = "encrypt"))]
+    pub fn encrypt_with(_plain: &[u8], _passphrase: &str, _log_n: u8) -> Result<Vec<u8>, Error> {
+        return Err(no_encryption());
+    }
+
+    pub fn encrypt(plain: &[u8], passphrase: &str) -> Result<Vec<u8>, Error> {
+        return encrypt_with(plain, passphrase, DEFAULT_LOG_N);
+    }
+
+    // wrong passphrase and tampered file look the same: tag does not match
+    #[cfg(feature = "encrypt")]
+    pub fn decrypt(data: &[u8], passphrase: &str) -> Result<Vec<u8>, Error> {
+        use aes_gcm::aead::{Aead, KeyInit};
+        use aes_gcm::{Aes256Gcm, Key, Nonce};
+        if data.len() < HEADER_LEN || &data[..8] != MAGIC {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "not an encrypted strgen file",
+            ));
+        }
+        let log_n = data[8];
+        let r = u32::from_be_bytes([data[9], data[10], data[11], data[12]]);
+        let p = u32::from_be_bytes([data[13], data[14], data[15], data[16]]);
+        // only parameters encrypt writes, anything else is damaged or crafted file
+        if log_n > MAX_LOG_N || r != 8 || p != 1 {
+            let message = format!(
+                "unsupported scrypt parameters log_n={} r={} p={}",
+                log_n, r, p
+            );
+            return Err(Error::new(ErrorKind::InvalidData, message));
+        }
+        let salt = &data[17..17 + SALT_LEN];
+        let nonce = &data[17 + SALT_LEN..HEADER_LEN];
+        let key = derive_key(passphrase, salt, log_n, r, p)?;
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+        return cipher
+            .decrypt(Nonce::from_slice(nonce), &data[HEADER_LEN..])
+            .map_err(|_e| Error::new(ErrorKind::InvalidData, "wrong passphrase or damaged file"));
+    }
+    #[cfg(not(feature = "encrypt"))]
+    pub fn decrypt(_data: &[u8], _passphrase: &str) -> Result<Vec<u8>, Error> {
+        return Err(no_encryption());
+    }
+
+    // terminal stops echoing typed characters while this lives
+    struct NoEcho {
+        active: bool,
+    }
+    impl NoEcho {
+        fn enable() -> NoEcho {
+            use std::io::IsTerminal;
+            if !cfg!(unix) || !stdin().is_terminal() {
+                return NoEcho { active: false };
+            }
+            let status = std::process::Command::new("stty").arg("-echo").status();
+            return NoEcho {
+                active: matches!(status, Ok(s) if s.success()),
+            };
+        }
+    }
+    impl Drop for NoEcho {
+        fn drop(&mut self) {
+            if self.active {
+                let _ = std::process::Command::new("stty").arg("echo").status();
+                println!();
+            }
+        }
+    }
+
+    fn prompt(key: &str) -> Result<String, Error> {
+        print!("{}", tr(key));
+        stdout().flush()?;
+        let _no_echo = NoEcho::enable();
+        let mut line = String::new();
+        stdin().lock().read_line(&mut line)?;
+        return Ok(String::from(line.trim_end_matches(['\n', '\r'])));
+    }
+
+    // from STRGEN_PASSPHRASE or typed without echo, twice when confirm is set
+    pub fn read_passphrase(confirm: bool) -> Result<String, Error> {
+        if let Ok(passphrase) = std::env::var(PASSPHRASE_VAR) {
+            if !passphrase.is_empty() {
+                return Ok(passphrase);
+            }
+        }
+        let passphrase = prompt("vault.passphrase")?;
+        if passphrase.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "empty passphrase"));
+        }
+        if confirm && prompt("vault.confirm")? != passphrase {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "passphrases do not match",
+            ));
+        }
+        return Ok(passphrase);
+    }
+
+    // keeps lines in memory and writes them encrypted at the end, plaintext never touches disk
+    pub struct EncryptedFileSink {
+        path: PathBuf,
+        passphrase: String,
+        plain: Vec<u8>,
+        wrap: Wrap,
+    }
+    impl EncryptedFileSink {
+        pub fn create(
+            path: &Path,
+            passphrase: String,
+            wrap: Wrap,
+        ) -> Result<EncryptedFileSink, Error> {
+            // fail before generating rather than after
+            encryption_available()?;
+            return Ok(EncryptedFileSink {
+                path: path.to_path_buf(),
+                passphrase,
+                plain: Vec::new(),
+                wrap,
+            });
+        }
+    }
+    impl Drop for EncryptedFileSink {
+        fn drop(&mut self) {
+            wipe_bytes(&mut self.plain);
+            wipe(&mut self.passphrase);
+        }
+    }
+    impl OutputSink for EncryptedFileSink {
+        fn write(&mut self, record: &Record) -> Result<(), Error> {
+            let mut text = record.text(self.wrap);
+            let needed = self.plain.len() + text.len() + 1;
+            // growing would leave old buffer behind unwiped, so move over by hand
+            if needed > self.plain.capacity() {
+                let mut grown: Vec<u8> = Vec::with_capacity(needed.max(self.plain.capacity() * 2));
+                grown.extend_from_slice(&self.plain);
+                wipe_bytes(&mut self.plain);
+                self.plain = grown;
+            }
+            self.plain.extend_from_slice(text.as_bytes());
+            self.plain.push(b'\n');
+            wipe(&mut text);
+            return Ok(());
+        }
+        fn finish(&mut self) -> Result<(), Error> {
+            let sealed = encrypt(&self.plain, &self.passphrase)?;
+            wipe_bytes(&mut self.plain);
+            self.plain.clear();
+            let mut file = File::create(&self.path)?;
+            file.write_all(&sealed)?;
+            return file.flush();
+        }
+    }
+}
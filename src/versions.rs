@@ -0,0 +1,68 @@
+pub mod versions {
+    use crate::stringer::RngBackend;
+
+    // generator algorithm versions
+    // every change that alters output for the same rng state gets a new variant,
+    // old variants must keep producing byte-identical output
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum GeneratorVersion {
+        // plain modulo over rng output
+        V1,
+        // rejection sampling, no modulo bias
+        V2,
+    }
+    impl GeneratorVersion {
+        pub fn latest() -> GeneratorVersion {
+            return GeneratorVersion::V2;
+        }
+        pub fn from(s: &str) -> GeneratorVersion {
+            return GeneratorVersion::parse(s).unwrap_or(GeneratorVersion::latest());
+        }
+        // like from, but unknown version is none instead of latest
+        pub fn parse(s: &str) -> Option<GeneratorVersion> {
+            return match s.trim().trim_start_matches(['v', 'V']) {
+                "1" => Some(GeneratorVersion::V1),
+                "2" => Some(GeneratorVersion::V2),
+                _ => None,
+            };
+        }
+        pub fn number(&self) -> u32 {
+            return match *self {
+                GeneratorVersion::V1 => 1,
+                GeneratorVersion::V2 => 2,
+            };
+        }
+        // index into collection of len elements, generators check for empty ones in setup
+        pub fn pick_index(&self, rng: &mut dyn RngBackend, len: usize) -> usize {
+            assert!(len > 0, "pick_index from empty collection");
+            match *self {
+                GeneratorVersion::V1 => {
+                    return rng.get() as usize % len;
+                }
+                GeneratorVersion::V2 => {
+                    let len = len as u32;
+                    // largest multiple of len, values above are rejected
+                    let zone = u32::MAX - (u32::MAX % len);
+                    loop {
+                        let num = rng.get();
+                        if num < zone {
+                            return (num % len) as usize;
+                        }
+                    }
+                }
+            }
+        }
+        // index picked proportionally to weights, which must not sum to zero
+        pub fn pick_weighted(&self, rng: &mut dyn RngBackend, weights: &[u32]) -> usize {
+            let total: u32 = weights.iter().sum();
+            let mut point = self.pick_index(rng, total as usize) as u32;
+            for (i, weight) in weights.iter().enumerate() {
+                if point < *weight {
+                    return i;
+                }
+                point -= weight;
+            }
+            return weights.len() - 1;
+        }
+    }
+}
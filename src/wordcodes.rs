@@ -0,0 +1,367 @@
+pub mod wordcodes {
+    use std::collections::HashMap;
+    use std::io::{Error, ErrorKind};
+    use std::path::Path;
+
+    use crate::strgen::string_generator_module::StringGenerator;
+    use crate::stringer::{Config, Languages, ListType, WordList, RNG};
+
+    // words of triple are joined with dot, like web addresses
+    pub const TRIPLE_SEPARATOR: char = '.';
+    // words of wordified number are joined with hyphen
+    pub const WORDIFY_SEPARATOR: char = '-';
+
+    fn gcd(a: u128, b: u128) -> u128 {
+        return if b == 0 { a } else { gcd(b, a % b) };
+    }
+
+    // x with a * x = 1 mod m, a and m coprime
+    fn inverse(a: u128, m: u128) -> u128 {
+        let (mut t, mut new_t): (i128, i128) = (0, 1);
+        let (mut r, mut new_r): (i128, i128) = (m as i128, a as i128);
+        while new_r != 0 {
+            let q = r / new_r;
+            (t, new_t) = (new_t, t - q * new_t);
+            (r, new_r) = (new_r, r - q * new_r);
+        }
+        return t.rem_euclid(m as i128) as u128;
+    }
+
+    // digits, most significant first, of same number in other base; zero is one digit
+    pub fn convert_base(digits: &[u32], from: u32, to: u32) -> Vec<u32> {
+        let mut number: Vec<u32> = digits.iter().skip_while(|d| **d == 0).copied().collect();
+        let mut converted: Vec<u32> = Vec::new();
+        // long division by to, remainders are digits from least significant one
+        while !number.is_empty() {
+            let mut quotient: Vec<u32> = Vec::with_capacity(number.len());
+            let mut remainder: u64 = 0;
+            for digit in number.iter() {
+                let value = remainder * from as u64 + *digit as u64;
+                let q = (value / to as u64) as u32;
+                if !quotient.is_empty() || q != 0 {
+                    quotient.push(q);
+                }
+                remainder = value % to as u64;
+            }
+            converted.push(remainder as u32);
+            number = quotient;
+        }
+        if converted.is_empty() {
+            converted.push(0);
+        }
+        converted.reverse();
+        return converted;
+    }
+
+    // decimal number, or hex with 0x or with letters a-f in it
+    pub fn number_digits(s: &str) -> Option<(Vec<u32>, u32)> {
+        let s = s.trim().to_lowercase();
+        let (s, base) = match s.strip_prefix("0x") {
+            Some(hex) => (String::from(hex), 16),
+            None if s
+                .chars()
+                .any(|c| c.is_ascii_hexdigit() && !c.is_ascii_digit()) =>
+            {
+                (s, 16)
+            }
+            None => (s, 10),
+        };
+        if s.is_empty() {
+            return None;
+        }
+        let digits: Option<Vec<u32>> = s.chars().map(|c| c.to_digit(base)).collect();
+        return Some((digits?, base));
+    }
+
+    // every number below words^3 is one ordered triple of words and back; numbers are spread
+    // over triples by fixed permutation, so neighbouring ids share no words. Order of words
+    // is the grid, same lists always give same triples
+    pub struct WordGrid {
+        words: Vec<String>,
+        index: HashMap<String, usize>,
+        capacity: u128,
+        step: u128,
+        offset: u128,
+    }
+    impl WordGrid {
+        // lower case, repeated words once
+        pub fn new(words: &[String]) -> Result<WordGrid, Error> {
+            let mut unique: Vec<String> = Vec::new();
+            let mut index: HashMap<String, usize> = HashMap::new();
+            for word in words.iter().map(|w| w.trim().to_lowercase()) {
+                let separator = word.contains(TRIPLE_SEPARATOR) || word.contains(WORDIFY_SEPARATOR);
+                if word.is_empty() || separator || index.contains_key(&word) {
+                    continue;
+                }
+                index.insert(word.clone(), unique.len());
+                unique.push(word);
+            }
+            if unique.len() < 2 {
+                let message = "word grid needs at least two distinct words";
+                return Err(Error::new(ErrorKind::InvalidData, message));
+            }
+            let n = unique.len() as u128;
+            let capacity = n * n * n;
+            // golden ratio of grid, made coprime so permutation can be undone
+            let mut step = capacity * 618_033_989 / 1_000_000_000;
+            while gcd(step, capacity) != 1 {
+                step += 1;
+            }
+            return Ok(WordGrid {
+                words: unique,
+                index,
+                capacity,
+                step,
+                offset: capacity / 3,
+            });
+        }
+        // nouns, adjectives and verbs of language, in that order
+        pub fn for_language(language: &Languages) -> Result<WordGrid, Error> {
+            let mut words: Vec<String> = Vec::new();
+            let kinds = [ListType::Nouns, ListType::Adjectives, ListType::Verbs];
+            for kind in kinds {
+                let mut list = WordList::new(kind, language.clone());
+                // languages without verbs list still have nouns and adjectives
+                if list.fill(Path::new("")).is_err() {
+                    continue;
+                }
+                for i in 0..list.get_list_len() {
+                    words.push(list.get_at(i));
+                }
+            }
+            return WordGrid::new(&words);
+        }
+        pub fn words(&self) -> usize {
+            return self.words.len();
+        }
+        // numbers 0 up to capacity have triples
+        pub fn capacity(&self) -> u128 {
+            return self.capacity;
+        }
+        pub fn encode(&self, number: u128) -> Option<String> {
+            if number >= self.capacity {
+                return None;
+            }
+            let n = self.words.len() as u128;
+            let spread = (number * self.step + self.offset) % self.capacity;
+            let digits = [spread / (n * n), spread / n % n, spread % n];
+            let words: Vec<&str> = digits
+                .iter()
+                .map(|d| self.words[*d as usize].as_str())
+                .collect();
+            return Some(words.join(&TRIPLE_SEPARATOR.to_string()));
+        }
+        // words may be in any case, separated by dots or spaces
+        pub fn decode(&self, triple: &str) -> Option<u128> {
+            let words: Vec<String> = triple
+                .split(|c: char| c == TRIPLE_SEPARATOR || c.is_whitespace())
+                .filter(|w| !w.is_empty())
+                .map(|w| w.to_lowercase())
+                .collect();
+            if words.len() != 3 {
+                return None;
+            }
+            let n = self.words.len() as u128;
+            let mut spread: u128 = 0;
+            for word in words.iter() {
+                spread = spread * n + *self.index.get(word)? as u128;
+            }
+            let shifted = (spread + self.capacity - self.offset) % self.capacity;
+            return Some(shifted * inverse(self.step, self.capacity) % self.capacity);
+        }
+        // number of any size as words, list is digits of base words, most significant first
+        pub fn wordify(&self, number: &str) -> Option<String> {
+            let (digits, base) = number_digits(number)?;
+            let words: Vec<&str> = convert_base(&digits, base, self.words.len() as u32)
+                .iter()
+                .map(|d| self.words[*d as usize].as_str())
+                .collect();
+            return Some(words.join(&WORDIFY_SEPARATOR.to_string()));
+        }
+        // number of wordified words, decimal or hex with 0x; leading zeros of hex are lost
+        pub fn unwordify(&self, words: &str, hex: bool) -> Option<String> {
+            let mut digits: Vec<u32> = Vec::new();
+            for word in words
+                .split(|c: char| c == WORDIFY_SEPARATOR || c.is_whitespace())
+                .filter(|w| !w.is_empty())
+            {
+                digits.push(*self.index.get(&word.to_lowercase())? as u32);
+            }
+            if digits.is_empty() {
+                return None;
+            }
+            let base = if hex { 16 } else { 10 };
+            let number: String = convert_base(&digits, self.words.len() as u32, base)
+                .iter()
+                .filter_map(|d| char::from_digit(*d, base))
+                .collect();
+            if hex {
+                return Some(format!("0x{}", number));
+            }
+            return Some(number);
+        }
+        // rows of latitude and columns of longitude, twice as many, filling capacity
+        pub fn grid(&self) -> (u128, u128) {
+            let rows = ((self.capacity / 2) as f64).sqrt().floor().max(1.0) as u128;
+            return (rows, rows * 2);
+        }
+        // number of cell lat,long falls in, none outside of earth
+        pub fn cell(&self, lat: f64, long: f64) -> Option<u128> {
+            if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&long) {
+                return None;
+            }
+            let (rows, cols) = self.grid();
+            let row = (((lat + 90.0) / 180.0 * rows as f64) as u128).min(rows - 1);
+            let col = (((long + 180.0) / 360.0 * cols as f64) as u128).min(cols - 1);
+            return Some(row * cols + col);
+        }
+        // lat,long of middle of cell
+        pub fn cell_center(&self, number: u128) -> Option<(f64, f64)> {
+            let (rows, cols) = self.grid();
+            if number >= rows * cols {
+                return None;
+            }
+            let lat = ((number / cols) as f64 + 0.5) / rows as f64 * 180.0 - 90.0;
+            let long = ((number % cols) as f64 + 0.5) / cols as f64 * 360.0 - 180.0;
+            return Some((lat, long));
+        }
+    }
+
+    // lat,long cell or plain number
+    pub fn parse_grid_point(grid: &WordGrid, s: &str) -> Result<u128, Error> {
+        let bad = |expected: String| {
+            let message = format!("word triple of {}, expected {}", s, expected);
+            return Error::new(ErrorKind::InvalidInput, message);
+        };
+        if let Some((lat, long)) = s.split_once(',') {
+            let earth = || bad(String::from("lat -90..90,long -180..180"));
+            let lat: f64 = lat.trim().parse().map_err(|_e| earth())?;
+            let long: f64 = long.trim().parse().map_err(|_e| earth())?;
+            return grid.cell(lat, long).ok_or_else(earth);
+        }
+        let below = || bad(format!("number below {} or lat,long", grid.capacity()));
+        let number: u128 = s.trim().parse().map_err(|_e| below())?;
+        if number >= grid.capacity() {
+            return Err(below());
+        }
+        return Ok(number);
+    }
+
+    // 4th parameter: number or lat,long, then :language; random number when left out
+    pub struct WordTriples {
+        grid: Option<WordGrid>,
+        number: Option<u128>,
+        last: u128,
+        rng: RNG,
+    }
+    impl Default for WordTriples {
+        fn default() -> Self {
+            return WordTriples::new();
+        }
+    }
+    impl WordTriples {
+        pub fn new() -> WordTriples {
+            let mut rng = RNG::new();
+            rng.seed();
+            return WordTriples {
+                grid: None,
+                number: None,
+                last: 0,
+                rng,
+            };
+        }
+    }
+    impl StringGenerator for WordTriples {
+        fn get(&mut self) -> String {
+            let Some(grid) = self.grid.as_ref() else {
+                return String::new();
+            };
+            self.last = match self.number {
+                Some(number) => number,
+                None => {
+                    let random = (self.rng.get() as u128) << 32 | self.rng.get() as u128;
+                    random % grid.capacity()
+                }
+            };
+            return grid.encode(self.last).unwrap_or_default();
+        }
+        fn fields(&self) -> Vec<(String, String)> {
+            return vec![(String::from("number"), self.last.to_string())];
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            let next = conf.get_next();
+            let (point, language) = next.split_once(':').unwrap_or((next.as_str(), "en"));
+            let grid = WordGrid::for_language(&Languages::from(language))?;
+            self.number = if point.trim().is_empty() {
+                None
+            } else {
+                Some(parse_grid_point(&grid, point)?)
+            };
+            self.grid = Some(grid);
+            self.rng = conf.rng(0);
+            return Ok(());
+        }
+    }
+
+    // 4th parameter: number[:language], decimal or hex, random number of len digits when
+    // left out (64 bits for len 0)
+    pub struct Wordified {
+        grid: Option<WordGrid>,
+        number: String,
+        digits: u32,
+        last: String,
+        rng: RNG,
+    }
+    impl Default for Wordified {
+        fn default() -> Self {
+            return Wordified::new();
+        }
+    }
+    impl Wordified {
+        pub fn new() -> Wordified {
+            let mut rng = RNG::new();
+            rng.seed();
+            return Wordified {
+                grid: None,
+                number: String::new(),
+                digits: 0,
+                last: String::new(),
+                rng,
+            };
+        }
+    }
+    impl StringGenerator for Wordified {
+        fn get(&mut self) -> String {
+            let Some(grid) = self.grid.as_ref() else {
+                return String::new();
+            };
+            self.last = if !self.number.is_empty() {
+                self.number.clone()
+            } else if self.digits == 0 {
+                ((self.rng.get() as u64) << 32 | self.rng.get() as u64).to_string()
+            } else {
+                (0..self.digits)
+                    .map(|_| char::from(b'0' + (self.rng.get() % 10) as u8))
+                    .collect()
+            };
+            return grid.wordify(&self.last).unwrap_or_default();
+        }
+        fn fields(&self) -> Vec<(String, String)> {
+            return vec![(String::from("number"), self.last.clone())];
+        }
+        fn setup(&mut self, conf: &Config) -> Result<(), Error> {
+            let next = conf.get_next();
+            let (number, language) = next.split_once(':').unwrap_or((next.as_str(), "en"));
+            let number = number.trim();
+            if !number.is_empty() && number_digits(number).is_none() {
+                let message = format!("wordify encodes decimal or hex numbers, got {}", number);
+                return Err(Error::new(ErrorKind::InvalidInput, message));
+            }
+            self.number = String::from(number);
+            self.digits = conf.get_length();
+            self.grid = Some(WordGrid::for_language(&Languages::from(language))?);
+            self.rng = conf.rng(0);
+            return Ok(());
+        }
+    }
+}
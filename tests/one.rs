@@ -1,13 +1,34 @@
 #[cfg(test)]
 pub mod tests {
     // use stranameg::stringer::languages::languages::Languages;
-    use stranameg::stringer::{Config, Languages, ListType, command_parser};
+    use stranameg::stringer::{Config, GeneratorVersion, Languages, ListType, RNG, command_parser};
 
     use stranameg::strgen::string_generator_module::*;
+
+    // sink keeping every record written to it
+    struct Collect(Vec<stranameg::stringer::Record>);
+    impl Collect {
+        fn new() -> Collect {
+            return Collect(Vec::new());
+        }
+        fn values(&self) -> Vec<String> {
+            return self.0.iter().map(|record| record.value.clone()).collect();
+        }
+        fn labels(&self) -> Vec<String> {
+            return self.0.iter().map(|record| record.label.clone()).collect();
+        }
+    }
+    impl stranameg::stringer::OutputSink for Collect {
+        fn write(&mut self, record: &stranameg::stringer::Record) -> std::io::Result<()> {
+            self.0.push(record.clone());
+            return Ok(());
+        }
+    }
+
     #[test]
     fn unnamed() {
         use stranameg::stringer::print_help;
-        print_help();
+        print_help().unwrap();
         assert_eq!(1, 1);
     }
     #[test]
@@ -18,15 +39,10 @@ pub mod tests {
         let mode = String::from("rls");
         let lang = String::from("en");
         let sarraya: [String; 5] = [String::new(), ammount, len, mode, lang];
-        let conf = Config::new(&sarraya);
+        let conf = Config::new(&sarraya).unwrap();
 
         let mut sg = LettterSequence::new("abc", length);
-        let result = sg.setup(&conf);
-        if result.is_err(){
-
-        }else{
-
-        }
+        sg.setup(&conf).ok();
         assert_eq!(sg.get().len(), length);
     }
     #[test]
@@ -37,7 +53,7 @@ pub mod tests {
         let mode = String::from("cow");
         let lang = String::from("ka");
         let sarraya: [String; 5] = [String::new(), ammount, len, mode, lang];
-        let conf = Config::new(&sarraya);
+        let conf = Config::new(&sarraya).unwrap();
 
         let lstype = ListType::Adjectives;
         let lan = Languages::Georgian;
@@ -55,7 +71,7 @@ pub mod tests {
         let kachars = "აბგდევზთიკლმნოპჟრსტუფქღყშჩცძწჭხჯჰ";
         for c in strong.chars() {
             let check = kachars.contains(c) || c == ' ' || c =='_';
-            assert_eq!(check, true);
+            assert!(check);
         }
     }
     
@@ -69,9 +85,2687 @@ pub mod tests {
         let alphabet = "alphabet";
         println!("{}", &strong);
         for s in strong.chars() {
-            assert_eq!(alphabet.contains(s), true);
+            assert!(alphabet.contains(s));
         }
         assert_eq!(sg.get().len(), 12);
     }
 
+    #[test]
+    fn generator_version() {
+        let vargs = vec!["mode=rls", "ver=v1"];
+        let confetti = command_parser::get_config(vargs);
+        assert_eq!(confetti.get_version(), GeneratorVersion::V1);
+        assert!(confetti.metadata().contains("generator=v1"));
+
+        let mut rng = RNG::new();
+        rng.seed();
+        for _i in 0..100 {
+            assert!(GeneratorVersion::V2.pick_index(&mut rng, 7) < 7);
+        }
+    }
+
+    #[test]
+    fn localized_messages() {
+        use stranameg::stringer::{set_ui_language, tr, tr_with, StrgenError};
+        set_ui_language(Languages::German);
+        assert_eq!(tr("repl.welcome"), "Willkommen im REPL-Modus");
+        assert_eq!(tr_with("cli.error", &["x"]), "Fehler:x");
+        let error = StrgenError::UnknownMode(String::from("nope"));
+        assert_eq!(error.to_string(), "unbekannter Modus nope, siehe --help für Modi");
+        // georgian catalog is partial, english is the fallback
+        set_ui_language(Languages::Georgian);
+        assert_eq!(tr("repl.missing"), "pass other parameters for this mode");
+        assert_eq!(tr("no.such.key"), "no.such.key");
+        assert_eq!(error.to_string(), "unknown mode nope, see --help for modes");
+        set_ui_language(Languages::English);
+        // french and spanish are ui languages only, word lists stay english
+        assert_eq!(Languages::from_code("FR").abbr(), "fr");
+        assert_eq!(Languages::from("fr").abbr(), "en");
+        assert_eq!(Languages::from("es").get_alphabet(), Languages::English.get_alphabet());
+    }
+
+    #[test]
+    fn list_file_paths() {
+        use std::ffi::OsStr;
+        use std::path::PathBuf;
+        use stranameg::stringer::split_list_files;
+        let split = split_list_files(OsStr::new("C:\\lists\\a.list:b.list"));
+        assert_eq!(split, vec![PathBuf::from("C:\\lists\\a.list"), PathBuf::from("b.list")]);
+        let split = split_list_files(OsStr::new("sample.list:sample2.list"));
+        assert_eq!(split.len(), 2);
+    }
+
+    #[test]
+    fn sequential_ids() {
+        use stranameg::stringer::SequentialId;
+        let path = std::env::temp_dir().join("stranameg_test.counter");
+        let _ = std::fs::remove_file(&path);
+        let mut seq = SequentialId::new();
+        seq.set_prefix("INV-");
+        seq.set_counter_path(path.clone());
+        assert_eq!(seq.reserve(2).unwrap(), 1);
+        assert_eq!(seq.get(), "INV-000001");
+        assert_eq!(seq.get(), "INV-000002");
+        // second run continues after reserved block
+        assert_eq!(seq.reserve(2).unwrap(), 3);
+        // ids past the block, e.g. when filters rejected some, come from new block
+        assert_eq!(seq.get(), "INV-000003");
+        assert_eq!(seq.get(), "INV-000004");
+        assert_eq!(seq.get(), "INV-000005");
+        assert_eq!(std::fs::read_to_string(&path).unwrap().trim(), "6");
+        // counter file broken while run is going, next block can't be reserved
+        std::fs::write(&path, "junk").unwrap();
+        seq.get();
+        assert!(seq.get().is_empty() && seq.exhausted());
+        assert_eq!(seq.failure().unwrap().kind(), std::io::ErrorKind::InvalidData);
+        let _ = std::fs::remove_file(&path);
+        let next = format!("next=INV-:six:{}", path.display());
+        let conf = command_parser::get_config(vec!["mode=seq", &next]);
+        let e = stranameg::stringer::stringer(conf.clone()).setup(&conf).err().unwrap();
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn snowflake_ids() {
+        use stranameg::stringer::{base62, Snowflake};
+        assert_eq!(base62(61), "z");
+        assert_eq!(base62(62), "10");
+        let mut flake = Snowflake::new();
+        flake.set_worker(5);
+        let first = flake.next_id();
+        let second = flake.next_id();
+        assert!(second > first);
+        assert_eq!((first >> 12) & 0x3ff, 5);
+    }
+
+    #[test]
+    fn log_timestamps() {
+        use stranameg::stringer::{apache, iso8601, json_escape};
+        assert_eq!(iso8601(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(iso8601(1700000000123), "2023-11-14T22:13:20.123Z");
+        assert_eq!(apache(1700000000123), "14/Nov/2023:22:13:20 +0000");
+        assert_eq!(json_escape("a\"b\\c\n"), "a\\\"b\\\\c\\n");
+    }
+
+    #[test]
+    fn safe_file_names() {
+        use stranameg::stringer::safe_file_name;
+        assert_eq!(safe_file_name("a:b?c"), "a_b_c");
+        assert_eq!(safe_file_name("con.txt"), "_con.txt");
+        assert_eq!(safe_file_name("name. "), "name");
+    }
+
+    #[test]
+    fn semver_range() {
+        use stranameg::stringer::SemVer;
+        let mut semver = SemVer::new();
+        semver.set_range("1.2.3-1.2.9");
+        for _i in 0..20 {
+            let version = semver.get();
+            assert!(version.starts_with("1.2."));
+            let patch: u64 = version[4..].parse().unwrap();
+            assert!((3..=9).contains(&patch));
+        }
+    }
+
+    #[test]
+    fn iban_and_currency() {
+        use stranameg::stringer::{iban_check_digits, is_valid_iban, CurrencyAmount, Iban};
+        // example from the iban registry
+        assert_eq!(iban_check_digits("GB", "WEST12345698765432"), "82");
+        assert!(is_valid_iban("GB82 WEST 1234 5698 7654 32"));
+        let mut iban = Iban::new();
+        iban.set_countries("GE,DE");
+        for _i in 0..10 {
+            assert!(is_valid_iban(&iban.get()));
+        }
+        assert_eq!(CurrencyAmount::new(Languages::English).format(123456789), "$1,234,567.89");
+        assert_eq!(CurrencyAmount::new(Languages::German).format(123456), "1.234,56 €");
+    }
+
+    #[test]
+    fn geohash_encoding() {
+        use stranameg::stringer::{geohash, Coordinates};
+        assert_eq!(geohash(57.64911, 10.40744, 11), "u4pruydqqvj");
+        let mut coords = Coordinates::new();
+        assert!(coords.set_bbox("41.0,40.0,43.5,46.7"));
+        assert!(!coords.set_bbox("1,2,3"));
+        let line = coords.get();
+        let lat: f64 = line.split(',').next().unwrap().parse().unwrap();
+        assert!((41.0..=43.5).contains(&lat));
+    }
+
+    #[test]
+    fn color_palettes() {
+        use stranameg::stringer::{hsl_to_hex, ColorPalette, PaletteFormat};
+        assert_eq!(hsl_to_hex(0.0, 1.0, 0.5), "#ff0000");
+        assert_eq!(hsl_to_hex(240.0, 1.0, 0.5), "#0000ff");
+        let palette = ColorPalette::new(4, PaletteFormat::Json);
+        assert_eq!(palette.palette("brave-lion"), palette.palette("brave-lion"));
+        assert_eq!(palette.palette("brave-lion").len(), 4);
+    }
+
+    #[test]
+    fn cron_fields() {
+        use stranameg::stringer::CronExpression;
+        let mut cron = CronExpression::new();
+        cron.set_fields("min,hour");
+        for _i in 0..20 {
+            let expression = cron.get();
+            let fields: Vec<&str> = expression.split(' ').collect();
+            assert_eq!(fields.len(), 5);
+            assert_eq!(&fields[2..], &["*", "*", "*"]);
+        }
+    }
+
+    #[test]
+    fn http_slugs() {
+        use stranameg::stringer::slugify;
+        assert_eq!(slugify("Gear Box!"), "gear-box");
+        assert_eq!(slugify("  a__b "), "a-b");
+    }
+
+    #[test]
+    fn jwt_shape() {
+        use stranameg::stringer::{base64url, JwtShaped};
+        assert_eq!(base64url(b"{\"alg\":\"HS256\"}"), "eyJhbGciOiJIUzI1NiJ9");
+        assert_eq!(base64url(&[0xfb, 0xff]), "-_8");
+        let mut jwt = JwtShaped::new();
+        jwt.set_sizes(30, Some(32));
+        let token = jwt.get();
+        let segments: Vec<&str> = token.split('.').collect();
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[1].len(), 40);
+        assert_eq!(segments[2].len(), 43);
+    }
+
+    #[test]
+    fn secrets_corpus() {
+        use stranameg::stringer::SecretsCorpus;
+        let mut corpus = SecretsCorpus::new();
+        let (secret, text) = corpus.sample("aws_access_key_id");
+        assert!(secret.starts_with("AKIA"));
+        assert_eq!(secret.len(), 20);
+        assert!(text.contains(&secret));
+        corpus.set_labels("none");
+        assert!(corpus.get().contains("\"label\":\"none\""));
+    }
+
+    #[test]
+    fn escaping() {
+        use stranameg::stringer::Escape;
+        assert_eq!(Escape::from("xml").apply("a<b&\"c'"), "a&lt;b&amp;&quot;c&apos;");
+        assert_eq!(Escape::from("html").apply("<a>\""), "&lt;a&gt;\"");
+        assert_eq!(Escape::from("shell").apply("it's"), "'it'\\''s'");
+        assert_eq!(Escape::from("json").apply("a\"b"), "\"a\\\"b\"");
+        assert_eq!(Escape::from("").apply("x&y"), "x&y");
+    }
+
+    #[test]
+    fn output_wraps() {
+        use stranameg::stringer::{idn_encode, parse_wraps, percent_encode, punycode, Wrap};
+        assert_eq!(punycode("münchen").unwrap(), "mnchen-3ya");
+        // delta past u32 is refused, as rfc 3492 asks
+        assert_eq!(punycode(&format!("{}\u{10ffff}", "a".repeat(5000))), None);
+        assert_eq!(Wrap::from("quote").apply("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(Wrap::from("squote").apply("it's"), "'it\\'s'");
+        assert_eq!(idn_encode("bücher.example"), "xn--bcher-kva.example");
+        assert_eq!(percent_encode("a b/ü"), "a%20b%2F%C3%BC");
+        assert_eq!(Wrap::from("bs").apply("a\"b\n"), "a\\\"b\\n");
+        let vargs = vec!["wrap=quote,file:pct"];
+        let confetti = command_parser::get_config(vargs);
+        assert_eq!(confetti.get_wrap("stdout"), Wrap::DoubleQuote);
+        assert_eq!(confetti.get_wrap("file"), Wrap::Percent);
+        assert_eq!(parse_wraps("squote")[0].1, Wrap::SingleQuote);
+    }
+
+    #[test]
+    fn custom_sink() {
+        use stranameg::stringer::{csv_field, run_generator_with};
+        let vargs = vec!["mode=rls", "num=5", "len=6"];
+        let confetti = command_parser::get_config(vargs);
+        let mut sink = Collect::new();
+        run_generator_with(&confetti, &mut sink).unwrap();
+        assert_eq!(sink.0.len(), 5);
+        assert_eq!(sink.0[4].index, 4);
+        assert_eq!(sink.0[0].value.chars().count(), 6);
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+
+    #[test]
+    fn http_sink() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+        use std::thread;
+        use stranameg::stringer::{run_generator_with, HttpPostSink, Wrap};
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let mut bodies: Vec<String> = Vec::new();
+            for (n, stream) in listener.incoming().take(3).enumerate() {
+                let mut stream = stream.unwrap();
+                let mut buf = [0u8; 4096];
+                let mut request = String::new();
+                while !request.contains("\r\n\r\n") || !request.ends_with(']') {
+                    let read = stream.read(&mut buf).unwrap();
+                    request.push_str(&String::from_utf8_lossy(&buf[..read]));
+                }
+                // first attempt fails, sink has to retry
+                if n == 0 {
+                    stream.write_all(b"HTTP/1.1 503 Busy\r\n\r\n").unwrap();
+                    continue;
+                }
+                stream.write_all(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+                bodies.push(request.split("\r\n\r\n").nth(1).unwrap().to_string());
+            }
+            return bodies;
+        });
+        let vargs = vec!["mode=rls", "num=5", "len=4"];
+        let confetti = command_parser::get_config(vargs);
+        let url = format!("http://127.0.0.1:{}/ingest", port);
+        let mut sink = HttpPostSink::new(&url, 3, Wrap::None).unwrap();
+        run_generator_with(&confetti, &mut sink).unwrap();
+        let bodies = server.join().unwrap();
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(bodies[0].matches(',').count(), 2);
+        assert_eq!(bodies[1].matches(',').count(), 1);
+        assert!(HttpPostSink::new("https://example.com", 3, Wrap::None).is_err());
+        let https = stranameg::stringer::open_sink("https://example.com/in", &confetti);
+        assert_eq!(https.err().unwrap().kind(), std::io::ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn kafka_sink_spec() {
+        use stranameg::stringer::open_sink;
+        let confetti = command_parser::get_config(vec!["num=1"]);
+        // no topic, or no kafka feature, either way nothing is opened
+        assert!(open_sink("kafka:127.0.0.1:9092", &confetti).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_socket_sink() {
+        use std::io::{BufRead, BufReader};
+        use std::os::unix::net::UnixListener;
+        use std::thread;
+        use stranameg::stringer::{run_generator_with, SlowReader, StreamSink, Wrap};
+        let path = std::env::temp_dir().join(format!("strgen-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let reader = thread::spawn(move || {
+            let (stream, _addr) = listener.accept().unwrap();
+            return BufReader::new(stream).lines().count();
+        });
+        let confetti = command_parser::get_config(vec!["mode=rls", "num=3000", "len=8"]);
+        let mut sink = StreamSink::socket(&path, SlowReader::Block, Wrap::None);
+        run_generator_with(&confetti, &mut sink).unwrap();
+        assert_eq!(sink.get_dropped(), 0);
+        assert_eq!(reader.join().unwrap(), 3000);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(SlowReader::from("drop"), SlowReader::Drop);
+    }
+
+    #[test]
+    fn tee_outputs() {
+        use stranameg::stringer::{open_sinks, out_spec};
+        assert_eq!(out_spec("-"), "stdout");
+        assert_eq!(out_spec("csv:a.csv"), "csv:a.csv");
+        assert_eq!(out_spec("out.txt"), "file:out.txt");
+        let dir = std::env::temp_dir();
+        let first = dir.join(format!("strgen-tee-{}.txt", std::process::id()));
+        let second = dir.join(format!("strgen-tee-{}.csv", std::process::id()));
+        let vargs = vec!["mode=rls", "num=4", "len=5"];
+        let mut confetti = command_parser::get_config(vargs);
+        confetti.add_sink(out_spec(first.to_str().unwrap()));
+        confetti.add_sink(format!("csv:{}", second.display()));
+        assert_eq!(confetti.get_sinks().len(), 2);
+        let mut sink = open_sinks(&confetti.get_sinks(), &confetti).unwrap();
+        stranameg::stringer::run_generator_with(&confetti, sink.as_mut()).unwrap();
+        let text = std::fs::read_to_string(&first).unwrap();
+        let csv = std::fs::read_to_string(&second).unwrap();
+        assert_eq!(text.lines().count(), 4);
+        assert_eq!(csv.lines().count(), 5);
+        assert!(csv.contains(text.lines().next().unwrap()));
+        for path in [&first, &second] {
+            let _ = std::fs::remove_file(path);
+            let _ = std::fs::remove_file(stranameg::stringer::sidecar(path, "meta"));
+        }
+    }
+
+    #[test]
+    fn labels_and_grouping() {
+        use std::io::Error;
+        use std::sync::{Arc, Mutex};
+        use stranameg::stringer::{run_generator_with, GroupingSink, OutputSink, Record, Wrap};
+        struct Shared(Arc<Mutex<Vec<Record>>>);
+        impl OutputSink for Shared {
+            fn write(&mut self, record: &Record) -> Result<(), Error> {
+                self.0.lock().unwrap().push(record.clone());
+                return Ok(());
+            }
+        }
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let vargs = vec!["mode=rls", "num=3", "labels=1"];
+        let confetti = command_parser::get_config(vargs);
+        run_generator_with(&confetti, &mut Shared(seen.clone())).unwrap();
+        assert_eq!(seen.lock().unwrap()[0].label, "rls");
+        let confetti = command_parser::get_config(vec!["mode=rls", "label=fuzz"]);
+        assert!(confetti.get_labels());
+
+        seen.lock().unwrap().clear();
+        let mut grouped = GroupingSink::new(Box::new(Shared(seen.clone())));
+        for (i, label) in ["a", "b", "a", "b"].iter().enumerate() {
+            let record = Record::new(i as u32, String::from("x"), String::from("rls"));
+            grouped.write(&record.with_label(label.to_string())).unwrap();
+        }
+        grouped.finish().unwrap();
+        let indices: Vec<u32> = seen.lock().unwrap().iter().map(|r| r.index).collect();
+        assert_eq!(indices, vec![0, 2, 1, 3]);
+        let record = Record::new(0, String::from("v"), String::from("m"));
+        assert_eq!(record.with_label(String::from("l")).text(Wrap::None), "v\tl");
+    }
+
+    #[test]
+    fn mixed_modes() {
+        use stranameg::stringer::{parse_mix, quotas, run_generator_with};
+        assert_eq!(quotas(&[70, 20, 10], 10), vec![7, 2, 1]);
+        assert_eq!(quotas(&[1, 1, 1], 10), vec![4, 3, 3]);
+        let (entries, blocks) = parse_mix("cow@de=70,jwt=20,blocks").unwrap();
+        assert!(blocks);
+        assert_eq!(entries[0].next, "de");
+        assert!(parse_mix("rls=x").is_err());
+        let e = parse_mix("foo=1").err().unwrap();
+        let known = stranameg::stringer::strgen_error(&e);
+        assert!(matches!(known, Some(stranameg::stringer::StrgenError::UnknownMode(_))));
+        let vargs = vec!["mode=mix", "num=30", "next=rls=60,pass=30", "labels=1"];
+        let confetti = command_parser::get_config(vargs);
+        let mut sink = Collect::new();
+        run_generator_with(&confetti, &mut sink).unwrap();
+        let labels = sink.labels();
+        assert_eq!(labels.iter().filter(|l| *l == "rls").count(), 20);
+        assert_eq!(labels.iter().filter(|l| *l == "pass").count(), 10);
+        let vargs = vec!["mode=mix", "num=9", "next=rls=2,pass=1,blocks", "labels=1"];
+        let confetti = command_parser::get_config(vargs);
+        let mut sink = Collect::new();
+        run_generator_with(&confetti, &mut sink).unwrap();
+        assert_eq!(sink.labels()[5..7], ["rls", "pass"]);
+    }
+
+    #[test]
+    fn corpus_sampling() {
+        use std::collections::HashSet;
+        use stranameg::stringer::CorpusSample;
+        let path = std::env::temp_dir().join(format!("strgen-corpus-{}.txt", std::process::id()));
+        std::fs::write(&path, "alpha,1\nbeta,0\ngamma,3\ndelta,2\n").unwrap();
+        let next = format!("next={}:norep", path.display());
+        let confetti = command_parser::get_config(vec!["mode=sample", "num=4", next.as_ref()]);
+        let mut sample = CorpusSample::new();
+        sample.setup(&confetti).unwrap();
+        assert_eq!(sample.len(), 4);
+        let drawn: HashSet<String> = (0..4).map(|_| sample.get()).collect();
+        assert_eq!(drawn.len(), 4);
+        assert_eq!(sample.get(), "");
+
+        let next = format!("next={}:col=2:sep=,", path.display());
+        let confetti = command_parser::get_config(vec!["mode=sample", "num=200", next.as_ref()]);
+        let mut sample = CorpusSample::new();
+        sample.setup(&confetti).unwrap();
+        assert!((0..200).all(|_| sample.get() != "beta,0"));
+
+        let next = format!("next={}:norep:col=2:sep=,", path.display());
+        let confetti = command_parser::get_config(vec!["mode=sample", "num=4", next.as_ref()]);
+        assert!(CorpusSample::new().setup(&confetti).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn shuffle_splits() {
+        use stranameg::stringer::shuffle_file;
+        let path = std::env::temp_dir().join(format!("strgen-shuffle-{}.txt", std::process::id()));
+        let lines: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        let written = shuffle_file(&path, &[80, 10, 10], 7).unwrap();
+        let parts: Vec<String> = written.iter().map(|p| std::fs::read_to_string(p).unwrap()).collect();
+        assert!(written[1].to_string_lossy().ends_with(".val"));
+        assert_eq!(parts[0].lines().count(), 80);
+        assert_eq!(parts[2].lines().count(), 10);
+        let mut all: Vec<u32> = parts.concat().lines().map(|l| l.parse().unwrap()).collect();
+        assert_ne!(all[..10].to_vec(), (0..10).collect::<Vec<u32>>());
+        all.sort();
+        assert_eq!(all, (0..100).collect::<Vec<u32>>());
+        // same seed, same split
+        shuffle_file(&path, &[80, 10, 10], 7).unwrap();
+        assert_eq!(std::fs::read_to_string(&written[0]).unwrap(), parts[0]);
+        for p in written.iter().chain([&path]) {
+            let _ = std::fs::remove_file(p);
+        }
+        assert_eq!(RNG::from_seed(1).get(), RNG::from_seed(1).get());
+    }
+
+    #[test]
+    fn cartesian_pairs() {
+        use std::collections::HashSet;
+        let dir = std::env::temp_dir();
+        let adjectives = dir.join(format!("strgen-adj-{}.list", std::process::id()));
+        let nouns = dir.join(format!("strgen-noun-{}.list", std::process::id()));
+        std::fs::write(&adjectives, "red,blue,green").unwrap();
+        std::fs::write(&nouns, "cat,dog").unwrap();
+        let next = format!("next={}:{}", adjectives.display(), nouns.display());
+        let confetti = command_parser::get_config(vec!["mode=pairs", next.as_ref()]);
+        let mut pairs = CartesianPairs::new(Languages::English);
+        pairs.setup(&confetti).unwrap();
+        assert_eq!(pairs.total(), 6);
+        assert_eq!(pairs.get(), "red_cat");
+        assert_eq!(pairs.get(), "red_dog");
+
+        let next = format!("{}:shuffle", next);
+        let confetti = command_parser::get_config(vec!["mode=pairs", next.as_ref()]);
+        let mut pairs = CartesianPairs::new(Languages::English);
+        pairs.setup(&confetti).unwrap();
+        let seen: HashSet<String> = (0..6).map(|_| pairs.get()).collect();
+        assert_eq!(seen.len(), 6);
+        assert!(pairs.exhausted());
+        let _ = std::fs::remove_file(&adjectives);
+        let _ = std::fs::remove_file(&nouns);
+    }
+
+    #[test]
+    fn coverage_sampling() {
+        use std::collections::HashSet;
+        use std::path::Path;
+        let mut list = WordList::new(ListType::Nouns, Languages::English);
+        for word in ["a", "b", "c", "d", "e"] {
+            list.add_word(String::from(word));
+        }
+        list.set_coverage(true);
+        let first: HashSet<String> = (0..5).map(|_| list.get()).collect();
+        assert_eq!(first.len(), 5);
+        let second: HashSet<String> = (0..5).map(|_| list.get()).collect();
+        assert_eq!(second.len(), 5);
+
+        let confetti = command_parser::get_config(vec!["mode=cow", "cover=1"]);
+        assert!(confetti.get_coverage());
+        let mut full = WordList::new(ListType::Adjectives, Languages::English);
+        full.fill(Path::new("")).unwrap();
+        let mut words = CoupledWords::new(ListType::Nouns, Languages::English);
+        words.setup(&confetti).unwrap();
+        let used: HashSet<String> = (0..full.get_list_len())
+            .map(|_| words.get().split('_').next().unwrap().to_string())
+            .collect();
+        assert_eq!(used.len(), full.get_list_len());
+    }
+
+    #[test]
+    fn stratified_sampling() {
+        let mut list = WordList::new(ListType::Nouns, Languages::English);
+        for (word, tag) in [("lion", "animal"), ("cat", "animal"), ("car", "object"), ("cup", "object")] {
+            list.add_tagged_word(String::from(word), String::from(tag));
+        }
+        list.set_strata(&Strata::from("tag:animal=30,object=70"), 10).unwrap();
+        let words: Vec<String> = (0..10).map(|_| list.get()).collect();
+        let animals = words.iter().filter(|w| *w == "lion" || *w == "cat").count();
+        assert_eq!(animals, 3);
+        list.set_strata(&Strata::from("len"), 8).unwrap();
+        let fours = (0..8).filter(|_| list.get().len() == 4).count();
+        assert_eq!(fours, 4);
+        assert!(list.set_strata(&Strata::from("tag:plant=1"), 5).is_err());
+        assert_eq!(Strata::from("len:4=1"), Strata::Lengths(vec![(4, 1)]));
+    }
+
+    #[test]
+    fn run_assertions() {
+        use stranameg::stringer::{run_generator_with, RunStats};
+        let mut stats = RunStats::new(true);
+        for value in ["ab", "ab", "abcd", "xy"] {
+            stats.add(value);
+        }
+        assert_eq!(stats.distinct(), 3);
+        assert_eq!(stats.duplicate_rate(), 0.25);
+        assert_eq!(stats.mean_length(), 2.5);
+
+        let vargs = vec!["mode=rls", "num=50", "len=8", "max_dup=0", "mean_len=8:0"];
+        let confetti = command_parser::get_config(vargs);
+        assert!(run_generator_with(&confetti, &mut Collect::new()).is_ok());
+        // 3 letters of 'abc' alphabet can't give 40 distinct strings
+        let vargs = vec!["mode=rla", "next=ab", "num=50", "len=3", "min_distinct=40"];
+        let confetti = command_parser::get_config(vargs);
+        assert!(run_generator_with(&confetti, &mut Collect::new()).is_err());
+    }
+
+    #[test]
+    fn similarity_filter() {
+        use stranameg::stringer::{levenshtein, run_generator_with, BkTree};
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        let mut tree = BkTree::new();
+        for word in ["book", "books", "cake", "boo", "cape", "cart"] {
+            tree.insert(word);
+        }
+        assert!(tree.has_within("bock", 1));
+        assert!(!tree.has_within("xyzw", 2));
+        let vargs = vec!["mode=rls", "num=40", "len=5", "min_dist=3", "min_prefix=2"];
+        let confetti = command_parser::get_config(vargs);
+        let mut sink = Collect::new();
+        run_generator_with(&confetti, &mut sink).unwrap();
+        let values = sink.values();
+        for (i, a) in values.iter().enumerate() {
+            for b in values[i + 1..].iter() {
+                assert!(levenshtein(a, b) >= 3);
+                assert_ne!(a[..2], b[..2]);
+            }
+        }
+        // only 4 strings of length 2 over 'ab'
+        let vargs = vec!["mode=rla", "next=ab", "num=5", "len=2", "min_dist=1"];
+        let confetti = command_parser::get_config(vargs);
+        assert!(run_generator_with(&confetti, &mut Collect::new()).is_err());
+    }
+
+    #[test]
+    fn phonetic_codes() {
+        use stranameg::stringer::{metaphone, soundex, Phonetic, PhoneticFilter, StringFilter};
+        assert_eq!(soundex("Robert"), "R163");
+        assert_eq!(soundex("Rupert"), "R163");
+        assert_eq!(soundex("Ashcraft"), "A261");
+        assert_eq!(soundex("Tymczak"), "T522");
+        assert_eq!(metaphone("knight"), "NT");
+        assert_eq!(metaphone("Thumb"), "0M");
+        assert_eq!(metaphone("Phillips"), "FLPS");
+        assert_eq!(metaphone("Smith"), metaphone("Smyth"));
+        let mut filter = PhoneticFilter::new(Phonetic::Soundex);
+        filter.remember("Robert Smith");
+        assert!(!filter.accepts("Rupert Smyth"));
+        assert!(filter.accepts("Robert Jones"));
+        assert_eq!(Phonetic::Metaphone.encode("ლომი"), "ლომი");
+    }
+
+    #[test]
+    fn reserved_words() {
+        use stranameg::stringer::{build_filters, ReservedWordFilter};
+        let sets = vec![String::from("sql"), String::from("windows")];
+        let filter = ReservedWordFilter::new(&sets).unwrap();
+        assert!(filter.is_reserved("SELECT"));
+        assert!(filter.is_reserved("docs/Con.txt"));
+        assert!(!filter.is_reserved("console"));
+        assert!(ReservedWordFilter::new(&[String::from("cobol")]).is_err());
+        let confetti = command_parser::get_config(vec!["mode=rls", "exclude=sql,fpath:windows"]);
+        assert_eq!(build_filters(&confetti).unwrap().len(), 1);
+        let confetti = command_parser::get_config(vec!["mode=rls", "exclude=fpath:windows"]);
+        assert_eq!(build_filters(&confetti).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn trademark_screening() {
+        use stranameg::stringer::{Phonetic, Screening, StringFilter, TrademarkFilter};
+        let screening = Screening::from("dir/names.txt:dist=1:phon=soundex:log=out.tsv");
+        assert_eq!(screening.path.to_str(), Some("dir/names.txt"));
+        assert_eq!(screening.max_distance, 1);
+        assert_eq!(screening.phonetic, Some(Phonetic::Soundex));
+        let names = vec![String::from("Coca-Cola"), String::from("Nike")];
+        let mut filter = TrademarkFilter::new(&names, 1, Some(Phonetic::Soundex));
+        assert!(!filter.accepts("cocacolo"));
+        assert!(!filter.accepts("Nyke"));
+        assert!(filter.accepts("bluebird"));
+        let dir = std::env::temp_dir().join("strgen_trademark");
+        std::fs::create_dir_all(&dir).unwrap();
+        let list = dir.join("names.txt");
+        let log = dir.join("rejected.tsv");
+        std::fs::write(&list, "# protected\nNike\n").unwrap();
+        let spec = format!("{}:dist=1:log={}", list.display(), log.display());
+        let mut filter = TrademarkFilter::load(&Screening::from(&spec)).unwrap();
+        assert!(!filter.accepts("Mike"));
+        assert!(filter.accepts("Adidas"));
+        drop(filter);
+        assert_eq!(std::fs::read_to_string(&log).unwrap(), "Mike\tNike\tedit 1\n");
+    }
+
+    #[test]
+    fn ranked_candidates() {
+        use stranameg::stringer::{
+            alliteration, pronounceability, rarity, run_generator_with, Scoring,
+        };
+        assert_eq!(pronounceability("banana"), 1.0);
+        assert!(pronounceability("strngth") < 0.5);
+        assert!(rarity("zzz") > rarity("eee"));
+        assert_eq!(alliteration("big bad wolf"), 0.5);
+        assert!(Scoring::from("pron,smell=2").is_err());
+        let scoring = Scoring::from("allit=2,len=-0.1").unwrap();
+        assert!(scoring.score("red rose") > scoring.score("red tulip"));
+        let vargs = vec!["mode=rank", "next=cow@en", "num=5", "over=20"];
+        let confetti = command_parser::get_config(vargs);
+        let mut sink = Collect::new();
+        run_generator_with(&confetti, &mut sink).unwrap();
+        assert_eq!(sink.0.len(), 5);
+        let scores: Vec<f64> = sink.0.iter().map(|r| r.label.parse().unwrap()).collect();
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+
+    #[test]
+    fn scoring_expressions() {
+        use stranameg::stringer::{parse_expression, Scoring};
+        let expr = parse_expression("2*allit + pron - 0.5*len").unwrap();
+        assert_eq!(expr.eval("big bad"), 2.0 + 1.0 - 3.5);
+        let scoring = Scoring::from("-(len - 4) * 2 + max(allit, 0.25) + abs(-1)").unwrap();
+        assert_eq!(scoring.score("abcdef"), -4.0 + 0.25 + 1.0);
+        assert_eq!(Scoring::from("len / 0").unwrap().score("abc"), 0.0);
+        assert_eq!(Scoring::from("len=2,pron").unwrap().score("aba"), 7.0);
+        assert!(parse_expression("2 * smell").is_err());
+        assert!(parse_expression("(len + 1").is_err());
+        assert!(parse_expression("len len").is_err());
+        assert!(parse_expression("abs(len, pron)").is_err());
+    }
+
+    #[test]
+    fn curation_session() {
+        use stranameg::stringer::{curate, Verdict};
+        assert_eq!(Verdict::from('S'), Some(Verdict::Star));
+        assert_eq!(Verdict::from('x'), None);
+        let confetti = command_parser::get_config(vec!["mode=rls", "num=10", "len=5"]);
+        let mut input: &[u8] = b"a\nr\n?s\nq";
+        let mut screen: Vec<u8> = Vec::new();
+        let mut accepted: Vec<u8> = Vec::new();
+        let mut starred: Vec<u8> = Vec::new();
+        let curation = curate(&confetti, 2, &mut input, &mut screen, &mut accepted, &mut starred).unwrap();
+        assert_eq!((curation.shown, curation.accepted, curation.starred, curation.rejected), (4, 2, 1, 1));
+        let accepted = String::from_utf8(accepted).unwrap();
+        let starred = String::from_utf8(starred).unwrap();
+        assert_eq!(accepted.lines().count(), 2);
+        assert_eq!(accepted.lines().nth(1), starred.lines().next());
+        assert!(String::from_utf8(screen).unwrap().contains("   2  "));
+    }
+
+    #[test]
+    fn history_lookup() {
+        use stranameg::stringer::{search_history, HistoryEntry, HistorySink, OutputSink, Record};
+        let entry = HistoryEntry {
+            timestamp: String::from("2024-05-01T12:30:00.000Z"),
+            run_id: String::from("1-ab"),
+            mode: String::from("http"),
+            value: String::from("GET /\tx\nHost: a\\b"),
+        };
+        assert_eq!(HistoryEntry::parse(&entry.line()), Some(entry));
+        let path = std::env::temp_dir().join("strgen_history_lookup.history");
+        let _ = std::fs::remove_file(&path);
+        for value in ["BraveFalcon", "quiet_river"] {
+            let mut sink = HistorySink::open(&path).unwrap();
+            sink.write(&Record::new(0, String::from(value), String::from("cowe"))).unwrap();
+            sink.finish().unwrap();
+        }
+        let found = search_history(&path, "falcon").unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].value, "BraveFalcon");
+        assert_eq!(found[0].mode, "cowe");
+        assert_eq!(search_history(&path, "_").unwrap().len(), 1);
+        assert!(search_history(&path, "eagle").unwrap().is_empty());
+    }
+
+    #[test]
+    fn name_reservations() {
+        use stranameg::stringer::{ReservationSpec, Reservations};
+        let spec = ReservationSpec::from("c:/shared/names.sqlite:ns=hosts:owner=ana");
+        assert_eq!(spec.path.to_str(), Some("c:/shared/names.sqlite"));
+        assert_eq!((spec.namespace.as_ref(), spec.owner.as_ref()), ("hosts", "ana"));
+        let path = std::env::temp_dir().join("strgen_reservations.sqlite");
+        let _ = std::fs::remove_file(&path);
+        let opened = Reservations::open(&path, "hosts", "ana");
+        if cfg!(not(feature = "sqlite")) {
+            assert!(opened.is_err());
+            return;
+        }
+        let mut ana = opened.unwrap();
+        let mut ben = Reservations::open(&path, "hosts", "ben").unwrap();
+        let mut other = Reservations::open(&path, "queues", "ben").unwrap();
+        assert!(ana.claim("brave_tree").unwrap());
+        assert!(!ben.claim("brave_tree").unwrap());
+        assert!(other.claim("brave_tree").unwrap());
+        assert_eq!(ben.find("brave_tree").unwrap().unwrap().owner, "ana");
+        let vargs = vec!["mode=rla", "next=ab", "num=4", "len=2"];
+        let mut confetti = command_parser::get_config(vargs);
+        confetti.set_reservation(Some(ReservationSpec::from(&format!("{}:ns=codes", path.display()))));
+        confetti.set_sink(String::from("file:/dev/null"));
+        stranameg::stringer::run_generator(&confetti).unwrap();
+        // only 4 strings of length 2 over 'ab', all taken now
+        assert!(stranameg::stringer::run_generator(&confetti).is_err());
+        let codes = Reservations::open(&path, "codes", "x").unwrap();
+        assert_eq!(codes.list().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn leased_names() {
+        use stranameg::stringer::{parse_duration, ReservationSpec, Reservations};
+        assert_eq!(parse_duration("90"), Some(90_000));
+        assert_eq!(parse_duration("2h"), Some(7_200_000));
+        assert_eq!(parse_duration("3w"), None);
+        assert_eq!(ReservationSpec::from("n.sqlite:ttl=7d").ttl, Some(604_800_000));
+        let path = std::env::temp_dir().join("strgen_leases.sqlite");
+        let _ = std::fs::remove_file(&path);
+        let opened = Reservations::open(&path, "preview", "ana");
+        if cfg!(not(feature = "sqlite")) {
+            assert!(opened.is_err());
+            return;
+        }
+        let mut ana = opened.unwrap();
+        let mut ben = Reservations::open(&path, "preview", "ben").unwrap();
+        ana.set_ttl(Some(20));
+        assert!(ana.claim("pr-blue-fox").unwrap());
+        assert!(ana.claim("pr-red-owl").unwrap());
+        assert!(!ben.claim("pr-blue-fox").unwrap());
+        assert!(!ben.release("pr-red-owl").unwrap());
+        assert!(ana.release("pr-red-owl").unwrap());
+        assert!(ben.claim("pr-red-owl").unwrap());
+        std::thread::sleep(std::time::Duration::from_millis(40));
+        assert!(ana.list().unwrap().iter().all(|r| r.name != "pr-blue-fox"));
+        assert!(!ana.renew("pr-blue-fox").unwrap());
+        assert!(ben.claim("pr-blue-fox").unwrap());
+        assert_eq!(ben.find("pr-blue-fox").unwrap().unwrap().owner, "ben");
+    }
+
+    #[test]
+    fn encrypted_output() {
+        use stranameg::stringer::{
+            decrypt, encrypt_with, run_generator_with, EncryptedFileSink, OutputSink, Wrap,
+        };
+        let path = std::env::temp_dir().join("strgen_encrypted.enc");
+        let sink = EncryptedFileSink::create(&path, String::from("hunter2"), Wrap::None);
+        if cfg!(not(feature = "encrypt")) {
+            assert!(sink.is_err());
+            assert!(encrypt_with(b"x", "p", 10).is_err());
+            return;
+        }
+        let sealed = encrypt_with(b"secret\n", "hunter2", 10).unwrap();
+        assert!(!sealed.windows(6).any(|w| w == b"secret"));
+        assert_eq!(decrypt(&sealed, "hunter2").unwrap(), b"secret\n");
+        assert!(decrypt(&sealed, "hunter3").is_err());
+        let mut tampered = sealed.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert!(decrypt(&tampered, "hunter2").is_err());
+        // header asking for huge scrypt cost is refused before deriving key
+        let mut costly = sealed.clone();
+        costly[8] = 40;
+        let e = decrypt(&costly, "hunter2").unwrap_err();
+        assert!(e.to_string().contains("scrypt parameters"));
+        let confetti = command_parser::get_config(vec!["mode=pass", "num=3"]);
+        let mut sink = sink.unwrap();
+        run_generator_with(&confetti, &mut sink as &mut dyn OutputSink).unwrap();
+        let plain = decrypt(&std::fs::read(&path).unwrap(), "hunter2").unwrap();
+        assert_eq!(String::from_utf8(plain).unwrap().lines().count(), 3);
+    }
+
+    #[test]
+    fn secret_handling() {
+        use stranameg::stringer::{
+            search_history, wipe, wipe_all, HistorySink, Modes, OutputSink, Record, RunStats,
+            REDACTED,
+        };
+        let mut secret = String::from("hunter2");
+        wipe(&mut secret);
+        assert!(secret.is_empty());
+        let mut batch = vec![String::from("a"), String::from("b")];
+        wipe_all(&mut batch);
+        assert!(batch.is_empty());
+        assert!(Modes::from("pass2").is_secret());
+        assert!(!Modes::from("cow").is_secret());
+        let mut stats = RunStats::new(true);
+        stats.add("x1");
+        stats.add("x1");
+        stats.add("x2");
+        assert_eq!(stats.distinct(), 2);
+        let path = std::env::temp_dir().join("strgen_secret.history");
+        let _ = std::fs::remove_file(&path);
+        let mut history = HistorySink::open(&path).unwrap();
+        history.set_redact(true);
+        history.write(&Record::new(0, String::from("Tr0ub4dor&3"), String::from("pass"))).unwrap();
+        history.finish().unwrap();
+        assert!(search_history(&path, "Tr0ub").unwrap().is_empty());
+        assert_eq!(search_history(&path, REDACTED).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn clipboard_output() {
+        use stranameg::stringer::{out_spec, read_clipboard, DEFAULT_CLIP_CLEAR};
+        let conf = command_parser::get_config(vec!["mode=pass", "num=1"]);
+        assert_eq!(conf.get_clip_clear(), DEFAULT_CLIP_CLEAR);
+        let conf = command_parser::get_config(vec!["mode=pass", "clip_clear=0"]);
+        assert_eq!(conf.get_clip_clear(), 0);
+        assert_eq!(out_spec("clip"), "clip");
+        // headless machines have no clipboard program, that has to be an error not a panic
+        if let Err(e) = read_clipboard() {
+            assert_eq!(e.kind(), std::io::ErrorKind::NotFound);
+        }
+        // clipboard is no file, nothing is left next to it
+        let path = std::env::temp_dir().join(format!("strgen_clip_{}", std::process::id()));
+        let sink = stranameg::stringer::open_sink(&format!("clip:{}", path.display()), &conf);
+        assert!(sink.is_ok());
+        assert!(!stranameg::stringer::sidecar(&path, "meta").exists());
+    }
+
+    #[test]
+    fn hybrid_passwords() {
+        use stranameg::stringer::{hybrid_entropy, hybrid_shape, HybridPassword, INJECTED};
+        let conf = command_parser::get_config(vec!["mode=hyb", "len=16"]);
+        assert!(conf.get_mode().is_secret());
+        let mut generator = HybridPassword::new(16);
+        generator.setup(&conf).unwrap();
+        let (syllables, injected) = hybrid_shape(16);
+        assert_eq!((syllables, injected), (6, 4));
+        for _ in 0..50 {
+            let password = generator.get();
+            assert_eq!(password.chars().count(), 16);
+            let symbols = password.chars().filter(|c| INJECTED.contains(*c)).count();
+            assert_eq!(symbols, injected);
+            // letters alternate consonant and vowel between symbols
+            let letters: String = password.chars().filter(|c| c.is_alphabetic()).collect();
+            for (i, c) in letters.to_lowercase().chars().enumerate() {
+                assert_eq!("aeiou".contains(c), i % 2 == 1, "{}", password);
+            }
+        }
+        // 6 syllables of 160, 35 ways to place 4 of 20 symbols
+        let expected = 6.0 * 160f64.log2() + 35f64.log2() + 4.0 * 20f64.log2();
+        assert!((hybrid_entropy(16) - expected).abs() < 1e-9);
+        assert_eq!(generator.label(), Some(String::from("66.3 bits")));
+        // 16 characters when no length is given
+        let conf = command_parser::get_config(vec!["mode=hyb"]);
+        let mut generator = HybridPassword::new(8);
+        generator.setup(&conf).unwrap();
+        assert_eq!(generator.get().chars().count(), 16);
+    }
+
+    #[test]
+    fn keyboard_walks() {
+        use stranameg::stringer::{
+            build_filters, next_accepted, KeyboardLayout, KeyboardWalkFilter, KeyboardWalks,
+            StringFilter,
+        };
+        let qwerty = KeyboardLayout::Qwerty;
+        assert!(qwerty.adjacent('1', 'q'));
+        assert!(qwerty.adjacent('a', 'z'));
+        assert!(!qwerty.adjacent('a', 'a'));
+        assert!(!qwerty.adjacent('q', 'p'));
+        assert_eq!(qwerty.find_walk("x9qwerty2", 4), Some(String::from("qwerty")));
+        assert_eq!(qwerty.find_walk("ab1QAZc", 4), Some(String::from("1QAZ")));
+        assert_eq!(qwerty.find_walk("!@#$ok", 4), Some(String::from("!@#$")));
+        assert_eq!(qwerty.find_walk("correct", 4), None);
+        // z is where y is on qwerty
+        assert!(KeyboardLayout::Qwertz.find_walk("tzui", 4).is_some());
+        assert!(KeyboardLayout::Azerty.find_walk("azer", 4).is_some());
+
+        let walks = KeyboardWalks::from("qwerty,azerty:3");
+        assert_eq!(walks.min_run, 3);
+        let mut filter = KeyboardWalkFilter::new(&walks).unwrap();
+        assert!(!filter.accepts("xxasdxx"));
+        assert!(filter.accepts("hunter2"));
+        assert!(KeyboardWalkFilter::new(&KeyboardWalks::from("colemak")).is_err());
+
+        let conf = command_parser::get_config(vec!["mode=pass", "len=24", "walks=qwerty:3"]);
+        assert_eq!(build_filters(&conf).unwrap().len(), 1);
+        let mut generator = stranameg::stringer::stringer(conf.clone());
+        generator.setup(&conf).unwrap();
+        let mut filters = build_filters(&conf).unwrap();
+        for _ in 0..50 {
+            let password = next_accepted(generator.as_mut(), &mut filters).unwrap().unwrap();
+            assert_eq!(qwerty.find_walk(&password, 3), None, "{}", password);
+        }
+    }
+
+    #[test]
+    fn password_policies() {
+        use stranameg::stringer::{
+            apply_policy, build_filters, Blocklist, CharClass, PasswordPolicy, PolicyFilter,
+            StringFilter,
+        };
+        let nist = PasswordPolicy::load("nist-63b").unwrap();
+        assert_eq!((nist.min_length, nist.max_length), (15, 64));
+        assert_eq!(nist.blocklist, Blocklist::Common);
+        assert_eq!(nist.expiry_hint(), "never");
+        let pci = PasswordPolicy::load("pci").unwrap();
+        assert_eq!(pci.required, vec![CharClass::Letter, CharClass::Digit]);
+        assert_eq!(pci.violations("abcdefghijkl"), vec![String::from("no digit")]);
+        assert_eq!(pci.length(8), 12);
+        assert_eq!(nist.length(100), 64);
+
+        let custom = PasswordPolicy::parse(
+            "# corp rules\nname=corp\nmin_length=10\nmin_classes=3\nexpiry_days=30\n",
+        )
+        .unwrap();
+        assert_eq!(custom.name, "corp");
+        assert!(custom.violations("Abcdefgh1!").is_empty());
+        assert_eq!(custom.violations("abcdefgh12").len(), 1);
+        assert!(custom.expiry_hint().starts_with("20"));
+        assert!(PasswordPolicy::parse("require=emoji").is_err());
+        assert!(PasswordPolicy::parse("min_length=12\nmax_length=8").is_err());
+        assert!(PasswordPolicy::load("no-such-preset").is_err());
+
+        let mut filter = PolicyFilter::new(nist).unwrap();
+        assert!(filter.is_blocked("Password1"));
+        assert!(!filter.accepts("short"));
+        assert!(filter.accepts("correct horse battery"));
+
+        let conf = command_parser::get_config(vec!["mode=pass", "len=8", "policy=pci-dss"]);
+        let conf = apply_policy(&conf).unwrap();
+        assert_eq!(conf.get_length(), 12);
+        assert_eq!(build_filters(&conf).unwrap().len(), 1);
+        assert!(conf.metadata().contains("policy=pci-dss expires="));
+    }
+
+    #[test]
+    fn pin_codes() {
+        use stranameg::stringer::{is_weak_pin, PinCode};
+        for weak in ["1111", "1212", "123123", "1234", "8765", "7890", "1987", "2024", "2580"] {
+            assert!(is_weak_pin(weak), "{}", weak);
+        }
+        for fine in ["4831", "2190", "570392", "18273645"] {
+            assert!(!is_weak_pin(fine), "{}", fine);
+        }
+        let conf = command_parser::get_config(vec!["mode=pin", "len=2"]);
+        assert!(conf.get_mode().is_secret());
+        let mut pins = PinCode::new(6);
+        pins.setup(&conf).unwrap();
+        assert_eq!(pins.get_length(), 4);
+        for _ in 0..200 {
+            let pin = pins.get();
+            assert_eq!(pin.len(), 4);
+            assert!(!is_weak_pin(&pin), "{}", pin);
+        }
+        assert_eq!(PinCode::new(30).get().len(), 12);
+        // 6 digits when no length is given
+        let conf = command_parser::get_config(vec!["mode=pin"]);
+        pins.setup(&conf).unwrap();
+        assert_eq!(pins.get().len(), 6);
+    }
+
+    #[test]
+    fn library_api() {
+        use stranameg::{run_generator_with, stringer};
+        let mut conf = stranameg::Config::default();
+        conf.set_mode(stranameg::Modes::from("rls"));
+        conf.set_length(10);
+        conf.set_amount(5);
+        let mut generator: Box<dyn stranameg::StringGenerator> = stringer(conf.clone());
+        generator.setup(&conf).unwrap();
+        assert_eq!(generator.get().chars().count(), 10);
+        let mut collected = Collect::new();
+        run_generator_with(&conf, &mut collected).unwrap();
+        assert_eq!(collected.0.len(), 5);
+        // generator types are reachable for own compositions
+        let mut letters = stranameg::stringer::LettterSequence::new("ab", 4);
+        assert!(letters.get().chars().all(|c| c == 'a' || c == 'b'));
+    }
+
+    #[test]
+    fn redemption_codes() {
+        use stranameg::stringer::{
+            check_char, normalize_code, run_generator_with, verify_code, CodeBatch, RedemptionCodes,
+            CODE_ALPHABET,
+        };
+        let check = check_char("U3Y5Z9LCDG3", CODE_ALPHABET).unwrap();
+        assert!(verify_code(&format!("spring-u3y5-z9lc-dg3{}", check), "SPRING"));
+        assert!(!verify_code(&format!("SPRING-U3Y5-Z9LC-DG4{}", check), "SPRING"));
+        // swapped neighbours
+        assert!(!verify_code(&format!("3UY5Z9LCDG3{}", check), ""));
+        assert_eq!(normalize_code("ab-cd ef", ""), "ABCDEF");
+        let batch = CodeBatch::from("group=3:prefix=X:batch=b1:expires=2030-01-01").unwrap();
+        assert_eq!((batch.group, batch.batch.as_str()), (3, "b1"));
+        assert!(CodeBatch::from("color=red").is_err());
+        assert!(CodeBatch::from("ttl=soon").is_err());
+
+        // a tiny code space runs out instead of repeating codes
+        let conf = command_parser::get_config(vec!["mode=code", "len=1", "next=check=0"]);
+        let mut codes = RedemptionCodes::new();
+        codes.setup(&conf).unwrap();
+        let mut seen = std::collections::HashSet::new();
+        while !codes.exhausted() {
+            assert!(seen.insert(codes.get()));
+        }
+        assert_eq!(seen.len(), CODE_ALPHABET.len());
+
+        // second run skips everything first run recorded in history
+        let history = std::env::temp_dir().join("strgen_codes.history");
+        let _ = std::fs::remove_file(&history);
+        let path = history.to_string_lossy().into_owned();
+        let args = vec!["mode=code", "len=2", "num=600", "next=check=0:batch=b7"];
+        let mut conf = command_parser::get_config(args.clone());
+        conf.set_history(path.clone());
+        let mut sink = stranameg::stringer::open_sinks(&[], &conf).unwrap();
+        run_generator_with(&conf, sink.as_mut()).unwrap();
+        drop(sink);
+        let first: std::collections::HashSet<String> =
+            stranameg::stringer::search_history(&history, "")
+                .unwrap()
+                .into_iter()
+                .map(|entry| entry.value)
+                .collect();
+        assert_eq!(first.len(), 600);
+        let mut rows = Collect::new();
+        run_generator_with(&conf, &mut rows).unwrap();
+        // 32 * 32 codes, 600 taken by first run, last few free ones may not be found by chance
+        assert!(rows.0.len() <= 32 * 32 - 600 && rows.0.len() > 380);
+        assert!(rows.0.iter().all(|row| !first.contains(&row.value)));
+        let fields = &rows.0[0].fields;
+        assert_eq!(fields[0], (String::from("batch"), String::from("b7")));
+        assert_eq!(fields[1].0, "expires");
+    }
+
+    #[test]
+    fn coupon_profile() {
+        use stranameg::stringer::{
+            apply_profile, build_filters, find_profanity, next_accepted, Modes, OutputProfile,
+        };
+        assert_eq!(find_profanity("AB-5H1T-XY"), Some("shit"));
+        assert_eq!(find_profanity("FU-CK22"), Some("fuck"));
+        assert_eq!(find_profanity("H7K9-M2PQ"), None);
+        assert!(OutputProfile::from("glitter").is_err());
+
+        let conf = command_parser::get_config(vec!["mode=rls", "len=4", "profile=coupon"]);
+        let conf = apply_profile(&conf).unwrap();
+        assert!(matches!(conf.get_mode(), Modes::RedemptionCodes));
+        assert_eq!(conf.get_length(), 8);
+        assert_eq!(conf.get_min_distance(), 1);
+        let mut filters = build_filters(&conf).unwrap();
+        assert_eq!(filters.len(), 2);
+        let mut generator = stranameg::stringer::stringer(conf.clone());
+        generator.setup(&conf).unwrap();
+        for _ in 0..100 {
+            let code = next_accepted(generator.as_mut(), &mut filters).unwrap().unwrap();
+            assert_eq!(find_profanity(&code), None);
+            assert!(stranameg::stringer::verify_code(&code, ""));
+            assert_eq!(code.len(), 9);
+        }
+        // code options survive the profile
+        let conf = command_parser::get_config(vec!["mode=code", "next=prefix=XM", "profile=coupon"]);
+        assert_eq!(apply_profile(&conf).unwrap().get_next(), "prefix=XM");
+        let conf = command_parser::get_config(vec!["mode=code", "next=shape=round", "profile=coupon"]);
+        assert!(apply_profile(&conf).is_err());
+    }
+
+    #[test]
+    fn named_flags() {
+        use stranameg::stringer::Modes;
+        let flags = |args: &[&str]| -> Result<Config, std::io::Error> {
+            let args: Vec<String> = args.iter().map(|a| String::from(*a)).collect();
+            return command_parser::get_flag_config(&args);
+        };
+        let conf = flags(&["--length", "8", "--mode", "coupled-nouns", "--lang", "de", "--amount=50"])
+            .unwrap();
+        assert!(matches!(conf.get_mode(), Modes::CoupledWordsNouns));
+        assert_eq!((conf.get_amount(), conf.get_length()), (50, 8));
+        assert_eq!(conf.get_next(), "de");
+        let conf = flags(&["--mode", "pin", "--labels", "--min-dist", "2"]).unwrap();
+        assert!(conf.get_labels());
+        assert_eq!(conf.get_min_distance(), 2);
+        assert!(flags(&["--mode", "bogus"]).is_err());
+        assert!(flags(&["--colour", "red"]).is_err());
+        assert!(flags(&["--amount", "many"]).is_err());
+        assert!(flags(&["50"]).is_err());
+        // every mode has its own long name and is listed in --help
+        let help = command_parser::flags_help();
+        let mut names = std::collections::HashSet::new();
+        for mode in Modes::ALL.iter() {
+            assert!(names.insert(mode.name()));
+            assert!(matches!(Modes::from_name(&mode.name()), Some(m) if m.abbr() == mode.abbr()));
+            assert!(help.contains(&mode.name()));
+        }
+        // every alt key is a flag too
+        for key in command_parser::KEYS.iter().filter(|k| **k != "mode") {
+            let flag = format!("--{}", key.replace('_', "-"));
+            // these take names, not numbers
+            let value = match *key {
+                "esc" => "json",
+                "wrap" => "quote",
+                "noise" => "sponge",
+                "phon" => "soundex",
+                "format" => "json",
+                "columns" => "uuid",
+                "difficulty" => "easy",
+                "rules" => "digit=1",
+                _ => "1",
+            };
+            assert!(flags(&[&flag, value]).is_ok(), "{}", flag);
+        }
+    }
+
+    #[test]
+    fn error_type() {
+        use stranameg::stringer::{strgen_error, StrgenError};
+        let args = |a: &[&str]| -> Vec<String> { a.iter().map(|s| String::from(*s)).collect() };
+        let e = Config::new(&args(&["", "1O", "8"])).err().unwrap();
+        assert!(matches!(&e, StrgenError::BadNumber(_, value) if value == "1O"));
+        assert_eq!(e.to_string(), "amount must be a number, got 1O");
+        assert!(Config::new(&args(&["", "3", "8", "cwo"])).is_ok());
+        let e = Config::new(&args(&["", "3", "8", "coww"])).err().unwrap();
+        assert!(matches!(e, StrgenError::UnknownMode(_)));
+        assert!(command_parser::try_get_config(vec!["mode=rls", "num=ten"]).is_err());
+        assert!(command_parser::try_get_config(vec!["mode=rlx"]).is_err());
+        // alt argument without value and blank params file line
+        let e = command_parser::try_get_config(vec!["mode"]).err().unwrap();
+        assert!(matches!(e, StrgenError::UnknownSetting(key) if key == "mode"));
+        assert!(command_parser::try_get_config(vec!["num=3", "", "  "]).is_ok());
+        // bad length or zero is error, not default
+        let e = Config::new(&args(&["", "3", "abc", "rls"])).err().unwrap();
+        assert!(matches!(&e, StrgenError::BadNumber(what, _) if what == "length"));
+        let e = Config::new(&args(&["", "0", "8", "rls"])).err().unwrap();
+        assert!(matches!(e, StrgenError::NotPositive(_)));
+        assert!(Config::new(&args(&["", "3", "0", "rls"])).is_err());
+        // 0 stands for no length in modes that don't use it
+        assert!(Config::new(&args(&["", "3", "0", "uuid"])).is_ok());
+        let alt = |v: Vec<&str>| command_parser::try_get_config(v).err().unwrap();
+        assert!(matches!(alt(vec!["len=abc"]), StrgenError::BadNumber(_, _)));
+        assert!(matches!(alt(vec!["num=0"]), StrgenError::NotPositive(_)));
+        assert!(matches!(alt(vec!["len=0", "mode=pass"]), StrgenError::NotPositive(_)));
+        assert!(command_parser::try_get_config(vec!["len=0", "mode=b62"]).is_ok());
+        assert!(matches!(alt(vec!["nmu=5"]), StrgenError::UnknownSetting(key) if key == "nmu"));
+        for bogus in ["esc=yaml", "wrap=file:curly", "noise=wobble", "ver=9", "phon=nysiis"] {
+            assert!(matches!(alt(vec![bogus]), StrgenError::BadValue(_, _)), "{}", bogus);
+        }
+        for bogus in ["noise=sponge=2", "clip_clear=soon", "batch=0", "max_dup=some"] {
+            assert!(command_parser::try_get_config(vec![bogus]).is_err(), "{}", bogus);
+        }
+        let rla = command_parser::get_config(vec!["mode=rla", "next= "]);
+        let mut sg = LettterSequence::new("abc", 4);
+        let e = sg.setup(&rla).err().unwrap();
+        assert!(matches!(strgen_error(&e), Some(StrgenError::EmptyList(_))));
+        // list file errors travel through generators as io errors and come back out
+        let e = match stranameg::stringer::read_lines("./lists/nothing.here.list") {
+            Err(e) => e,
+            Ok(_lines) => panic!("missing list was read"),
+        };
+        assert_eq!(e.kind(), std::io::ErrorKind::NotFound);
+        assert!(matches!(strgen_error(&e), Some(StrgenError::MissingListFile(_))));
+        assert!(matches!(StrgenError::from(e), StrgenError::MissingListFile(_)));
+        let empty = std::env::temp_dir().join("strgen_empty.abc");
+        std::fs::write(&empty, " \n").unwrap();
+        let conf = command_parser::get_config(vec!["mode=rlaf", &format!("next={}", empty.display())]);
+        let mut generator = LettterSequence::new("", 4);
+        let e = generator.setup(&conf).err().unwrap();
+        assert!(matches!(StrgenError::from(e), StrgenError::EmptyList(_)));
+        let _ = std::fs::remove_file(empty);
+    }
+
+    #[test]
+    fn profanity_substrings() {
+        use stranameg::stringer::{build_filters, next_accepted, AhoCorasick, ProfanityFilter};
+        let words: Vec<String> = ["he", "she", "his", "hers"].iter().map(|w| w.to_string()).collect();
+        let automaton = AhoCorasick::new(&words);
+        assert_eq!(automaton.find("ushers"), Some("she"));
+        assert_eq!(automaton.find_all("ushers"), vec![(1, "she"), (2, "he"), (2, "hers")]);
+        assert_eq!(automaton.find("xyz"), None);
+        // inside random letters, in any case, with digits for letters
+        let filter = ProfanityFilter::new();
+        assert_eq!(filter.find("qzSHITxw"), Some("shit"));
+        assert_eq!(filter.find("kP0rNv"), Some("porn"));
+        assert_eq!(filter.find("qzxwvb"), None);
+        let list = std::env::temp_dir().join("strgen_blocklist.list");
+        std::fs::write(&list, "# own words\nzebra\nqu1x\n").unwrap();
+        let own = ProfanityFilter::load(&list.display().to_string()).unwrap();
+        assert_eq!(own.find("aaZEBRAa"), Some("zebra"));
+        assert_eq!(own.find("AQUIX"), Some("quix"));
+        assert!(ProfanityFilter::load("./lists/nothing.here.list").is_err());
+        let _ = std::fs::remove_file(list);
+        // every letter sequence that comes out is clean
+        let conf = command_parser::get_config(vec!["mode=rla", "next=abcefhiklnoprstuv", "len=12", "profanity=1"]);
+        let mut sg = stranameg::stringer::stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let mut filters = build_filters(&conf).unwrap();
+        for _ in 0..200 {
+            let value = next_accepted(sg.as_mut(), &mut filters).unwrap().unwrap();
+            assert_eq!(filter.find(&value), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn substring_constraints() {
+        use stranameg::stringer::{
+            build_filters, next_accepted, ConstraintEngine, PasswordPolicy, PolicyFilter,
+            StringFilter, Violation,
+        };
+        let list = |a: &[&str]| -> Vec<String> { a.iter().map(|s| s.to_string()).collect() };
+        let engine = ConstraintEngine::new(&list(&["ab", "cd"]), &list(&["zz"]));
+        assert_eq!(engine.violation("xxcdyyab"), None);
+        assert_eq!(engine.violation("xxcdyy"), Some(Violation::Missing(String::from("ab"))));
+        assert_eq!(engine.violation("abcdzz"), Some(Violation::Forbidden(String::from("zz"))));
+        assert_eq!(engine.violation("abcdzz").unwrap().to_string(), "contains zz");
+        assert!(ConstraintEngine::new(&[], &[]).is_empty());
+        // vanity search
+        let conf = command_parser::get_config(vec!["mode=rla", "next=abcdef", "len=6", "contains=cafe", "forbid=aa"]);
+        let mut sg = stranameg::stringer::stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let mut filters = build_filters(&conf).unwrap();
+        for _ in 0..20 {
+            let value = next_accepted(sg.as_mut(), &mut filters).unwrap().unwrap();
+            assert!(value.contains("cafe") && !value.contains("aa"), "{}", value);
+        }
+        // policies forbid substrings without case
+        let policy = PasswordPolicy::parse("min_length=4\nforbid=acme, summer").unwrap();
+        let mut filter = PolicyFilter::new(policy).unwrap();
+        assert!(!filter.accepts("xxACMExx"));
+        assert!(!filter.accepts("Summer2024"));
+        assert!(filter.accepts("Winter2024"));
+    }
+
+    #[test]
+    fn seeded_runs() {
+        use stranameg::stringer::stringer;
+        let run = |vargs: Vec<&str>| -> Vec<String> {
+            let conf = command_parser::get_config(vargs);
+            let mut sg = stringer(conf.clone());
+            sg.setup(&conf).unwrap();
+            return (0..20).map(|_| sg.get()).collect();
+        };
+        for mode in ["mode=rls", "mode=cow", "mode=sen", "mode=hyb", "mode=iban", "mode=code"] {
+            let first = run(vec![mode, "len=10", "seed=42"]);
+            assert_eq!(first, run(vec![mode, "len=10", "seed=42"]), "{}", mode);
+            assert_ne!(first, run(vec![mode, "len=10", "seed=43"]), "{}", mode);
+        }
+        let conf = command_parser::get_config(vec!["mode=cow", "seed=1"]);
+        assert!(conf.metadata().ends_with(" seed=1"));
+        assert!(command_parser::try_get_config(vec!["seed=x1"]).is_err());
+        // adjective and noun don't move in step
+        let mut a = conf.rng(1);
+        let mut b = conf.rng(2);
+        assert_ne!((a.get(), a.get()), (b.get(), b.get()));
+    }
+
+    #[test]
+    fn rng_backends() {
+        use stranameg::stringer::{OsEntropy, RngBackend};
+        let secure = |vargs: Vec<&str>| command_parser::get_config(vargs).uses_secure_rng().unwrap();
+        assert!(secure(vec!["mode=pass"]));
+        assert!(secure(vec!["mode=code"]));
+        assert!(!secure(vec!["mode=cow"]));
+        assert!(!secure(vec!["mode=pass", "seed=3"]));
+        assert!(!secure(vec!["mode=pin", "rng=fast"]));
+        assert!(secure(vec!["mode=rls", "rng=secure"]));
+        let conf = command_parser::get_config(vec!["mode=pass", "rng=dice"]);
+        assert!(conf.uses_secure_rng().is_err());
+        assert!(stranameg::stringer::stringer(conf.clone()).setup(&conf).is_err());
+        // more than one buffer worth, numbers keep coming and don't repeat
+        let mut entropy = OsEntropy::new().unwrap();
+        let drawn: std::collections::HashSet<u32> = (0..1000).map(|_| entropy.get()).collect();
+        assert!(drawn.len() > 990);
+        let conf = command_parser::get_config(vec!["mode=pass", "len=20"]);
+        let mut sg = stranameg::stringer::stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        assert_eq!(sg.get().chars().count(), 20);
+    }
+
+    #[test]
+    fn similar_strings() {
+        use stranameg::stringer::{levenshtein, stringer, Neighborhood};
+        let hood = Neighborhood::from("ab:ops=sub,del:abc=abc:all").unwrap();
+        let all: Vec<String> = hood.enumerate().unwrap().into_iter().map(|(s, _e)| s).collect();
+        // 2 positions x 2 other letters, 2 deletions
+        assert_eq!(all, vec!["bb", "cb", "aa", "ac", "b", "a"]);
+        let hood = Neighborhood::from("abc:dist=2:abc=xy:all").unwrap();
+        let all = hood.enumerate().unwrap();
+        assert!(all.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert!(all.iter().all(|(s, edits)| s != "abc" && levenshtein(s, "abc") <= 2 * edits));
+        assert!(Neighborhood::from("abc:ops=shout").is_err());
+        assert!(Neighborhood::from(":dist=2").is_err());
+        assert!(Neighborhood::from("x:2:dist=1").unwrap().target == "x:2");
+        // random neighbours, target itself never
+        let conf = command_parser::get_config(vec!["mode=near", "next=google:dist=2"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        for _ in 0..200 {
+            let s = sg.get();
+            assert!(s != "google" && levenshtein(&s, "google") <= 4, "{}", s);
+        }
+        let conf = command_parser::get_config(vec!["mode=near", "next=go:ops=swap:all"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        assert_eq!(sg.get(), "og");
+        assert_eq!(sg.fields(), vec![(String::from("edits"), String::from("1"))]);
+        assert!(sg.exhausted());
+        let conf = command_parser::get_config(vec!["mode=near", "next=aa:ops=swap"]);
+        assert!(stringer(conf.clone()).setup(&conf).is_err());
+    }
+
+    #[test]
+    fn sound_alike_names() {
+        use stranameg::stringer::{stringer, Phonetic, SoundAlikeSpec};
+        let spec = SoundAlikeSpec::from("Philip:translit=0").unwrap();
+        let variants = spec.variants().unwrap();
+        let code = Phonetic::Metaphone.encode("philip");
+        assert!(variants.iter().any(|(v, c)| v == "filip" && c == "ph>f"));
+        // spelling only variants keep metaphone code of name
+        for (v, c) in variants.iter() {
+            assert_eq!(Phonetic::Metaphone.encode(v), code, "{} {}", v, c);
+        }
+        let spec = SoundAlikeSpec::from("Ivanov:depth=1").unwrap();
+        let variants = spec.variants().unwrap();
+        assert!(variants.iter().any(|(v, _c)| v == "ivanoff"));
+        let spec = SoundAlikeSpec::from("Ivanov:depth=1:translit=0").unwrap();
+        assert!(!spec.variants().unwrap().iter().any(|(v, _c)| v == "ivanoff"));
+        assert!(SoundAlikeSpec::from(":all").is_err());
+        // name written capitalized comes back capitalized, all ends run
+        let conf = command_parser::get_config(vec!["mode=sound", "next=Philip:depth=1:all"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let mut seen = Vec::new();
+        while !sg.exhausted() {
+            let s = sg.get();
+            assert!(s.starts_with(char::is_uppercase) && s != "Philip", "{}", s);
+            seen.push(s);
+        }
+        assert!(seen.contains(&String::from("Filip")));
+        assert_eq!(sg.fields()[0].0, "changes");
+    }
+
+    #[test]
+    fn length_ranges() {
+        use stranameg::stringer::{parse_length_range, stringer, Config};
+        assert_eq!(parse_length_range("8..16"), Some((8, 16)));
+        assert_eq!(parse_length_range("8..=16"), Some((8, 16)));
+        assert_eq!(parse_length_range("16..8"), None);
+        assert_eq!(parse_length_range("12"), None);
+        assert!(command_parser::try_get_config(vec!["len=9..x"]).is_err());
+        // length from config, not 16 of constructor
+        let conf = command_parser::get_config(vec!["mode=pass", "len=7"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        assert_eq!(sg.get().chars().count(), 7);
+        // passwords without length stay 16 long, other modes 12
+        let pwd = String::from("PWD");
+        let conf = stranameg::stringer::fast_switch::alias_config(pwd, String::new());
+        assert_eq!(conf.get_length(), 16);
+        assert_eq!(command_parser::get_config(vec!["mode=pass2"]).get_length(), 16);
+        assert_eq!(command_parser::get_config(vec!["mode=rls"]).get_length(), 12);
+        for mode in ["mode=rls", "mode=pass", "mode=hyb", "mode=pin"] {
+            let conf = command_parser::get_config(vec![mode, "len=4..9", "seed=3"]);
+            assert_eq!(conf.get_length_range(), (4, 9));
+            let mut sg = stringer(conf.clone());
+            sg.setup(&conf).unwrap();
+            let lengths: Vec<usize> = (0..300).map(|_| sg.get().chars().count()).collect();
+            assert!(lengths.iter().all(|n| (4..=9).contains(n)), "{}", mode);
+            assert!(lengths.iter().min() != lengths.iter().max(), "{}", mode);
+        }
+        let args: Vec<String> = ["x", "3", "5..6"].iter().map(|a| a.to_string()).collect();
+        let conf = Config::from(&args).unwrap();
+        assert_eq!(conf.get_length_range(), (5, 6));
+        assert!(conf.metadata().contains("length=5..6"));
+    }
+
+    #[test]
+    fn noise_injection() {
+        use stranameg::stringer::{flip_case, run_generator_with, Noise, RNG};
+        let noise = Noise::from("sponge,double=0.2,swap=x");
+        assert_eq!(noise.sponge, 0.5);
+        assert_eq!(noise.double, 0.2);
+        assert_eq!(noise.swap, 0.05);
+        assert!(Noise::from("").is_empty() && Noise::from("wobble=1").is_empty());
+        assert_eq!(flip_case('a'), 'A');
+        assert_eq!(flip_case('ß'), 'ß');
+        let mut rng = RNG::seeded(Some(5), 0);
+        // every letter flipped, nothing else
+        assert_eq!(Noise::from("sponge=1").apply("abC-1", &mut rng), "ABc-1");
+        assert_eq!(Noise::from("double=1").apply("ab-", &mut rng), "aabb-");
+        assert_eq!(Noise::from("swap=1").apply("abcde", &mut rng), "badce");
+        let noisy = Noise::from("sponge").apply(&"a".repeat(1000), &mut rng);
+        let upper = noisy.chars().filter(|c| c.is_uppercase()).count();
+        assert!(upper > 400 && upper < 600, "{}", upper);
+        // same seed, same mess
+        let run = || {
+            let conf = command_parser::get_config(vec![
+                "mode=rls", "num=20", "len=10", "noise=sponge,double=0.1", "seed=7",
+            ]);
+            let mut sink = Collect::new();
+            run_generator_with(&conf, &mut sink).unwrap();
+            return sink.values();
+        };
+        let first = run();
+        assert_eq!(first, run());
+        assert!(first.iter().any(|s| s.chars().any(char::is_uppercase)));
+    }
+
+    #[test]
+    fn composed_passwords() {
+        use stranameg::stringer::{stringer, CharClass, Composition};
+        let composition = Composition::from("digit=2:symbol=1:repeat=2").unwrap();
+        assert_eq!(composition.min_length(), 3);
+        assert_eq!(composition.max_repeat, 2);
+        assert!(Composition::from("digits=2").is_err());
+        assert!(Composition::from("digit=x").is_err());
+        assert!(Composition::from("classes=lower,vowel").is_err());
+        assert_eq!(Composition::longest_run("abbbc"), 3);
+        let conf = command_parser::get_config(vec![
+            "mode=pass", "len=10", "rules=digit=3:symbol=2:upper=1:repeat=1", "seed=4",
+        ]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        for _ in 0..200 {
+            let p = sg.get();
+            let count = |class: CharClass| p.chars().filter(|c| class.matches(*c)).count();
+            assert_eq!(p.chars().count(), 10);
+            assert!(count(CharClass::Digit) >= 3 && count(CharClass::Symbol) >= 2, "{}", p);
+            assert!(count(CharClass::Upper) >= 1, "{}", p);
+            assert_eq!(Composition::longest_run(&p), 1, "{}", p);
+        }
+        let conf = command_parser::get_config(vec!["mode=pass", "rules=digit=1:classes=digit"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        assert!(sg.get().chars().all(|c| c.is_ascii_digit()));
+        let conf = command_parser::get_config(vec!["mode=pass", "len=4", "rules=digit=5"]);
+        assert!(stringer(conf.clone()).setup(&conf).is_err());
+        assert!(command_parser::try_get_config(vec!["rules=digits=2"]).is_err());
+        // 4th parameter of pass is no rule
+        let args = ["", "3", "12", "pass", "en"];
+        let conf = Config::new(&args.map(String::from)).unwrap();
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        assert_eq!(sg.get().chars().count(), 12);
+    }
+
+    #[test]
+    fn keyboard_typos() {
+        use stranameg::stringer::{KeyboardLayout, Typos, RNG};
+        let qwerty = KeyboardLayout::Qwerty;
+        let mut around_s = qwerty.neighbours('s');
+        around_s.sort();
+        assert_eq!(around_s, vec!['a', 'd', 'e', 'w', 'x', 'z']);
+        assert!(qwerty.neighbours('S').iter().all(|c| c.is_uppercase()));
+        assert!(qwerty.neighbours('ü').is_empty());
+        assert!(Typos::from("0.1:colemak").is_err());
+        assert!(Typos::from("2").is_err());
+        assert!(command_parser::try_get_config(vec!["typos=x"]).is_err());
+        let typos = Typos::from("0.05").unwrap();
+        assert_eq!(typos.layout, KeyboardLayout::Qwerty);
+        let mut rng = RNG::seeded(Some(11), 0);
+        assert_eq!(Typos::from("0").unwrap().apply("hello", &mut rng), "hello");
+        // every character slips, each slip is one of three kinds
+        let typos = Typos::from("1:qwertz").unwrap();
+        for _ in 0..200 {
+            let typed = typos.apply("zu", &mut rng);
+            let substituted = typed.chars().count() == 2
+                && typed.chars().zip("zu".chars()).all(|(t, c)| {
+                    t == c || KeyboardLayout::Qwertz.adjacent(t, c)
+                });
+            assert!(substituted || typed == "uz" || typed.chars().count() < 2, "{}", typed);
+        }
+        let conf = command_parser::get_config(vec!["typos=0.2:azerty"]);
+        assert_eq!(conf.get_typos().unwrap().layout, KeyboardLayout::Azerty);
+    }
+
+    #[test]
+    fn paired_noisy_copies() {
+        use stranameg::stringer::{run_generator_with, Wrap};
+        let run = |vargs: Vec<&str>| {
+            let conf = command_parser::get_config(vargs);
+            let mut sink = Collect::new();
+            run_generator_with(&conf, &mut sink).unwrap();
+            return sink.0;
+        };
+        let records = run(vec![
+            "mode=rls", "num=50", "len=12", "pairs=1", "typos=0.3", "seed=2",
+        ]);
+        assert_eq!(records.len(), 50);
+        assert!(records.iter().all(|r| r.value.chars().count() == 12));
+        assert!(records.iter().any(|r| r.pair.as_ref() != Some(&r.value)));
+        let first = &records[0];
+        let noisy = first.pair.clone().unwrap();
+        assert_eq!(first.fields, vec![(String::from("noisy"), noisy.clone())]);
+        assert_eq!(first.text(Wrap::None), format!("{}\t{}", first.value, noisy));
+        // default typos when no noise is asked for
+        let records = run(vec!["mode=rls", "num=200", "len=20", "pairs=1", "seed=2"]);
+        assert!(records.iter().any(|r| r.pair.as_ref() != Some(&r.value)));
+        // without pairs noisy copy replaces clean one
+        let records = run(vec!["mode=rls", "num=5", "noise=sponge=1", "seed=2"]);
+        assert!(records.iter().all(|r| r.pair.is_none()));
+        assert!(records.iter().all(|r| r.value.chars().all(|c| !c.is_lowercase())));
+    }
+
+    #[test]
+    fn uuids() {
+        use stranameg::stringer::{stringer, uuid_v4, Modes};
+        assert_eq!(uuid_v4([0xff; 16]), "ffffffff-ffff-4fff-bfff-ffffffffffff");
+        assert_eq!(uuid_v4([0; 16]), "00000000-0000-4000-8000-000000000000");
+        assert!(Modes::Uuid.needs_secure_rng());
+        let conf = command_parser::get_config(vec!["mode=uuid"]);
+        assert!(conf.uses_secure_rng().unwrap());
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let first = sg.get();
+        assert_eq!(first.len(), 36);
+        assert_eq!(&first[14..15], "4");
+        assert!("89ab".contains(&first[19..20]));
+        assert_ne!(first, sg.get());
+        let conf = command_parser::get_config(vec!["mode=uuid", "next=upper"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        assert!(!sg.get().chars().any(|c| c.is_lowercase()));
+    }
+
+    #[test]
+    fn pseudonyms() {
+        use std::fs::{read_to_string, remove_file, write};
+        use stranameg::stringer::{
+            hmac_sha256, run_generator_with, stringer, Languages, Pseudonymizer, PSEUDONYM_ROUNDS,
+        };
+        // rfc 4231 test case 2
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(mac[..4], [0x5b, 0xdc, 0xc1, 0x46]);
+        let mut first = Pseudonymizer::new(b"key one", Languages::English).unwrap();
+        let mut again = Pseudonymizer::new(b"key one", Languages::English).unwrap();
+        let mut other = Pseudonymizer::new(b"key two", Languages::English).unwrap();
+        let alice = first.pseudonym("alice@example.com").unwrap();
+        assert_eq!(alice.split('-').count(), 3);
+        assert_eq!(alice, again.pseudonym("alice@example.com").unwrap());
+        assert_eq!(alice, first.pseudonym("alice@example.com").unwrap());
+        assert_ne!(alice, other.pseudonym("alice@example.com").unwrap());
+        assert!(Pseudonymizer::new(b"", Languages::English).is_err());
+        // collision moves later id on
+        let mut taken = Pseudonymizer::new(b"key one", Languages::English).unwrap();
+        taken.remember(&alice, "someone else");
+        assert_ne!(taken.pseudonym("alice@example.com").unwrap(), alice);
+        // every round taken is error, not endless search
+        let mut full = Pseudonymizer::new(b"key one", Languages::English).unwrap();
+        for round in 0..PSEUDONYM_ROUNDS {
+            let candidate = full.candidate("carol", round);
+            full.remember(&candidate, "someone else");
+        }
+        assert!(full.pseudonym("carol").is_err());
+
+        let dir = std::env::temp_dir();
+        let input = dir.join("strgen_pseud_ids.txt");
+        let key = dir.join("strgen_pseud.key");
+        let map = dir.join("strgen_pseud.map");
+        let _ = remove_file(&map);
+        write(&input, "alice@example.com\n\nbob@example.com\nalice@example.com\n").unwrap();
+        write(&key, "key one\n").unwrap();
+        let next = format!("next={}:key={}:map={}", input.display(), key.display(), map.display());
+        let conf = command_parser::get_config(vec!["mode=pseud", "num=10", &next]);
+        let mut given = Vec::new();
+        {
+            let mut sg = stringer(conf.clone());
+            sg.setup(&conf).unwrap();
+            while !sg.exhausted() {
+                given.push(sg.get());
+            }
+        }
+        assert_eq!(given.len(), 3);
+        assert_eq!(given[0], alice);
+        assert_eq!(given[0], given[2]);
+        let mapping = read_to_string(&map).unwrap();
+        assert_eq!(mapping.lines().count(), 2);
+        assert!(mapping.contains(&format!("{}\talice@example.com", alice)));
+        // run taking fewer than input has saves what it gave
+        let _ = remove_file(&map);
+        let conf = command_parser::get_config(vec!["mode=pseud", "num=1", &next]);
+        run_generator_with(&conf, &mut Collect::new()).unwrap();
+        assert_eq!(read_to_string(&map).unwrap().lines().count(), 1);
+        // mapping that can't be written fails the run
+        let next = format!("next={}:key={}:map=/no/such/dir.map", input.display(), key.display());
+        let conf = command_parser::get_config(vec!["mode=pseud", "num=10", &next]);
+        assert!(run_generator_with(&conf, &mut Collect::new()).is_err());
+        let conf = command_parser::get_config(vec!["mode=pseud", "next=x.txt:key=/no/such.key"]);
+        assert!(stringer(conf.clone()).setup(&conf).is_err());
+        for path in [input, key, map] {
+            let _ = remove_file(path);
+        }
+    }
+
+    #[test]
+    fn ulids() {
+        use stranameg::stringer::{crockford32, stringer, ulid, Ulid, CROCKFORD32};
+        assert_eq!(crockford32(31, 2), "0Z");
+        assert!(!CROCKFORD32.iter().any(|c| b"ILOU".contains(c)));
+        assert_eq!(ulid(0, 0), "00000000000000000000000000");
+        // spec example time part: 1469918176385 ms is 01ARYZ6S41
+        assert!(ulid(1469918176385, 0).starts_with("01ARYZ6S41"));
+        assert_eq!(ulid(u64::MAX, u128::MAX), "7ZZZZZZZZZZZZZZZZZZZZZZZZZ");
+        let mut mono = Ulid::new();
+        mono.set_monotonic(true);
+        let ids: Vec<String> = (0..1000).map(|_| mono.next_at(1000).unwrap()).collect();
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+        assert!(ids.iter().all(|id| id.len() == 26 && id.starts_with("00000000Z8")));
+        let later = mono.next_at(1001).unwrap();
+        assert!(later > ids[999]);
+        let conf = command_parser::get_config(vec!["mode=ulid", "next=mono"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let batch: Vec<String> = (0..5000).map(|_| sg.get()).collect();
+        assert!(batch.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn scrubbing() {
+        use std::fs::{remove_file, write};
+        use stranameg::stringer::{scramble, stringer, RNG};
+        let mut rng = RNG::seeded(Some(9), 0);
+        let line = "2024-05-01 John.Doe@acme.com ÄÖ łódź #42";
+        let scrambled = scramble(line, &mut rng);
+        assert_ne!(scrambled, line);
+        assert_eq!(scrambled.chars().count(), line.chars().count());
+        for (a, b) in line.chars().zip(scrambled.chars()) {
+            assert_eq!(a.is_ascii_digit(), b.is_ascii_digit());
+            assert_eq!(a.is_uppercase(), b.is_uppercase());
+            assert_eq!(a.is_lowercase(), b.is_lowercase());
+            if !a.is_alphanumeric() {
+                assert_eq!(a, b);
+            }
+        }
+        let input = std::env::temp_dir().join("strgen_scrub.log");
+        write(&input, "id=17 ok\n\nUser Bob\n").unwrap();
+        let next = format!("next={}", input.display());
+        let conf = command_parser::get_config(vec!["mode=scrub", &next]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let lines: Vec<String> = (0..3).map(|_| sg.get()).collect();
+        assert!(sg.exhausted());
+        let shape: String = lines[0]
+            .chars()
+            .map(|c| if c.is_ascii_digit() { '9' } else if c.is_lowercase() { 'a' } else { c })
+            .collect();
+        assert_eq!(shape, "aa=99 aa");
+        assert_eq!(lines[1], "");
+        assert_eq!(lines[2].len(), 8);
+        let _ = remove_file(input);
+    }
+
+    #[test]
+    fn templates() {
+        use stranameg::stringer::{stringer, Part, Template};
+        let template = Template::parse("{{id}}-{upper:3}{digit}").unwrap();
+        assert_eq!(template.parts[0], Part::Literal(String::from("{id}-")));
+        assert_eq!(template.parts.len(), 3);
+        assert!(Template::parse("{colour}").is_err());
+        assert!(Template::parse("{digits:x}").is_err());
+        assert!(Template::parse("{noun").is_err());
+        let conf =
+            command_parser::get_config(vec!["mode=tpl", "next={upper:3}{digits:2}", "seed=4"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        for _ in 0..50 {
+            let s = sg.get();
+            assert_eq!(s.len(), 5);
+            assert!(s[..3].chars().all(|c| c.is_ascii_uppercase()));
+            assert!(s[3..].chars().all(|c| c.is_ascii_digit()));
+        }
+        let conf =
+            command_parser::get_config(vec!["mode=tpl", "next={adjective}-{noun}-{digits:4}"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let s = sg.get();
+        let parts: Vec<&str> = s.split('-').collect();
+        assert_eq!(parts.len(), 3);
+        assert!(!parts[0].is_empty() && !parts[1].is_empty());
+        assert_eq!(parts[2].len(), 4);
+    }
+
+    #[test]
+    fn regex_synthesis() {
+        use stranameg::stringer::{stringer, Node, RegexParser};
+        assert_eq!(
+            RegexParser::parse("a{2,3}").unwrap(),
+            Node::Group(vec![vec![Node::Repeat(Box::new(Node::Literal('a')), 2, 3)]])
+        );
+        assert!(RegexParser::parse("[a-").is_err());
+        assert!(RegexParser::parse("(ab").is_err());
+        assert!(RegexParser::parse("ab)").is_err());
+        assert!(RegexParser::parse("*a").is_err());
+        assert!(RegexParser::parse("a{3,1}").is_err());
+        let conf = command_parser::get_config(vec![
+            "mode=regex",
+            "next=^[A-Z]{2}-\\d{4}(-(ab|cd))?[^a-zA-Z0-9 ]$",
+            "seed=2",
+        ]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        for _ in 0..100 {
+            let s = sg.get();
+            let chars: Vec<char> = s.chars().collect();
+            assert!(chars[..2].iter().all(|c| c.is_ascii_uppercase()), "{}", s);
+            assert_eq!(chars[2], '-');
+            assert!(chars[3..7].iter().all(|c| c.is_ascii_digit()), "{}", s);
+            assert!(s.len() == 8 || s[7..10] == *"-ab" || s[7..10] == *"-cd", "{}", s);
+            let last = chars[chars.len() - 1];
+            assert!(!last.is_ascii_alphanumeric() && last != ' ', "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_tokens() {
+        use std::fs::{remove_file, write};
+        use stranameg::stringer::{crc32, stringer, TokenRegistry, TokenScheme};
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+        let scheme = TokenScheme::from("acme=acme_test_:20").unwrap();
+        assert!(scheme.checksum);
+        assert!(TokenScheme::from("acme=acme_test_").is_err());
+        assert!(TokenScheme::from("acme=acme_test_:20:md5").is_err());
+        let token = scheme.make("abcdefghijABCDEFGHIJ");
+        assert_eq!(token.len(), "acme_test_".len() + 26);
+        assert!(scheme.verify(&token));
+        let typo = token.replacen("abc", "abd", 1);
+        assert!(!scheme.verify(&typo));
+        let registry_file = std::env::temp_dir().join("strgen_test.tokens");
+        write(&registry_file, "# org\nacme=acme_test_:20\nlegacy=lgc_:8:nocheck\n").unwrap();
+        let path = registry_file.to_string_lossy().into_owned();
+        let registry = TokenRegistry::open(&path).unwrap();
+        assert_eq!(registry.verify(&token).map(|s| s.name.clone()), Some(String::from("acme")));
+        assert!(registry.verify("lgc_aB3dE6gH").is_some());
+        assert!(registry.verify("lgc_aB3dE6g!").is_none());
+        let next = format!("next=acme:registry={}", path);
+        let conf = command_parser::get_config(vec!["mode=token", &next]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        for _ in 0..20 {
+            let token = sg.get();
+            assert!(token.starts_with("acme_test_"));
+            assert_eq!(registry.verify(&token).map(|s| s.name.clone()), Some(String::from("acme")));
+        }
+        let conf = command_parser::get_config(vec!["mode=token"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        assert!(TokenRegistry::new().verify(&sg.get()).is_some());
+        let _ = remove_file(registry_file);
+    }
+
+    #[test]
+    fn padding_to_width() {
+        use stranameg::stringer::{run_generator_with, Padding, RNG};
+        let mut rng = RNG::seeded(Some(3), 0);
+        let padding = Padding::from("8").unwrap();
+        let padded = padding.apply("owl", &mut rng);
+        assert!(padded.starts_with("owl") && padded.len() == 8);
+        assert!(padded[3..].chars().all(|c| c.is_ascii_digit()));
+        assert_eq!(padding.apply("nightingale", &mut rng), "nighting");
+        assert_eq!(Padding::from("4:x").unwrap().apply("ab", &mut rng), "abxx");
+        assert!(Padding::from("0").is_err());
+        assert!(command_parser::try_get_config(vec!["pad=wide"]).is_err());
+        let conf = command_parser::get_config(vec!["mode=cow", "num=30", "pad=12:hex", "seed=1"]);
+        let mut sink = Collect::new();
+        run_generator_with(&conf, &mut sink).unwrap();
+        assert_eq!(sink.0.len(), 30);
+        assert!(sink.values().iter().all(|s| s.chars().count() == 12));
+    }
+
+    #[test]
+    fn markov_words() {
+        use stranameg::stringer::{stringer, MarkovChain, RNG};
+        let mut chain = MarkovChain::new(2);
+        chain.train("abc");
+        let mut rng = RNG::seeded(Some(1), 0);
+        // one word of training, only one walk
+        let walked = chain.walk(&mut rng, &GeneratorVersion::latest(), 10);
+        assert_eq!(walked, Some(String::from("abc")));
+        assert_eq!(chain.walk(&mut rng, &GeneratorVersion::latest(), 2), None);
+        let conf = command_parser::get_config(vec![
+            "mode=markov",
+            "len=4..9",
+            "next=lists/names.en.list:order=2",
+            "seed=5",
+        ]);
+        let names = std::fs::read_to_string("lists/names.en.list").unwrap();
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        for _ in 0..30 {
+            let word = sg.get();
+            let length = word.chars().count();
+            assert!((4..=9).contains(&length), "{}", word);
+            assert!(!names.split([',', '\n']).any(|name| name.trim() == word), "{}", word);
+        }
+        let bad = command_parser::get_config(vec!["mode=markov", "next=:order=9"]);
+        assert!(stringer(bad.clone()).setup(&bad).is_err());
+        // order as long as words only walks list words, run fails instead of giving them out
+        let list = std::env::temp_dir().join("strgen_markov.list");
+        std::fs::write(&list, "happy\ndesperate\nrepressed\n").unwrap();
+        let next = format!("next={}:order=5", list.display());
+        let conf = command_parser::get_config(vec!["mode=markov", "num=3", &next]);
+        let mut sink = Collect::new();
+        assert!(stranameg::stringer::run_generator_with(&conf, &mut sink).is_err());
+        assert!(sink.0.is_empty());
+        let _ = std::fs::remove_file(list);
+    }
+
+    #[test]
+    fn fixed_width_output() {
+        use std::fs::{read_to_string, remove_file};
+        use stranameg::stringer::{fixed_cell, run_generator};
+        assert_eq!(fixed_cell("7", 3, true), "  7");
+        assert_eq!(fixed_cell("owl", 5, false), "owl  ");
+        assert_eq!(fixed_cell("nightingale", 5, false), "night");
+        let path = std::env::temp_dir().join("strgen_fixed.txt");
+        let sink = format!("sink=fixed:{}", path.display());
+        let conf = command_parser::get_config(vec!["mode=cow", "num=12", "seed=3", &sink]);
+        run_generator(&conf).unwrap();
+        let text = read_to_string(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 12);
+        // widths from longest cells, every line as long as the others
+        assert!(lines.iter().all(|l| l.chars().count() == lines[0].chars().count()));
+        assert!(lines[0].starts_with(" 0 ") && lines[11].starts_with("11 "));
+        let widths = "widths=4,8,3";
+        let conf = command_parser::get_config(vec!["mode=cow", "num=3", "seed=3", &sink, widths]);
+        run_generator(&conf).unwrap();
+        let text = read_to_string(&path).unwrap();
+        assert!(text.lines().all(|l| l.chars().count() == 4 + 1 + 8 + 1 + 3));
+        assert!(command_parser::try_get_config(vec!["widths=4,x"]).is_err());
+        let _ = remove_file(&path);
+        let _ = remove_file(path.with_extension("txt.meta"));
+    }
+
+    #[test]
+    fn syllable_passwords() {
+        use stranameg::stringer::{stringer, syllables_for, Modes};
+        assert_eq!(syllables_for(12), 4);
+        assert_eq!(syllables_for(1), 1);
+        assert!(Modes::Syllables.is_secret() && Modes::Syllables.needs_secure_rng());
+        let nuclei = Languages::English.nuclei();
+        let conf = command_parser::get_config(vec!["mode=syl", "len=12", "next=en:-", "seed=8"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        for _ in 0..20 {
+            let password = sg.get();
+            let syllables: Vec<&str> = password.split('-').collect();
+            assert_eq!(syllables.len(), 4, "{}", password);
+            for syllable in syllables {
+                let onset = Languages::English.onsets().iter().any(|o| syllable.starts_with(o));
+                assert!(onset, "{}", syllable);
+                assert!(nuclei.iter().any(|n| syllable.contains(n)), "{}", syllable);
+            }
+        }
+        let conf = command_parser::get_config(vec!["mode=syl", "len=6", "next=ka"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let password = sg.get();
+        assert!(password.chars().all(|c| ('ა'..='ჰ').contains(&c)), "{}", password);
+    }
+
+    #[test]
+    fn diceware_export() {
+        use std::path::Path;
+        use stranameg::stringer::{dice_index, diceware_lines, diceware_table, read_list};
+        assert_eq!(dice_index(0, 5), "11111");
+        assert_eq!(dice_index(7775, 5), "66666");
+        assert_eq!(dice_index(6, 5), "11121");
+        let words = read_list(Path::new("lists/nouns.en.list")).unwrap();
+        assert!(diceware_table(&words, false, false).is_err());
+        let table = diceware_table(&words, true, false).unwrap();
+        assert_eq!(table.len(), 7776);
+        assert!(table.windows(2).all(|w| w[0] < w[1]));
+        let many: Vec<String> = (0..8000).map(|i| format!("w{}", i)).collect();
+        assert!(diceware_table(&many, false, false).is_err());
+        let trimmed = diceware_table(&many, false, true).unwrap();
+        assert_eq!(trimmed.len(), 7776);
+        assert!(!trimmed.contains(&String::from("w7999")));
+        let lines = diceware_lines(&table);
+        assert_eq!(lines.lines().count(), 7776);
+        assert!(lines.starts_with(&format!("11111\t{}\n", table[0])));
+    }
+
+    #[test]
+    fn svo_sentences() {
+        use stranameg::stringer::{stringer, GermanCase, GermanNoun};
+        use stranameg::grammar::grammar::GermanGenders;
+        let tree = GermanNoun::new(String::from("Baum"), GermanGenders::Masculine);
+        assert_eq!(tree.with_adjective("alt", GermanCase::Nominative), "der alte Baum");
+        assert_eq!(tree.with_adjective("leise", GermanCase::Accusative), "den leisen Baum");
+        let car = GermanNoun::new(String::from("Auto"), GermanGenders::Neuter);
+        assert_eq!(car.with_adjective("schnell", GermanCase::Accusative), "das schnelle Auto");
+        let conf = command_parser::get_config(vec!["mode=svo", "next=en", "seed=2"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let sentence = sg.get();
+        assert!(sentence.starts_with("The ") && sentence.ends_with('.'), "{}", sentence);
+        assert!(sentence.matches(" the ").count() == 1, "{}", sentence);
+        let conf = command_parser::get_config(vec!["mode=svo", "next=de", "seed=2"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        for _ in 0..20 {
+            let sentence = sg.get();
+            let words: Vec<&str> = sentence.split(' ').collect();
+            assert_eq!(words.len(), 7, "{}", sentence);
+            assert!(["Der", "Die", "Das"].contains(&words[0]), "{}", sentence);
+            assert!(["den", "die", "das"].contains(&words[4]), "{}", sentence);
+            // masculine object takes -en
+            if words[4] == "den" {
+                assert!(words[5].ends_with("en"), "{}", sentence);
+            }
+        }
+        let conf = command_parser::get_config(vec!["mode=svo", "next=ka"]);
+        assert!(stringer(conf.clone()).setup(&conf).is_err());
+    }
+
+    #[test]
+    fn list_stats() {
+        use std::path::Path;
+        use stranameg::stringer::{read_entries, ListStats};
+        let words: Vec<String> = ["owl", "Ant", "ant", "owl", "élan", "bee"]
+            .iter()
+            .map(|w| String::from(*w))
+            .collect();
+        let stats = ListStats::of(&words);
+        assert_eq!((stats.entries, stats.unique, stats.duplicates()), (6, 5, 1));
+        assert_eq!(stats.lengths.get(&3), Some(&5));
+        assert_eq!(stats.starts.get(&'a'), Some(&2));
+        assert_eq!(stats.starts.get(&'é'), Some(&1));
+        assert!((stats.non_ascii_fraction() - 1.0 / 6.0).abs() < 1e-9);
+        assert!((stats.entropy() - 5f64.log2()).abs() < 1e-9);
+        let report = stats.report();
+        assert!(report.contains("duplicates\t1\n"));
+        let german = read_entries(Path::new("lists/adjectives.de.list")).unwrap();
+        assert!(ListStats::of(&german).non_ascii > 0);
+    }
+
+    #[test]
+    fn lorem_paragraphs() {
+        use stranameg::stringer::{LoremParagraphs, LOREM};
+        let conf = command_parser::get_config(vec![
+            "mode=lorem", "num=1", "seed=7", "sentences=3", "words=6",
+        ]);
+        let mut lorem = LoremParagraphs::new();
+        lorem.setup(&conf).unwrap();
+        let paragraph = lorem.get();
+        let sentences: Vec<&str> = paragraph.split_inclusive('.').map(|s| s.trim()).collect();
+        assert_eq!(sentences.len(), 3);
+        for sentence in sentences {
+            assert!(sentence.chars().next().unwrap().is_uppercase());
+            let words: Vec<&str> = sentence.split_whitespace().collect();
+            assert_eq!(words.len(), 6);
+            let last = words[5].trim_end_matches('.').to_lowercase();
+            assert!(LOREM.contains(&last.as_str()));
+            assert!(!words[5].contains(','));
+        }
+        let mut again = LoremParagraphs::new();
+        again.setup(&conf).unwrap();
+        assert_eq!(again.get(), paragraph);
+        let german = command_parser::get_config(vec!["mode=lorem", "next=de", "words=4"]);
+        let mut lorem = LoremParagraphs::new();
+        lorem.setup(&german).unwrap();
+        assert_eq!(lorem.get().split_whitespace().count(), 20);
+        assert!(command_parser::try_get_config(vec!["mode=lorem", "words=many"]).is_err());
+    }
+
+    #[test]
+    fn append_to_outputs() {
+        use std::fs;
+        use stranameg::stringer::{open_output, open_sink, Record};
+        let dir = std::env::temp_dir().join(format!("strgen_append_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("out.csv");
+        let spec = format!("csv:{}", path.display());
+        let record = |value: &str| Record::new(0, String::from(value), String::from("cow"));
+        let mut conf = command_parser::get_config(vec!["mode=cow"]);
+        for (value, append) in [("first", false), ("second", true), ("third", true)] {
+            conf.set_append(append);
+            let mut sink = open_sink(&spec, &conf).unwrap();
+            sink.write(&record(value)).unwrap();
+            sink.finish().unwrap();
+        }
+        let text = fs::read_to_string(&path).unwrap();
+        assert_eq!(text, "index,value,mode\n0,first,cow\n0,second,cow\n0,third,cow\n");
+        let meta = fs::read_to_string(dir.join("out.csv.meta")).unwrap();
+        assert_eq!(meta.lines().count(), 3);
+        conf.set_append(false);
+        let mut sink = open_sink(&spec, &conf).unwrap();
+        sink.write(&record("fresh")).unwrap();
+        sink.finish().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "index,value,mode\n0,fresh,cow\n");
+        let missing = dir.join("missing").join("out.txt");
+        let error = open_output(&missing, true).unwrap_err();
+        assert!(error.to_string().contains("cannot write to"));
+        let conf = command_parser::get_config(vec!["mode=cow", "append=1"]);
+        assert!(conf.get_append());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn list_diff() {
+        use std::path::Path;
+        use stranameg::stringer::{list_hash, list_hashes, ListDiff, Languages, Modes};
+        let list = |words: &[&str]| -> Vec<String> {
+            return words.iter().map(|w| String::from(*w)).collect();
+        };
+        let old = list(&["Apfel", "Baum", "car", "tree  house"]);
+        let new = list(&["apfel", "Baum", "tree house", "dog"]);
+        let diff = ListDiff::of(&old, &new);
+        assert_eq!(diff.added, list(&["dog"]));
+        assert_eq!(diff.removed, list(&["car"]));
+        let changed = vec![
+            (String::from("Apfel"), String::from("apfel")),
+            (String::from("tree  house"), String::from("tree house")),
+        ];
+        assert_eq!(diff.changed, changed);
+        assert!(diff.report().ends_with("added 1, removed 1, changed 2\n"));
+        assert!(ListDiff::of(&old, &old).is_empty());
+        let hash = list_hash(Path::new("lists/nouns.en.list")).unwrap();
+        assert_eq!(hash.len(), 12);
+        let hashes = list_hashes(&Languages::English);
+        assert!(hashes.contains(&format!("nouns.en.list:{}", hash)));
+        assert!(hashes.iter().all(|h| h.contains(".en.")));
+        assert!(Modes::CoupledWordsNouns.uses_word_lists());
+        let conf = command_parser::get_config(vec!["mode=cow", "next=en"]);
+        assert!(conf.metadata().contains(&format!("nouns.en.list:{}", hash)));
+        let conf = command_parser::get_config(vec!["mode=uuid"]);
+        assert!(!conf.metadata().contains("lists="));
+    }
+
+    #[test]
+    fn structured_formats() {
+        use std::fs;
+        use stranameg::stringer::{open_sink, parse_format, OutputFormat, Record};
+        let (format, fields) = parse_format("jsonl:value,lang").unwrap();
+        assert_eq!(format, OutputFormat::Jsonl);
+        assert_eq!(fields, vec!["value", "lang"]);
+        assert!(parse_format("yaml").is_err());
+        assert!(parse_format("json:value,colour").is_err());
+        assert!(command_parser::try_get_config(vec!["format=xml"]).is_err());
+        let dir = std::env::temp_dir().join(format!("strgen_format_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("out.txt");
+        let write = |args: Vec<&str>, values: &[&str]| -> String {
+            let conf = command_parser::get_config(args);
+            let mut sink = open_sink(&format!("file:{}", path.display()), &conf).unwrap();
+            for (i, value) in values.iter().enumerate() {
+                let record = Record::new(i as u32, String::from(*value), String::from("cow"));
+                sink.write(&record).unwrap();
+            }
+            sink.finish().unwrap();
+            return fs::read_to_string(&path).unwrap();
+        };
+        let json = write(vec!["mode=cow", "next=de", "format=json"], &["a\"b", "c"]);
+        let expected = "[\n{\"index\":0,\"value\":\"a\\\"b\",\"mode\":\"cow\",\"lang\":\"de\"},\n\
+            {\"index\":1,\"value\":\"c\",\"mode\":\"cow\",\"lang\":\"de\"}\n]\n";
+        assert_eq!(json, expected);
+        assert_eq!(write(vec!["mode=uuid", "format=json"], &[]), "[]\n");
+        let lines = write(vec!["mode=uuid", "format=jsonl:value,lang"], &["x"]);
+        assert_eq!(lines, "{\"value\":\"x\"}\n");
+        let csv = write(vec!["mode=cow", "format=csv:value,index"], &["x,y"]);
+        assert_eq!(csv, "value,index\n\"x,y\",0\n");
+        let plain = write(vec!["mode=cow"], &["x"]);
+        assert_eq!(plain, "x\n");
+        let conf = command_parser::get_config(vec!["mode=cow", "format=json", "append=1"]);
+        assert!(open_sink(&format!("file:{}", path.display()), &conf).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn list_extraction() {
+        use stranameg::stringer::{alphabet_of, extract_words, list_lines, ExtractOptions};
+        let text = "The cat sat. The cat ran! A dog, the dog; the Café. café shit shit x1y x1y \
+            Straße Straße";
+        let mut options = ExtractOptions::default();
+        let words = extract_words(text, &options);
+        assert_eq!(words, vec!["the", "café", "cat", "dog", "straße"]);
+        options.set("--alphabet", "latin").unwrap();
+        assert_eq!(extract_words(text, &options), vec!["the", "cat", "dog"]);
+        options.alphabet = alphabet_of("german");
+        options.set("--top", "2").unwrap();
+        assert_eq!(extract_words(text, &options), vec!["the", "cat"]);
+        assert!(options.set("--top", "many").is_err());
+        assert!(!options.set("--pad", "1").unwrap());
+        let options = ExtractOptions {
+            keep_case: true,
+            min_length: 4,
+            ..Default::default()
+        };
+        assert_eq!(extract_words(text, &options), vec!["Straße"]);
+        let words: Vec<String> = (0..12).map(|i| format!("w{}", i)).collect();
+        assert_eq!(list_lines(&words).lines().count(), 2);
+        assert!(list_lines(&words).starts_with("w0,w1,w2,"));
+    }
+
+    #[test]
+    fn column_rows() {
+        use std::fs;
+        use stranameg::stringer::{mailbox, open_sink, parse_columns, stringer, Record};
+        assert_eq!(parse_columns("name, email,uuid").unwrap().len(), 3);
+        assert!(parse_columns("name,phone").is_err());
+        assert!(command_parser::try_get_config(vec!["columns=name,phone"]).is_err());
+        assert_eq!(mailbox("Zoë-Ann"), "zoann");
+        assert_eq!(mailbox("ნინო"), "user");
+        let args = vec!["num=3", "seed=5", "columns=name,email,uuid,pin"];
+        let conf = command_parser::get_config(args.clone());
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let name = sg.get();
+        let row = sg.fields();
+        let headers: Vec<&str> = row.iter().map(|(h, _v)| h.as_str()).collect();
+        assert_eq!(headers, vec!["name", "email", "uuid", "pin"]);
+        assert_eq!(row[0].1, name);
+        assert!(row[1].1.starts_with(&mailbox(&name)) && row[1].1.contains("@example."));
+        assert_eq!(row[2].1.len(), 36);
+        assert!(row[3].1.chars().all(|c| c.is_ascii_digit()));
+        let mut again = stringer(conf.clone());
+        again.setup(&conf).unwrap();
+        again.get();
+        assert_eq!(again.fields(), row);
+        let dir = std::env::temp_dir().join(format!("strgen_columns_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (format, separator) in [("csv", ","), ("tsv", "\t")] {
+            let path = dir.join(format!("rows.{}", format));
+            let mut args = args.clone();
+            let format = format!("format={}", format);
+            args.push(&format);
+            let conf = command_parser::get_config(args);
+            let mut sink = open_sink(&format!("file:{}", path.display()), &conf).unwrap();
+            let record = Record::new(0, name.clone(), String::from("rls")).with_fields(row.clone());
+            sink.write(&record).unwrap();
+            sink.finish().unwrap();
+            let text = fs::read_to_string(&path).unwrap();
+            let lines: Vec<&str> = text.lines().collect();
+            assert_eq!(lines[0], ["name", "email", "uuid", "pin"].join(separator));
+            assert_eq!(lines[1].split(separator).count(), 4);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn lemma_extraction() {
+        use stranameg::stringer::{extract_words, ExtractOptions, Languages};
+        let german = Languages::German;
+        assert_eq!(german.stem("Bäume"), german.stem("Baum"));
+        assert_eq!(german.stem("Kindern"), "kind");
+        assert_eq!(german.stem("alten"), german.stem("alter"));
+        let english = Languages::English;
+        assert_eq!(english.stem("cities"), "city");
+        assert_eq!(english.stem("glass"), "glass");
+        assert_eq!(english.stem("trees"), "tree");
+        assert_eq!(Languages::Georgian.stem("ხეები"), "ხეები");
+        let text = "Der Baum, die Bäume, den Bäumen, dem Baum. Das Kind und die Kinder, \
+            den Kindern. alte alten alter Haus Häuser";
+        let mut options = ExtractOptions {
+            keep_case: true,
+            min_count: 1,
+            ..Default::default()
+        };
+        let plain = extract_words(text, &options);
+        assert!(plain.contains(&String::from("Bäume")));
+        options.set("--stem", "de").unwrap();
+        let words = extract_words(text, &options);
+        assert_eq!(&words[..3], &["Baum", "Kind", "alte"]);
+        assert!(words.contains(&String::from("Haus")));
+        assert!(!words.iter().any(|w| w == "Bäume" || w == "Kinder" || w == "Häuser"));
+        let options = ExtractOptions {
+            stem: Some(english),
+            ..Default::default()
+        };
+        assert_eq!(extract_words("city cities glass glass", &options), vec!["city", "glass"]);
+    }
+
+    #[test]
+    fn batch_generation() {
+        use stranameg::stringer::{run_generator_with, stringer, GENERATE_BATCH};
+        let amount = GENERATE_BATCH * 2 + 7;
+        let num = format!("num={}", amount);
+        let conf = command_parser::get_config(vec!["mode=rls", &num, "seed=3"]);
+        let mut sink = Collect::new();
+        run_generator_with(&conf, &mut sink).unwrap();
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let one_by_one: Vec<String> = (0..amount).map(|_| sg.get()).collect();
+        let batched: Vec<String> = sink.0.iter().map(|r| r.value.clone()).collect();
+        assert_eq!(batched, one_by_one);
+        assert_eq!(sink.0.last().unwrap().index as usize, amount - 1);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        assert_eq!(sg.generate_batch(5), one_by_one[..5].to_vec());
+        // fields of every string are kept, rows are never batched
+        let conf = command_parser::get_config(vec!["num=3", "seed=3", "columns=uuid,pin"]);
+        let mut sink = Collect::new();
+        run_generator_with(&conf, &mut sink).unwrap();
+        assert_eq!(sink.0[2].fields[0].1, sink.0[2].value);
+        assert_ne!(sink.0[1].fields, sink.0[2].fields);
+    }
+
+    #[cfg(feature = "tagging")]
+    #[test]
+    fn pos_extraction() {
+        use stranameg::stringer::{extract_words, ExtractOptions, Languages, Tag, Tagger};
+        let tagger = Tagger::new(&Languages::English);
+        assert_eq!(tagger.guess("kindness"), Some(Tag::Noun));
+        assert_eq!(tagger.guess("careless"), Some(Tag::Adjective));
+        assert_eq!(tagger.guess("al"), None);
+        let text = "The careful zorbler stops. A careful zorbler waits. The zorbler. \
+            The happiness of a dark blick. the quiet blick.";
+        let tags = tagger.tags(text);
+        assert_eq!(tags.get("zorbler"), Some(&Tag::Noun));
+        assert_eq!(tags.get("blick"), Some(&Tag::Noun));
+        assert_eq!(tags.get("careful"), Some(&Tag::Adjective));
+        assert_eq!(tags.get("stops"), None);
+        let mut options = ExtractOptions {
+            min_count: 1,
+            min_length: 4,
+            ..Default::default()
+        };
+        options.set("--pos", "noun").unwrap();
+        let nouns = extract_words(text, &options);
+        let has = |words: &Vec<String>, word: &str| words.iter().any(|w| w == word);
+        assert!(has(&nouns, "zorbler") && has(&nouns, "happiness"));
+        assert!(!has(&nouns, "careful") && !has(&nouns, "stops"));
+        options.set("--pos", "adjective").unwrap();
+        assert!(has(&extract_words(text, &options), "careful"));
+        // german nouns are known by capital letter inside sentence
+        let mut tagger = Tagger::new(&Languages::German);
+        tagger.add("plimm", Tag::Adjective);
+        let tags = tagger.tags("Der plimme Quarz fällt. Ein plimm Quarz liegt auf der Wiese.");
+        assert_eq!(tags.get("quarz"), Some(&Tag::Noun));
+        assert_eq!(tags.get("plimme"), Some(&Tag::Adjective));
+        assert!(Tag::from("pronoun").is_err());
+    }
+
+    #[test]
+    fn frequency_tiers() {
+        use stranameg::stringer::{frequency_tiers, tier_lines, TierOptions};
+        let words: Vec<String> = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"]
+            .iter()
+            .map(|w| String::from(*w))
+            .collect();
+        let mut options = TierOptions::default();
+        let tiers = frequency_tiers(&words, &options);
+        let of = |tiers: &Vec<(String, &str)>, tier: &str| {
+            return tiers
+                .iter()
+                .filter(|(_w, t)| *t == tier)
+                .map(|(w, _t)| w.as_str())
+                .collect::<Vec<&str>>()
+                .join("");
+        };
+        assert_eq!(of(&tiers, "common"), "ab");
+        assert_eq!(of(&tiers, "uncommon"), "cde");
+        assert_eq!(of(&tiers, "rare"), "fghij");
+        // corpus ranks words, those missing from it are rare, list order is kept
+        options.set("--cuts", "10,30").unwrap();
+        options.corpus = Some(String::from("j j j i i h a"));
+        let tiers = frequency_tiers(&words, &options);
+        assert_eq!(of(&tiers, "common"), "j");
+        assert_eq!(of(&tiers, "uncommon"), "ai");
+        assert_eq!(of(&tiers, "rare"), "bcdefgh");
+        options.set("--drop", "rare").unwrap();
+        let tiers = frequency_tiers(&words, &options);
+        assert_eq!(tier_lines(&tiers), "a#uncommon,i#uncommon,j#common\n");
+        assert!(options.set("--cuts", "60,30").is_err());
+        assert!(options.set("--drop", "never").is_err());
+        assert!(!options.set("--top", "5").unwrap());
+    }
+
+    #[test]
+    fn shared_german_nouns() {
+        use stranameg::stringer::{stringer, Config, GermanNounList, Languages, Modes};
+        let first = GermanNounList::shared(&Languages::German);
+        let second = GermanNounList::shared(&Languages::German);
+        assert!(std::sync::Arc::ptr_eq(&first, &second));
+        assert!(!first.is_empty());
+        assert_eq!(first.get_adapted(String::from("Baum"), String::from("alt")), "Der alte Baum");
+        // no gendered nouns for english, never read again either
+        assert!(GermanNounList::shared(&Languages::English).is_empty());
+        let mut conf = Config::default();
+        conf.set_mode(Modes::CoupledWordsNouns);
+        conf.set_next(String::from("de"));
+        conf.set_seed(Some(5));
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let strings: Vec<String> = (0..20).map(|_| sg.get()).collect();
+        // nouns of list missing from nouns.de.dic come out empty
+        assert!(strings.iter().any(|s| s.starts_with("Der ") || s.starts_with("Die ")));
+    }
+
+    #[test]
+    fn difficulty_levels() {
+        use stranameg::stringer::{command_parser, stringer, Difficulty, ListType, Languages};
+        use stranameg::stringer::WordList;
+        assert!(Difficulty::from("impossible").is_err());
+        assert!(command_parser::try_get_config(vec!["difficulty=extreme"]).is_err());
+        let path = std::env::temp_dir().join("strgen_difficulty.list");
+        std::fs::write(&path, "cat#common,dog#common,owl#uncommon\nelephant#rare,axolotl#rare\n")
+            .unwrap();
+        let words = |difficulty: &str| {
+            let mut list = WordList::new(ListType::Nouns, Languages::English);
+            list.fill(&path).unwrap();
+            list.set_difficulty(&Difficulty::from(difficulty).unwrap())?;
+            let words: Vec<String> = (0..list.get_list_len()).map(|i| list.get_at(i)).collect();
+            return Ok::<Vec<String>, std::io::Error>(words);
+        };
+        assert_eq!(words("easy").unwrap(), vec!["cat", "dog"]);
+        assert_eq!(words("medium").unwrap(), vec!["cat", "dog", "owl"]);
+        assert_eq!(words("hard").unwrap(), vec!["elephant", "axolotl"]);
+        assert_eq!(words("").unwrap().len(), 5);
+        // untiered lists are cut by length only, nothing left is error
+        std::fs::write(&path, "ox,elephant").unwrap();
+        assert!(words("easy").is_err());
+        std::fs::remove_file(&path).unwrap();
+        let conf = command_parser::get_config(vec!["mode=cow", "difficulty=easy", "seed=2"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let (adjective, noun) = sg.get().split_once('_').map(|(a, n)| (a.len(), n.len())).unwrap();
+        assert!((3..=7).contains(&adjective) && (3..=7).contains(&noun));
+        let bits = sg.label().unwrap();
+        assert!(bits.ends_with(" bits"), "{}", bits);
+        let conf = command_parser::get_config(vec!["mode=cow"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        assert_eq!(sg.label(), None);
+    }
+
+    #[test]
+    fn empty_word_lists() {
+        use stranameg::stringer::{command_parser, run_generator, ListType, Languages, WordList};
+        let path = std::env::temp_dir().join("strgen_empty.list");
+        std::fs::write(&path, "\n , \n").unwrap();
+        let mut list = WordList::new(ListType::Nouns, Languages::English);
+        let e = list.fill(&path).unwrap_err();
+        assert_eq!(e.to_string(), format!("list {} is empty", path.display()));
+        // drawing from list that was never filled gives nothing instead of dividing by zero
+        assert_eq!(list.get(), "");
+        let next = format!("next={}:{}", path.display(), path.display());
+        let conf = command_parser::get_config(vec!["mode=cowf", &next, "num=3", "sink=stdout"]);
+        let e = run_generator(&conf).unwrap_err();
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn major_system() {
+        use stranameg::stringer::{command_parser, major_digits, stringer, MajorWords};
+        assert_eq!(major_digits("meter"), "314");
+        assert_eq!(major_digits("butter"), "914");
+        assert_eq!(major_digits("shop"), "69");
+        assert_eq!(major_digits("city"), "01");
+        assert_eq!(major_digits("night"), "21");
+        assert_eq!(major_digits("axe"), "70");
+        assert_eq!(major_digits("away"), "");
+        let mut major = MajorWords::new();
+        for word in ["meter", "tin", "meat", "tuna", "hat"] {
+            major.add_word(word);
+        }
+        // fewest words, any word of same digits
+        for _ in 0..10 {
+            let words = major.encode("31412");
+            assert!(words == "meter tin" || words == "meter tuna", "{}", words);
+        }
+        assert_eq!(major.encode("3158"), "meat 5 8");
+        let conf = command_parser::get_config(vec!["mode=major", "next=8080", "seed=4"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let words = sg.get();
+        let digits: String = words.split(' ').map(major_digits).collect();
+        assert_eq!(digits, "8080", "{}", words);
+        assert_eq!(sg.fields(), vec![(String::from("number"), String::from("8080"))]);
+        let conf = command_parser::get_config(vec!["mode=major", "len=6", "seed=4"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        sg.get();
+        assert_eq!(sg.fields()[0].1.len(), 6);
+        let conf = command_parser::get_config(vec!["mode=major", "next=80a0"]);
+        assert!(stringer(conf.clone()).setup(&conf).is_err());
+    }
+    #[test]
+    fn pronounceable_bytes() {
+        use stranameg::stringer::{
+            bubblebabble, bubblebabble_decode, proquint, proquint_decode, stringer,
+        };
+        assert_eq!(proquint(&[127, 0, 0, 1]), "lusab-babad");
+        assert_eq!(proquint_decode("lusab-babad"), Some(vec![127, 0, 0, 1]));
+        assert_eq!(proquint_decode("lusab-babax"), None);
+        assert_eq!(bubblebabble(b""), "xexax");
+        assert_eq!(bubblebabble(b"1234567890"), "xesef-disof-gytuf-katof-movif-baxux");
+        assert_eq!(bubblebabble(b"Pineapple"), "xigak-nyryk-humil-bosek-sonax");
+        let decoded = bubblebabble_decode("xigak-nyryk-humil-bosek-sonax");
+        assert_eq!(decoded, Some(b"Pineapple".to_vec()));
+        // checksum catches swapped group
+        assert_eq!(bubblebabble_decode("xigak-humil-nyryk-bosek-sonax"), None);
+        let conf = command_parser::get_config(vec!["mode=proquint", "len=5"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let s = sg.get();
+        assert_eq!(proquint_decode(&s).map(|b| b.len()), Some(6), "{}", s);
+        let conf = command_parser::get_config(vec!["mode=bubble", "len=0"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let s = sg.get();
+        assert_eq!(bubblebabble_decode(&s).map(|b| b.len()), Some(8), "{}", s);
+        // without length, 4 bytes of proquint and 8 of bubble babble
+        for (mode, bytes) in [("mode=proquint", 4), ("mode=bubble", 8)] {
+            let conf = command_parser::get_config(vec![mode]);
+            let mut sg = stringer(conf.clone());
+            sg.setup(&conf).unwrap();
+            let s = sg.get();
+            let decoded = proquint_decode(&s).or_else(|| bubblebabble_decode(&s));
+            assert_eq!(decoded.map(|b| b.len()), Some(bytes), "{}", s);
+        }
+    }
+    #[test]
+    fn unique_run() {
+        use stranameg::stringer::{run_generator_with, BloomFilter};
+        let vargs = vec!["num=9", "len=2", "mode=rla", "next=abc", "unique=1"];
+        let conf = command_parser::get_config(vargs);
+        let mut sink = Collect::new();
+        run_generator_with(&conf, &mut sink).unwrap();
+        let distinct: std::collections::HashSet<String> = sink.values().into_iter().collect();
+        assert_eq!(distinct.len(), 9);
+        // two of three letters give nine strings, not more
+        let vargs = vec!["num=10", "len=2", "mode=rla", "next=abc", "unique=1"];
+        let conf = command_parser::get_config(vargs);
+        let err = run_generator_with(&conf, &mut Collect::new()).unwrap_err();
+        assert!(err.to_string().contains("after 9,"), "{}", err);
+        let mut bloom = BloomFilter::new(1000, 1e-6);
+        for i in 0..1000 {
+            bloom.insert(&i.to_string());
+        }
+        assert!((0..1000).all(|i| bloom.contains(&i.to_string())));
+        assert!((1000..2000).filter(|i| bloom.contains(&i.to_string())).count() < 2);
+    }
+    #[test]
+    fn exclude_and_record_file() {
+        use stranameg::stringer::run_generator_with;
+        let path = std::env::temp_dir().join(format!("strgen-{}.names", std::process::id()));
+        std::fs::remove_file(&path).ok();
+        std::fs::write(&path, "aa\nab\n\n").unwrap();
+        let file = path.to_string_lossy().into_owned();
+        let exclude = format!("exclude_file={}", file);
+        let record = format!("record={}", file);
+        // four strings of two letters, two taken by file
+        let vargs = vec!["num=2", "len=2", "mode=rla", "next=ab", &exclude, &record, "unique=1"];
+        let mut sink = Collect::new();
+        run_generator_with(&command_parser::get_config(vargs.clone()), &mut sink).unwrap();
+        let mut values = sink.values();
+        values.sort();
+        assert_eq!(values, vec!["ba", "bb"]);
+        let lines = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(lines.lines().filter(|l| !l.is_empty()).count(), 4);
+        // next run has nothing left
+        assert!(run_generator_with(&command_parser::get_config(vargs), &mut sink).is_err());
+        std::fs::remove_file(&path).ok();
+    }
+    #[test]
+    fn word_triples() {
+        use stranameg::stringer::{stringer, WordGrid};
+        let words: Vec<String> = ["ant", "bee", "cat", "dog", "eel"].map(String::from).to_vec();
+        let grid = WordGrid::new(&words).unwrap();
+        assert_eq!(grid.capacity(), 125);
+        // every number is own triple, and comes back
+        let mut triples = std::collections::HashSet::new();
+        for number in 0..125 {
+            let triple = grid.encode(number).unwrap();
+            assert_eq!(grid.decode(&triple), Some(number), "{}", triple);
+            assert!(triples.insert(triple));
+        }
+        assert_eq!(grid.encode(125), None);
+        assert_eq!(grid.decode("ant.bee.fox"), None);
+        assert_eq!(grid.decode("Ant Bee Cat"), grid.decode("ant.bee.cat"));
+        assert!(WordGrid::new(&words[..1]).is_err());
+        // cell of point is around it
+        let (lat, long) = grid.cell_center(grid.cell(41.7, 44.8).unwrap()).unwrap();
+        assert!((lat - 41.7).abs() < 30.0 && (long - 44.8).abs() < 30.0);
+        assert_eq!(grid.cell(91.0, 0.0), None);
+        let conf = command_parser::get_config(vec!["mode=triple", "next=4711"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let triple = sg.get();
+        let grid = WordGrid::for_language(&Languages::English).unwrap();
+        assert_eq!(grid.decode(&triple), Some(4711));
+        assert_eq!(sg.fields()[0].1, "4711");
+        let conf = command_parser::get_config(vec!["mode=triple", "next=99999999999999"]);
+        assert!(stringer(conf.clone()).setup(&conf).is_err());
+    }
+    #[test]
+    fn wordified_numbers() {
+        use stranameg::stringer::{convert_base, number_digits, stringer, WordGrid};
+        assert_eq!(convert_base(&[2, 5, 5], 10, 16), vec![15, 15]);
+        assert_eq!(convert_base(&[0, 0], 10, 7), vec![0]);
+        assert_eq!(number_digits("0xFF"), Some((vec![15, 15], 16)));
+        assert_eq!(number_digits("beef").map(|d| d.1), Some(16));
+        assert_eq!(number_digits("12x"), None);
+        let words: Vec<String> = ["ant", "bee", "cat"].map(String::from).to_vec();
+        let grid = WordGrid::new(&words).unwrap();
+        // 11 is 102 in base 3
+        assert_eq!(grid.wordify("11").unwrap(), "bee-ant-cat");
+        assert_eq!(grid.wordify("0xb").unwrap(), "bee-ant-cat");
+        assert_eq!(grid.unwordify("bee-ant-cat", false).unwrap(), "11");
+        assert_eq!(grid.unwordify("Bee Ant Cat", true).unwrap(), "0xb");
+        assert_eq!(grid.unwordify("bee-fox", false), None);
+        // numbers larger than any integer type come back as they were
+        let grid = WordGrid::for_language(&Languages::English).unwrap();
+        let uuid = "0x9f1c2e4b7a3d4e5f8a6b1c2d3e4f5a6b7c8d9e0f";
+        let wordified = grid.wordify(uuid).unwrap();
+        assert_eq!(grid.unwordify(&wordified, true).unwrap(), uuid);
+        let big = "123456789012345678901234567890123456789012345678901234567890";
+        assert_eq!(grid.unwordify(&grid.wordify(big).unwrap(), false).unwrap(), big);
+        let conf = command_parser::get_config(vec!["mode=wordify", "len=12", "seed=3"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let wordified = sg.get();
+        let number = sg.fields()[0].1.clone();
+        assert_eq!(number.len(), 12);
+        let decoded = grid.unwordify(&wordified, false).unwrap();
+        assert_eq!(decoded.parse::<u64>().unwrap(), number.parse::<u64>().unwrap());
+        let conf = command_parser::get_config(vec!["mode=wordify", "next=12g"]);
+        assert!(stringer(conf.clone()).setup(&conf).is_err());
+    }
+    #[test]
+    fn generator_iterator() {
+        use stranameg::{stringer, strings};
+        let conf = command_parser::get_config(vec!["mode=rls", "len=6", "seed=8"]);
+        let names: Vec<String> = strings(&conf).unwrap().take(5).collect();
+        assert_eq!(names.len(), 5);
+        assert!(names.iter().all(|n| n.chars().count() == 6));
+        // same strings as get, one buffer
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let mut buffer = String::new();
+        for name in names.iter() {
+            sg.generate_into(&mut buffer);
+            assert_eq!(&buffer, name);
+        }
+        // default generate_into of other generators
+        let conf = command_parser::get_config(vec!["mode=uuid", "seed=8"]);
+        let first = strings(&conf).unwrap().next().unwrap();
+        strings(&conf).unwrap().generate_into(&mut buffer);
+        assert_eq!(buffer, first);
+        // exhausted generators end iteration
+        let conf = command_parser::get_config(vec!["mode=pairs", "next=en"]);
+        let pairs = strings(&conf).unwrap().count();
+        let mut adjectives = WordList::new(ListType::Adjectives, Languages::English);
+        let mut nouns = WordList::new(ListType::Nouns, Languages::English);
+        adjectives.fill(std::path::Path::new("")).unwrap();
+        nouns.fill(std::path::Path::new("")).unwrap();
+        assert_eq!(pairs, adjectives.get_list_len() * nouns.get_list_len());
+    }
+    #[test]
+    fn config_builder() {
+        use stranameg::stringer::{stringer, Modes, StrgenError};
+        // example of the request, run through generator
+        let run = || -> Result<Vec<String>, Box<dyn std::error::Error>> {
+            let conf = Config::builder()
+                .mode(Modes::Password)
+                .amount(100)
+                .length(20)
+                .language(Languages::German)
+                .build()?;
+            let mut sg = stringer(conf.clone());
+            sg.setup(&conf)?;
+            return Ok((0..conf.get_amount()).map(|_| sg.get()).collect());
+        };
+        let passwords = run().unwrap();
+        assert_eq!(passwords.len(), 100);
+        assert!(passwords.iter().all(|p| p.chars().count() == 20));
+        let conf = Config::builder()
+            .mode(Modes::Password)
+            .amount(100)
+            .length(20)
+            .language(Languages::German)
+            .seed(7)
+            .set("min_dist", "2")
+            .build()
+            .unwrap();
+        assert!(matches!(conf.get_mode(), Modes::Password));
+        assert_eq!((conf.get_amount(), conf.get_length()), (100, 20));
+        assert_eq!(conf.get_next(), "de");
+        assert_eq!(conf.get_seed(), Some(7));
+        assert_eq!(conf.get_min_distance(), 2);
+        // same config as alt arguments give
+        let alt = command_parser::get_config(vec!["mode=pass", "num=100", "len=20", "next=de"]);
+        assert_eq!(alt.get_length(), conf.get_length());
+        let e = Config::builder().set("num", "many").build().err().unwrap();
+        assert!(matches!(e, StrgenError::BadNumber(_, value) if value == "many"));
+        let e = Config::builder().set("colour", "red").build().err().unwrap();
+        assert!(matches!(e, StrgenError::UnknownSetting(key) if key == "colour"));
+        assert!(Config::builder().length_range(9, 3).build().is_err());
+        // first error wins, later settings don't hide it
+        let e = Config::builder().set("mode", "bogus").set("len", "8").build().err().unwrap();
+        assert!(matches!(e, StrgenError::UnknownMode(_)));
+        let e = Config::builder().amount(0).build().err().unwrap();
+        assert!(matches!(e, StrgenError::NotPositive(what) if what == "amount"));
+        assert!(Config::builder().length(0).build().is_err());
+        // language is 4th parameter of word modes only, and needs lists
+        let cow = Config::builder().mode(Modes::CoupledWordsNouns).language(Languages::Georgian);
+        assert_eq!(cow.build().unwrap().get_next(), "ka");
+        let rla = Config::builder().mode(Modes::RandomLettersFromCustomAlphabet);
+        let e = rla.language(Languages::German).build().err().unwrap();
+        assert!(matches!(e, StrgenError::LanguageMismatch(_, mode) if mode == "rla"));
+        let cow = Config::builder().mode(Modes::CoupledWordsNouns).language(Languages::French);
+        assert!(cow.build().is_err());
+        // later next replaces language
+        let rla = Config::builder().mode(Modes::RandomLettersFromCustomAlphabet);
+        let conf = rla.language(Languages::German).next("xyz").build().unwrap();
+        assert_eq!(conf.get_next(), "xyz");
+    }
+    #[test]
+    fn encoded_numbers() {
+        use stranameg::stringer::{
+            base62_decode, base62_u128, crockford_decode, crockford_number, stringer,
+        };
+        assert_eq!(crockford_number(4711, 0, false), "4K7");
+        assert_eq!(crockford_number(4711, 0, true), "4K7C");
+        assert_eq!(crockford_number(0, 4, false), "0000");
+        // 36 mod 37 is last check symbol
+        assert_eq!(crockford_number(36, 0, true), "14U");
+        assert_eq!(crockford_decode("4k7-c", true), Some(4711));
+        assert_eq!(crockford_decode("4K7D", true), None);
+        assert_eq!(crockford_decode("ILO", false), crockford_decode("110", false));
+        assert_eq!(crockford_decode("4U7", false), None);
+        assert_eq!(crockford_decode(&crockford_number(u128::MAX, 0, true), true), Some(u128::MAX));
+        assert_eq!(base62_u128(4711), "1Dz");
+        assert_eq!(base62_decode("1Dz"), Some(4711));
+        assert_eq!(base62_decode(&base62_u128(u128::MAX)), Some(u128::MAX));
+        assert_eq!(base62_decode("zzzzzzzzzzzzzzzzzzzzzz"), None);
+        assert_eq!(base62_decode("1-z"), None);
+        // random ones are padded, so text order is number order
+        let conf = command_parser::get_config(vec!["mode=b62", "len=6", "seed=5"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        let mut rows: Vec<(String, u128)> = Vec::new();
+        for _ in 0..20 {
+            let id = sg.get();
+            assert_eq!(id.len(), 6);
+            let number: u128 = sg.fields()[0].1.parse().unwrap();
+            assert_eq!(base62_decode(&id), Some(number));
+            rows.push((id, number));
+        }
+        let mut by_text = rows.clone();
+        by_text.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+        rows.sort_by_key(|r| r.1);
+        assert_eq!(by_text, rows);
+        // without length, 13 characters of crockford and 11 of base62
+        for (mode, width) in [("mode=b32", 13), ("mode=b62", 11)] {
+            let conf = command_parser::get_config(vec![mode]);
+            let mut sg = stringer(conf.clone());
+            sg.setup(&conf).unwrap();
+            assert_eq!(sg.get().len(), width);
+        }
+        let conf = command_parser::get_config(vec!["mode=b32", "next=4711:check"]);
+        let mut sg = stringer(conf.clone());
+        sg.setup(&conf).unwrap();
+        assert_eq!(sg.get(), "4K7C");
+        let conf = command_parser::get_config(vec!["mode=b62", "next=1:check"]);
+        assert!(stringer(conf.clone()).setup(&conf).is_err());
+    }
 }